@@ -1,14 +1,48 @@
 #[macro_use]
 extern crate criterion;
 extern crate rand;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 #[macro_use]
 extern crate ult_algo;
 
 use criterion::Criterion;
 use rand::Rng;
+use std::collections::BTreeSet;
+use ult_algo::dp;
+use ult_algo::ds;
+use ult_algo::geometry;
+use ult_algo::math;
 use ult_algo::sequence;
 include_sequence_search!();
 
+/// Quickselect with the two-way Lomuto partition that `quick_smallest` used before it partitioned
+/// three ways, where items equal to the pivot all go to its right.
+fn two_way_quick_smallest(mut list: &mut [i32], mut k: usize) -> i32 {
+    let mut rng = rand::thread_rng();
+    while list.len() > 1 {
+        let last_idx = list.len() - 1;
+        list.swap(rng.gen_range(0, list.len()), last_idx);
+        let mut store_idx = 0;
+        for i in 0..last_idx {
+            if list[i] < list[last_idx] {
+                list.swap(store_idx, i);
+                store_idx += 1;
+            }
+        }
+        list.swap(store_idx, last_idx);
+        if k == store_idx {
+            break;
+        } else if k < store_idx {
+            list = &mut { list }[..store_idx];
+        } else {
+            k -= store_idx + 1;
+            list = &mut { list }[store_idx + 1..];
+        }
+    }
+    list[k]
+}
+
 fn sequence_benchmark(c: &mut Criterion) {
     let sequence_: Vec<i32> = (-100..100).collect();
     let pattern: Vec<i32> = (101..200).collect();
@@ -27,6 +61,92 @@ fn sequence_benchmark(c: &mut Criterion) {
         move |b| b.iter(|| sequence::match_::levenshtein_distance(&source, &target)),
     );
 
+    let source: Vec<char> = "sitting in my kitchen like a boss knitting its scarf"
+        .chars()
+        .collect();
+    let target: Vec<char> = "kittens love sitting on the knit of my scarf in my kitchen"
+        .chars()
+        .collect();
+    c.bench_function(
+        "sequence::match_::levenshtein_myers(&source, &target)",
+        move |b| b.iter(|| sequence::match_::levenshtein_myers(&source, &target)),
+    );
+
+    let mut rng = rand::thread_rng();
+    let source: Vec<char> = (0..1000)
+        .map(|_| rng.gen_range(b'a', b'z') as char)
+        .collect();
+    let target: Vec<char> = (0..1000)
+        .map(|_| rng.gen_range(b'a', b'z') as char)
+        .collect();
+    let (source_, target_) = (source.clone(), target.clone());
+    c.bench_function(
+        "sequence::match_::levenshtein_distance(&source, &target) (1000 chars)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance(&source_, &target_)),
+    );
+    c.bench_function(
+        "sequence::match_::levenshtein_myers(&source, &target) (1000 chars)",
+        move |b| b.iter(|| sequence::match_::levenshtein_myers(&source, &target)),
+    );
+
+    let source: Vec<u32> = (0..2000).collect();
+    let target: Vec<u32> = (0..2000).rev().collect();
+    c.bench_function(
+        "sequence::match_::levenshtein_distance(&source, &target) (2000 items)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance(&source, &target)),
+    );
+
+    let source: Vec<u32> = (0..1000).collect();
+    let target: Vec<u32> = (0..1000)
+        .map(|x| if x % 10 == 0 { x + 1 } else { x })
+        .collect();
+    let (source_, target_) = (source.clone(), target.clone());
+    c.bench_function(
+        "sequence::match_::levenshtein_distance(&source, &target) (distance 100)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance(&source_, &target_)),
+    );
+    c.bench_function(
+        "sequence::match_::levenshtein_distance_bounded(&source, &target, 3) (distance 100)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance_bounded(&source, &target, 3)),
+    );
+
+    let patterns: Vec<Vec<u8>> = (0..100)
+        .map(|_| (0..8).map(|_| rng.gen_range(b'a', b'e')).collect())
+        .collect();
+    let corpus: Vec<u8> = (0..100_000).map(|_| rng.gen_range(b'a', b'e')).collect();
+    let sparse = sequence::match_::aho_corasick::AhoCorasick::new(&patterns);
+    let dense = sequence::match_::aho_corasick::AhoCorasick::with_alphabet(
+        &patterns,
+        sequence::match_::ByteAlphabet,
+    );
+    let corpus_ = corpus.clone();
+    c.bench_function(
+        "sequence::match_::aho_corasick::AhoCorasick::new(&patterns).find_all(&corpus) (100KB)",
+        move |b| b.iter(|| sparse.find_all(&corpus_).len()),
+    );
+    c.bench_function(
+        "sequence::match_::aho_corasick::AhoCorasick::with_alphabet(&patterns, ByteAlphabet)\
+         .find_all(&corpus) (100KB)",
+        move |b| b.iter(|| dense.find_all(&corpus).len()),
+    );
+
+    // Trimming the common ends leaves one item of each in the middle, but nothing at the ends.
+    let source: Vec<u32> = (0..5_000).collect();
+    let mut target = source.clone();
+    target[2_500] = 0;
+    let (source_, target_) = (source.clone(), target.clone());
+    c.bench_function(
+        "sequence::match_::levenshtein_distance(&source, &target) (5_000 items, middle differs)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance(&source_, &target_)),
+    );
+    target[2_500] = 2_500;
+    target[0] = 1;
+    target[4_999] = 0;
+    c.bench_function(
+        "sequence::match_::levenshtein_distance(&source, &target) (5_000 items, ends differ)",
+        move |b| b.iter(|| sequence::match_::levenshtein_distance(&source, &target)),
+    );
+
     c.bench_function(
         "sequence::selection::quick_smallest(&mut sequence, k)",
         |b| {
@@ -36,6 +156,84 @@ fn sequence_benchmark(c: &mut Criterion) {
         },
     );
 
+    let mut random: Vec<i32> = (0..10_000).collect();
+    rand::thread_rng().shuffle(&mut random);
+    let sorted: Vec<i32> = (0..10_000).collect();
+    let duplicates: Vec<i32> = (0..10_000)
+        .map(|_| rand::thread_rng().gen_range(0, 10))
+        .collect();
+    // 90% of the items are equal, like the status codes of mostly successful requests.
+    let mostly_equal: Vec<i32> = (0..10_000)
+        .map(|i| {
+            if i % 10 == 0 {
+                rand::thread_rng().gen_range(100, 600)
+            } else {
+                200
+            }
+        })
+        .collect();
+    for &(name, input) in [
+        ("random", &random),
+        ("sorted", &sorted),
+        ("duplicates", &duplicates),
+        ("mostly_equal", &mostly_equal),
+    ]
+    .iter()
+    {
+        let input_ = input.clone();
+        c.bench_function(
+            &format!(
+                "sequence::selection::quick_smallest(&mut {}, 5_000) (n=10_000)",
+                name
+            ),
+            move |b| {
+                b.iter_with_setup(
+                    || input_.clone(),
+                    |mut list| *sequence::selection::quick_smallest(&mut list, 5_000),
+                )
+            },
+        );
+        let input_ = input.clone();
+        c.bench_function(
+            &format!(
+                "two-way partitioning quickselect (&mut {}, 5_000) (n=10_000)",
+                name
+            ),
+            move |b| {
+                b.iter_with_setup(
+                    || input_.clone(),
+                    |mut list| two_way_quick_smallest(&mut list, 5_000),
+                )
+            },
+        );
+        let input_ = input.clone();
+        c.bench_function(
+            &format!(
+                "sequence::selection::introselect(&mut {}, 5_000) (n=10_000)",
+                name
+            ),
+            move |b| {
+                b.iter_with_setup(
+                    || input_.clone(),
+                    |mut list| *sequence::selection::introselect(&mut list, 5_000),
+                )
+            },
+        );
+        let input_ = input.clone();
+        c.bench_function(
+            &format!(
+                "sequence::selection::median_of_medians(&mut {}, 5_000) (n=10_000)",
+                name
+            ),
+            move |b| {
+                b.iter_with_setup(
+                    || input_.clone(),
+                    |mut list| *sequence::selection::median_of_medians(&mut list, 5_000),
+                )
+            },
+        );
+    }
+
     c.bench_function(
         "sequence::search::ternary(search_target, |x| x.powf(x), 50.0, 1000.0, 0.0001)",
         |b| {
@@ -77,6 +275,18 @@ fn sequence_benchmark(c: &mut Criterion) {
         move |b| b.iter(|| sequence::search::interpolation(&sequence, &99)),
     );
 
+    let mut haystack = vec![0u8; 1 << 20];
+    *haystack.last_mut().unwrap() = 1;
+    let haystack_ = haystack.clone();
+    c.bench_function(
+        "sequence::search::linear_scan(&haystack, &1) (1MB)",
+        move |b| b.iter(|| sequence::search::linear_scan(&haystack_, &1)),
+    );
+    c.bench_function(
+        "sequence::search::linear_scan_simd(&haystack, 1) (1MB)",
+        move |b| b.iter(|| sequence::search::linear_scan_simd(&haystack, 1)),
+    );
+
     c.bench_function("sequence::permutation::HeapGen::new(sequence)", |b| {
         let sequence: Vec<i32> = (-100..100).collect();
         let mut gen = sequence::permutation::HeapGen::new(sequence);
@@ -88,7 +298,248 @@ fn sequence_benchmark(c: &mut Criterion) {
         let mut gen = sequence::permutation::SJTEven::new(sequence);
         b.iter(|| gen.next())
     });
+
+    // 100 sorted runs of 1000 items, interleaved so every item comes from the next run.
+    c.bench_function(
+        "sequence::merge::merge_sorted(iters).count() (k=100, n=1000)",
+        |b| {
+            b.iter(|| {
+                let iters: Vec<std::vec::IntoIter<u32>> = (0..100u32)
+                    .map(|i| {
+                        (0..1000)
+                            .map(|j| j * 100 + i)
+                            .collect::<Vec<u32>>()
+                            .into_iter()
+                    })
+                    .collect();
+                sequence::merge::merge_sorted(iters).count()
+            })
+        },
+    );
+}
+
+fn ds_benchmark(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    for &n in &[1_000, 1_000_000] {
+        let data: Vec<i32> = (0..n).map(|_| rng.gen()).collect();
+        let table = ds::sparse_table::SparseTable::new(&data);
+        let ranges: Vec<(usize, usize)> = (0..1024)
+            .map(|_| {
+                let l = rng.gen_range(0, data.len());
+                (l, rng.gen_range(l, data.len()))
+            })
+            .collect();
+        let mut i = 0;
+        c.bench_function(
+            &format!("ds::sparse_table::SparseTable::query(l, r) (n={})", n),
+            move |b| {
+                b.iter(|| {
+                    i = (i + 1) % ranges.len();
+                    table.query(ranges[i].0, ranges[i].1)
+                })
+            },
+        );
+    }
+
+    let operations: Vec<(u8, u32)> = (0..100_000)
+        .map(|_| (rng.gen_range(0, 3), rng.gen_range(0, 10_000)))
+        .collect();
+    let operations_ = operations.clone();
+    c.bench_function(
+        "ds::skip_list::SkipList (100_000 random operations)",
+        move |b| {
+            b.iter(|| {
+                let mut list = ds::skip_list::SkipList::new(16, 0.5);
+                let mut found = 0;
+                for &(operation, x) in &operations_ {
+                    match operation {
+                        0 => list.insert(x),
+                        1 => found += list.contains(&x) as u32,
+                        _ => found += list.remove(&x) as u32,
+                    }
+                }
+                found
+            })
+        },
+    );
+    let operations_ = operations.clone();
+    c.bench_function(
+        "ds::splay_tree::SplayTree (100_000 random operations)",
+        move |b| {
+            b.iter(|| {
+                let mut tree = ds::splay_tree::SplayTree::new();
+                let mut found = 0;
+                for &(operation, x) in &operations_ {
+                    match operation {
+                        0 => tree.insert(x),
+                        1 => found += tree.contains(&x) as u32,
+                        _ => found += tree.remove(&x) as u32,
+                    }
+                }
+                found
+            })
+        },
+    );
+    c.bench_function(
+        "std::collections::BTreeSet (100_000 random operations)",
+        move |b| {
+            b.iter(|| {
+                let mut set = BTreeSet::new();
+                let mut found = 0;
+                for &(operation, x) in &operations {
+                    match operation {
+                        0 => {
+                            set.insert(x);
+                        }
+                        1 => found += set.contains(&x) as u32,
+                        _ => found += set.remove(&x) as u32,
+                    }
+                }
+                found
+            })
+        },
+    );
+
+    let operations: Vec<(u8, u32)> = (0..100_000)
+        .map(|_| (rng.gen_range(0, 3), rng.gen_range(0, 1 << 20)))
+        .collect();
+    let operations_ = operations.clone();
+    c.bench_function(
+        "ds::van_emde_boas_tree::VanEmdeBoasTree (100_000 random operations)",
+        move |b| {
+            b.iter(|| {
+                let mut tree = ds::van_emde_boas_tree::VanEmdeBoasTree::new(1 << 20);
+                let mut found = 0;
+                for &(operation, x) in &operations_ {
+                    match operation {
+                        0 => tree.insert(x),
+                        1 => found += tree.successor(x).unwrap_or(0),
+                        _ => tree.delete(x),
+                    }
+                }
+                found
+            })
+        },
+    );
+    c.bench_function(
+        "std::collections::BTreeSet successor (100_000 random operations)",
+        move |b| {
+            b.iter(|| {
+                let mut set = BTreeSet::new();
+                let mut found = 0;
+                for &(operation, x) in &operations {
+                    match operation {
+                        0 => {
+                            set.insert(x);
+                        }
+                        1 => found += set.range(x + 1..).next().cloned().unwrap_or(0),
+                        _ => {
+                            set.remove(&x);
+                        }
+                    }
+                }
+                found
+            })
+        },
+    );
+}
+
+fn math_benchmark(c: &mut Criterion) {
+    // The logarithm of 3 to base 2 mod 1_000_003 is 254_277, so brute force takes long.
+    c.bench_function("math::number_theory::discrete_log(2, 3, 1_000_003)", |b| {
+        b.iter(|| math::number_theory::discrete_log(2, 3, 1_000_003))
+    });
+    c.bench_function("discrete_log(2, 3, 1_000_003) by brute force", |b| {
+        b.iter(|| {
+            let (mut power, mut x) = (1u64, 0);
+            while power != 3 {
+                power = power * 2 % 1_000_003;
+                x += 1;
+            }
+            x
+        })
+    });
+}
+
+fn dp_benchmark(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let weights: Vec<u64> = (0..100).map(|_| rng.gen_range(1, 1_000)).collect();
+    let values: Vec<u64> = (0..100).map(|_| rng.gen_range(1, 1_000)).collect();
+    c.bench_function(
+        "dp::knapsack::knapsack_01(&weights, &values, 10_000) (n=100)",
+        move |b| b.iter(|| dp::knapsack::knapsack_01(&weights, &values, 10_000)),
+    );
+}
+
+fn geometry_benchmark(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let points: Vec<geometry::Point> = (0..2_000)
+        .map(|_| geometry::Point::new(rng.gen_range(-1e4, 1e4), rng.gen_range(-1e4, 1e4)))
+        .collect();
+    let points_ = points.clone();
+    c.bench_function(
+        "geometry::closest_pair::closest_pair(&points) (n=2_000)",
+        move |b| b.iter(|| geometry::closest_pair::closest_pair(&points_)),
+    );
+    // Comparing every pair instead.
+    c.bench_function("closest pair by brute force (n=2_000)", move |b| {
+        b.iter(|| {
+            let mut best = (f64::INFINITY, 0, 0);
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    let (dx, dy) = (points[i].x - points[j].x, points[i].y - points[j].y);
+                    if dx * dx + dy * dy < best.0 {
+                        best = (dx * dx + dy * dy, i, j);
+                    }
+                }
+            }
+            best
+        })
+    });
+}
+
+#[cfg(feature = "parallel")]
+fn parallel_benchmark(c: &mut Criterion) {
+    use rayon::prelude::*;
+
+    c.bench_function(
+        "sequence::permutation::HeapGen::new(sequence).count() (n=8)",
+        |b| {
+            b.iter(|| {
+                let sequence: Vec<i32> = (0..8).collect();
+                sequence::permutation::HeapGen::new(sequence).count()
+            })
+        },
+    );
+
+    c.bench_function(
+        "sequence::permutation::par_permutations(sequence).count() (n=8)",
+        |b| {
+            b.iter(|| {
+                let sequence: Vec<i32> = (0..8).collect();
+                sequence::permutation::par_permutations(sequence).count()
+            })
+        },
+    );
 }
 
-criterion_group!(benches, sequence_benchmark);
+#[cfg(not(feature = "parallel"))]
+criterion_group!(
+    benches,
+    sequence_benchmark,
+    ds_benchmark,
+    math_benchmark,
+    dp_benchmark,
+    geometry_benchmark
+);
+#[cfg(feature = "parallel")]
+criterion_group!(
+    benches,
+    sequence_benchmark,
+    ds_benchmark,
+    math_benchmark,
+    dp_benchmark,
+    geometry_benchmark,
+    parallel_benchmark
+);
 criterion_main!(benches);
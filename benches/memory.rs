@@ -0,0 +1,68 @@
+//! Reports the peak heap memory used by the edit distance functions.
+//!
+//! Run it with `cargo bench --bench memory`.
+
+extern crate ult_algo;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use ult_algo::sequence::match_;
+
+/// Wraps the system allocator and keeps track of the peak number of allocated bytes.
+struct PeakAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAllocator = PeakAllocator;
+
+/// Runs f and returns the peak number of bytes it allocated on top of what was already allocated.
+fn peak_memory<F: FnOnce()>(f: F) -> usize {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    f();
+    PEAK.load(Ordering::SeqCst) - baseline
+}
+
+fn main() {
+    for &n in [100, 1_000, 10_000].iter() {
+        let source: Vec<u32> = (0..n).collect();
+        let target: Vec<u32> = (0..n).rev().collect();
+
+        let optimised = peak_memory(|| {
+            match_::levenshtein_distance_optimised(&source, &target);
+        });
+        println!(
+            "match_::levenshtein_distance_optimised (n={}): {} bytes",
+            n, optimised
+        );
+
+        // The full matrix of 10_000 * 10_000 u64 is ~800MB, so only the smaller sizes run.
+        if n <= 1_000 {
+            let full_matrix = peak_memory(|| {
+                match_::levenshtein_edits(&source, &target);
+            });
+            println!(
+                "match_::levenshtein_edits (full matrix, n={}): {} bytes",
+                n, full_matrix
+            );
+        }
+    }
+}
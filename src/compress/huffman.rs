@@ -0,0 +1,403 @@
+//! # Huffman
+//!
+//! A collection of functions to compress and decompress data with Huffman coding.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+
+/// # [Huffman Coding](https://en.wikipedia.org/wiki/Huffman_coding)
+///
+/// Compresses bytes into a self-contained format, so that decompression does not need anything
+/// but the compressed bytes:
+///
+/// * 8 bytes: number of original bytes (little-endian)
+/// * 2 bytes: number of distinct bytes *k* (little-endian), omitted if there are no bytes
+/// * *k* pairs of (byte, code length), sorted by byte; this is enough to rebuild the
+///   [canonical Huffman code](https://en.wikipedia.org/wiki/Canonical_Huffman_code)
+/// * the codes of all bytes, packed most significant bit first and padded with zeros
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::compress::huffman;
+///
+/// let input = b"abracadabra".to_vec();
+/// let compressed = huffman::huffman_compress_bytes(&input);
+/// assert_eq!(huffman::huffman_decompress_bytes(&compressed).unwrap(), input);
+/// ```
+pub fn huffman_compress_bytes(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    output.extend_from_slice(&(input.len() as u64).to_le_bytes());
+    if input.is_empty() {
+        return output;
+    }
+
+    let mut frequencies = [0u64; 256];
+    for &byte in input {
+        frequencies[byte as usize] += 1;
+    }
+    let lengths = code_lengths(&frequencies);
+    let codes = canonical_codes(&lengths);
+
+    // Write the code lengths of every byte that appears in the input.
+    let distinct = lengths.iter().filter(|&&length| length > 0).count();
+    output.extend_from_slice(&(distinct as u16).to_le_bytes());
+    for (byte, &length) in lengths.iter().enumerate() {
+        if length > 0 {
+            output.push(byte as u8);
+            output.push(length);
+        }
+    }
+
+    // Pack the codes, most significant bit first.
+    let (mut buffer, mut buffered_bits) = (0u8, 0);
+    for &byte in input {
+        let (code, length) = codes[byte as usize];
+        for shift in (0..length).rev() {
+            buffer = (buffer << 1) | ((code >> shift) & 1) as u8;
+            buffered_bits += 1;
+            if buffered_bits == 8 {
+                output.push(buffer);
+                buffer = 0;
+                buffered_bits = 0;
+            }
+        }
+    }
+    if buffered_bits > 0 {
+        output.push(buffer << (8 - buffered_bits)); // pad with zeros
+    }
+    output
+}
+
+/// Decompresses bytes compressed by `huffman_compress_bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::compress::huffman::{self, DecompressError};
+///
+/// let compressed = huffman::huffman_compress_bytes(b"hello, world");
+/// assert_eq!(huffman::huffman_decompress_bytes(&compressed).unwrap(), b"hello, world");
+/// assert_eq!(
+///     huffman::huffman_decompress_bytes(&compressed[..5]),
+///     Err(DecompressError::Truncated)
+/// );
+/// ```
+pub fn huffman_decompress_bytes(compressed: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    let mut reader = compressed.iter().cloned();
+    let mut size = [0u8; 8];
+    for byte in size.iter_mut() {
+        *byte = reader.next().ok_or(DecompressError::Truncated)?;
+    }
+    let size = u64::from_le_bytes(size) as usize;
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Read the code lengths.
+    let low = reader.next().ok_or(DecompressError::Truncated)?;
+    let high = reader.next().ok_or(DecompressError::Truncated)?;
+    let distinct = u16::from_le_bytes([low, high]) as usize;
+    if distinct == 0 || distinct > 256 {
+        return Err(DecompressError::InvalidHeader);
+    }
+    let mut lengths = [0u8; 256];
+    for _ in 0..distinct {
+        let byte = reader.next().ok_or(DecompressError::Truncated)?;
+        let length = reader.next().ok_or(DecompressError::Truncated)?;
+        if length == 0 || length > 64 || lengths[byte as usize] != 0 {
+            return Err(DecompressError::InvalidHeader);
+        }
+        lengths[byte as usize] = length;
+    }
+    let decoder = Decoder::new(&lengths)?;
+    // Every code takes at least one bit, which bounds the size the bit stream can hold.
+    if size > reader.len().saturating_mul(8) {
+        return Err(DecompressError::Truncated);
+    }
+
+    // Decode one bit at a time until every byte is recovered; the rest is padding.
+    let mut output = Vec::with_capacity(size);
+    let (mut code, mut length) = (0u64, 0u8);
+    'decoding: for packed in reader {
+        for shift in (0..8).rev() {
+            code = (code << 1) | ((packed >> shift) & 1) as u64;
+            length += 1;
+            if let Some(byte) = decoder.decode(code, length)? {
+                output.push(byte);
+                if output.len() == size {
+                    break 'decoding;
+                }
+                code = 0;
+                length = 0;
+            }
+        }
+    }
+
+    if output.len() < size {
+        Err(DecompressError::Truncated)
+    } else {
+        Ok(output)
+    }
+}
+
+/// Errors from decompressing Huffman-coded bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecompressError {
+    /// The compressed bytes end before all bytes are decoded
+    Truncated,
+    /// The code lengths do not describe a valid Huffman code
+    InvalidHeader,
+    /// The bit stream contains a code that does not belong to any byte
+    InvalidCode,
+}
+
+impl fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecompressError::Truncated => write!(f, "compressed bytes are truncated"),
+            DecompressError::InvalidHeader => write!(f, "code lengths are invalid"),
+            DecompressError::InvalidCode => write!(f, "bit stream contains an invalid code"),
+        }
+    }
+}
+
+impl Error for DecompressError {}
+
+/// Computes the Huffman code length of every byte from its frequency (0 for absent bytes).
+fn code_lengths(frequencies: &[u64; 256]) -> [u8; 256] {
+    // Nodes 0..256 are the leaves; merged nodes are appended along with their children.
+    let mut parents: Vec<usize> = vec![0; 256];
+    let mut heap = BinaryHeap::new();
+    for (byte, &frequency) in frequencies.iter().enumerate() {
+        if frequency > 0 {
+            heap.push(Reverse((frequency, byte)));
+        }
+    }
+
+    let mut lengths = [0u8; 256];
+    if heap.len() == 1 {
+        // A lone byte still needs one bit per occurrence.
+        let Reverse((_, byte)) = heap.pop().unwrap();
+        lengths[byte] = 1;
+        return lengths;
+    }
+
+    // Repeatedly merge the two least frequent nodes.
+    while heap.len() > 1 {
+        let Reverse((left_frequency, left)) = heap.pop().unwrap();
+        let Reverse((right_frequency, right)) = heap.pop().unwrap();
+        let merged = parents.len();
+        parents.push(merged); // the root is its own parent
+        parents[left] = merged;
+        parents[right] = merged;
+        heap.push(Reverse((left_frequency + right_frequency, merged)));
+    }
+
+    // The code length of a leaf is its depth in the tree.
+    for (byte, length) in lengths.iter_mut().enumerate() {
+        if frequencies[byte] > 0 {
+            let mut node = byte;
+            while parents[node] != node {
+                node = parents[node];
+                *length += 1;
+            }
+        }
+    }
+    lengths
+}
+
+/// Assigns canonical (code, length) pairs: shorter codes come first, and codes of the same
+/// length are consecutive numbers in byte order.
+fn canonical_codes(lengths: &[u8; 256]) -> [(u64, u8); 256] {
+    let mut codes = [(0u64, 0u8); 256];
+    let mut next_codes = first_codes(lengths);
+    for (byte, &length) in lengths.iter().enumerate() {
+        if length > 0 {
+            codes[byte] = (next_codes[length as usize], length);
+            next_codes[length as usize] += 1;
+        }
+    }
+    codes
+}
+
+/// Computes the first canonical code of every code length, indexed by length.
+fn first_codes(lengths: &[u8; 256]) -> [u64; 65] {
+    let mut counts = [0u64; 65];
+    for &length in lengths.iter() {
+        counts[length as usize] += 1;
+    }
+    counts[0] = 0;
+
+    let mut first_codes = [0u64; 65];
+    let mut code = 0u64;
+    for length in 1..65 {
+        code = (code + counts[length - 1]) << 1;
+        first_codes[length] = code;
+    }
+    first_codes
+}
+
+/// Maps canonical codes back to their bytes.
+struct Decoder {
+    /// First code of every code length
+    first_codes: [u64; 65],
+    /// Number of codes of every code length
+    counts: [u64; 65],
+    /// Position in `bytes` of the first byte of every code length
+    offsets: [usize; 65],
+    /// Bytes sorted by (code length, byte)
+    bytes: Vec<u8>,
+}
+
+impl Decoder {
+    fn new(lengths: &[u8; 256]) -> Result<Decoder, DecompressError> {
+        let mut counts = [0u64; 65];
+        for &length in lengths.iter().filter(|&&length| length > 0) {
+            counts[length as usize] += 1;
+        }
+
+        // Kraft's inequality must hold, otherwise some codes would collide.
+        let mut available = 1u128;
+        for &count in counts.iter().skip(1) {
+            available <<= 1;
+            if u128::from(count) > available {
+                return Err(DecompressError::InvalidHeader);
+            }
+            available -= u128::from(count);
+        }
+
+        let mut bytes: Vec<u8> = (0..256)
+            .filter(|&byte| lengths[byte] > 0)
+            .map(|byte| byte as u8)
+            .collect();
+        bytes.sort_by_key(|&byte| lengths[byte as usize]);
+        let mut offsets = [0usize; 65];
+        for length in 1..65 {
+            offsets[length] = offsets[length - 1] + counts[length - 1] as usize;
+        }
+
+        Ok(Decoder {
+            first_codes: first_codes(lengths),
+            counts,
+            offsets,
+            bytes,
+        })
+    }
+
+    /// Returns the byte of a code, if any byte has that code.
+    fn decode(&self, code: u64, length: u8) -> Result<Option<u8>, DecompressError> {
+        let length = length as usize;
+        if length > 64 {
+            return Err(DecompressError::InvalidCode);
+        }
+        let first_code = self.first_codes[length];
+        if code >= first_code && code - first_code < self.counts[length] {
+            let i = self.offsets[length] + (code - first_code) as usize;
+            Ok(Some(self.bytes[i]))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod huffman_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn random_bytes(len: usize) -> Vec<u8> {
+        let mut rng = test_util::rng();
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn round_trips_random_bytes() {
+        for &len in [0, 1, 100, 10_000].iter() {
+            let input = random_bytes(len);
+            let compressed = huffman_compress_bytes(&input);
+            assert_eq!(huffman_decompress_bytes(&compressed).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn round_trips_english_text() {
+        let input = "It is a truth universally acknowledged, that a single man in possession \
+                     of a good fortune, must be in want of a wife.\n\nHowever little known the \
+                     feelings or views of such a man may be on his first entering a \
+                     neighbourhood, this truth is so well fixed in the minds of the \
+                     surrounding families, that he is considered the rightful property of \
+                     some one or other of their daughters.\n"
+            .repeat(20)
+            .into_bytes();
+        let compressed = huffman_compress_bytes(&input);
+        assert!(compressed.len() < input.len() * 2 / 3);
+        assert_eq!(huffman_decompress_bytes(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn round_trips_single_distinct_byte() {
+        let input = vec![b'a'; 1000];
+        let compressed = huffman_compress_bytes(&input);
+        assert_eq!(huffman_decompress_bytes(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn round_trips_skewed_frequencies() {
+        // Fibonacci-like frequencies make for a deep tree.
+        let mut input = vec![];
+        let (mut a, mut b) = (1, 1);
+        for byte in 0..20u8 {
+            input.extend(vec![byte; a]);
+            let next = a + b;
+            a = b;
+            b = next;
+        }
+        let compressed = huffman_compress_bytes(&input);
+        assert_eq!(huffman_decompress_bytes(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn receives_truncated_bytes() {
+        let compressed = huffman_compress_bytes(b"hello, world");
+        for len in 0..compressed.len() {
+            assert!(huffman_decompress_bytes(&compressed[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn receives_size_beyond_bit_stream() {
+        let mut compressed = (i64::MAX as u64).to_le_bytes().to_vec();
+        compressed.extend_from_slice(&[2, 0, b'a', 1, b'b', 1, 0]);
+        assert_eq!(
+            huffman_decompress_bytes(&compressed),
+            Err(DecompressError::Truncated)
+        );
+    }
+
+    #[test]
+    fn receives_invalid_header() {
+        // Three bytes with 1-bit codes cannot be told apart.
+        let mut compressed = 3u64.to_le_bytes().to_vec();
+        compressed.extend_from_slice(&[3, 0, b'a', 1, b'b', 1, b'c', 1, 0]);
+        assert_eq!(
+            huffman_decompress_bytes(&compressed),
+            Err(DecompressError::InvalidHeader)
+        );
+    }
+
+    #[test]
+    fn receives_invalid_code() {
+        // Only "0" is a valid code, so "1" never decodes.
+        let mut compressed = 1u64.to_le_bytes().to_vec();
+        compressed.extend_from_slice(&[1, 0, b'a', 1]);
+        compressed.extend_from_slice(&[0xff; 9]);
+        assert_eq!(
+            huffman_decompress_bytes(&compressed),
+            Err(DecompressError::InvalidCode)
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! # Compress
+//!
+//! A collection of modules containing compression algorithms.
+
+pub mod huffman;
@@ -0,0 +1,159 @@
+//! # Activity Selection
+//!
+//! A collection of functions to schedule activities that compete for a single resource.
+
+/// An activity that holds the resource from its start time until, but not including, its finish
+/// time, so one activity may start exactly when another finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Activity {
+    pub start: u64,
+    pub finish: u64,
+    pub id: usize,
+}
+
+/// # [Activity Selection Problem](https://en.wikipedia.org/wiki/Activity_selection_problem)
+///
+/// Selects the largest set of activities that do not overlap in O(n log n) time. The activities
+/// are sorted in place by finish time, then each one is greedily selected if it starts no
+/// earlier than the last selected activity finishes: finishing as early as possible leaves the
+/// most room for the others. Returns the ids of the selected activities, sorted by finish time.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::activity_selection::{self, Activity};
+///
+/// let mut activities = [
+///     Activity { start: 1, finish: 4, id: 0 },
+///     Activity { start: 3, finish: 5, id: 1 },
+///     Activity { start: 0, finish: 6, id: 2 },
+///     Activity { start: 5, finish: 7, id: 3 },
+///     Activity { start: 8, finish: 9, id: 4 },
+/// ];
+/// assert_eq!(activity_selection::activity_selection(&mut activities), vec![0, 3, 4]);
+/// ```
+///
+/// # Panics
+///
+/// * activities should not finish before they start
+pub fn activity_selection(activities: &mut [Activity]) -> Vec<usize> {
+    if activities
+        .iter()
+        .any(|activity| activity.start > activity.finish)
+    {
+        panic!("activities should not finish before they start");
+    }
+    activities.sort_by_key(|activity| (activity.finish, activity.start));
+
+    let mut selected = Vec::new();
+    let mut free_from = 0;
+    for activity in activities.iter() {
+        if activity.start >= free_from {
+            selected.push(activity.id);
+            free_from = activity.finish;
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod activity_selection_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn activities(times: &[(u64, u64)]) -> Vec<Activity> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(id, &(start, finish))| Activity { start, finish, id })
+            .collect()
+    }
+
+    #[test]
+    fn receives_classic_example() {
+        // Taking the earliest-starting activity (id 0) only leaves room for one more.
+        let mut activities = activities(&[
+            (0, 10),
+            (1, 4),
+            (3, 5),
+            (0, 6),
+            (5, 7),
+            (3, 9),
+            (5, 9),
+            (6, 10),
+            (8, 11),
+            (8, 12),
+            (2, 14),
+            (12, 16),
+        ]);
+        assert_eq!(activity_selection(&mut activities), vec![1, 4, 8, 11]);
+    }
+
+    #[test]
+    fn selects_disjoint_activities() {
+        assert_eq!(
+            activity_selection(&mut activities(&[(3, 4), (1, 2)])),
+            vec![1, 0]
+        );
+        // Finishing when the other starts is not an overlap.
+        assert_eq!(
+            activity_selection(&mut activities(&[(2, 4), (1, 2)])),
+            vec![1, 0]
+        );
+    }
+
+    #[test]
+    fn selects_one_of_overlapping_activities() {
+        assert_eq!(
+            activity_selection(&mut activities(&[(1, 5), (2, 3)])),
+            vec![1]
+        );
+        assert_eq!(
+            activity_selection(&mut activities(&[(2, 6), (2, 6), (2, 6)])),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn receives_empty_input() {
+        assert_eq!(activity_selection(&mut []), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "activities should not finish before they start")]
+    fn receives_invalid_activity() {
+        activity_selection(&mut activities(&[(1, 2), (5, 4)]));
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let times: Vec<(u64, u64)> = (0..rng.gen_range(0, 12))
+                .map(|_| {
+                    let start = rng.gen_range(0, 20);
+                    (start, start + rng.gen_range(0, 8))
+                })
+                .collect();
+            let largest = (0..1u32 << times.len())
+                .filter(|subset| {
+                    let mut chosen: Vec<(u64, u64)> = (0..times.len())
+                        .filter(|&i| subset >> i & 1 == 1)
+                        .map(|i| times[i])
+                        .collect();
+                    chosen.sort();
+                    chosen.windows(2).all(|pair| pair[0].1 <= pair[1].0)
+                })
+                .map(|subset| subset.count_ones() as usize)
+                .max()
+                .unwrap();
+
+            let selected = activity_selection(&mut activities(&times));
+            assert_eq!(selected.len(), largest);
+            for pair in selected.windows(2) {
+                assert!(times[pair[0]].1 <= times[pair[1]].0);
+            }
+        }
+    }
+}
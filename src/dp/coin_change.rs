@@ -0,0 +1,136 @@
+//! # Coin Change
+//!
+//! A collection of functions to make an amount out of coins of given denominations.
+
+/// # [Change-making Problem](https://en.wikipedia.org/wiki/Change-making_problem)
+///
+/// Finds the fewest coins, with every denomination available any number of times, that add up
+/// to the amount in O(n · amount) time, or `None` if no coins do. Zero-valued and repeated
+/// denominations are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::coin_change;
+///
+/// assert_eq!(coin_change::coin_change(&[1, 3, 4], 6), Some(2));
+/// assert_eq!(coin_change::coin_change(&[3], 1), None);
+/// ```
+pub fn coin_change(coins: &[u64], amount: u64) -> Option<u64> {
+    let coins = denominations(coins);
+    let amount = amount as usize;
+    // fewest[a] is the fewest coins adding up to a, if any.
+    let mut fewest: Vec<Option<u64>> = vec![None; amount + 1];
+    fewest[0] = Some(0);
+    for a in 1..=amount {
+        fewest[a] = coins
+            .iter()
+            .filter(|&&coin| coin <= a)
+            .filter_map(|&coin| fewest[a - coin])
+            .min()
+            .map(|count| count + 1);
+    }
+    fewest[amount]
+}
+
+/// Counts the distinct multisets of coins, with every denomination available any number of
+/// times, that add up to the amount in O(n · amount) time. The amount 0 is made in one way, with
+/// no coins. Zero-valued and repeated denominations are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::coin_change;
+///
+/// // 1 + 1 + 1 + 1, 1 + 1 + 2, 2 + 2 and 1 + 3
+/// assert_eq!(coin_change::coin_change_ways(&[1, 2, 3], 4), 4);
+/// ```
+pub fn coin_change_ways(coins: &[u64], amount: u64) -> u64 {
+    let amount = amount as usize;
+    // After each coin, ways[a] counts the ways to make a out of the coins so far, so that every
+    // multiset is counted once in the order of the coins.
+    let mut ways = vec![0u64; amount + 1];
+    ways[0] = 1;
+    for coin in denominations(coins) {
+        for a in coin..=amount {
+            ways[a] += ways[a - coin];
+        }
+    }
+    ways[amount]
+}
+
+/// Returns the distinct non-zero denominations.
+fn denominations(coins: &[u64]) -> Vec<usize> {
+    let mut coins: Vec<usize> = coins
+        .iter()
+        .filter(|&&coin| coin > 0)
+        .map(|&coin| coin as usize)
+        .collect();
+    coins.sort_unstable();
+    coins.dedup();
+    coins
+}
+
+#[cfg(test)]
+mod coin_change_tests {
+    use super::*;
+
+    #[test]
+    fn receives_zero_amount() {
+        assert_eq!(coin_change(&[1, 2], 0), Some(0));
+        assert_eq!(coin_change(&[], 0), Some(0));
+    }
+
+    #[test]
+    fn makes_exact_amount() {
+        assert_eq!(coin_change(&[1, 2, 5], 11), Some(3));
+        assert_eq!(coin_change(&[2, 5], 3), None);
+        assert_eq!(coin_change(&[7], 21), Some(3));
+    }
+
+    #[test]
+    fn prefers_fewer_coins_to_greedy_choice() {
+        assert_eq!(coin_change(&[1, 3, 4], 6), Some(2));
+    }
+
+    #[test]
+    fn receives_impossible_amount() {
+        assert_eq!(coin_change(&[3], 1), None);
+        assert_eq!(coin_change(&[], 5), None);
+        assert_eq!(coin_change(&[0], 5), None);
+    }
+
+    #[test]
+    fn receives_duplicate_coins() {
+        assert_eq!(coin_change(&[2, 2, 3, 3], 7), Some(3));
+    }
+}
+
+#[cfg(test)]
+mod coin_change_ways_tests {
+    use super::*;
+
+    #[test]
+    fn receives_zero_amount() {
+        assert_eq!(coin_change_ways(&[1, 2], 0), 1);
+        assert_eq!(coin_change_ways(&[], 0), 1);
+    }
+
+    #[test]
+    fn receives_impossible_amount() {
+        assert_eq!(coin_change_ways(&[3], 1), 0);
+    }
+
+    #[test]
+    fn receives_duplicate_coins() {
+        assert_eq!(coin_change_ways(&[1, 2, 2, 1, 0], 4), 3);
+    }
+
+    #[test]
+    fn receives_us_coins() {
+        let coins = [1, 5, 10, 25, 50, 100];
+        assert_eq!(coin_change_ways(&coins, 10), 4);
+        assert_eq!(coin_change_ways(&coins[..5], 100), 292);
+        assert_eq!(coin_change_ways(&coins, 100), 293);
+    }
+}
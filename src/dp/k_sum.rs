@@ -0,0 +1,296 @@
+//! # K-Sum
+//!
+//! A collection of functions to find items of a sequence that add up to a target.
+
+use std::collections::HashMap;
+
+/// # [2-Sum](https://en.wikipedia.org/wiki/3SUM)
+///
+/// Finds two items at different indices that add up to the target in O(n) time, remembering the
+/// index of every value seen so far in a hash map. The indices (i, j), with i < j, are those of
+/// the first pair to be completed, that is the one with the smallest j, then the smallest i.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::k_sum;
+///
+/// assert_eq!(k_sum::two_sum(&[2, 7, 11, 15], 9), Some((0, 1)));
+/// assert_eq!(k_sum::two_sum(&[3, 3], 6), Some((0, 1)));
+/// assert_eq!(k_sum::two_sum(&[3], 6), None);
+/// ```
+pub fn two_sum(nums: &[i64], target: i64) -> Option<(usize, usize)> {
+    let mut seen: HashMap<i64, usize> = HashMap::new();
+    for (j, &num) in nums.iter().enumerate() {
+        if let Some(&i) = target.checked_sub(num).and_then(|rest| seen.get(&rest)) {
+            return Some((i, j));
+        }
+        seen.entry(num).or_insert(j);
+    }
+    None
+}
+
+/// # [3-Sum](https://en.wikipedia.org/wiki/3SUM)
+///
+/// Finds every distinct triple of values at different indices that add up to the target in
+/// O(n²) time: once the items are sorted, the first item is fixed and the other two are found by
+/// moving two pointers towards each other. Each triple is given by the indices of its items in
+/// ascending order, and the triples are ordered by their sorted values. Sums are computed without
+/// overflowing.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::k_sum;
+///
+/// // -1 + -1 + 2 and -1 + 0 + 1
+/// assert_eq!(k_sum::three_sum(&[-1, 0, 1, 2, -1, -4], 0), vec![(0, 3, 4), (0, 1, 2)]);
+/// ```
+pub fn three_sum(nums: &[i64], target: i64) -> Vec<(usize, usize, usize)> {
+    let mut order: Vec<usize> = (0..nums.len()).collect();
+    order.sort_by_key(|&i| (nums[i], i));
+    let value = |w: usize| i128::from(nums[order[w]]);
+
+    let mut found = Vec::new();
+    let n = order.len();
+    for first in 0..n {
+        if first > 0 && value(first) == value(first - 1) {
+            continue;
+        }
+        let (mut low, mut high) = (first + 1, n.saturating_sub(1));
+        while low < high {
+            let sum = value(first) + value(low) + value(high);
+            if sum < i128::from(target) {
+                low += 1;
+            } else if sum > i128::from(target) {
+                high -= 1;
+            } else {
+                let mut indices = [order[first], order[low], order[high]];
+                indices.sort();
+                found.push((indices[0], indices[1], indices[2]));
+                // Skip the other items with the same values.
+                while low < high && value(low) == value(low + 1) {
+                    low += 1;
+                }
+                low += 1;
+                high -= 1;
+            }
+        }
+    }
+    found
+}
+
+/// # [K-Sum](https://en.wikipedia.org/wiki/3SUM)
+///
+/// Finds every distinct combination of k values at different indices that add up to the target
+/// in O(n^(k - 1)) time for k >= 2, by fixing one item at a time and recursing until two items
+/// remain, which two pointers find in a linear scan. The items are sorted in place; each
+/// combination is sorted, and the combinations are ordered lexicographically. Sums are computed
+/// without overflowing. For k = 0, the only combination is the empty one if the target is 0.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::k_sum;
+///
+/// let mut nums = vec![1, 0, -1, 0, -2, 2];
+/// assert_eq!(
+///     k_sum::k_sum(&mut nums, 0, 4),
+///     vec![vec![-2, -1, 1, 2], vec![-2, 0, 0, 2], vec![-1, 0, 0, 1]]
+/// );
+/// assert_eq!(nums, vec![-2, -1, 0, 0, 1, 2]);
+/// ```
+pub fn k_sum(nums: &mut [i64], target: i64, k: usize) -> Vec<Vec<i64>> {
+    nums.sort();
+    let mut found = Vec::new();
+    if k > nums.len() {
+        return found; // not enough items to choose from
+    }
+    k_sum_sorted(
+        nums,
+        i128::from(target),
+        k,
+        &mut Vec::with_capacity(k),
+        &mut found,
+    );
+    found
+}
+
+/// Appends to `found` every combination of k of the sorted items adding up to the target,
+/// each following the items already in `prefix`.
+fn k_sum_sorted(
+    nums: &[i64],
+    target: i128,
+    k: usize,
+    prefix: &mut Vec<i64>,
+    found: &mut Vec<Vec<i64>>,
+) {
+    match k {
+        0 => {
+            if target == 0 {
+                found.push(prefix.clone());
+            }
+        }
+        1 => {
+            if nums
+                .binary_search_by_key(&target, |&num| i128::from(num))
+                .is_ok()
+            {
+                let mut combination = prefix.clone();
+                combination.push(target as i64);
+                found.push(combination);
+            }
+        }
+        2 => {
+            let (mut low, mut high) = (0, nums.len().saturating_sub(1));
+            while low < high {
+                let sum = i128::from(nums[low]) + i128::from(nums[high]);
+                if sum < target || (low > 0 && nums[low] == nums[low - 1]) {
+                    low += 1;
+                } else if sum > target {
+                    high -= 1;
+                } else {
+                    let mut combination = prefix.clone();
+                    combination.extend_from_slice(&[nums[low], nums[high]]);
+                    found.push(combination);
+                    low += 1;
+                    high -= 1;
+                }
+            }
+        }
+        _ => {
+            for first in 0..nums.len() {
+                if first > 0 && nums[first] == nums[first - 1] {
+                    continue;
+                }
+                prefix.push(nums[first]);
+                let rest = target - i128::from(nums[first]);
+                k_sum_sorted(&nums[first + 1..], rest, k - 1, prefix, found);
+                prefix.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod k_sum_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn finds_two_sum_anywhere() {
+        assert_eq!(two_sum(&[4, 6, 1, 8, 3], 10), Some((0, 1)));
+        assert_eq!(two_sum(&[5, 1, 9, 2, 7], 9), Some((3, 4)));
+        assert_eq!(two_sum(&[5, 1, 8, 3, 20], 11), Some((2, 3)));
+        assert_eq!(two_sum(&[1, 20, 8, 3, 6], 9), Some((0, 2)));
+    }
+
+    #[test]
+    fn finds_no_two_sum() {
+        assert_eq!(two_sum(&[1, 2, 3], 7), None);
+        assert_eq!(two_sum(&[5], 10), None);
+        assert_eq!(two_sum(&[], 0), None);
+        assert_eq!(two_sum(&[i64::MIN, -1], i64::MAX), None);
+    }
+
+    #[test]
+    fn receives_duplicate_values() {
+        assert_eq!(two_sum(&[3, 2, 3, 3], 6), Some((0, 2)));
+        assert_eq!(two_sum(&[0, 0], 0), Some((0, 1)));
+        assert_eq!(three_sum(&[0, 0, 0, 0], 0).len(), 1);
+        assert_eq!(k_sum(&mut [2, 2, 2, 2, 2], 8, 4), vec![vec![2, 2, 2, 2]]);
+    }
+
+    #[test]
+    fn finds_multiple_three_sums() {
+        let nums = [-4, -2, -2, 0, 1, 2, 3, 4, 6];
+        let triples = three_sum(&nums, 0);
+        let values: Vec<[i64; 3]> = triples
+            .iter()
+            .map(|&(i, j, k)| [nums[i], nums[j], nums[k]])
+            .collect();
+        assert_eq!(
+            values,
+            vec![[-4, -2, 6], [-4, 0, 4], [-4, 1, 3], [-2, -2, 4], [-2, 0, 2],]
+        );
+        for &(i, j, k) in &triples {
+            assert!(i < j && j < k);
+        }
+        assert!(three_sum(&[1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn avoids_overflow() {
+        let nums = [i64::MAX, i64::MAX, i64::MIN, i64::MIN, 0];
+        assert_eq!(three_sum(&nums, i64::MAX - 1), vec![(0, 1, 2)]);
+        assert_eq!(
+            k_sum(&mut nums.clone(), -1, 3),
+            vec![vec![i64::MIN, 0, i64::MAX]]
+        );
+    }
+
+    #[test]
+    fn finds_four_sums() {
+        let mut nums = [2, 2, 2, 2, 2];
+        assert_eq!(k_sum(&mut nums, 8, 4), vec![vec![2; 4]]);
+        let mut nums = [1, 0, -1, 0, -2, 2];
+        assert_eq!(
+            k_sum(&mut nums, 0, 4),
+            vec![vec![-2, -1, 1, 2], vec![-2, 0, 0, 2], vec![-1, 0, 0, 1]]
+        );
+    }
+
+    #[test]
+    fn receives_small_k() {
+        assert_eq!(k_sum(&mut [1, 2], 0, 0), vec![Vec::<i64>::new()]);
+        assert!(k_sum(&mut [1, 2], 1, 0).is_empty());
+        assert_eq!(k_sum(&mut [3, 1, 3], 3, 1), vec![vec![3]]);
+        assert_eq!(k_sum(&mut [3, 1, 2, 0], 3, 2), vec![vec![0, 3], vec![1, 2]]);
+        assert!(k_sum(&mut [1, 2], 3, 3).is_empty());
+    }
+
+    #[test]
+    fn receives_k_larger_than_len() {
+        let mut nums = [2, 1, 0];
+        assert!(k_sum(&mut nums, 3, 4).is_empty());
+        assert!(k_sum(&mut nums, 0, usize::MAX).is_empty());
+        assert_eq!(nums, [0, 1, 2]);
+    }
+
+    #[test]
+    fn agrees_with_every_combination() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let mut nums: Vec<i64> = test_util::random_vec(&mut rng, 0..10, -5..6);
+            let target = rng.gen_range(-8, 9);
+            let k = rng.gen_range(0, 5);
+            // Every subset of k indices, as sorted values.
+            let mut expected: Vec<Vec<i64>> = (0u32..1 << nums.len())
+                .filter(|mask| mask.count_ones() as usize == k)
+                .map(|mask| {
+                    let mut values: Vec<i64> = (0..nums.len())
+                        .filter(|i| mask >> i & 1 == 1)
+                        .map(|i| nums[i])
+                        .collect();
+                    values.sort();
+                    values
+                })
+                .filter(|values| values.iter().sum::<i64>() == target)
+                .collect();
+            expected.sort();
+            expected.dedup();
+
+            let triples: Vec<Vec<i64>> = three_sum(&nums, target)
+                .into_iter()
+                .map(|(i, j, k)| {
+                    let mut values = vec![nums[i], nums[j], nums[k]];
+                    values.sort();
+                    values
+                })
+                .collect();
+            assert_eq!(triples, k_sum(&mut nums.clone(), target, 3));
+            assert_eq!(k_sum(&mut nums, target, k), expected);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! # Knapsack
+//!
+//! A collection of functions to solve knapsack problems.
+
+/// # [0/1 Knapsack Problem](https://en.wikipedia.org/wiki/Knapsack_problem#0-1_knapsack_problem)
+///
+/// Selects items, each at most once, to maximize their total value while their total weight
+/// does not exceed the capacity, filling a table of the best value of each item prefix and
+/// capacity in O(nW) time and space. Returns the maximum value and the ascending indices of the
+/// selected items.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::knapsack;
+///
+/// let weights = [1, 3, 4, 5];
+/// let values = [1, 4, 5, 7];
+/// assert_eq!(knapsack::knapsack_01(&weights, &values, 7), (9, vec![1, 2]));
+/// ```
+///
+/// # Panics
+///
+/// * weights and values should have the same length
+pub fn knapsack_01(weights: &[u64], values: &[u64], capacity: u64) -> (u64, Vec<usize>) {
+    if weights.len() != values.len() {
+        panic!("weights and values should have the same length");
+    }
+    let capacity = capacity as usize;
+    let n = weights.len();
+
+    // best[i][w] is the best value of the first i items within weight w.
+    let mut best = vec![vec![0u64; capacity + 1]; n + 1];
+    for i in 1..=n {
+        let weight = weights[i - 1] as usize;
+        for w in 0..=capacity {
+            best[i][w] = best[i - 1][w];
+            if weight <= w {
+                best[i][w] = best[i][w].max(best[i - 1][w - weight] + values[i - 1]);
+            }
+        }
+    }
+
+    // An item is selected where taking it changed the best value.
+    let mut selected = Vec::new();
+    let mut w = capacity;
+    for i in (1..=n).rev() {
+        if best[i][w] != best[i - 1][w] {
+            selected.push(i - 1);
+            w -= weights[i - 1] as usize;
+        }
+    }
+    selected.reverse();
+    (best[n][capacity], selected)
+}
+
+#[cfg(test)]
+mod knapsack_01_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn total(items: &[u64], selected: &[usize]) -> u64 {
+        selected.iter().map(|&i| items[i]).sum()
+    }
+
+    #[test]
+    fn receives_textbook_example() {
+        let weights = [10, 20, 30];
+        let values = [60, 100, 120];
+        assert_eq!(knapsack_01(&weights, &values, 50), (220, vec![1, 2]));
+    }
+
+    #[test]
+    fn receives_zero_capacity() {
+        assert_eq!(knapsack_01(&[1, 2, 3], &[10, 20, 30], 0), (0, vec![]));
+    }
+
+    #[test]
+    fn fits_all_items_exactly() {
+        assert_eq!(
+            knapsack_01(&[1, 2, 3], &[10, 20, 30], 6),
+            (60, vec![0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn leaves_items_out() {
+        let weights = [5, 4, 6, 3];
+        let values = [10, 40, 30, 50];
+        assert_eq!(knapsack_01(&weights, &values, 10), (90, vec![1, 3]));
+    }
+
+    #[test]
+    fn receives_empty_items() {
+        assert_eq!(knapsack_01(&[], &[], 100), (0, vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "weights and values should have the same length")]
+    fn receives_mismatched_lengths() {
+        knapsack_01(&[1, 2], &[1], 10);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 10);
+            let weights: Vec<u64> = (0..n).map(|_| rng.gen_range(1, 20)).collect();
+            let values: Vec<u64> = (0..n).map(|_| rng.gen_range(0, 50)).collect();
+            let capacity = rng.gen_range(0, 60);
+            let best = (0..1u32 << n)
+                .map(|subset| {
+                    let items: Vec<usize> = (0..n).filter(|&i| subset >> i & 1 == 1).collect();
+                    (total(&weights, &items), total(&values, &items))
+                })
+                .filter(|&(weight, _)| weight <= capacity)
+                .map(|(_, value)| value)
+                .max()
+                .unwrap_or(0);
+
+            let (value, selected) = knapsack_01(&weights, &values, capacity);
+            assert_eq!(value, best);
+            assert_eq!(total(&values, &selected), value);
+            assert!(total(&weights, &selected) <= capacity);
+        }
+    }
+}
+
+/// # [Fractional Knapsack Problem](https://en.wikipedia.org/wiki/Continuous_knapsack_problem)
+///
+/// Selects fractions of items to maximize their total value while their total weight does not
+/// exceed the capacity, where taking a fraction of an item takes that fraction of its weight and
+/// value. Greedily taking the items in descending order of value per weight, the last one in
+/// part, is optimal, in O(n log n) time. Returns the maximum value and the fraction in
+/// [0.0, 1.0] taken of each item. Items without a positive value are never taken.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::knapsack;
+///
+/// let weights = [10.0, 20.0, 30.0];
+/// let values = [60.0, 100.0, 120.0];
+/// let (value, fractions) = knapsack::knapsack_fractional(&weights, &values, 50.0);
+/// assert_eq!(value, 240.0);
+/// assert_eq!(fractions, vec![1.0, 1.0, 2.0 / 3.0]);
+/// ```
+///
+/// # Panics
+///
+/// * weights and values should have the same length
+/// * weights should not be negative or NaN
+/// * values should not be NaN
+pub fn knapsack_fractional(weights: &[f64], values: &[f64], capacity: f64) -> (f64, Vec<f64>) {
+    if weights.len() != values.len() {
+        panic!("weights and values should have the same length");
+    }
+    if !weights.iter().all(|&weight| weight >= 0.0) {
+        panic!("weights should not be negative or NaN");
+    }
+    if values.iter().any(|value| value.is_nan()) {
+        panic!("values should not be NaN");
+    }
+
+    // Items without weight have an infinite ratio, so they are taken first.
+    let mut order: Vec<usize> = (0..weights.len()).filter(|&i| values[i] > 0.0).collect();
+    order.sort_by(|&i, &j| {
+        let ratio = |k: usize| values[k] / weights[k];
+        ratio(j).partial_cmp(&ratio(i)).unwrap()
+    });
+
+    let mut fractions = vec![0.0; weights.len()];
+    let mut value = 0.0;
+    let mut remaining = capacity.max(0.0);
+    for i in order {
+        if weights[i] <= remaining {
+            fractions[i] = 1.0;
+            value += values[i];
+            remaining -= weights[i];
+        } else {
+            fractions[i] = remaining / weights[i];
+            value += values[i] * fractions[i];
+            break;
+        }
+    }
+    (value, fractions)
+}
+
+#[cfg(test)]
+mod knapsack_fractional_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn total(items: &[f64], fractions: &[f64]) -> f64 {
+        items
+            .iter()
+            .zip(fractions)
+            .map(|(item, fraction)| item * fraction)
+            .sum()
+    }
+
+    #[test]
+    fn fits_all_items() {
+        let (value, fractions) = knapsack_fractional(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0], 10.0);
+        assert_eq!(value, 6.0);
+        assert_eq!(fractions, vec![1.0; 3]);
+    }
+
+    #[test]
+    fn takes_part_of_one_item() {
+        let (value, fractions) = knapsack_fractional(&[4.0], &[10.0], 1.0);
+        assert_eq!(value, 2.5);
+        assert_eq!(fractions, vec![0.25]);
+
+        let (value, fractions) = knapsack_fractional(&[4.0, 2.0, 8.0], &[8.0, 6.0, 8.0], 3.0);
+        assert_eq!(value, 8.0);
+        assert_eq!(fractions, vec![0.25, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn receives_edge_items() {
+        // Weightless items are taken whole, worthless ones are left out.
+        let (value, fractions) =
+            knapsack_fractional(&[0.0, 1.0, 5.0, 2.0], &[4.0, 0.0, 10.0, -1.0], 2.0);
+        assert_eq!(value, 8.0);
+        assert_eq!(fractions, vec![1.0, 0.0, 0.4, 0.0]);
+        assert_eq!(knapsack_fractional(&[1.0], &[1.0], 0.0), (0.0, vec![0.0]));
+        assert_eq!(knapsack_fractional(&[], &[], 5.0), (0.0, vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "weights should not be negative or NaN")]
+    fn receives_negative_weight() {
+        knapsack_fractional(&[1.0, -1.0], &[1.0, 1.0], 1.0);
+    }
+
+    #[test]
+    fn relaxes_01_knapsack() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(0, 12);
+            let weights: Vec<u64> = (0..n).map(|_| rng.gen_range(1, 20)).collect();
+            let values: Vec<u64> = (0..n).map(|_| rng.gen_range(1, 50)).collect();
+            let capacity = rng.gen_range(0, 80);
+            let (best_01, _) = knapsack_01(&weights, &values, capacity);
+
+            let weights: Vec<f64> = weights.into_iter().map(|weight| weight as f64).collect();
+            let values: Vec<f64> = values.into_iter().map(|value| value as f64).collect();
+            let (value, fractions) = knapsack_fractional(&weights, &values, capacity as f64);
+            assert!(value >= best_01 as f64 - 1e-9);
+            assert!((total(&values, &fractions) - value).abs() < 1e-9);
+            assert!(fractions
+                .iter()
+                .all(|&fraction| (0.0..=1.0).contains(&fraction)));
+            // At most one item is taken in part.
+            assert!(fractions.iter().filter(|&&f| f > 0.0 && f < 1.0).count() <= 1);
+
+            let weight = total(&weights, &fractions);
+            if fractions.iter().all(|&fraction| fraction == 1.0) {
+                assert!(weight <= capacity as f64);
+            } else {
+                assert!((weight - capacity as f64).abs() < 1e-9);
+            }
+        }
+    }
+}
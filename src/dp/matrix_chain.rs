@@ -0,0 +1,120 @@
+//! # Matrix Chain
+//!
+//! A collection of functions to order the multiplications of a chain of matrices.
+
+/// # [Matrix Chain Multiplication](https://en.wikipedia.org/wiki/Matrix_chain_multiplication)
+///
+/// Finds the fewest scalar multiplications needed to multiply a chain of n matrices, where
+/// matrix i is `dims[i] × dims[i + 1]`, by trying every last split of every subchain in O(n³)
+/// time. Returns the cost and a table where `split[i][j]` is the matrix after which the subchain
+/// of matrices i to j (inclusive, 0-based) is split; only entries with i < j are meaningful.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::matrix_chain;
+///
+/// let dims = [10, 100, 5, 50];
+/// let (cost, split) = matrix_chain::matrix_chain_order(&dims);
+/// assert_eq!(cost, 7500);
+/// assert_eq!(matrix_chain::matrix_chain_parenthesise(&dims, &split), "((A1 A2) A3)");
+/// ```
+pub fn matrix_chain_order(dims: &[usize]) -> (u64, Vec<Vec<usize>>) {
+    let n = dims.len().saturating_sub(1);
+    // cost[i][j] is the fewest multiplications for matrices i to j.
+    let mut cost = vec![vec![0u64; n]; n];
+    let mut split = vec![vec![0; n]; n];
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len - 1;
+            cost[i][j] = u64::MAX;
+            for k in i..j {
+                let product = (dims[i] * dims[k + 1] * dims[j + 1]) as u64;
+                let candidate = cost[i][k] + cost[k + 1][j] + product;
+                if candidate < cost[i][j] {
+                    cost[i][j] = candidate;
+                    split[i][j] = k;
+                }
+            }
+        }
+    }
+    let total = if n == 0 { 0 } else { cost[0][n - 1] };
+    (total, split)
+}
+
+/// Renders the order of multiplications from the split table of `matrix_chain_order`, naming
+/// the matrices A1 to An and parenthesising every product of two subchains. An empty chain is
+/// rendered as an empty string.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::matrix_chain;
+///
+/// let dims = [30, 35, 15, 5, 10, 20, 25];
+/// let (_, split) = matrix_chain::matrix_chain_order(&dims);
+/// assert_eq!(
+///     matrix_chain::matrix_chain_parenthesise(&dims, &split),
+///     "((A1 (A2 A3)) ((A4 A5) A6))"
+/// );
+/// ```
+pub fn matrix_chain_parenthesise(dims: &[usize], split: &[Vec<usize>]) -> String {
+    let n = dims.len().saturating_sub(1);
+    let mut rendered = String::new();
+    if n > 0 {
+        render(split, 0, n - 1, &mut rendered);
+    }
+    rendered
+}
+
+fn render(split: &[Vec<usize>], i: usize, j: usize, rendered: &mut String) {
+    if i == j {
+        rendered.push_str(&format!("A{}", i + 1));
+        return;
+    }
+    rendered.push('(');
+    render(split, i, split[i][j], rendered);
+    rendered.push(' ');
+    render(split, split[i][j] + 1, j, rendered);
+    rendered.push(')');
+}
+
+#[cfg(test)]
+mod matrix_chain_order_tests {
+    use super::*;
+
+    #[test]
+    fn receives_textbook_example() {
+        let dims = [30, 35, 15, 5, 10, 20, 25];
+        let (cost, split) = matrix_chain_order(&dims);
+        assert_eq!(cost, 15125);
+        assert_eq!(split[0][5], 2);
+        assert_eq!(
+            matrix_chain_parenthesise(&dims, &split),
+            "((A1 (A2 A3)) ((A4 A5) A6))"
+        );
+    }
+
+    #[test]
+    fn receives_single_matrix() {
+        let (cost, split) = matrix_chain_order(&[3, 4]);
+        assert_eq!(cost, 0);
+        assert_eq!(matrix_chain_parenthesise(&[3, 4], &split), "A1");
+    }
+
+    #[test]
+    fn receives_empty_chain() {
+        for dims in &[vec![], vec![5]] {
+            let (cost, split) = matrix_chain_order(dims);
+            assert_eq!(cost, 0);
+            assert_eq!(matrix_chain_parenthesise(dims, &split), "");
+        }
+    }
+
+    #[test]
+    fn receives_two_matrices() {
+        let (cost, split) = matrix_chain_order(&[2, 3, 4]);
+        assert_eq!(cost, 24);
+        assert_eq!(matrix_chain_parenthesise(&[2, 3, 4], &split), "(A1 A2)");
+    }
+}
@@ -0,0 +1,10 @@
+//! # Dynamic Programming
+//!
+//! A collection of modules containing dynamic programming algorithms.
+
+pub mod activity_selection;
+pub mod coin_change;
+pub mod k_sum;
+pub mod knapsack;
+pub mod matrix_chain;
+pub mod optimal_bst;
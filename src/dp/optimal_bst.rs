@@ -0,0 +1,145 @@
+//! # Optimal Binary Search Tree
+//!
+//! A collection of functions to arrange keys into a binary search tree by access probability.
+
+/// # [Optimal Binary Search Tree](https://en.wikipedia.org/wiki/Optimal_binary_search_tree)
+///
+/// Finds the least expected cost of a search in a binary search tree of n keys, where key i is
+/// searched with `probabilities[i]` and the gap before key i (or after the last key, for i = n)
+/// with `dummy_probabilities[i]`. A search costs the depth of the node it ends at, counting the
+/// root as 1; unsuccessful searches end at leaves for the gaps.
+///
+/// With Knuth's optimization, the root of every range of keys is only searched between the
+/// roots of the range without its last key and without its first key, which takes O(n²) time
+/// overall instead of O(n³). Returns the cost and a table where `root[i][j]` is the key at the
+/// root of the optimal tree of keys i to j - 1 (0-based); only entries with i < j are meaningful.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::dp::optimal_bst;
+///
+/// let probabilities = [0.15, 0.10, 0.05, 0.10, 0.20];
+/// let dummy_probabilities = [0.05, 0.10, 0.05, 0.05, 0.05, 0.10];
+/// let (cost, root) = optimal_bst::optimal_bst(&probabilities, &dummy_probabilities);
+/// assert!((cost - 2.75).abs() < 1e-9);
+/// assert_eq!(root[0][5], 1);
+/// ```
+///
+/// # Panics
+///
+/// * dummy_probabilities should have one more item than probabilities
+/// * probabilities should sum up to 1
+pub fn optimal_bst(probabilities: &[f64], dummy_probabilities: &[f64]) -> (f64, Vec<Vec<usize>>) {
+    let (p, q) = (probabilities, dummy_probabilities);
+    let n = p.len();
+    if q.len() != n + 1 {
+        panic!("dummy_probabilities should have one more item than probabilities");
+    }
+    let total: f64 = p.iter().chain(q).sum();
+    if total.is_nan() || (total - 1.0).abs() >= 1e-9 {
+        panic!("probabilities should sum up to 1");
+    }
+
+    // cost[i][j] is the expected cost of the tree of keys i..j, whose probabilities, including
+    // the gaps q[i..=j], sum up to weight[i][j].
+    let mut cost = vec![vec![0.0; n + 1]; n + 1];
+    let mut weight = vec![vec![0.0; n + 1]; n + 1];
+    let mut root = vec![vec![0; n + 1]; n + 1];
+    for i in 0..=n {
+        cost[i][i] = q[i];
+        weight[i][i] = q[i];
+    }
+    for len in 1..=n {
+        for i in 0..=n - len {
+            let j = i + len;
+            weight[i][j] = weight[i][j - 1] + p[j - 1] + q[j];
+            let (low, high) = if len == 1 {
+                (i, i)
+            } else {
+                (root[i][j - 1], root[i + 1][j])
+            };
+            cost[i][j] = f64::INFINITY;
+            for r in low..=high {
+                let candidate = cost[i][r] + cost[r + 1][j] + weight[i][j];
+                if candidate < cost[i][j] {
+                    cost[i][j] = candidate;
+                    root[i][j] = r;
+                }
+            }
+        }
+    }
+    (cost[0][n], root)
+}
+
+#[cfg(test)]
+mod optimal_bst_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    /// Tries every root of every range in O(n³) time.
+    fn cubic(p: &[f64], q: &[f64]) -> f64 {
+        let n = p.len();
+        let mut cost = vec![vec![0.0; n + 1]; n + 1];
+        for i in 0..=n {
+            cost[i][i] = q[i];
+        }
+        for len in 1..=n {
+            for i in 0..=n - len {
+                let j = i + len;
+                let weight: f64 = p[i..j].iter().chain(&q[i..=j]).sum();
+                cost[i][j] = (i..j)
+                    .map(|r| cost[i][r] + cost[r + 1][j] + weight)
+                    .fold(f64::INFINITY, f64::min);
+            }
+        }
+        cost[0][n]
+    }
+
+    #[test]
+    fn receives_textbook_example() {
+        let p = [0.15, 0.10, 0.05, 0.10, 0.20];
+        let q = [0.05, 0.10, 0.05, 0.05, 0.05, 0.10];
+        let (cost, root) = optimal_bst(&p, &q);
+        assert!((cost - 2.75).abs() < 1e-9);
+        // k2 is the root, k1 its left child, and k5 its right child with k4 and then k3 below.
+        assert_eq!(root[0][5], 1);
+        assert_eq!(root[0][1], 0);
+        assert_eq!(root[2][5], 4);
+        assert_eq!(root[2][4], 3);
+        assert_eq!(root[2][3], 2);
+    }
+
+    #[test]
+    fn receives_no_keys() {
+        let (cost, _) = optimal_bst(&[], &[1.0]);
+        assert_eq!(cost, 1.0);
+    }
+
+    #[test]
+    fn agrees_with_cubic_algorithm() {
+        let mut rng = test_util::rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1, 12);
+            let weights: Vec<f64> = (0..2 * n + 1).map(|_| rng.gen_range(0.0, 1.0)).collect();
+            let total: f64 = weights.iter().sum();
+            let weights: Vec<f64> = weights.iter().map(|w| w / total).collect();
+            let (p, q) = weights.split_at(n);
+            let (cost, _) = optimal_bst(p, q);
+            assert!((cost - cubic(p, q)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "probabilities should sum up to 1")]
+    fn receives_unnormalized_probabilities() {
+        optimal_bst(&[0.5, 0.5], &[0.1, 0.1, 0.1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dummy_probabilities should have one more item than probabilities")]
+    fn receives_mismatched_lengths() {
+        optimal_bst(&[0.5, 0.5], &[0.0, 0.0]);
+    }
+}
@@ -0,0 +1,194 @@
+//! # Count-Min Sketch
+//!
+//! A probabilistic data structure to estimate the frequencies of items in a stream.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+/// # [Count-Min Sketch](https://en.wikipedia.org/wiki/Count%E2%80%93min_sketch)
+///
+/// Estimates how many times each item has been added, using `depth` rows of `width` counters.
+/// An estimate is never smaller than the true count; with width ⌈e / ε⌉ and depth ⌈ln(1 / δ)⌉,
+/// it exceeds the true count by more than ε times the number of additions with probability at
+/// most δ.
+///
+/// The hash function of each row is simulated by double hashing: *h<sub>i</sub>(x) = h<sub>1</sub>(x)
+/// + i · h<sub>2</sub>(x)*.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::count_min_sketch::CountMinSketch;
+///
+/// let mut sketch = CountMinSketch::new(64, 4);
+/// sketch.add(b"apple");
+/// sketch.add(b"apple");
+/// sketch.add(b"banana");
+/// assert!(sketch.estimate(b"apple") >= 2);
+/// assert!(sketch.estimate(b"banana") >= 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    counters: Vec<u64>,
+}
+
+impl CountMinSketch {
+    /// Creates an empty sketch with `depth` rows of `width` counters.
+    ///
+    /// # Panics
+    ///
+    /// * width or depth is 0
+    pub fn new(width: usize, depth: usize) -> CountMinSketch {
+        if width == 0 || depth == 0 {
+            panic!("width and depth should be larger than 0");
+        }
+        CountMinSketch {
+            width,
+            depth,
+            counters: vec![0; width * depth],
+        }
+    }
+
+    /// Creates an empty sketch whose estimates exceed the true counts by at most `epsilon` times
+    /// the number of additions, with probability at least 1 - `delta`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::ds::count_min_sketch::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::with_error_bounds(0.01, 0.01);
+    /// assert_eq!((sketch.width(), sketch.depth()), (272, 5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * epsilon is not in (0, 1]
+    /// * delta is not in (0, 1)
+    pub fn with_error_bounds(epsilon: f64, delta: f64) -> CountMinSketch {
+        if !(epsilon > 0.0 && epsilon <= 1.0) {
+            panic!("epsilon should be in (0, 1]");
+        }
+        if !(delta > 0.0 && delta < 1.0) {
+            panic!("delta should be in (0, 1)");
+        }
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        CountMinSketch::new(width, depth)
+    }
+
+    /// Returns the number of counters in each row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Adds one occurrence of the item.
+    pub fn add(&mut self, item: &[u8]) {
+        let (h1, h2) = hashes(item);
+        for row in 0..self.depth {
+            let index = self.index(row, h1, h2);
+            self.counters[index] = self.counters[index].saturating_add(1);
+        }
+    }
+
+    /// Estimates the number of occurrences of the item, which is never smaller than the true one.
+    pub fn estimate(&self, item: &[u8]) -> u64 {
+        let (h1, h2) = hashes(item);
+        (0..self.depth)
+            .map(|row| self.counters[self.index(row, h1, h2)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Returns the index of the counter of the item in the given row.
+    fn index(&self, row: usize, h1: u64, h2: u64) -> usize {
+        let hash = h1.wrapping_add((row as u64).wrapping_mul(h2));
+        row * self.width + (hash % self.width as u64) as usize
+    }
+}
+
+/// Hashes the item twice with different seeds; the second hash is odd so that it never cancels.
+fn hashes(item: &[u8]) -> (u64, u64) {
+    let hash = |seed: u64| {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(seed);
+        hasher.write(item);
+        hasher.finish()
+    };
+    (hash(0), hash(1) | 1)
+}
+
+#[cfg(test)]
+mod count_min_sketch_tests {
+    use super::*;
+    use rand::Rng;
+    use std::collections::HashMap;
+    use test_util;
+
+    #[test]
+    fn never_underestimates() {
+        let mut rng = test_util::rng();
+        let mut sketch = CountMinSketch::new(16, 3);
+        let mut counts = HashMap::new();
+        for _ in 0..10_000 {
+            let item = rng.gen_range(0u32, 1000).to_le_bytes();
+            sketch.add(&item);
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        for (item, &count) in &counts {
+            assert!(sketch.estimate(item) >= count);
+        }
+    }
+
+    #[test]
+    fn increments_on_each_addition() {
+        let mut sketch = CountMinSketch::new(1000, 5);
+        assert_eq!(sketch.estimate(b"item"), 0);
+        for i in 1..=10 {
+            sketch.add(b"item");
+            assert_eq!(sketch.estimate(b"item"), i);
+        }
+        assert_eq!(sketch.estimate(b""), 0);
+    }
+
+    #[test]
+    fn stays_within_error_bound() {
+        let mut rng = test_util::rng();
+        for &(epsilon, delta) in &[(0.01, 0.05), (0.005, 0.01), (0.05, 0.1)] {
+            let mut sketch = CountMinSketch::with_error_bounds(epsilon, delta);
+            let mut counts = HashMap::new();
+            let additions = 20_000;
+            for _ in 0..additions {
+                // Skewed towards small items, like most real streams.
+                let item = (rng.gen_range(0u32, 100) * rng.gen_range(0u32, 100)).to_le_bytes();
+                sketch.add(&item);
+                *counts.entry(item).or_insert(0u64) += 1;
+            }
+            let bound = (epsilon * additions as f64) as u64;
+            let failures = counts
+                .iter()
+                .filter(|&(item, &count)| sketch.estimate(item) > count + bound)
+                .count();
+            assert!(failures as f64 <= delta * counts.len() as f64 + 1.0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "width and depth should be larger than 0")]
+    fn receives_zero_width() {
+        CountMinSketch::new(0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "delta should be in (0, 1)")]
+    fn receives_invalid_delta() {
+        CountMinSketch::with_error_bounds(0.1, 1.0);
+    }
+}
@@ -0,0 +1,278 @@
+//! # Cuckoo Hash Map
+//!
+//! A hash map with constant worst-case lookups.
+
+use rand::{self, Rng};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// # [Cuckoo Hashing](https://en.wikipedia.org/wiki/Cuckoo_hashing)
+///
+/// Maps keys to values in two tables with a hash function each, keeping every key in one of its
+/// two possible slots, so that looking a key up or removing it checks at most two slots. A key
+/// inserted into a taken slot kicks the key there out to its slot in the other table, and so on.
+/// When that runs into a cycle, the map rehashes everything with new hash functions, and twice
+/// as many slots if it is more than half full. A rehash that runs into a cycle again doubles the
+/// slots and tries once more, up to `MAX_REHASH_ATTEMPTS` times, after which the keys are taken
+/// to collide under every hash function, as a degenerate `Hash` makes them, and insert panics.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::cuckoo_map::CuckooMap;
+///
+/// let mut map = CuckooMap::new(8);
+/// map.insert("apple", 3);
+/// map.insert("banana", 5);
+/// map.insert("apple", 4);
+/// assert_eq!(map.get(&"apple"), Some(&4));
+/// assert_eq!(map.remove(&"banana"), Some(5));
+/// assert_eq!(map.get(&"banana"), None);
+/// assert_eq!(map.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CuckooMap<K: Hash + Eq + Clone, V: Clone> {
+    tables: [Vec<Option<(K, V)>>; 2],
+    // Seeds of the hash functions of the two tables.
+    seeds: [u64; 2],
+    len: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> CuckooMap<K, V> {
+    /// Creates an empty map with room for `capacity` entries at a load factor of 50%, which is
+    /// `capacity` slots per table.
+    pub fn new(capacity: usize) -> CuckooMap<K, V> {
+        let size = capacity.max(1);
+        CuckooMap {
+            tables: [vec_of_none(size), vec_of_none(size)],
+            seeds: new_seeds(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts the value for the key, replacing any value the key had. Returns false if the key
+    /// could not be placed because of a cycle, in which case the map has been rehashed to fit
+    /// it; the key is inserted either way.
+    ///
+    /// # Panics
+    ///
+    /// * keys collide in both tables after `MAX_REHASH_ATTEMPTS` rehashes
+    pub fn insert(&mut self, key: K, value: V) -> bool {
+        for table in 0..2 {
+            let index = self.index(table, &key);
+            if let Some((ref k, ref mut v)) = self.tables[table][index] {
+                if *k == key {
+                    *v = value;
+                    return true;
+                }
+            }
+        }
+        self.len += 1;
+        match self.place((key, value)) {
+            None => true,
+            Some(homeless) => {
+                self.rehash(homeless);
+                false
+            }
+        }
+    }
+
+    /// Returns the value of the key, checking at most two slots.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        (0..2).find_map(|table| match self.tables[table][self.index(table, key)] {
+            Some((ref k, ref v)) if k == key => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Removes the key, returning its value if it was in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        for table in 0..2 {
+            let index = self.index(table, key);
+            let slot = &mut self.tables[table][index];
+            if slot.as_ref().is_some_and(|(k, _)| k == key) {
+                self.len -= 1;
+                return slot.take().map(|(_, v)| v);
+            }
+        }
+        None
+    }
+
+    /// Places an entry by kicking other entries out to their other slots. Returns the entry left
+    /// without a slot if it takes too many kicks, which means there is probably a cycle.
+    fn place(&mut self, mut entry: (K, V)) -> Option<(K, V)> {
+        let size = self.tables[0].len();
+        let max_kicks = 8 * (usize::BITS - size.leading_zeros()) as usize + 8;
+        let mut table = 0;
+        for _ in 0..max_kicks {
+            let index = self.index(table, &entry.0);
+            // Stop once the slot was free; otherwise carry on with the entry kicked out.
+            entry = self.tables[table][index].replace(entry)?;
+            // The kicked entry was in this table, so its other slot is in the other one.
+            table = 1 - table;
+        }
+        Some(entry)
+    }
+
+    /// Moves all entries and the homeless one into new tables with new hash functions, growing
+    /// them if the map is more than half full, until every entry has a slot.
+    fn rehash(&mut self, homeless: (K, V)) {
+        let mut entries = vec![homeless];
+        let mut size = self.tables[0].len();
+        if self.len > size {
+            size *= 2;
+        }
+        for _ in 0..MAX_REHASH_ATTEMPTS {
+            for table in self.tables.iter_mut() {
+                entries.extend(table.drain(..).flatten());
+            }
+            self.tables = [vec_of_none(size), vec_of_none(size)];
+            self.seeds = new_seeds();
+            let mut failed = None;
+            while let Some(entry) = entries.pop() {
+                failed = self.place(entry);
+                if failed.is_some() {
+                    break;
+                }
+            }
+            match failed {
+                None => return,
+                Some(entry) => {
+                    // Try again with more room, taking back the entries placed so far.
+                    entries.push(entry);
+                    size *= 2;
+                }
+            }
+        }
+        panic!("keys should not collide under every hash function");
+    }
+
+    /// Returns the slot of the key in the given table.
+    fn index(&self, table: usize, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(self.seeds[table]);
+        key.hash(&mut hasher);
+        (hasher.finish() % self.tables[table].len() as u64) as usize
+    }
+}
+
+/// The number of times a rehash tries new hash functions, doubling the slots every time, before
+/// it gives up. An honest `Hash` fails this many times in a row with negligible probability.
+pub const MAX_REHASH_ATTEMPTS: usize = 8;
+
+fn vec_of_none<T>(size: usize) -> Vec<Option<T>> {
+    (0..size).map(|_| None).collect()
+}
+
+fn new_seeds() -> [u64; 2] {
+    let mut rng = rand::thread_rng();
+    [rng.gen(), rng.gen()]
+}
+
+#[cfg(test)]
+mod cuckoo_map_tests {
+    use super::*;
+    use std::collections::HashMap;
+    use test_util::{self, Counted};
+
+    #[test]
+    fn agrees_with_hash_map() {
+        let mut rng = test_util::rng();
+        let mut map = CuckooMap::new(16);
+        let mut expected = HashMap::new();
+        for _ in 0..20_000 {
+            let key = rng.gen_range(0, 2000);
+            match rng.gen_range(0, 3) {
+                0 => {
+                    let value = rng.gen::<u64>();
+                    map.insert(key, value);
+                    expected.insert(key, value);
+                }
+                1 => assert_eq!(map.get(&key), expected.get(&key)),
+                _ => assert_eq!(map.remove(&key), expected.remove(&key)),
+            }
+        }
+        assert_eq!(map.len(), expected.len());
+        for (key, value) in expected.iter() {
+            assert_eq!(map.get(key), Some(value));
+        }
+    }
+
+    #[test]
+    fn compares_at_most_twice_per_lookup() {
+        let mut map = CuckooMap::new(500);
+        for x in 0..1000 {
+            map.insert(Counted(x), x);
+        }
+        for x in 0..2000 {
+            Counted::reset();
+            assert_eq!(map.get(&Counted(x)), if x < 1000 { Some(&x) } else { None });
+            assert!(Counted::comparisons() <= 2);
+        }
+    }
+
+    #[test]
+    fn keeps_keys_at_high_load() {
+        let mut map = CuckooMap::new(1000);
+        for x in 0..990 {
+            map.insert(x, x * 2);
+        }
+        assert_eq!(map.len(), 990);
+        assert!((0..990).all(|x| map.get(&x) == Some(&(x * 2))));
+        assert!((990..2000).all(|x| map.get(&x).is_none()));
+    }
+
+    #[test]
+    fn keeps_keys_after_rehashing() {
+        // Three keys cannot fit into two slots, so the map runs into a cycle and rehashes.
+        let mut map = CuckooMap::new(1);
+        let placed: Vec<bool> = (0..100).map(|x| map.insert(x, -x)).collect();
+        assert!(placed.iter().any(|&placed| !placed));
+        assert_eq!(map.len(), 100);
+        assert!((0..100).all(|x| map.get(&x) == Some(&-x)));
+        for x in 0..50 {
+            assert_eq!(map.remove(&x), Some(-x));
+        }
+        assert!((0..50).all(|x| map.get(&x).is_none()));
+        assert!((50..100).all(|x| map.get(&x) == Some(&-x)));
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Colliding(i64);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, _state: &mut H) {}
+    }
+
+    #[test]
+    #[should_panic(expected = "keys should not collide under every hash function")]
+    fn receives_keys_colliding_under_every_seed() {
+        // The keys share both of their slots, which only two of them fit into.
+        let mut map = CuckooMap::new(4);
+        for x in 0..3 {
+            map.insert(Colliding(x), x);
+        }
+    }
+
+    #[test]
+    fn replaces_values() {
+        let mut map = CuckooMap::new(4);
+        assert!(map.insert("k", 1));
+        assert!(map.insert("k", 2));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&"k"), Some(&2));
+        assert_eq!(map.remove(&"k"), Some(2));
+        assert_eq!(map.remove(&"k"), None);
+        assert!(map.is_empty());
+    }
+}
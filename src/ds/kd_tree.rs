@@ -0,0 +1,204 @@
+//! # k-d Tree
+//!
+//! A space-partitioning data structure to find the nearest neighbours of points.
+
+use std::cmp::Ordering;
+
+/// # [k-d Tree](https://en.wikipedia.org/wiki/K-d_tree)
+///
+/// Organizes points of D dimensions in a balanced binary tree: each node splits its points at
+/// the median along the axis with the widest spread, so that nearest neighbour queries can skip
+/// whole subtrees that are farther than the best points found so far. Distances are Euclidean.
+///
+/// The tree is stored implicitly: the points of each subtree occupy a range of a single vector,
+/// with the splitting point in the middle.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::kd_tree::KdTree;
+///
+/// let tree = KdTree::new(vec![[0.0, 0.0], [5.0, 5.0], [1.0, 2.0], [9.0, 1.0]]);
+/// assert_eq!(tree.nearest(&[1.5, 1.5]), Some([1.0, 2.0]));
+/// assert_eq!(tree.k_nearest(&[6.0, 4.0], 2), vec![[5.0, 5.0], [9.0, 1.0]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KdTree<const D: usize> {
+    points: Vec<[f64; D]>,
+    // axes[i] is the splitting axis of the subtree whose middle is points[i].
+    axes: Vec<usize>,
+}
+
+impl<const D: usize> KdTree<D> {
+    /// Builds the tree of the given points in O(n log n) on average.
+    pub fn new(points: Vec<[f64; D]>) -> KdTree<D> {
+        let axes = vec![0; points.len()];
+        let mut tree = KdTree { points, axes };
+        let len = tree.points.len();
+        tree.build(0, len);
+        tree
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if there are no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the point nearest to the query, or None if the tree is empty.
+    pub fn nearest(&self, query: &[f64; D]) -> Option<[f64; D]> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    /// Returns the k points nearest to the query, sorted by ascending distance; fewer are
+    /// returned if the tree has less than k points.
+    pub fn k_nearest(&self, query: &[f64; D], k: usize) -> Vec<[f64; D]> {
+        let mut best = Vec::with_capacity(k.min(self.len()) + 1);
+        if k > 0 {
+            self.search(0, self.points.len(), query, k, &mut best);
+        }
+        best.into_iter().map(|(_, i)| self.points[i]).collect()
+    }
+
+    /// Partitions points[lo..hi] around the median of the widest axis, recursively.
+    fn build(&mut self, lo: usize, hi: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let axis = (0..D)
+            .max_by(|&a, &b| compare(&self.spread(lo, hi, a), &self.spread(lo, hi, b)))
+            .unwrap_or(0);
+        let mid = lo + (hi - lo) / 2;
+        self.points[lo..hi].select_nth_unstable_by(mid - lo, |p, q| compare(&p[axis], &q[axis]));
+        self.axes[mid] = axis;
+        self.build(lo, mid);
+        self.build(mid + 1, hi);
+    }
+
+    /// Returns the difference between the largest and smallest coordinates along an axis.
+    fn spread(&self, lo: usize, hi: usize, axis: usize) -> f64 {
+        let coordinates = self.points[lo..hi].iter().map(|p| p[axis]);
+        let (min, max) = coordinates.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+        max - min
+    }
+
+    /// Collects the k nearest points of points[lo..hi] into best, sorted by squared distance.
+    fn search(
+        &self,
+        lo: usize,
+        hi: usize,
+        query: &[f64; D],
+        k: usize,
+        best: &mut Vec<(f64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let distance = squared_distance(&self.points[mid], query);
+        if best.len() < k || distance < best[best.len() - 1].0 {
+            let position = best
+                .iter()
+                .position(|&(d, _)| distance < d)
+                .unwrap_or(best.len());
+            best.insert(position, (distance, mid));
+            best.truncate(k);
+        }
+
+        // Search the side of the query first, then the other side only if it may be closer.
+        let axis = self.axes[mid];
+        let difference = query[axis] - self.points[mid][axis];
+        let (near, far) = if difference < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search(near.0, near.1, query, k, best);
+        if best.len() < k || difference * difference < best[best.len() - 1].0 {
+            self.search(far.0, far.1, query, k, best);
+        }
+    }
+}
+
+fn squared_distance<const D: usize>(p: &[f64; D], q: &[f64; D]) -> f64 {
+    p.iter().zip(q.iter()).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+fn compare(a: &f64, b: &f64) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod kd_tree_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn brute_force<const D: usize>(points: &[[f64; D]], query: &[f64; D]) -> Vec<f64> {
+        let mut distances: Vec<f64> = points.iter().map(|p| squared_distance(p, query)).collect();
+        distances.sort_by(compare);
+        distances
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        let points: Vec<[f64; 2]> = (0..1000)
+            .map(|_| [rng.gen_range(-100.0, 100.0), rng.gen_range(-100.0, 100.0)])
+            .collect();
+        let tree = KdTree::new(points.clone());
+        for _ in 0..200 {
+            let query = [rng.gen_range(-120.0, 120.0), rng.gen_range(-120.0, 120.0)];
+            let nearest = tree.nearest(&query).unwrap();
+            assert_eq!(
+                squared_distance(&nearest, &query),
+                brute_force(&points, &query)[0]
+            );
+        }
+    }
+
+    #[test]
+    fn returns_k_nearest_sorted() {
+        let mut rng = test_util::rng();
+        let points: Vec<[f64; 3]> = (0..500)
+            .map(|_| [rng.gen(), rng.gen(), rng.gen()])
+            .collect();
+        let tree = KdTree::new(points.clone());
+        let query = [0.5, 0.5, 0.5];
+        let found = tree.k_nearest(&query, 10);
+        let distances: Vec<f64> = found.iter().map(|p| squared_distance(p, &query)).collect();
+        assert_eq!(distances, brute_force(&points, &query)[..10].to_vec());
+        assert_eq!(tree.k_nearest(&query, 1000).len(), 500);
+        assert!(tree.k_nearest(&query, 0).is_empty());
+    }
+
+    #[test]
+    fn receives_empty_tree() {
+        let tree: KdTree<2> = KdTree::new(Vec::new());
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(&[0.0, 0.0]), None);
+        assert!(tree.k_nearest(&[0.0, 0.0], 3).is_empty());
+    }
+
+    #[test]
+    fn receives_one_dimension() {
+        let tree = KdTree::new(vec![[7.0], [1.0], [4.0], [10.0], [-3.0]]);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.nearest(&[5.0]), Some([4.0]));
+        assert_eq!(tree.nearest(&[-100.0]), Some([-3.0]));
+        assert_eq!(tree.k_nearest(&[8.0], 3), vec![[7.0], [10.0], [4.0]]);
+        assert_eq!(tree.k_nearest(&[8.0], usize::MAX).len(), 5);
+    }
+
+    #[test]
+    fn receives_duplicate_points() {
+        let tree = KdTree::new(vec![[1.0, 1.0]; 4]);
+        assert_eq!(tree.k_nearest(&[0.0, 0.0], 2), vec![[1.0, 1.0]; 2]);
+    }
+}
@@ -0,0 +1,12 @@
+//! # Data Structures
+//!
+//! A collection of modules containing data structures.
+
+pub mod count_min_sketch;
+pub mod cuckoo_map;
+pub mod kd_tree;
+pub mod persistent_segment_tree;
+pub mod skip_list;
+pub mod sparse_table;
+pub mod splay_tree;
+pub mod van_emde_boas_tree;
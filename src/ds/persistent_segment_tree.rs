@@ -0,0 +1,222 @@
+//! # Persistent Segment Tree
+//!
+//! A data structure to answer range sum queries over every version of an updated sequence.
+
+/// # [Persistent](https://en.wikipedia.org/wiki/Persistent_data_structure) [Segment Tree](https://en.wikipedia.org/wiki/Segment_tree)
+///
+/// Answers range sum queries in O(log n) over any version of a sequence. Every update copies
+/// only the O(log n) nodes on the path to the updated position, sharing all other nodes with
+/// the version it was applied to, and creates a new version; the old version stays intact.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::persistent_segment_tree::PersistentSegTree;
+///
+/// let (mut tree, v0) = PersistentSegTree::new(&[1, 2, 3, 4]);
+/// let v1 = tree.update(v0, 1, 20);
+/// let v2 = tree.update(v0, 3, 40);
+/// assert_eq!(tree.query(v0, 0, 3), 10);
+/// assert_eq!(tree.query(v1, 0, 3), 28);
+/// assert_eq!(tree.query(v2, 0, 3), 46);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PersistentSegTree {
+    len: usize,
+    nodes: Vec<Node>,
+    // roots[v] is the root node of version v.
+    roots: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    sum: i64,
+    left: usize,
+    right: usize,
+}
+
+impl PersistentSegTree {
+    /// Builds the tree of the data, returning it along with the id of its first version.
+    pub fn new(data: &[i64]) -> (PersistentSegTree, usize) {
+        let mut tree = PersistentSegTree {
+            len: data.len(),
+            nodes: Vec::with_capacity(2 * data.len()),
+            roots: Vec::new(),
+        };
+        let root = if data.is_empty() {
+            tree.push(0, 0, 0)
+        } else {
+            tree.build(data)
+        };
+        tree.roots.push(root);
+        (tree, 0)
+    }
+
+    /// Returns the number of elements in every version.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of versions.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Creates a new version from the given one where the element at pos is val, returning the
+    /// id of the new version.
+    ///
+    /// # Panics
+    ///
+    /// * version does not exist
+    /// * pos is out of bounds
+    pub fn update(&mut self, version: usize, pos: usize, val: i64) -> usize {
+        let root = self.root(version);
+        if pos >= self.len {
+            panic!("pos should be less than the number of elements");
+        }
+        let new_root = self.set(root, 0, self.len, pos, val);
+        self.roots.push(new_root);
+        self.roots.len() - 1
+    }
+
+    /// Returns the sum of the elements of the given version in the inclusive range [l, r].
+    ///
+    /// # Panics
+    ///
+    /// * version does not exist
+    /// * l is larger than r or r is out of bounds
+    pub fn query(&self, version: usize, l: usize, r: usize) -> i64 {
+        let root = self.root(version);
+        if l > r || r >= self.len {
+            panic!("range should be within the elements and not be empty");
+        }
+        self.sum(root, 0, self.len, l, r + 1)
+    }
+
+    fn root(&self, version: usize) -> usize {
+        match self.roots.get(version) {
+            Some(&root) => root,
+            None => panic!("version {} should exist", version),
+        }
+    }
+
+    fn push(&mut self, sum: i64, left: usize, right: usize) -> usize {
+        self.nodes.push(Node { sum, left, right });
+        self.nodes.len() - 1
+    }
+
+    /// Builds the subtree of a non-empty slice, returning its root.
+    fn build(&mut self, data: &[i64]) -> usize {
+        if data.len() == 1 {
+            return self.push(data[0], 0, 0);
+        }
+        let mid = data.len() / 2;
+        let left = self.build(&data[..mid]);
+        let right = self.build(&data[mid..]);
+        let sum = self.nodes[left].sum + self.nodes[right].sum;
+        self.push(sum, left, right)
+    }
+
+    /// Copies the path from node, which covers [lo, hi), down to pos, returning the new node.
+    fn set(&mut self, node: usize, lo: usize, hi: usize, pos: usize, val: i64) -> usize {
+        if hi - lo == 1 {
+            return self.push(val, 0, 0);
+        }
+        let mid = lo + (hi - lo) / 2;
+        let Node { left, right, .. } = self.nodes[node];
+        let (left, right) = if pos < mid {
+            (self.set(left, lo, mid, pos, val), right)
+        } else {
+            (left, self.set(right, mid, hi, pos, val))
+        };
+        let sum = self.nodes[left].sum + self.nodes[right].sum;
+        self.push(sum, left, right)
+    }
+
+    /// Sums the elements in [l, r) under node, which covers [lo, hi).
+    fn sum(&self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> i64 {
+        if r <= lo || hi <= l {
+            return 0;
+        }
+        if l <= lo && hi <= r {
+            return self.nodes[node].sum;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let Node { left, right, .. } = self.nodes[node];
+        self.sum(left, lo, mid, l, r) + self.sum(right, mid, hi, l, r)
+    }
+}
+
+#[cfg(test)]
+mod persistent_segment_tree_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn keeps_old_versions() {
+        let data = [5, -3, 8, 0, 2];
+        let (mut tree, v0) = PersistentSegTree::new(&data);
+        let v1 = tree.update(v0, 2, 100);
+        let v2 = tree.update(v1, 0, -5);
+        assert_eq!(tree.versions(), 3);
+        assert_eq!(tree.query(v0, 0, 4), 12);
+        assert_eq!(tree.query(v0, 2, 2), 8);
+        assert_eq!(tree.query(v1, 0, 4), 104);
+        assert_eq!(tree.query(v2, 0, 4), 94);
+        assert_eq!(tree.query(v2, 1, 3), 97);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        let data: Vec<i64> = (0..300).map(|_| rng.gen_range(-1000, 1000)).collect();
+        let (mut tree, v0) = PersistentSegTree::new(&data);
+        let mut versions = vec![data];
+        assert_eq!(v0, 0);
+        for _ in 0..500 {
+            let version = rng.gen_range(0, versions.len());
+            let pos = rng.gen_range(0, 300);
+            let val = rng.gen_range(-1000, 1000);
+            let mut data = versions[version].clone();
+            data[pos] = val;
+            assert_eq!(tree.update(version, pos, val), versions.len());
+            versions.push(data);
+        }
+        for _ in 0..2000 {
+            let version = rng.gen_range(0, versions.len());
+            let l = rng.gen_range(0, 300);
+            let r = rng.gen_range(l, 300);
+            let expected: i64 = versions[version][l..=r].iter().sum();
+            assert_eq!(tree.query(version, l, r), expected);
+        }
+    }
+
+    #[test]
+    fn receives_single_element() {
+        let (mut tree, v0) = PersistentSegTree::new(&[7]);
+        let v1 = tree.update(v0, 0, -7);
+        assert_eq!(tree.query(v0, 0, 0), 7);
+        assert_eq!(tree.query(v1, 0, 0), -7);
+    }
+
+    #[test]
+    #[should_panic(expected = "version 1 should exist")]
+    fn receives_unknown_version() {
+        let (tree, _) = PersistentSegTree::new(&[1, 2]);
+        tree.query(1, 0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "range should be within the elements and not be empty")]
+    fn receives_empty_data() {
+        let (tree, v0) = PersistentSegTree::new(&[]);
+        assert!(tree.is_empty());
+        tree.query(v0, 0, 0);
+    }
+}
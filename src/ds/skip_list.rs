@@ -0,0 +1,265 @@
+//! # Skip List
+//!
+//! A probabilistic data structure to keep a sorted collection of values.
+
+use rand::{self, Rng};
+use std::mem;
+
+/// # [Skip List](https://en.wikipedia.org/wiki/Skip_list)
+///
+/// Keeps values sorted in a linked list with express lanes: every node is promoted to the next
+/// level with probability `p`, up to `max_level` levels, so that searching, inserting and
+/// removing take expected O(log n) time. Equal values are stored separately.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::skip_list::SkipList;
+///
+/// let mut list = SkipList::new(16, 0.5);
+/// for &x in &[5, 1, 4, 1, 3] {
+///     list.insert(x);
+/// }
+/// assert!(list.remove(&4));
+/// assert!(!list.contains(&4));
+/// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![1, 1, 3, 5]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SkipList<T: Ord> {
+    // nodes[HEAD] is the head, the only node without a value.
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    max_level: usize,
+    p: f64,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    val: Option<T>,
+    next: Vec<Option<usize>>,
+}
+
+const HEAD: usize = 0;
+
+impl<T: Ord> SkipList<T> {
+    /// Creates an empty skip list with at most `max_level` levels and promotion probability `p`.
+    ///
+    /// # Panics
+    ///
+    /// * max_level is 0
+    /// * p is not in (0, 1)
+    pub fn new(max_level: usize, p: f64) -> SkipList<T> {
+        if max_level == 0 {
+            panic!("max_level should be larger than 0");
+        }
+        if !(p > 0.0 && p < 1.0) {
+            panic!("p should be in (0, 1)");
+        }
+        SkipList {
+            nodes: vec![Node {
+                val: None,
+                next: vec![None; max_level],
+            }],
+            free: Vec::new(),
+            max_level,
+            p,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts the value before any equal values.
+    pub fn insert(&mut self, val: T) {
+        let update = self.predecessors(&val);
+        let mut level = 1;
+        let mut rng = rand::thread_rng();
+        while level < self.max_level && rng.gen::<f64>() < self.p {
+            level += 1;
+        }
+
+        let next = (0..level).map(|l| self.nodes[update[l]].next[l]).collect();
+        let node = Node {
+            val: Some(val),
+            next,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (l, &prev) in update.iter().enumerate().take(level) {
+            self.nodes[prev].next[l] = Some(index);
+        }
+        self.len += 1;
+    }
+
+    /// Returns true if an equal value is in the list.
+    pub fn contains(&self, val: &T) -> bool {
+        let prev = self.predecessors(val)[0];
+        self.nodes[prev].next[0].is_some_and(|next| self.value(next) == val)
+    }
+
+    /// Removes one value equal to the given one, returning true if there was such a value.
+    pub fn remove(&mut self, val: &T) -> bool {
+        let update = self.predecessors(val);
+        let index = match self.nodes[update[0]].next[0] {
+            Some(index) if self.value(index) == val => index,
+            _ => return false,
+        };
+        // The first node not less than val is also the first one at every level it is on.
+        let removed = mem::replace(
+            &mut self.nodes[index],
+            Node {
+                val: None,
+                next: Vec::new(),
+            },
+        );
+        for (l, &next) in removed.next.iter().enumerate() {
+            self.nodes[update[l]].next[l] = next;
+        }
+        self.free.push(index);
+        self.len -= 1;
+        true
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            next: self.nodes[HEAD].next[0],
+        }
+    }
+
+    /// Returns the last node less than val at each level.
+    fn predecessors(&self, val: &T) -> Vec<usize> {
+        let mut update = vec![HEAD; self.max_level];
+        let mut current = HEAD;
+        for l in (0..self.max_level).rev() {
+            while let Some(next) = self.nodes[current].next[l] {
+                if self.value(next) >= val {
+                    break;
+                }
+                current = next;
+            }
+            update[l] = current;
+        }
+        update
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index]
+            .val
+            .as_ref()
+            .expect("only the head has no value")
+    }
+}
+
+/// An iterator over the values of a skip list in ascending order.
+pub struct Iter<'a, T: Ord + 'a> {
+    list: &'a SkipList<T>,
+    next: Option<usize>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let list = self.list;
+        self.next.map(|index| {
+            self.next = list.nodes[index].next[0];
+            list.value(index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod skip_list_tests {
+    use super::*;
+    use test_util;
+
+    #[test]
+    fn iterates_in_sorted_order() {
+        let mut rng = test_util::rng();
+        let mut list = SkipList::new(12, 0.5);
+        let mut expected = Vec::new();
+        for _ in 0..2000 {
+            let x = rng.gen_range(0, 500);
+            if rng.gen_range(0, 3) == 0 {
+                let removed = expected
+                    .iter()
+                    .position(|&y| y == x)
+                    .map(|i| expected.remove(i));
+                assert_eq!(list.remove(&x), removed.is_some());
+            } else {
+                list.insert(x);
+                expected.push(x);
+            }
+        }
+        expected.sort();
+        assert_eq!(list.len(), expected.len());
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn does_not_contain_removed_value() {
+        let mut list = SkipList::new(4, 0.25);
+        list.insert("b");
+        list.insert("a");
+        assert!(list.contains(&"a"));
+        assert!(list.remove(&"a"));
+        assert!(!list.contains(&"a"));
+        assert!(!list.remove(&"a"));
+        assert!(list.contains(&"b"));
+    }
+
+    #[test]
+    fn stores_duplicates_separately() {
+        let mut list = SkipList::new(8, 0.5);
+        for _ in 0..3 {
+            list.insert(7);
+        }
+        assert_eq!(list.len(), 3);
+        assert!(list.remove(&7));
+        assert!(list.contains(&7));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&7, &7]);
+    }
+
+    #[test]
+    fn receives_single_level() {
+        let mut list = SkipList::new(1, 0.5);
+        for x in (0..100).rev() {
+            list.insert(x);
+        }
+        assert!(list.iter().cloned().eq(0..100));
+    }
+
+    #[test]
+    fn receives_empty_list() {
+        let mut list: SkipList<i32> = SkipList::new(4, 0.5);
+        assert!(list.is_empty());
+        assert!(!list.contains(&0));
+        assert!(!list.remove(&0));
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "p should be in (0, 1)")]
+    fn receives_invalid_probability() {
+        SkipList::<i32>::new(4, 1.0);
+    }
+}
@@ -0,0 +1,119 @@
+//! # Sparse Table
+//!
+//! A data structure to answer range minimum queries over a static sequence.
+
+/// # [Sparse Table](https://en.wikipedia.org/wiki/Range_minimum_query#Solution_using_constant_time_and_linearithmic_space)
+///
+/// Precomputes the minimum of every range whose length is a power of two in O(n log n), so that
+/// the minimum of any range is the minimum of two overlapping precomputed ranges in O(1).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::sparse_table::SparseTable;
+///
+/// let table = SparseTable::new(&[5, 2, 8, 1, 9, 3]);
+/// assert_eq!(table.query(0, 2), 2);
+/// assert_eq!(table.query(2, 5), 1);
+/// assert_eq!(table.query(4, 4), 9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseTable<T: Copy + Ord> {
+    // levels[k][i] is the minimum of data[i..i + 2^k].
+    levels: Vec<Vec<T>>,
+}
+
+impl<T: Copy + Ord> SparseTable<T> {
+    /// Builds the sparse table of the given data.
+    pub fn new(data: &[T]) -> SparseTable<T> {
+        let mut levels = vec![data.to_vec()];
+        let mut width = 1;
+        while 2 * width <= data.len() {
+            let previous = &levels[levels.len() - 1];
+            let level = (0..=data.len() - 2 * width)
+                .map(|i| previous[i].min(previous[i + width]))
+                .collect();
+            levels.push(level);
+            width *= 2;
+        }
+        SparseTable { levels }
+    }
+
+    /// Returns the number of elements of the data.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns true if the data has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Returns the minimum of the data in the inclusive range [l, r].
+    ///
+    /// # Panics
+    ///
+    /// * l is larger than r
+    /// * r is out of bounds
+    pub fn query(&self, l: usize, r: usize) -> T {
+        if l > r || r >= self.len() {
+            panic!("range should be within the data and not be empty");
+        }
+        let k = (usize::BITS - 1 - (r - l + 1).leading_zeros()) as usize;
+        let level = &self.levels[k];
+        level[l].min(level[r + 1 - (1 << k)])
+    }
+}
+
+#[cfg(test)]
+mod sparse_table_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        let data: Vec<i32> = (0..1000).map(|_| rng.gen_range(-10_000, 10_000)).collect();
+        let table = SparseTable::new(&data);
+        for _ in 0..10_000 {
+            let l = rng.gen_range(0, data.len());
+            let r = rng.gen_range(l, data.len());
+            assert_eq!(table.query(l, r), *data[l..=r].iter().min().unwrap());
+        }
+    }
+
+    #[test]
+    fn receives_boundary_ranges() {
+        let data = [4, 7, 1, 9, 0, 3, 8];
+        let table = SparseTable::new(&data);
+        let n = data.len();
+        for (i, &x) in data.iter().enumerate() {
+            assert_eq!(table.query(i, i), x);
+        }
+        assert_eq!(table.query(0, n - 1), 0);
+        assert_eq!(table.query(0, 3), 1);
+        assert_eq!(table.query(5, n - 1), 3);
+    }
+
+    #[test]
+    fn receives_single_element() {
+        let table = SparseTable::new(&['x']);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.query(0, 0), 'x');
+    }
+
+    #[test]
+    #[should_panic(expected = "range should be within the data and not be empty")]
+    fn receives_empty_data() {
+        let table: SparseTable<i32> = SparseTable::new(&[]);
+        assert!(table.is_empty());
+        table.query(0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range should be within the data and not be empty")]
+    fn receives_reversed_range() {
+        SparseTable::new(&[1, 2, 3]).query(2, 1);
+    }
+}
@@ -0,0 +1,363 @@
+//! # Splay Tree
+//!
+//! A self-adjusting binary search tree to keep a sorted set of values.
+
+/// # [Splay Tree](https://en.wikipedia.org/wiki/Splay_tree)
+///
+/// Keeps values sorted in a binary search tree that is restructured on every access: the
+/// accessed value (or the last one visited while looking for it) is splayed to the root by
+/// rotations, top-down. Inserting, searching and removing take amortized O(log n) time, and
+/// values accessed again soon after are found close to the root. Equal values are stored once.
+///
+/// Nodes live in a vector and refer to each other by index, so no unsafe code is needed.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::splay_tree::SplayTree;
+///
+/// let mut tree = SplayTree::new();
+/// for &x in &[5, 1, 4, 1, 3] {
+///     tree.insert(x);
+/// }
+/// assert!(tree.remove(&4));
+/// assert!(!tree.contains(&4));
+/// assert_eq!(tree.iter().cloned().collect::<Vec<_>>(), vec![1, 3, 5]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SplayTree<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    // Only nodes in the free list have no value.
+    val: Option<T>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T: Ord> Default for SplayTree<T> {
+    fn default() -> SplayTree<T> {
+        SplayTree::new()
+    }
+}
+
+impl<T: Ord> SplayTree<T> {
+    /// Creates an empty splay tree.
+    pub fn new() -> SplayTree<T> {
+        SplayTree {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts the value and splays it to the root, unless an equal value is already there.
+    pub fn insert(&mut self, val: T) {
+        let root = match self.root {
+            Some(root) => self.splay(root, &val),
+            None => {
+                self.root = Some(self.allocate(val, None, None));
+                self.len += 1;
+                return;
+            }
+        };
+        // The new value goes between the root and the subtree on its side.
+        let index = if val < *self.value(root) {
+            let left = self.nodes[root].left.take();
+            self.allocate(val, left, Some(root))
+        } else if val > *self.value(root) {
+            let right = self.nodes[root].right.take();
+            self.allocate(val, Some(root), right)
+        } else {
+            self.root = Some(root);
+            return;
+        };
+        self.root = Some(index);
+        self.len += 1;
+    }
+
+    /// Returns true if an equal value is in the tree, splaying the tree around it.
+    pub fn contains(&mut self, val: &T) -> bool {
+        match self.root {
+            Some(root) => {
+                let root = self.splay(root, val);
+                self.root = Some(root);
+                self.value(root) == val
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the value equal to the given one, returning true if there was such a value.
+    pub fn remove(&mut self, val: &T) -> bool {
+        if !self.contains(val) {
+            return false;
+        }
+        let root = self.root.expect("a tree containing val has a root");
+        let (left, right) = (self.nodes[root].left, self.nodes[root].right);
+        // Splaying the left subtree for val brings its largest value up, without a right child.
+        self.root = match left {
+            Some(left) => {
+                let left = self.splay(left, val);
+                self.nodes[left].right = right;
+                Some(left)
+            }
+            None => right,
+        };
+        self.nodes[root] = Node {
+            val: None,
+            left: None,
+            right: None,
+        };
+        self.free.push(root);
+        self.len -= 1;
+        true
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            tree: self,
+            stack: Vec::new(),
+        };
+        iter.push_left_spine(self.root);
+        iter
+    }
+
+    /// Splays the subtree at root for val, top-down, and returns the new root of the subtree:
+    /// the node with val if there is one, or else the last node visited while looking for it.
+    /// Nodes passed on the way are hung onto a left tree of smaller values and a right tree of
+    /// larger values, which become the children of the new root.
+    fn splay(&mut self, mut root: usize, val: &T) -> usize {
+        // Roots of the left and right trees, and the nodes to hang the next nodes under.
+        let (mut left_root, mut left_max): (Option<usize>, Option<usize>) = (None, None);
+        let (mut right_root, mut right_min): (Option<usize>, Option<usize>) = (None, None);
+        loop {
+            if *val < *self.value(root) {
+                let mut child = match self.nodes[root].left {
+                    Some(child) => child,
+                    None => break,
+                };
+                if *val < *self.value(child) {
+                    // Zig-zig: rotate right first.
+                    self.nodes[root].left = self.nodes[child].right;
+                    self.nodes[child].right = Some(root);
+                    root = child;
+                    child = match self.nodes[root].left {
+                        Some(child) => child,
+                        None => break,
+                    };
+                }
+                // Hang root under the right tree.
+                match right_min {
+                    Some(min) => self.nodes[min].left = Some(root),
+                    None => right_root = Some(root),
+                }
+                right_min = Some(root);
+                root = child;
+            } else if *val > *self.value(root) {
+                let mut child = match self.nodes[root].right {
+                    Some(child) => child,
+                    None => break,
+                };
+                if *val > *self.value(child) {
+                    // Zag-zag: rotate left first.
+                    self.nodes[root].right = self.nodes[child].left;
+                    self.nodes[child].left = Some(root);
+                    root = child;
+                    child = match self.nodes[root].right {
+                        Some(child) => child,
+                        None => break,
+                    };
+                }
+                // Hang root under the left tree.
+                match left_max {
+                    Some(max) => self.nodes[max].right = Some(root),
+                    None => left_root = Some(root),
+                }
+                left_max = Some(root);
+                root = child;
+            } else {
+                break; // found it!
+            }
+        }
+
+        // Reassemble: the children of root go under the left and right trees, which take their
+        // places.
+        if let Some(max) = left_max {
+            self.nodes[max].right = self.nodes[root].left;
+            self.nodes[root].left = left_root;
+        }
+        if let Some(min) = right_min {
+            self.nodes[min].left = self.nodes[root].right;
+            self.nodes[root].right = right_root;
+        }
+        root
+    }
+
+    fn allocate(&mut self, val: T, left: Option<usize>, right: Option<usize>) -> usize {
+        let node = Node {
+            val: Some(val),
+            left,
+            right,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index]
+            .val
+            .as_ref()
+            .expect("only free nodes have no value")
+    }
+}
+
+/// An iterator over the values of a splay tree in ascending order.
+pub struct Iter<'a, T: Ord + 'a> {
+    tree: &'a SplayTree<T>,
+    // Nodes whose values and right subtrees are still to be visited, the next one on top.
+    stack: Vec<usize>,
+}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut node: Option<usize>) {
+        while let Some(index) = node {
+            self.stack.push(index);
+            node = self.tree.nodes[index].left;
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let tree = self.tree;
+        self.stack.pop().map(|index| {
+            self.push_left_spine(tree.nodes[index].right);
+            tree.value(index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod splay_tree_tests {
+    use super::*;
+    use rand::Rng;
+    use std::collections::BTreeSet;
+    use test_util::{self, Counted};
+
+    #[test]
+    fn agrees_with_btree_set() {
+        let mut rng = test_util::rng();
+        let mut tree = SplayTree::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..5000 {
+            let x = rng.gen_range(0, 500);
+            match rng.gen_range(0, 3) {
+                0 => {
+                    tree.insert(x);
+                    expected.insert(x);
+                }
+                1 => assert_eq!(tree.contains(&x), expected.contains(&x)),
+                _ => assert_eq!(tree.remove(&x), expected.remove(&x)),
+            }
+        }
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn iterates_in_sorted_order() {
+        let mut tree = SplayTree::new();
+        for &x in &["pear", "apple", "fig", "kiwi", "apple", "banana"] {
+            tree.insert(x);
+        }
+        assert_eq!(
+            tree.iter().cloned().collect::<Vec<_>>(),
+            vec!["apple", "banana", "fig", "kiwi", "pear"]
+        );
+        assert_eq!(tree.len(), 5);
+    }
+
+    #[test]
+    fn splays_accessed_value_to_root() {
+        let mut tree = SplayTree::new();
+        for x in 0..100 {
+            tree.insert(x);
+        }
+        for &x in &[37, 0, 99, 50, 37] {
+            assert!(tree.contains(&x));
+            assert_eq!(tree.value(tree.root.unwrap()), &x);
+        }
+        assert!(!tree.contains(&1000));
+        assert_eq!(tree.value(tree.root.unwrap()), &99);
+    }
+
+    #[test]
+    fn accesses_same_value_in_constant_time() {
+        let mut tree = SplayTree::new();
+        for x in 0..10_000 {
+            tree.insert(Counted(x * 7 % 10_000));
+        }
+        tree.contains(&Counted(1234));
+        Counted::reset();
+        for _ in 0..1000 {
+            assert!(tree.contains(&Counted(1234)));
+        }
+        // Once at the root, it takes two comparisons (less than, larger than) and an equality
+        // check per access.
+        assert_eq!(Counted::comparisons(), 3000);
+    }
+
+    #[test]
+    fn stays_shallow_on_sequential_inserts() {
+        let mut tree = SplayTree::new();
+        for x in 0..100_000 {
+            tree.insert(x);
+        }
+        // A full pass over the values in order takes amortized O(1) time per value.
+        for x in 0..100_000 {
+            assert!(tree.contains(&x));
+        }
+        assert!(tree.iter().cloned().eq(0..100_000));
+    }
+
+    #[test]
+    fn receives_empty_tree() {
+        let mut tree: SplayTree<i32> = SplayTree::new();
+        assert!(tree.is_empty());
+        assert!(!tree.contains(&0));
+        assert!(!tree.remove(&0));
+        assert_eq!(tree.iter().next(), None);
+        tree.insert(1);
+        assert!(tree.remove(&1));
+        assert!(tree.is_empty());
+        assert_eq!(tree.iter().next(), None);
+    }
+}
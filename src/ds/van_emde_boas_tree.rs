@@ -0,0 +1,398 @@
+//! # Van Emde Boas Tree
+//!
+//! A data structure to keep a set of integers from a bounded universe.
+
+use std::cmp;
+
+/// # [Van Emde Boas Tree](https://en.wikipedia.org/wiki/Van_Emde_Boas_tree)
+///
+/// Keeps a set of integers in [0, U), answering membership, successor and predecessor queries
+/// and performing insertions and deletions in O(log log U) time.
+///
+/// A tree over k-bit integers stores its minimum and maximum directly, and the other integers in
+/// 2<sup>⌈k/2⌉</sup> clusters, each a tree over the ⌊k/2⌋ low bits, plus a summary tree of the
+/// non-empty clusters; every operation recurses into only one of them. Clusters and summaries
+/// are allocated when they first receive an integer.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::ds::van_emde_boas_tree::VanEmdeBoasTree;
+///
+/// let mut tree = VanEmdeBoasTree::new(1 << 16);
+/// for &x in &[3, 10, 42, 9999] {
+///     tree.insert(x);
+/// }
+/// tree.delete(42);
+/// assert!(tree.contains(10));
+/// assert_eq!(tree.successor(10), Some(9999));
+/// assert_eq!(tree.predecessor(9999), Some(10));
+/// assert_eq!(tree.successor(9999), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VanEmdeBoasTree {
+    universe_size: u32,
+    root: Node,
+}
+
+impl VanEmdeBoasTree {
+    /// Creates an empty tree for the integers in [0, universe_size).
+    ///
+    /// # Panics
+    ///
+    /// * universe_size is 0
+    pub fn new(universe_size: u32) -> VanEmdeBoasTree {
+        if universe_size == 0 {
+            panic!("universe_size should be larger than 0");
+        }
+        // Round the universe up to the next power of two, with at least 1 bit.
+        let bits = cmp::max(1, 32 - (universe_size - 1).leading_zeros());
+        VanEmdeBoasTree {
+            universe_size,
+            root: Node::new(bits),
+        }
+    }
+
+    /// Returns the size of the universe.
+    pub fn universe_size(&self) -> u32 {
+        self.universe_size
+    }
+
+    /// Returns true if the tree has no integers.
+    pub fn is_empty(&self) -> bool {
+        self.root.min.is_none()
+    }
+
+    /// Returns the smallest integer, if any.
+    pub fn min(&self) -> Option<u32> {
+        self.root.min
+    }
+
+    /// Returns the largest integer, if any.
+    pub fn max(&self) -> Option<u32> {
+        self.root.min.map(|_| self.root.max)
+    }
+
+    /// Inserts the integer; inserting it again has no effect.
+    ///
+    /// # Panics
+    ///
+    /// * x is not less than the universe size
+    pub fn insert(&mut self, x: u32) {
+        self.check(x);
+        self.root.insert(x);
+    }
+
+    /// Deletes the integer, if it is in the tree.
+    ///
+    /// # Panics
+    ///
+    /// * x is not less than the universe size
+    pub fn delete(&mut self, x: u32) {
+        self.check(x);
+        if self.root.contains(x) {
+            self.root.delete(x);
+        }
+    }
+
+    /// Returns true if the integer is in the tree.
+    pub fn contains(&self, x: u32) -> bool {
+        x < self.universe_size && self.root.contains(x)
+    }
+
+    /// Returns the smallest integer in the tree that is larger than x, if any.
+    pub fn successor(&self, x: u32) -> Option<u32> {
+        if x >= self.universe_size {
+            return None;
+        }
+        self.root.successor(x)
+    }
+
+    /// Returns the largest integer in the tree that is smaller than x, if any.
+    pub fn predecessor(&self, x: u32) -> Option<u32> {
+        if x >= self.universe_size {
+            return self.max();
+        }
+        self.root.predecessor(x)
+    }
+
+    fn check(&self, x: u32) {
+        if x >= self.universe_size {
+            panic!("x should be less than the universe size");
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bits: u32,
+    // max is only meaningful when min is not None.
+    min: Option<u32>,
+    max: u32,
+    summary: Option<Box<Node>>,
+    clusters: Vec<Option<Box<Node>>>,
+}
+
+impl Node {
+    fn new(bits: u32) -> Node {
+        Node {
+            bits,
+            min: None,
+            max: 0,
+            summary: None,
+            clusters: Vec::new(),
+        }
+    }
+
+    fn low_bits(&self) -> u32 {
+        self.bits / 2
+    }
+
+    fn high(&self, x: u32) -> usize {
+        (x >> self.low_bits()) as usize
+    }
+
+    fn low(&self, x: u32) -> u32 {
+        x & ((1 << self.low_bits()) - 1)
+    }
+
+    fn index(&self, high: usize, low: u32) -> u32 {
+        ((high as u32) << self.low_bits()) | low
+    }
+
+    fn cluster(&self, high: usize) -> Option<&Node> {
+        self.clusters
+            .get(high)
+            .and_then(|cluster| cluster.as_deref())
+    }
+
+    fn contains(&self, x: u32) -> bool {
+        match self.min {
+            None => false,
+            Some(min) if x == min || x == self.max => true,
+            _ if self.bits == 1 => false,
+            _ => self
+                .cluster(self.high(x))
+                .is_some_and(|cluster| cluster.contains(self.low(x))),
+        }
+    }
+
+    fn insert(&mut self, mut x: u32) {
+        let min = match self.min {
+            None => {
+                self.min = Some(x);
+                self.max = x;
+                return;
+            }
+            Some(min) => min,
+        };
+        if x == min {
+            return;
+        }
+        if x < min {
+            // The minimum is not stored in the clusters, so the old one goes there instead.
+            self.min = Some(x);
+            x = min;
+        }
+        if self.bits > 1 {
+            let (high, low) = (self.high(x), self.low(x));
+            if self.clusters.is_empty() {
+                self.clusters = vec![None; 1 << (self.bits - self.low_bits())];
+            }
+            let low_bits = self.low_bits();
+            match self.clusters[high] {
+                Some(ref mut cluster) => cluster.insert(low),
+                None => {
+                    let mut cluster = Node::new(low_bits);
+                    cluster.insert(low);
+                    self.clusters[high] = Some(Box::new(cluster));
+                    let summary_bits = self.bits - low_bits;
+                    self.summary
+                        .get_or_insert_with(|| Box::new(Node::new(summary_bits)))
+                        .insert(high as u32);
+                }
+            }
+        }
+        if x > self.max {
+            self.max = x;
+        }
+    }
+
+    /// Deletes x, which should be in the tree.
+    fn delete(&mut self, mut x: u32) {
+        let min = self.min.expect("x should be in the tree");
+        if min == self.max {
+            self.min = None;
+            return;
+        }
+        if self.bits == 1 {
+            // Both 0 and 1 are in the tree.
+            let other = 1 - x;
+            self.min = Some(other);
+            self.max = other;
+            return;
+        }
+
+        if x == min {
+            // The next minimum moves out of its cluster to replace the deleted one.
+            let first = self.summary.as_ref().and_then(|summary| summary.min);
+            let first = first.expect("non-empty tree should have a cluster") as usize;
+            x = self.index(first, self.cluster(first).and_then(|c| c.min).unwrap_or(0));
+            self.min = Some(x);
+        }
+
+        let (high, low) = (self.high(x), self.low(x));
+        let is_empty = {
+            let cluster = self.clusters[high]
+                .as_mut()
+                .expect("x should be in the tree");
+            cluster.delete(low);
+            cluster.min.is_none()
+        };
+        if is_empty {
+            self.clusters[high] = None;
+            let summary_is_empty = {
+                let summary = self.summary.as_mut().expect("summary should exist");
+                summary.delete(high as u32);
+                summary.min.is_none()
+            };
+            if summary_is_empty {
+                self.summary = None;
+                self.clusters = Vec::new();
+            }
+            if x == self.max {
+                self.max = match self.summary.as_ref().and_then(|s| s.min.map(|_| s.max)) {
+                    None => self.min.unwrap_or(0),
+                    Some(last) => {
+                        let last = last as usize;
+                        self.index(last, self.cluster(last).map_or(0, |c| c.max))
+                    }
+                };
+            }
+        } else if x == self.max {
+            self.max = self.index(high, self.cluster(high).map_or(0, |c| c.max));
+        }
+    }
+
+    fn successor(&self, x: u32) -> Option<u32> {
+        let min = self.min?;
+        if x < min {
+            return Some(min);
+        }
+        if self.bits == 1 {
+            return if x == 0 && self.max == 1 {
+                Some(1)
+            } else {
+                None
+            };
+        }
+        let (high, low) = (self.high(x), self.low(x));
+        if let Some(cluster) = self.cluster(high) {
+            if cluster.min.is_some() && low < cluster.max {
+                return cluster.successor(low).map(|low| self.index(high, low));
+            }
+        }
+        let next = self.summary.as_ref()?.successor(high as u32)? as usize;
+        let cluster = self.cluster(next)?;
+        cluster.min.map(|low| self.index(next, low))
+    }
+
+    fn predecessor(&self, x: u32) -> Option<u32> {
+        let min = self.min?;
+        if x > self.max {
+            return Some(self.max);
+        }
+        if self.bits == 1 {
+            return if x == 1 && min == 0 { Some(0) } else { None };
+        }
+        let (high, low) = (self.high(x), self.low(x));
+        if let Some(cluster) = self.cluster(high) {
+            if cluster.min.is_some_and(|cluster_min| low > cluster_min) {
+                return cluster.predecessor(low).map(|low| self.index(high, low));
+            }
+        }
+        let previous = self
+            .summary
+            .as_ref()
+            .and_then(|summary| summary.predecessor(high as u32));
+        match previous {
+            Some(previous) => {
+                let previous = previous as usize;
+                self.cluster(previous).map(|c| self.index(previous, c.max))
+            }
+            // The minimum is not in any cluster.
+            None if x > min => Some(min),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod van_emde_boas_tree_tests {
+    use super::*;
+    use rand::Rng;
+    use std::collections::BTreeSet;
+    use test_util;
+
+    #[test]
+    fn agrees_with_btree_set() {
+        let mut rng = test_util::rng();
+        for &universe_size in &[1, 2, 3, 100, 1 << 10, 1 << 20] {
+            let mut tree = VanEmdeBoasTree::new(universe_size);
+            let mut set = BTreeSet::new();
+            for _ in 0..10_000 {
+                let x = rng.gen_range(0, universe_size);
+                match rng.gen_range(0, 5) {
+                    0 | 1 => {
+                        tree.insert(x);
+                        set.insert(x);
+                    }
+                    2 => {
+                        tree.delete(x);
+                        set.remove(&x);
+                    }
+                    _ => {}
+                }
+                assert_eq!(tree.contains(x), set.contains(&x));
+                assert_eq!(tree.successor(x), set.range(x + 1..).next().cloned());
+                assert_eq!(tree.predecessor(x), set.range(..x).next_back().cloned());
+                assert_eq!(tree.min(), set.iter().next().cloned());
+                assert_eq!(tree.max(), set.iter().next_back().cloned());
+            }
+        }
+    }
+
+    #[test]
+    fn receives_full_universe() {
+        let mut tree = VanEmdeBoasTree::new(u32::MAX);
+        tree.insert(0);
+        tree.insert(u32::MAX - 1);
+        tree.insert(1 << 31);
+        assert_eq!(tree.successor(0), Some(1 << 31));
+        assert_eq!(tree.successor(1 << 31), Some(u32::MAX - 1));
+        assert_eq!(tree.predecessor(u32::MAX - 1), Some(1 << 31));
+        tree.delete(1 << 31);
+        assert_eq!(tree.successor(0), Some(u32::MAX - 1));
+        assert_eq!(tree.predecessor(u32::MAX), Some(u32::MAX - 1));
+    }
+
+    #[test]
+    fn receives_empty_tree() {
+        let mut tree = VanEmdeBoasTree::new(16);
+        assert!(tree.is_empty());
+        assert!(!tree.contains(3));
+        assert_eq!(tree.successor(3), None);
+        assert_eq!(tree.predecessor(3), None);
+        tree.delete(3);
+        tree.insert(3);
+        tree.insert(3);
+        tree.delete(3);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "x should be less than the universe size")]
+    fn receives_out_of_universe_integer() {
+        VanEmdeBoasTree::new(10).insert(10);
+    }
+}
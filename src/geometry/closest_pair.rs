@@ -0,0 +1,224 @@
+//! # Closest Pair
+//!
+//! A collection of functions to find the two points of a set that are closest to each other.
+
+use geometry::Point;
+
+/// # [Closest Pair of Points](https://en.wikipedia.org/wiki/Closest_pair_of_points_problem)
+///
+/// Finds the two points with the smallest Euclidean distance between them in O(n log n) time,
+/// by divide and conquer. The points are split at the median x; once the closest pairs of both
+/// halves are known, with distance d, only the points within d of the split line can form a
+/// closer pair, and in that strip sorted by y each point needs comparing with a bounded number
+/// of the points after it. The halves are merged by y as the recursion returns, so no further
+/// sorting is needed.
+///
+/// The pair is made of two distinct items of the slice, which may be equal points, and is
+/// ordered by x, then by y. When several pairs are the closest, any of them may be returned.
+/// `None` is returned for fewer than two points.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::geometry::Point;
+/// use ult_algo::geometry::closest_pair;
+///
+/// let points = vec![
+///     Point::new(2.0, 3.0),
+///     Point::new(12.0, 30.0),
+///     Point::new(40.0, 50.0),
+///     Point::new(5.0, 1.0),
+///     Point::new(12.0, 10.0),
+///     Point::new(3.0, 4.0),
+/// ];
+/// assert_eq!(
+///     closest_pair::closest_pair(&points),
+///     Some((Point::new(2.0, 3.0), Point::new(3.0, 4.0)))
+/// );
+/// assert_eq!(closest_pair::closest_pair(&points[..1]), None);
+/// ```
+///
+/// # Panics
+///
+/// * points should not have NaN coordinates
+pub fn closest_pair(points: &[Point]) -> Option<(Point, Point)> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .and_then(|ordering| Some(ordering.then(a.y.partial_cmp(&b.y)?)))
+            .expect("points should not have NaN coordinates")
+    });
+    let mut buffer = Vec::with_capacity(points.len());
+    closest_pair_sorted(&mut points, &mut buffer).map(|(_, a, b)| (a, b))
+}
+
+/// Finds the closest pair of points sorted by x as (squared distance, first, second), leaving
+/// the points sorted by y.
+fn closest_pair_sorted(
+    points: &mut [Point],
+    buffer: &mut Vec<Point>,
+) -> Option<(f64, Point, Point)> {
+    if points.len() <= 3 {
+        let mut best = None;
+        for (i, &a) in points.iter().enumerate() {
+            for &b in &points[i + 1..] {
+                best = closer(best, (squared_distance(a, b), a, b));
+            }
+        }
+        points.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap());
+        return best;
+    }
+
+    let mid = points.len() / 2;
+    let mid_x = points[mid].x;
+    let (left, right) = points.split_at_mut(mid);
+    let mut best = closest_pair_sorted(left, buffer);
+    if let Some(right_best) = closest_pair_sorted(right, buffer) {
+        best = closer(best, right_best);
+    }
+    merge_by_y(points, mid, buffer);
+
+    // Collect the strip around the split line, already sorted by y.
+    let (mut min, _, _) = best.expect("three or more points should have a closest pair");
+    buffer.clear();
+    buffer.extend(
+        points
+            .iter()
+            .filter(|p| (p.x - mid_x) * (p.x - mid_x) < min),
+    );
+    for (i, &a) in buffer.iter().enumerate() {
+        for &b in buffer[i + 1..].iter() {
+            if (b.y - a.y) * (b.y - a.y) >= min {
+                break;
+            }
+            let distance = squared_distance(a, b);
+            if distance < min {
+                min = distance;
+                best = Some(if (a.x, a.y) <= (b.x, b.y) {
+                    (distance, a, b)
+                } else {
+                    (distance, b, a)
+                });
+            }
+        }
+    }
+    best
+}
+
+/// Merges the two halves of the points, split at mid and each sorted by y, into one.
+fn merge_by_y(points: &mut [Point], mid: usize, buffer: &mut Vec<Point>) {
+    buffer.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < points.len() {
+        if points[i].y <= points[j].y {
+            buffer.push(points[i]);
+            i += 1;
+        } else {
+            buffer.push(points[j]);
+            j += 1;
+        }
+    }
+    buffer.extend_from_slice(&points[i..mid]);
+    buffer.extend_from_slice(&points[j..]);
+    points.copy_from_slice(buffer);
+}
+
+/// Returns the pair with the smaller squared distance, preferring the current best on ties.
+fn closer(
+    best: Option<(f64, Point, Point)>,
+    pair: (f64, Point, Point),
+) -> Option<(f64, Point, Point)> {
+    match best {
+        Some(best) if best.0 <= pair.0 => Some(best),
+        _ => Some(pair),
+    }
+}
+
+fn squared_distance(a: Point, b: Point) -> f64 {
+    (a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)
+}
+
+#[cfg(test)]
+mod closest_pair_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn brute_force(points: &[Point]) -> Option<f64> {
+        let mut best: Option<f64> = None;
+        for (i, &a) in points.iter().enumerate() {
+            for &b in &points[i + 1..] {
+                let distance = squared_distance(a, b);
+                best = Some(best.map_or(distance, |best| best.min(distance)));
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn receives_small_set() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 4.0),
+            Point::new(-3.0, 7.0),
+            Point::new(5.5, 3.0),
+            Point::new(10.0, -2.0),
+        ];
+        assert_eq!(
+            closest_pair(&points),
+            Some((Point::new(5.0, 4.0), Point::new(5.5, 3.0)))
+        );
+    }
+
+    #[test]
+    fn returns_identical_points() {
+        let point = Point::new(1.5, -2.0);
+        let points = vec![Point::new(0.0, 0.0), point, Point::new(3.0, 3.0), point];
+        assert_eq!(closest_pair(&points), Some((point, point)));
+        assert_eq!(closest_pair(&[point, point]), Some((point, point)));
+    }
+
+    #[test]
+    fn receives_collinear_points() {
+        // Gaps grow along the line, except between 6 and 7, which straddle the middle split.
+        let xs = [
+            0.0, 10.0, 30.0, 60.0, 100.0, 150.0, 151.5, 210.0, 280.0, 360.0,
+        ];
+        let points: Vec<Point> = xs.iter().map(|&x| Point::new(x, 0.0)).collect();
+        assert_eq!(
+            closest_pair(&points),
+            Some((Point::new(150.0, 0.0), Point::new(151.5, 0.0)))
+        );
+
+        let vertical: Vec<Point> = xs.iter().map(|&y| Point::new(2.0, y)).collect();
+        assert_eq!(
+            closest_pair(&vertical),
+            Some((Point::new(2.0, 150.0), Point::new(2.0, 151.5)))
+        );
+    }
+
+    #[test]
+    fn receives_fewer_than_two_points() {
+        assert_eq!(closest_pair(&[]), None);
+        assert_eq!(closest_pair(&[Point::new(1.0, 1.0)]), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for n in 2..40 {
+            let points: Vec<Point> = (0..n)
+                .map(|_| Point::new(rng.gen_range(-5, 5) as f64, rng.gen_range(-5, 5) as f64))
+                .collect();
+            let (a, b) = closest_pair(&points).unwrap();
+            assert_eq!(Some(squared_distance(a, b)), brute_force(&points));
+        }
+
+        let points: Vec<Point> = (0..10_000)
+            .map(|_| Point::new(rng.gen_range(-1e4, 1e4), rng.gen_range(-1e4, 1e4)))
+            .collect();
+        let (a, b) = closest_pair(&points).unwrap();
+        assert!(points.contains(&a) && points.contains(&b));
+        assert_eq!(Some(squared_distance(a, b)), brute_force(&points));
+    }
+}
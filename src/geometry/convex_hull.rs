@@ -0,0 +1,164 @@
+//! # Convex Hull
+//!
+//! A collection of functions to find the smallest convex polygon enclosing a set of points.
+
+use geometry::{cross, Point};
+
+/// # [Andrew's Monotone Chain](https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain)
+///
+/// Finds the convex hull of the points in O(n log n) time by sorting them lexicographically and
+/// building the lower and upper hulls in one sweep each. The hull points are returned in
+/// counter-clockwise order, starting from the lowest-leftmost point. Points lying on an edge of
+/// the hull are not hull vertices and are excluded; duplicates are reported once. With fewer than
+/// three distinct points, all of them are returned.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::geometry::Point;
+/// use ult_algo::geometry::convex_hull;
+///
+/// let points = vec![
+///     Point::new(0.0, 0.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(1.0, 1.0),
+///     Point::new(2.0, 2.0),
+///     Point::new(0.0, 2.0),
+/// ];
+/// assert_eq!(
+///     convex_hull::convex_hull(points),
+///     vec![
+///         Point::new(0.0, 0.0),
+///         Point::new(2.0, 0.0),
+///         Point::new(2.0, 2.0),
+///         Point::new(0.0, 2.0),
+///     ]
+/// );
+/// ```
+///
+/// # Panics
+///
+/// * points should not have NaN coordinates
+pub fn convex_hull(mut points: Vec<Point>) -> Vec<Point> {
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .and_then(|ordering| Some(ordering.then(a.y.partial_cmp(&b.y)?)))
+            .expect("points should not have NaN coordinates")
+    });
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+    for &point in points.iter() {
+        push_turning_left(&mut hull, point, 2);
+    }
+    let lower_len = hull.len() + 1;
+    for &point in points.iter().rev().skip(1) {
+        push_turning_left(&mut hull, point, lower_len);
+    }
+    // The upper hull ends at the first point, which is already in place.
+    hull.pop();
+    hull
+}
+
+/// Pushes `point` onto the chain after popping every point that would not make a strict left
+/// turn, never shrinking the chain below `min_len - 1` points.
+fn push_turning_left(chain: &mut Vec<Point>, point: Point, min_len: usize) {
+    while chain.len() >= min_len
+        && cross(chain[chain.len() - 2], chain[chain.len() - 1], point) <= 0.0
+    {
+        chain.pop();
+    }
+    chain.push(point);
+}
+
+#[cfg(test)]
+mod convex_hull_tests {
+    use super::*;
+
+    fn unit_square() -> Vec<Point> {
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn keeps_square_corners() {
+        let mut points = unit_square();
+        points.reverse();
+        assert_eq!(convex_hull(points), unit_square());
+    }
+
+    #[test]
+    fn excludes_interior_points() {
+        let mut points = unit_square();
+        points.push(Point::new(0.5, 0.5));
+        points.push(Point::new(0.25, 0.75));
+        assert_eq!(convex_hull(points), unit_square());
+    }
+
+    #[test]
+    fn excludes_collinear_boundary_points() {
+        let mut points = unit_square();
+        points.push(Point::new(0.5, 0.0));
+        points.push(Point::new(1.0, 0.5));
+        points.push(Point::new(0.0, 0.25));
+        points.push(Point::new(0.5, 1.0));
+        assert_eq!(convex_hull(points), unit_square());
+    }
+
+    #[test]
+    fn returns_single_point() {
+        let point = Point::new(3.0, -1.0);
+        assert_eq!(convex_hull(vec![point, point]), vec![point]);
+        assert_eq!(convex_hull(vec![]), vec![]);
+    }
+
+    #[test]
+    fn reduces_collinear_points_to_endpoints() {
+        let points = (0..5)
+            .map(|i| Point::new(i as f64, 2.0 * i as f64))
+            .collect();
+        assert_eq!(
+            convex_hull(points),
+            vec![Point::new(0.0, 0.0), Point::new(4.0, 8.0)]
+        );
+    }
+
+    #[test]
+    fn orders_hull_counter_clockwise() {
+        let points = vec![
+            Point::new(0.0, 3.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.0, 3.0),
+        ];
+        let hull = convex_hull(points);
+        assert_eq!(
+            hull,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(3.0, 1.0),
+                Point::new(4.0, 4.0),
+                Point::new(0.0, 3.0),
+            ]
+        );
+        for i in 0..hull.len() {
+            let (a, b, c) = (
+                hull[i],
+                hull[(i + 1) % hull.len()],
+                hull[(i + 2) % hull.len()],
+            );
+            assert!(cross(a, b, c) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! # Geometry
+//!
+//! A collection of modules containing computational geometry algorithms.
+
+pub mod closest_pair;
+pub mod convex_hull;
+pub mod polygon;
+
+/// A point on the Cartesian plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a point from its coordinates.
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+}
+
+/// Returns twice the signed area of the triangle `origin`, `a`, `b`: positive when `b` lies to
+/// the left of the ray from `origin` through `a`, negative to the right, zero when collinear.
+fn cross(origin: Point, a: Point, b: Point) -> f64 {
+    (a.x - origin.x) * (b.y - origin.y) - (a.y - origin.y) * (b.x - origin.x)
+}
@@ -0,0 +1,221 @@
+//! # Polygon
+//!
+//! A collection of functions to locate points relative to a polygon.
+
+use geometry::{cross, Point};
+
+/// The location of a point relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    OnBoundary,
+}
+
+/// # [Point in Polygon](https://en.wikipedia.org/wiki/Point_in_polygon#Ray_casting_algorithm)
+///
+/// Locates a point relative to a simple polygon, given as its vertices in order (either
+/// orientation, without repeating the first vertex at the end), in O(n) time. A ray is cast from
+/// the point towards positive x and the edges it crosses are counted: an odd count means the
+/// point is inside. Each edge includes its lower endpoint but not its upper one, so a ray through
+/// a vertex is counted once and horizontal edges are never crossed. A point collinear with an
+/// edge and between its endpoints, vertices included, is on the boundary. An empty polygon has
+/// no inside, so every point is outside it.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::geometry::Point;
+/// use ult_algo::geometry::polygon::{self, PointLocation};
+///
+/// let square = vec![
+///     Point::new(0.0, 0.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(2.0, 2.0),
+///     Point::new(0.0, 2.0),
+/// ];
+/// assert_eq!(polygon::point_in_polygon(&square, &Point::new(1.0, 1.0)), PointLocation::Inside);
+/// assert_eq!(polygon::point_in_polygon(&square, &Point::new(3.0, 1.0)), PointLocation::Outside);
+/// assert_eq!(polygon::point_in_polygon(&square, &Point::new(2.0, 1.0)), PointLocation::OnBoundary);
+/// ```
+pub fn point_in_polygon(polygon: &[Point], p: &Point) -> PointLocation {
+    let p = *p;
+    let mut inside = false;
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let side = cross(a, b, p);
+        if side == 0.0
+            && p.x >= a.x.min(b.x)
+            && p.x <= a.x.max(b.x)
+            && p.y >= a.y.min(b.y)
+            && p.y <= a.y.max(b.y)
+        {
+            return PointLocation::OnBoundary;
+        }
+        // An upward edge crosses the ray when the point is to its left, a downward one when
+        // the point is to its right.
+        if (a.y <= p.y && p.y < b.y && side > 0.0) || (b.y <= p.y && p.y < a.y && side < 0.0) {
+            inside = !inside;
+        }
+    }
+
+    if inside {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+#[cfg(test)]
+mod point_in_polygon_tests {
+    use super::*;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point> {
+        coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    fn hexagon() -> Vec<Point> {
+        points(&[
+            (2.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 3.0),
+            (2.0, 4.0),
+            (0.0, 3.0),
+            (0.0, 1.0),
+        ])
+    }
+
+    #[test]
+    fn receives_inside_point() {
+        let polygon = hexagon();
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(2.0, 2.0)),
+            PointLocation::Inside
+        );
+        // The ray passes through the vertices (4, 1) and (4, 3) of the boundary.
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(1.0, 1.0)),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(1.0, 3.0)),
+            PointLocation::Inside
+        );
+    }
+
+    #[test]
+    fn receives_outside_point() {
+        let polygon = hexagon();
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(5.0, 2.0)),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(0.5, 0.0)),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(-1.0, 1.0)),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(-1.0, 4.0)),
+            PointLocation::Outside
+        );
+    }
+
+    #[test]
+    fn receives_vertex() {
+        let polygon = hexagon();
+        for vertex in polygon.iter() {
+            assert_eq!(
+                point_in_polygon(&polygon, vertex),
+                PointLocation::OnBoundary
+            );
+        }
+    }
+
+    #[test]
+    fn receives_edge_point() {
+        let polygon = hexagon();
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(3.0, 0.5)),
+            PointLocation::OnBoundary
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(0.0, 2.0)),
+            PointLocation::OnBoundary
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(4.0, 1.5)),
+            PointLocation::OnBoundary
+        );
+        // Collinear with the edge from (0, 3) to (0, 1), but beyond it.
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(0.0, 5.0)),
+            PointLocation::Outside
+        );
+    }
+
+    #[test]
+    fn receives_concave_polygon() {
+        // A "U" shape: the notch between x = 1 and x = 3 goes down to y = 1.
+        let polygon = points(&[
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 3.0),
+            (3.0, 3.0),
+            (3.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 3.0),
+            (0.0, 3.0),
+        ]);
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(2.0, 2.0)),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(0.5, 2.0)),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(3.5, 2.0)),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(2.0, 0.5)),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(2.0, 1.0)),
+            PointLocation::OnBoundary
+        );
+        // The ray runs along the bottom of the notch.
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(0.5, 1.0)),
+            PointLocation::Inside
+        );
+    }
+
+    #[test]
+    fn accepts_either_orientation() {
+        let mut polygon = hexagon();
+        polygon.reverse();
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(1.0, 1.0)),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&polygon, &Point::new(5.0, 2.0)),
+            PointLocation::Outside
+        );
+    }
+
+    #[test]
+    fn receives_empty_polygon() {
+        assert_eq!(
+            point_in_polygon(&[], &Point::new(0.0, 0.0)),
+            PointLocation::Outside
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! # Graph
+//!
+//! A collection of modules containing graph algorithms.
+
+pub mod pagerank;
+
+/// A directed graph whose vertices are numbered from 0, stored as adjacency lists. Parallel
+/// edges and self-loops are allowed.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::graph::Graph;
+///
+/// let mut graph = Graph::new(3);
+/// graph.add_edge(0, 1);
+/// graph.add_edge(0, 2);
+/// graph.add_edge(2, 0);
+/// assert_eq!(graph.neighbours(0), &[1, 2]);
+/// assert_eq!(graph.out_degree(1), 0);
+/// assert_eq!(graph.edge_count(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph {
+    // adjacency[u] lists the heads of the edges leaving u, in insertion order.
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Creates a graph with n vertices and no edges.
+    pub fn new(n: usize) -> Graph {
+        Graph {
+            adjacency: vec![Vec::new(); n],
+        }
+    }
+
+    /// Creates a graph with n vertices and the given edges, as (tail, head) pairs.
+    ///
+    /// # Panics
+    ///
+    /// * edges should only join vertices smaller than n
+    pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::new(n);
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Adds an edge from one vertex to another.
+    ///
+    /// # Panics
+    ///
+    /// * edges should only join vertices smaller than n
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        if from >= self.adjacency.len() || to >= self.adjacency.len() {
+            panic!("edges should only join vertices smaller than n");
+        }
+        self.adjacency[from].push(to);
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Returns the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.adjacency.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the heads of the edges leaving a vertex.
+    ///
+    /// # Panics
+    ///
+    /// * vertex is out of bounds
+    pub fn neighbours(&self, vertex: usize) -> &[usize] {
+        &self.adjacency[vertex]
+    }
+
+    /// Returns the number of edges leaving a vertex.
+    ///
+    /// # Panics
+    ///
+    /// * vertex is out of bounds
+    pub fn out_degree(&self, vertex: usize) -> usize {
+        self.adjacency[vertex].len()
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::*;
+
+    #[test]
+    fn builds_from_edges() {
+        let graph = Graph::from_edges(4, &[(0, 1), (1, 2), (1, 2), (3, 3)]);
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.neighbours(1), &[2, 2]);
+        assert_eq!(graph.neighbours(3), &[3]);
+        assert!(graph.neighbours(2).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "edges should only join vertices smaller than n")]
+    fn receives_edge_out_of_bounds() {
+        Graph::new(2).add_edge(0, 2);
+    }
+}
@@ -0,0 +1,133 @@
+//! # PageRank
+//!
+//! A collection of functions to rank the vertices of a graph by the links pointing to them.
+
+use graph::Graph;
+use std::mem;
+
+/// # [PageRank](https://en.wikipedia.org/wiki/PageRank)
+///
+/// Ranks the vertices of a directed graph by the probability that a random surfer is on each
+/// of them, computed by power iteration in O(iterations · (V + E)) time. At each step the surfer
+/// follows a random edge leaving the current vertex with probability `damping`, and jumps to a
+/// uniformly random vertex otherwise. From a dangling vertex, which no edge leaves, the surfer
+/// always jumps, so its rank is spread evenly over all vertices. The ranks start uniform and
+/// always sum up to 1.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::graph::{pagerank, Graph};
+///
+/// // 0 and 1 both link to 2, which links back to 0.
+/// let graph = Graph::from_edges(3, &[(0, 2), (1, 2), (2, 0)]);
+/// let ranks = pagerank::pagerank(&graph, 0.85, 100);
+/// assert!(ranks[2] > ranks[0] && ranks[0] > ranks[1]);
+/// assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+/// ```
+///
+/// # Panics
+///
+/// * damping should be between 0 and 1
+pub fn pagerank(graph: &Graph, damping: f64, iterations: u32) -> Vec<f64> {
+    if !(0.0..=1.0).contains(&damping) {
+        panic!("damping should be between 0 and 1");
+    }
+    let n = graph.vertex_count();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let uniform = 1.0 / n as f64;
+    let mut ranks = vec![uniform; n];
+    let mut next = vec![0.0; n];
+    for _ in 0..iterations {
+        let dangling: f64 = (0..n)
+            .filter(|&u| graph.out_degree(u) == 0)
+            .map(|u| ranks[u])
+            .sum();
+        // The rank that reaches every vertex alike, by jumping.
+        let teleport = (1.0 - damping) * uniform + damping * dangling * uniform;
+        next.iter_mut().for_each(|rank| *rank = teleport);
+        for (u, &rank) in ranks.iter().enumerate() {
+            let share = damping * rank / graph.out_degree(u).max(1) as f64;
+            for &v in graph.neighbours(u) {
+                next[v] += share;
+            }
+        }
+        mem::swap(&mut ranks, &mut next);
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod pagerank_tests {
+    use super::*;
+
+    fn assert_distribution(ranks: &[f64]) {
+        assert!(ranks.iter().all(|rank| rank.is_finite() && *rank >= 0.0));
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ranks_complete_graph_uniformly() {
+        let n = 5;
+        let edges: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| (0..n).filter(move |&v| v != u).map(move |v| (u, v)))
+            .collect();
+        let ranks = pagerank(&Graph::from_edges(n, &edges), 0.85, 50);
+        assert_distribution(&ranks);
+        assert!(ranks.iter().all(|rank| (rank - 0.2).abs() < 1e-12));
+    }
+
+    #[test]
+    fn converges_on_strongly_connected_graph() {
+        // A cycle 0 -> 1 -> 2 -> 3 -> 0 with a chord 0 -> 2.
+        let graph = Graph::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+        let ranks = pagerank(&graph, 0.85, 100);
+        assert_distribution(&ranks);
+        let more = pagerank(&graph, 0.85, 200);
+        assert!(ranks.iter().zip(&more).all(|(a, b)| (a - b).abs() < 1e-12));
+
+        // The ranks are a fixed point of one more step.
+        let (d, n) = (0.85, 4.0);
+        let in_rank = |v: usize| -> f64 {
+            (0..4)
+                .filter(|&u| graph.neighbours(u).contains(&v))
+                .map(|u| ranks[u] / graph.out_degree(u) as f64)
+                .sum()
+        };
+        for (v, rank) in ranks.iter().enumerate() {
+            assert!((rank - ((1.0 - d) / n + d * in_rank(v))).abs() < 1e-12);
+        }
+        assert!(ranks[2] > ranks[1]);
+    }
+
+    #[test]
+    fn spreads_dangling_rank() {
+        // 2 has no outgoing edges.
+        let graph = Graph::from_edges(3, &[(0, 1), (1, 2), (0, 2)]);
+        let ranks = pagerank(&graph, 0.85, 100);
+        assert_distribution(&ranks);
+        assert!(ranks[2] > ranks[1] && ranks[1] > ranks[0]);
+
+        // Without any edge, every vertex is dangling.
+        let ranks = pagerank(&Graph::new(4), 0.85, 10);
+        assert_eq!(ranks, vec![0.25; 4]);
+    }
+
+    #[test]
+    fn receives_edge_cases() {
+        assert!(pagerank(&Graph::new(0), 0.85, 10).is_empty());
+        let graph = Graph::from_edges(2, &[(0, 1)]);
+        assert_eq!(pagerank(&graph, 0.85, 0), vec![0.5, 0.5]);
+        // Without damping, the surfer only ever jumps.
+        assert_eq!(pagerank(&graph, 0.0, 5), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "damping should be between 0 and 1")]
+    fn receives_invalid_damping() {
+        pagerank(&Graph::new(1), 1.5, 1);
+    }
+}
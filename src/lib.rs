@@ -1,12 +1,45 @@
 //! # ult_algo (Ultimate Algorithms)
 //!
 //! A library that hosts algorithms of many kinds.
+//!
+//! Disabling the default `std` feature builds the crate with `#![no_std]`; only the modules
+//! that can work with `core` alone (currently `sequence::search`) are available then.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
 extern crate bit_vec;
+#[cfg(feature = "std")]
+extern crate core;
 extern crate num_traits;
+#[cfg(feature = "std")]
 extern crate rand;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(all(test, feature = "std"))]
+extern crate regex;
+#[cfg(feature = "unicode")]
+extern crate unicode_segmentation;
+
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
 
+#[cfg(feature = "std")]
+pub mod compress;
+#[cfg(feature = "std")]
+pub mod dp;
+#[cfg(feature = "std")]
+pub mod ds;
+#[cfg(feature = "std")]
+pub mod geometry;
+#[cfg(feature = "std")]
+pub mod graph;
+#[cfg(feature = "std")]
+pub mod math;
 pub mod sequence;
+#[cfg(all(test, feature = "std"))]
+mod test_util;
 
 #[cfg(test)]
 mod tests {}
@@ -0,0 +1,239 @@
+//! # Continued Fraction
+//!
+//! Continued fraction expansions and the rational approximations they give.
+
+/// # [Continued Fraction](https://en.wikipedia.org/wiki/Continued_fraction) Expansion
+///
+/// Computes up to max_terms coefficients [a0; a1, a2, ...] of the simple continued fraction
+/// x = a0 + 1 / (a1 + 1 / (a2 + ...)). The expansion is computed exactly on the binary value of
+/// x, and stops early once a convergent rounds to x, so that 3.245 gives [3; 4, 12, 4] rather
+/// than the long tail of its binary rounding error. It also stops before a convergent would no
+/// longer fit in i64. A complete expansion never ends in 1 unless it is the only coefficient.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::continued_fraction;
+///
+/// assert_eq!(continued_fraction::continued_fraction(3.245, 5), vec![3, 4, 12, 4]);
+/// assert_eq!(continued_fraction::continued_fraction(-0.75, 5), vec![-1, 4]);
+/// ```
+///
+/// # Panics
+///
+/// * x should be finite and smaller than 2^63 in magnitude
+pub fn continued_fraction(x: f64, max_terms: usize) -> Vec<i64> {
+    if !x.is_finite() || x.abs() >= 9.223_372_036_854_776e18 {
+        panic!("x should be finite and smaller than 2^63 in magnitude");
+    }
+    let (mut numerator, mut denominator) = exact_fraction(x);
+    let mut terms = Vec::new();
+    let (mut p, mut q) = ((1i128, 0i128), (0i128, 1i128));
+    let mut exhausted = false;
+    while terms.len() < max_terms && denominator != 0 {
+        let term = numerator.div_euclid(denominator);
+        let convergent = (term * p.0 + p.1, term * q.0 + q.1);
+        if convergent.0.abs() > i128::from(i64::MAX) || convergent.1 > i128::from(i64::MAX) {
+            break;
+        }
+        terms.push(term as i64);
+        p = (convergent.0, p.0);
+        q = (convergent.1, q.0);
+        let remainder = numerator.rem_euclid(denominator);
+        if remainder == 0 || convergent.0 as f64 / convergent.1 as f64 == x {
+            exhausted = true;
+            break;
+        }
+        numerator = denominator;
+        denominator = remainder;
+    }
+    // [..., a, 1] is the same number as [..., a + 1].
+    if exhausted && terms.len() >= 2 && terms[terms.len() - 1] == 1 {
+        terms.pop();
+        *terms.last_mut().unwrap() += 1;
+    }
+    terms
+}
+
+/// Returns the exact value of x as a fraction of integers with a power of 2 as denominator.
+/// Values too small to be expressed this way lose their lowest bits, rounding toward 0.
+fn exact_fraction(x: f64) -> (i128, i128) {
+    let bits = x.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = (bits & ((1 << 52) - 1)) as i128;
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+        (fraction, -1074) // subnormal
+    } else {
+        (fraction | 1 << 52, biased_exponent - 1075)
+    };
+    if exponent < -126 {
+        // Below 2^-254 every bit is shifted out, which >> does not allow.
+        mantissa = mantissa.checked_shr((-126 - exponent) as u32).unwrap_or(0);
+        exponent = -126;
+    }
+    if x < 0.0 {
+        mantissa = -mantissa;
+    }
+    if exponent >= 0 {
+        return (mantissa << exponent, 1);
+    }
+    (mantissa, 1 << -exponent)
+}
+
+/// Computes the convergents of a continued fraction, which are the fractions (numerator,
+/// denominator) given by its first 1, 2, ... coefficients. They alternate around the value of
+/// the continued fraction, each closer than any fraction with a smaller denominator.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::continued_fraction;
+///
+/// let convergents = continued_fraction::convergents(&[3, 7, 15, 1]);
+/// assert_eq!(convergents, vec![(3, 1), (22, 7), (333, 106), (355, 113)]);
+/// ```
+pub fn convergents(coefficients: &[i64]) -> Vec<(i64, i64)> {
+    let (mut p, mut q) = ((1, 0), (0, 1));
+    coefficients
+        .iter()
+        .map(|&a| {
+            p = (a * p.0 + p.1, p.0);
+            q = (a * q.0 + q.1, q.0);
+            (p.0, q.0)
+        })
+        .collect()
+}
+
+/// # [Best Rational Approximation](https://en.wikipedia.org/wiki/Continued_fraction#Best_rational_approximations)
+///
+/// Finds the fraction (numerator, denominator) closest to x among those with a denominator of
+/// at most max_denominator, by walking down the
+/// [Stern–Brocot tree](https://en.wikipedia.org/wiki/Stern%E2%80%93Brocot_tree) toward x.
+/// Every run of steps in the same direction is taken at once; the run lengths are the
+/// coefficients of the continued fraction of x, so the walk passes through its convergents.
+/// When the next convergent has too large a denominator, the answer is either the last one or
+/// the furthest point of the cut-short run (a semiconvergent). On a tie, the smaller
+/// denominator wins.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::continued_fraction;
+///
+/// assert_eq!(continued_fraction::rational_approx(0.333, 10), (1, 3));
+/// assert_eq!(continued_fraction::rational_approx(std::f64::consts::PI, 1000), (355, 113));
+/// ```
+///
+/// # Panics
+///
+/// * max_denominator should be larger than 0
+/// * x should be finite and smaller than 2^63 in magnitude
+pub fn rational_approx(x: f64, max_denominator: i64) -> (i64, i64) {
+    if max_denominator <= 0 {
+        panic!("max_denominator should be larger than 0");
+    }
+    let terms = continued_fraction(x, usize::MAX);
+    // The last two convergents, starting from 1/0 and a0/1.
+    let (mut previous, mut last) = ((1, 0), (terms[0], 1));
+    for &a in &terms[1..] {
+        if a > (max_denominator - previous.1) / last.1 {
+            // Only part of the run fits; compare its end with the last convergent.
+            let k = (max_denominator - previous.1) / last.1;
+            let semiconvergent = (previous.0 + k * last.0, previous.1 + k * last.1);
+            let error = |(p, q): (i64, i64)| (x - p as f64 / q as f64).abs();
+            if error(semiconvergent) < error(last) {
+                return semiconvergent;
+            }
+            return last;
+        }
+        let next = (a * last.0 + previous.0, a * last.1 + previous.1);
+        previous = last;
+        last = next;
+    }
+    last
+}
+
+#[cfg(test)]
+mod continued_fraction_tests {
+    use super::*;
+    use std::f64::consts::{E, PI};
+
+    #[test]
+    fn expands_known_values() {
+        assert_eq!(continued_fraction(3.245, 5), vec![3, 4, 12, 4]);
+        assert_eq!(continued_fraction(3.245, 2), vec![3, 4]);
+        assert_eq!(continued_fraction(PI, 5), vec![3, 7, 15, 1, 292]);
+        assert_eq!(continued_fraction(E, 7), vec![2, 1, 2, 1, 1, 4, 1]);
+        assert_eq!(continued_fraction(0.5, 5), vec![0, 2]);
+        assert_eq!(continued_fraction(7.0, 5), vec![7]);
+        assert_eq!(continued_fraction(-2.5, 5), vec![-3, 2]);
+        assert_eq!(continued_fraction(1.0, 5), vec![1]);
+        assert_eq!(continued_fraction(1.5, 0), vec![]);
+    }
+
+    #[test]
+    fn expands_square_root_of_two() {
+        let terms = continued_fraction(2f64.sqrt(), 20);
+        assert_eq!(terms[0], 1);
+        assert!(terms[1..19].iter().all(|&a| a == 2));
+    }
+
+    #[test]
+    fn converges_to_pi() {
+        let convergents = convergents(&continued_fraction(PI, 30));
+        let errors: Vec<f64> = convergents
+            .iter()
+            .map(|&(p, q)| (PI - p as f64 / q as f64).abs())
+            .collect();
+        assert_eq!(
+            &convergents[..4],
+            &[(3, 1), (22, 7), (333, 106), (355, 113)]
+        );
+        assert!(errors.windows(2).all(|pair| pair[1] < pair[0]));
+        let (p, q) = *convergents.last().unwrap();
+        assert_eq!(p as f64 / q as f64, PI);
+    }
+
+    #[test]
+    fn approximates_rationally() {
+        assert_eq!(rational_approx(0.333, 10), (1, 3));
+        assert_eq!(rational_approx(0.333, 1000), (333, 1000));
+        assert_eq!(rational_approx(PI, 7), (22, 7));
+        assert_eq!(rational_approx(PI, 112), (333, 106));
+        assert_eq!(rational_approx(PI, 1), (3, 1));
+        assert_eq!(rational_approx(-0.75, 100), (-3, 4));
+        // 3/13 is a semiconvergent of 0.2307..., between the convergents 1/4 and 3/13.
+        assert_eq!(rational_approx(0.230769, 13), (3, 13));
+        assert_eq!(rational_approx(0.2307, 12), (2, 9));
+    }
+
+    #[test]
+    fn approximates_better_than_every_smaller_denominator() {
+        for &x in [PI, E, 0.618_033_988_7, 0.1, 12.345].iter() {
+            for max_denominator in 1..60 {
+                let (p, q) = rational_approx(x, max_denominator);
+                let error = (x - p as f64 / q as f64).abs();
+                assert!(q <= max_denominator);
+                for d in 1..max_denominator + 1 {
+                    let n = (x * d as f64).round();
+                    assert!(error <= (x - n / d as f64).abs() + 1e-15);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn receives_tiny_values() {
+        // Values below 2^-126 are rounded toward 0, here all the way to it.
+        for &x in &[1e-70, 1e-300, -1e-300, 5e-324] {
+            assert_eq!(continued_fraction(x, 5), vec![0]);
+            assert_eq!(rational_approx(x, 10), (0, 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "x should be finite and smaller than 2^63 in magnitude")]
+    fn receives_infinity() {
+        continued_fraction(f64::INFINITY, 3);
+    }
+}
@@ -0,0 +1,105 @@
+//! # Gray Code
+//!
+//! A collection of functions to convert between binary integers and their Gray codes.
+
+/// # [Gray Code](https://en.wikipedia.org/wiki/Gray_code)
+///
+/// Converts a binary integer to its reflected binary Gray code, in which consecutive integers
+/// differ by exactly one bit.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::gray_code;
+///
+/// assert_eq!(gray_code::to_gray(2), 3);
+/// assert_eq!(gray_code::to_gray(7), 4);
+/// ```
+pub fn to_gray(n: u64) -> u64 {
+    n ^ (n >> 1)
+}
+
+/// Converts a reflected binary Gray code back to its binary integer, where every bit is the
+/// parity of the Gray code bits at and above it.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::gray_code;
+///
+/// assert_eq!(gray_code::from_gray(4), 7);
+/// assert_eq!(gray_code::from_gray(gray_code::to_gray(12345)), 12345);
+/// ```
+pub fn from_gray(gray: u64) -> u64 {
+    let mut n = gray;
+    let mut shift = 1;
+    while shift < 64 {
+        n ^= n >> shift;
+        shift *= 2;
+    }
+    n
+}
+
+/// Generates the Gray codes of 0 to 2^n - 1 in order.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::gray_code;
+///
+/// assert_eq!(gray_code::gray_sequence(2), vec![0, 1, 3, 2]);
+/// ```
+///
+/// # Panics
+///
+/// * n should be smaller than 64
+pub fn gray_sequence(n: u32) -> Vec<u64> {
+    if n >= 64 {
+        panic!("n should be smaller than 64");
+    }
+    (0..1u64 << n).map(to_gray).collect()
+}
+
+#[cfg(test)]
+mod gray_code_tests {
+    use super::*;
+
+    #[test]
+    fn converts_zero() {
+        assert_eq!(to_gray(0), 0);
+        assert_eq!(from_gray(0), 0);
+    }
+
+    #[test]
+    fn generates_known_sequence() {
+        assert_eq!(gray_sequence(3), vec![0, 1, 3, 2, 6, 7, 5, 4]);
+        assert_eq!(gray_sequence(0), vec![0]);
+    }
+
+    #[test]
+    fn changes_one_bit_at_a_time() {
+        let sequence = gray_sequence(10);
+        assert_eq!(sequence.len(), 1024);
+        for pair in sequence.windows(2) {
+            assert_eq!((pair[0] ^ pair[1]).count_ones(), 1);
+        }
+        // The sequence is cyclic too.
+        assert_eq!((sequence[0] ^ sequence[1023]).count_ones(), 1);
+    }
+
+    #[test]
+    fn round_trips() {
+        for x in 0..1024 {
+            assert_eq!(from_gray(to_gray(x)), x);
+        }
+        for &x in &[u64::MAX, u64::MAX / 3, 1 << 63] {
+            assert_eq!(from_gray(to_gray(x)), x);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be smaller than 64")]
+    fn receives_too_many_bits() {
+        gray_sequence(64);
+    }
+}
@@ -0,0 +1,162 @@
+//! # Numerical Integration
+//!
+//! Methods to approximate definite integrals.
+
+/// # [Simpson's Rule](https://en.wikipedia.org/wiki/Simpson%27s_rule) (Composite)
+///
+/// Approximates the integral of f from a to b by splitting it into n intervals of equal width
+/// and fitting a parabola over every pair of them, evaluating f n + 1 times. The error is
+/// O(h⁴) for an interval width h, and it is exact for polynomials up to degree 3.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::integration;
+///
+/// let area = integration::integrate_simpson(|x| x * x, 0.0, 3.0, 2);
+/// assert!((area - 9.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// * n should be even and larger than 0
+pub fn integrate_simpson<F>(f: F, a: f64, b: f64, n: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if n == 0 || !n.is_multiple_of(2) {
+        panic!("n should be even and larger than 0");
+    }
+    let h = (b - a) / n as f64;
+    // The inner points are weighted 4, 2, 4, ..., 4 and the ends 1.
+    let inner: f64 = (1..n)
+        .map(|i| {
+            let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+            weight * f(a + i as f64 * h)
+        })
+        .sum();
+    (f(a) + inner + f(b)) * h / 3.0
+}
+
+/// Nodes on [-1, 1] and their weights for 1 to 5 points, symmetric around 0.
+const GAUSS_LEGENDRE: [&[(f64, f64)]; 5] = [
+    &[(0.0, 2.0)],
+    &[
+        (-0.577_350_269_189_625_8, 1.0),
+        (0.577_350_269_189_625_8, 1.0),
+    ],
+    &[
+        (-0.774_596_669_241_483_4, 5.0 / 9.0),
+        (0.0, 8.0 / 9.0),
+        (0.774_596_669_241_483_4, 5.0 / 9.0),
+    ],
+    &[
+        (-0.861_136_311_594_052_6, 0.347_854_845_137_453_9),
+        (-0.339_981_043_584_856_3, 0.652_145_154_862_546_1),
+        (0.339_981_043_584_856_3, 0.652_145_154_862_546_1),
+        (0.861_136_311_594_052_6, 0.347_854_845_137_453_9),
+    ],
+    &[
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.0, 128.0 / 225.0),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    ],
+];
+
+/// # [Gauss–Legendre Quadrature](https://en.wikipedia.org/wiki/Gauss%E2%80%93Legendre_quadrature)
+///
+/// Approximates the integral of f from a to b as a weighted sum of f at n points, which are the
+/// roots of the n-th Legendre polynomial mapped from [-1, 1] onto [a, b]. It is exact for
+/// polynomials up to degree 2n - 1, so it is more accurate than Simpson's rule for smooth
+/// functions with as many evaluations.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::integration;
+///
+/// // 3 points integrate x⁵ exactly.
+/// let area = integration::integrate_gauss_legendre(|x| x.powi(5), 0.0, 2.0, 3);
+/// assert!((area - 64.0 / 6.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// * n should be between 1 and 5
+pub fn integrate_gauss_legendre<F>(f: F, a: f64, b: f64, n: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if !(1..=5).contains(&n) {
+        panic!("n should be between 1 and 5");
+    }
+    let (half_width, middle) = ((b - a) / 2.0, (a + b) / 2.0);
+    let sum: f64 = GAUSS_LEGENDRE[n - 1]
+        .iter()
+        .map(|&(node, weight)| weight * f(half_width * node + middle))
+        .sum();
+    half_width * sum
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use std::f64::consts::{E, PI};
+
+    #[test]
+    fn integrates_sine() {
+        assert!((integrate_simpson(f64::sin, 0.0, PI, 100) - 2.0).abs() < 1e-7);
+        assert!((integrate_gauss_legendre(f64::sin, 0.0, PI, 5) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrates_polynomials_exactly() {
+        let third = 1.0 / 3.0;
+        assert!((integrate_simpson(|x| x * x, 0.0, 1.0, 2) - third).abs() < 1e-15);
+        for n in 2..6 {
+            assert!((integrate_gauss_legendre(|x| x * x, 0.0, 1.0, n) - third).abs() < 1e-15);
+        }
+        // n points are exact up to degree 2n - 1.
+        for n in 1..6 {
+            let degree = 2 * n as i32 - 1;
+            let exact = 2f64.powi(degree + 1) / f64::from(degree + 1);
+            let area = integrate_gauss_legendre(|x| x.powi(degree), 0.0, 2.0, n);
+            assert!((area - exact).abs() < 1e-12, "n={}", n);
+        }
+    }
+
+    #[test]
+    fn weights_add_up_to_interval_length() {
+        for n in 1..6 {
+            assert!((integrate_gauss_legendre(|_| 1.0, -3.0, 4.5, n) - 7.5).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn gauss_legendre_beats_simpson_with_as_many_evaluations() {
+        // Both evaluate e^x 5 times.
+        let simpson_error = (integrate_simpson(f64::exp, 0.0, 1.0, 4) - (E - 1.0)).abs();
+        let gauss_error = (integrate_gauss_legendre(f64::exp, 0.0, 1.0, 5) - (E - 1.0)).abs();
+        assert!(gauss_error * 1e6 < simpson_error);
+    }
+
+    #[test]
+    fn receives_reversed_bounds() {
+        assert!((integrate_simpson(f64::cos, PI / 2.0, 0.0, 50) + 1.0).abs() < 1e-6);
+        assert!((integrate_gauss_legendre(f64::cos, PI / 2.0, 0.0, 5) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be even and larger than 0")]
+    fn receives_odd_simpson_intervals() {
+        integrate_simpson(f64::sin, 0.0, 1.0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be between 1 and 5")]
+    fn receives_too_many_gauss_legendre_points() {
+        integrate_gauss_legendre(f64::sin, 0.0, 1.0, 6);
+    }
+}
@@ -0,0 +1,225 @@
+//! # Linear Algebra
+//!
+//! Solving systems of linear equations and inverting matrices, stored as vectors of rows.
+
+/// Relative size below which a pivot is taken as zero, as rounding rarely leaves exact zeros.
+const PIVOT_TOLERANCE: f64 = 1e-12;
+
+/// # [Gaussian Elimination](https://en.wikipedia.org/wiki/Gaussian_elimination)
+///
+/// Solves the linear system Ax = b for x in O(n³) time, where a is the n×n matrix A given as a
+/// vector of rows. Partial pivoting swaps the row with the largest entry in each column into
+/// the pivot position, which keeps the rounding errors small. The matrix is singular, and None
+/// is returned, if a column has no usable pivot: one larger than a tiny fraction of the largest
+/// entry of a.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::linear_algebra;
+///
+/// // x + y = 3 and x - y = 1
+/// let a = vec![vec![1.0, 1.0], vec![1.0, -1.0]];
+/// let x = linear_algebra::gaussian_elimination(a, vec![3.0, 1.0]).unwrap();
+/// assert!((x[0] - 2.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+///
+/// let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+/// assert_eq!(linear_algebra::gaussian_elimination(singular, vec![1.0, 2.0]), None);
+/// ```
+///
+/// # Panics
+///
+/// * a should be a square matrix with as many rows as b has values
+pub fn gaussian_elimination(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        panic!("a should be a square matrix with as many rows as b has values");
+    }
+    let tolerance = largest_entry(&a) * PIVOT_TOLERANCE;
+    for col in 0..n {
+        let pivot = pivot_row(&a, col, tolerance)?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (row, i) in lower.iter_mut().zip(col + 1..n) {
+            let factor = row[col] / pivot_row[col];
+            for (value, &pivot_value) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[i] -= factor * b[col];
+        }
+    }
+    // Back substitution on the upper triangular system.
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+/// # [Matrix Inverse](https://en.wikipedia.org/wiki/Invertible_matrix) by Gauss–Jordan Elimination
+///
+/// Computes the inverse of the square matrix m, given as a vector of rows, in O(n³) time. The
+/// matrix is augmented with the identity, [m | I], and reduced with partial pivoting until the
+/// left half is the identity, which leaves the inverse in the right half: [I | m⁻¹]. Returns
+/// None if m is singular, under the same rule as [`gaussian_elimination`].
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::linear_algebra;
+///
+/// let m = vec![vec![4.0, 7.0], vec![2.0, 6.0]];
+/// let inverse = linear_algebra::matrix_inverse(&m).unwrap();
+/// let expected = [[0.6, -0.7], [-0.2, 0.4]];
+/// for i in 0..2 {
+///     for j in 0..2 {
+///         assert!((inverse[i][j] - expected[i][j]).abs() < 1e-12);
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// * m should be a square matrix
+pub fn matrix_inverse(m: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = m.len();
+    if m.iter().any(|row| row.len() != n) {
+        panic!("m should be a square matrix");
+    }
+    let tolerance = largest_entry(m) * PIVOT_TOLERANCE;
+    let mut augmented: Vec<Vec<f64>> = m
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| if i == j { 1.0 } else { 0.0 }));
+            row
+        })
+        .collect();
+    for col in 0..n {
+        let pivot = pivot_row(&augmented, col, tolerance)?;
+        augmented.swap(col, pivot);
+        let scale = augmented[col][col];
+        for value in &mut augmented[col] {
+            *value /= scale;
+        }
+        let pivot_row = augmented[col].clone();
+        for (i, row) in augmented.iter_mut().enumerate() {
+            if i != col && row[col] != 0.0 {
+                let factor = row[col];
+                for (value, &pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+    Some(augmented.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+/// Returns the row at or below col with the largest entry in column col, or None if even that
+/// entry is not larger than tolerance.
+fn pivot_row(a: &[Vec<f64>], col: usize, tolerance: f64) -> Option<usize> {
+    let pivot = (col..a.len()).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+    if a[pivot][col].abs() > tolerance {
+        Some(pivot)
+    } else {
+        None
+    }
+}
+
+/// Returns the largest absolute value among the first n columns of the n rows of a.
+fn largest_entry(a: &[Vec<f64>]) -> f64 {
+    a.iter()
+        .flat_map(|row| &row[..a.len()])
+        .fold(0.0, |largest, value| value.abs().max(largest))
+}
+
+#[cfg(test)]
+mod linear_algebra_tests {
+    use super::*;
+
+    fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        a.iter()
+            .map(|row| {
+                (0..b[0].len())
+                    .map(|j| row.iter().zip(b).map(|(x, b_row)| x * b_row[j]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn solves_three_by_three_system() {
+        // 2x + y - z = 8, -3x - y + 2z = -11 and -2x + y + 2z = -3 give x = 2, y = 3, z = -1.
+        let a = vec![
+            vec![2.0, 1.0, -1.0],
+            vec![-3.0, -1.0, 2.0],
+            vec![-2.0, 1.0, 2.0],
+        ];
+        let x = gaussian_elimination(a, vec![8.0, -11.0, -3.0]).unwrap();
+        for (value, expected) in x.iter().zip(&[2.0, 3.0, -1.0]) {
+            assert!((value - expected).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn needs_pivoting() {
+        // The first pivot is zero, so the rows have to be swapped.
+        let a = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        assert_eq!(
+            gaussian_elimination(a, vec![5.0, 7.0]),
+            Some(vec![7.0, 5.0])
+        );
+    }
+
+    #[test]
+    fn detects_singular_matrices() {
+        let a = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+        assert_eq!(gaussian_elimination(a.clone(), vec![1.0, 2.0, 3.0]), None);
+        assert_eq!(matrix_inverse(&a), None);
+        assert_eq!(matrix_inverse(&[vec![0.0]]), None);
+    }
+
+    #[test]
+    fn inverts_matrices() {
+        let a = vec![
+            vec![2.0, 1.0, -1.0, 0.5],
+            vec![-3.0, -1.0, 2.0, 4.0],
+            vec![-2.0, 1.0, 2.0, 1.0],
+            vec![1.0, 0.0, 3.0, -2.0],
+        ];
+        let inverse = matrix_inverse(&a).unwrap();
+        for product in &[multiply(&a, &inverse), multiply(&inverse, &a)] {
+            for (i, row) in product.iter().enumerate() {
+                for (j, &value) in row.iter().enumerate() {
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((value - expected).abs() < 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn handles_one_by_one_and_empty_matrices() {
+        assert_eq!(matrix_inverse(&[vec![4.0]]), Some(vec![vec![0.25]]));
+        assert_eq!(
+            gaussian_elimination(vec![vec![4.0]], vec![2.0]),
+            Some(vec![0.5])
+        );
+        assert_eq!(matrix_inverse(&[]), Some(vec![]));
+        assert_eq!(gaussian_elimination(vec![], vec![]), Some(vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "a should be a square matrix with as many rows as b has values")]
+    fn receives_mismatched_system() {
+        gaussian_elimination(vec![vec![1.0, 2.0]], vec![1.0]);
+    }
+}
@@ -0,0 +1,11 @@
+//! # Math
+//!
+//! A collection of modules containing mathematical algorithms.
+
+pub mod continued_fraction;
+pub mod gray_code;
+pub mod integration;
+pub mod linear_algebra;
+pub mod number_theory;
+pub mod ode;
+pub mod polynomial;
@@ -0,0 +1,1032 @@
+//! # Number Theory
+//!
+//! A collection of functions on the integers.
+
+use std::collections::HashMap;
+
+/// # [Euler's Totient Function](https://en.wikipedia.org/wiki/Euler%27s_totient_function)
+///
+/// Counts the integers in [1, n] that are coprime to n, by factorizing n with trial division
+/// and applying Euler's product formula φ(n) = n ∏ (1 - 1/p) over its prime factors p.
+/// φ(0) is defined as 0.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// assert_eq!(number_theory::euler_totient(12), 4);
+/// assert_eq!(number_theory::euler_totient(13), 12);
+/// ```
+pub fn euler_totient(n: u64) -> u64 {
+    let (mut n, mut totient) = (n, n);
+    let mut p = 2;
+    while p <= n / p {
+        if n % p == 0 {
+            while n % p == 0 {
+                n /= p;
+            }
+            totient -= totient / p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        totient -= totient / n; // the remaining prime factor
+    }
+    totient
+}
+
+#[cfg(test)]
+mod euler_totient_tests {
+    use super::*;
+
+    #[test]
+    fn receives_small_numbers() {
+        assert_eq!(euler_totient(0), 0);
+        assert_eq!(euler_totient(1), 1);
+        assert_eq!(euler_totient(2), 1);
+        assert_eq!(euler_totient(12), 4);
+        assert_eq!(euler_totient(36), 12);
+    }
+
+    #[test]
+    fn receives_primes() {
+        for &p in &[2, 3, 5, 7, 97, 7919, 1_000_000_007] {
+            assert_eq!(euler_totient(p), p - 1);
+        }
+    }
+
+    #[test]
+    fn sums_to_n_over_divisors() {
+        for n in 1..500 {
+            let sum: u64 = (1..=n).filter(|d| n % d == 0).map(euler_totient).sum();
+            assert_eq!(sum, n);
+        }
+    }
+}
+
+/// # [Euler's Totient Function](https://en.wikipedia.org/wiki/Euler%27s_totient_function) Sieve
+///
+/// Calculates φ(n) for every n in [0, limit] in O(n log log n), by sieving the primes like the
+/// sieve of Eratosthenes and applying the factor (1 - 1/p) of each prime p to its multiples.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// assert_eq!(number_theory::euler_totient_sieve(10), vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+/// ```
+pub fn euler_totient_sieve(limit: usize) -> Vec<u64> {
+    let mut totients: Vec<u64> = (0..=limit as u64).collect();
+    for p in 2..=limit {
+        if totients[p] == p as u64 {
+            // p is untouched by smaller primes, so it is a prime.
+            for multiple in (p..=limit).step_by(p) {
+                totients[multiple] -= totients[multiple] / p as u64;
+            }
+        }
+    }
+    totients
+}
+
+#[cfg(test)]
+mod euler_totient_sieve_tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_euler_totient() {
+        let totients = euler_totient_sieve(1000);
+        assert_eq!(totients.len(), 1001);
+        for (n, &totient) in totients.iter().enumerate() {
+            assert_eq!(totient, euler_totient(n as u64));
+        }
+    }
+
+    #[test]
+    fn receives_small_limits() {
+        assert_eq!(euler_totient_sieve(0), vec![0]);
+        assert_eq!(euler_totient_sieve(1), vec![0, 1]);
+    }
+}
+
+/// # Smallest Prime Factor Sieve
+///
+/// Precomputes the smallest prime factor of every number up to a limit with a
+/// [sieve of Eratosthenes](https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes) in
+/// O(n log log n), after which any number up to the limit is factorized in O(log n) by dividing
+/// out its smallest prime factor until 1 is left. This is much faster than factorizing many
+/// small numbers one by one.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory::SmallestPrimeFactor;
+///
+/// let spf = SmallestPrimeFactor::new(100);
+/// assert_eq!(spf.factorize(84), vec![2, 2, 3, 7]);
+/// assert_eq!(spf.factorize(97), vec![97]);
+/// assert_eq!(spf.factorize(1), vec![]);
+/// ```
+pub struct SmallestPrimeFactor {
+    spf: Vec<u32>,
+}
+
+impl SmallestPrimeFactor {
+    /// Sieves the smallest prime factors of the numbers up to limit.
+    ///
+    /// # Panics
+    ///
+    /// * limit should fit in u32
+    pub fn new(limit: usize) -> SmallestPrimeFactor {
+        if limit > u32::MAX as usize {
+            panic!("limit should fit in u32");
+        }
+        // 0 is its own entry, and numbers not yet marked by a smaller prime are primes.
+        let mut spf: Vec<u32> = (0..=limit as u32).collect();
+        let mut p = 2;
+        while p <= limit / p {
+            if spf[p] == p as u32 {
+                for multiple in (p * p..=limit).step_by(p) {
+                    if spf[multiple] == multiple as u32 {
+                        spf[multiple] = p as u32;
+                    }
+                }
+            }
+            p += 1;
+        }
+        SmallestPrimeFactor { spf }
+    }
+
+    /// Returns the prime factors of n in ascending order, repeated by their multiplicity.
+    ///
+    /// # Panics
+    ///
+    /// * n should be between 1 and the limit of the sieve
+    pub fn factorize(&self, mut n: u32) -> Vec<u32> {
+        if n < 1 || n as usize >= self.spf.len() {
+            panic!("n should be between 1 and the limit of the sieve");
+        }
+        let mut factors = Vec::new();
+        while n > 1 {
+            let p = self.spf[n as usize];
+            factors.push(p);
+            n /= p;
+        }
+        factors
+    }
+}
+
+#[cfg(test)]
+mod smallest_prime_factor_tests {
+    use super::*;
+
+    fn trial_division(mut n: u32) -> Vec<u32> {
+        let mut factors = Vec::new();
+        let mut p = 2;
+        while p <= n / p {
+            while n.is_multiple_of(p) {
+                factors.push(p);
+                n /= p;
+            }
+            p += 1;
+        }
+        if n > 1 {
+            factors.push(n);
+        }
+        factors
+    }
+
+    #[test]
+    fn agrees_with_trial_division() {
+        let spf = SmallestPrimeFactor::new(1000);
+        for n in 1..=1000 {
+            assert_eq!(spf.factorize(n), trial_division(n));
+        }
+    }
+
+    #[test]
+    fn factorizes_prime_powers() {
+        let spf = SmallestPrimeFactor::new(1_000_000);
+        for &p in &[2, 3, 31, 997] {
+            assert_eq!(spf.factorize(p * p), vec![p, p]);
+        }
+        assert_eq!(spf.factorize(1 << 19), vec![2; 19]);
+        assert_eq!(spf.factorize(999_983), vec![999_983]);
+    }
+
+    #[test]
+    fn receives_limit() {
+        assert_eq!(SmallestPrimeFactor::new(12).factorize(12), vec![2, 2, 3]);
+        assert_eq!(SmallestPrimeFactor::new(1).factorize(1), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be between 1 and the limit of the sieve")]
+    fn receives_n_above_limit() {
+        SmallestPrimeFactor::new(100).factorize(101);
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be between 1 and the limit of the sieve")]
+    fn receives_zero() {
+        SmallestPrimeFactor::new(100).factorize(0);
+    }
+}
+
+/// # [Baby-Step Giant-Step](https://en.wikipedia.org/wiki/Baby-step_giant-step)
+///
+/// Finds the smallest x ≥ 0 such that base<sup>x</sup> ≡ target (mod modulus), the discrete
+/// logarithm of target, in O(√modulus) time and space, or None if there is no such x.
+///
+/// With m = ⌈√modulus⌉, every candidate is written as x = p·m - q with 1 ≤ p ≤ m and
+/// 0 ≤ q ≤ m: the baby steps store target · base<sup>q</sup> for every q, and the giant steps
+/// look up base<sup>p·m</sup> for every p. When base and modulus share a factor, the factor is
+/// first divided out of the congruence, one step of x at a time.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// assert_eq!(number_theory::discrete_log(2, 4, 7), Some(2));
+/// assert_eq!(number_theory::discrete_log(3, 1, 7), Some(0));
+/// assert_eq!(number_theory::discrete_log(2, 3, 7), None);
+/// ```
+///
+/// # Panics
+///
+/// * modulus is 0
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        panic!("modulus should be larger than 0");
+    }
+    let (mut base, mut target, mut modulus) = (base % modulus, target % modulus, modulus);
+    if modulus == 1 {
+        return Some(0);
+    }
+
+    // Reduce base^x ≡ target to k · base^(x - steps) ≡ target' with base coprime to modulus'.
+    let (mut k, mut steps) = (1 % modulus, 0);
+    loop {
+        let g = gcd(base, modulus);
+        if g == 1 {
+            break;
+        }
+        if target == k {
+            return Some(steps);
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        modulus /= g;
+        steps += 1;
+        k = mul_mod(k, base / g, modulus);
+        base %= modulus;
+    }
+
+    let m = (modulus as f64).sqrt().ceil() as u64 + 1;
+    let mut baby_steps = HashMap::new();
+    let mut current = target % modulus;
+    for q in 0..=m {
+        baby_steps.insert(current, q); // the largest q gives the smallest x
+        current = mul_mod(current, base, modulus);
+    }
+
+    let giant_step = pow_mod(base, m, modulus);
+    let mut current = k;
+    for p in 1..=m {
+        current = mul_mod(current, giant_step, modulus);
+        if let Some(&q) = baby_steps.get(&current) {
+            return Some(p * m - q + steps);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod discrete_log_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn brute_force(base: u64, target: u64, modulus: u64) -> Option<u64> {
+        let mut current = 1 % modulus;
+        for x in 0..=2 * modulus {
+            if current == target % modulus {
+                return Some(x);
+            }
+            current = current * base % modulus;
+        }
+        None
+    }
+
+    #[test]
+    fn receives_small_prime_modulus() {
+        assert_eq!(discrete_log(2, 1, 7), Some(0));
+        assert_eq!(discrete_log(2, 4, 7), Some(2));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        assert_eq!(discrete_log(3, 6, 7), Some(3));
+        assert_eq!(discrete_log(5, 3, 7), Some(5));
+    }
+
+    #[test]
+    fn receives_unsolvable_congruences() {
+        assert_eq!(discrete_log(2, 3, 7), None); // 2 generates only 1, 2 and 4
+        assert_eq!(discrete_log(4, 3, 8), None);
+        assert_eq!(discrete_log(0, 5, 11), None);
+    }
+
+    #[test]
+    fn receives_non_coprime_base() {
+        assert_eq!(discrete_log(2, 0, 8), Some(3));
+        assert_eq!(discrete_log(6, 0, 36), Some(2));
+        assert_eq!(discrete_log(0, 1, 11), Some(0));
+        assert_eq!(discrete_log(0, 0, 11), Some(1));
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        test_util::agrees(
+            2000,
+            |rng| {
+                let modulus = rng.gen_range(1, 300);
+                (rng.gen_range(0, 300), rng.gen_range(0, 300), modulus)
+            },
+            |&(base, target, modulus)| discrete_log(base, target, modulus),
+            |&(base, target, modulus)| brute_force(base, target, modulus),
+        );
+    }
+
+    #[test]
+    fn receives_large_prime_modulus() {
+        let modulus = 1_000_000_007;
+        let x = discrete_log(5, 123_456_789, modulus).unwrap();
+        assert_eq!(pow_mod(5, x, modulus), 123_456_789);
+    }
+}
+
+/// # [Multiplicative Order](https://en.wikipedia.org/wiki/Multiplicative_order)
+///
+/// Finds the smallest x > 0 such that base<sup>x</sup> ≡ 1 (mod modulus), or None if base is not
+/// coprime to modulus. The order divides φ(modulus), so it is found by dividing the prime
+/// factors out of φ(modulus) as long as the power stays 1.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// assert_eq!(number_theory::multiplicative_order(3, 7), Some(6));
+/// assert_eq!(number_theory::multiplicative_order(2, 7), Some(3));
+/// assert_eq!(number_theory::multiplicative_order(2, 8), None);
+/// ```
+///
+/// # Panics
+///
+/// * modulus is 0
+pub fn multiplicative_order(base: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        panic!("modulus should be larger than 0");
+    }
+    if gcd(base, modulus) != 1 {
+        return None;
+    }
+    let totient = euler_totient(modulus);
+    let (mut order, mut rest, mut p) = (totient, totient, 2);
+    while rest > 1 {
+        if p > rest / p {
+            p = rest; // the remaining prime factor
+        }
+        if rest % p == 0 {
+            while rest % p == 0 {
+                rest /= p;
+            }
+            while order % p == 0 && pow_mod(base, order / p, modulus) == 1 % modulus {
+                order /= p;
+            }
+        }
+        p += 1;
+    }
+    Some(order)
+}
+
+#[cfg(test)]
+mod multiplicative_order_tests {
+    use super::*;
+
+    #[test]
+    fn receives_small_moduli() {
+        assert_eq!(multiplicative_order(3, 7), Some(6));
+        assert_eq!(multiplicative_order(6, 7), Some(2));
+        assert_eq!(multiplicative_order(1, 7), Some(1));
+        assert_eq!(multiplicative_order(5, 1), Some(1));
+        assert_eq!(multiplicative_order(0, 7), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        for modulus in 2..200 {
+            for base in 0..modulus {
+                let expected = (1..=modulus).find(|&x| pow_mod(base, x, modulus) == 1);
+                assert_eq!(multiplicative_order(base, modulus), expected);
+            }
+        }
+    }
+}
+
+/// # [Tonelli–Shanks Algorithm](https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm)
+///
+/// Finds a square root of n modulo the prime p, an x such that x<sup>2</sup> ≡ n (mod p), in
+/// O(log² p) multiplications. The other root is p - x. Returns None if n is not a quadratic
+/// residue, which the Legendre symbol n<sup>(p-1)/2</sup> mod p shows first. The result is
+/// meaningless if p is not a prime.
+///
+/// With p - 1 = q · 2<sup>s</sup> for an odd q, the guess n<sup>(q+1)/2</sup> is off by a
+/// factor whose order is a power of 2, which is halved in every step by multiplying in a power
+/// of a non-residue.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// let root = number_theory::sqrt_mod(4, 7).unwrap();
+/// assert!(root == 2 || root == 5);
+/// assert_eq!(number_theory::sqrt_mod(2, 7), Some(4));
+/// assert_eq!(number_theory::sqrt_mod(3, 7), None);
+/// ```
+///
+/// # Panics
+///
+/// * p should be larger than 1
+pub fn sqrt_mod(n: u64, p: u64) -> Option<u64> {
+    if p <= 1 {
+        panic!("p should be larger than 1");
+    }
+    let n = n % p;
+    if n == 0 || p == 2 {
+        return Some(n);
+    }
+    if pow_mod(n, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let (mut q, mut s) = (p - 1, 0);
+    while q % 2 == 0 {
+        q /= 2;
+        s += 1;
+    }
+    // Half of the numbers are non-residues, so one is found after a few tries.
+    let z = (2..p).find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)?;
+    let (mut m, mut c) = (s, pow_mod(z, q, p));
+    let (mut t, mut root) = (pow_mod(n, q, p), pow_mod(n, q.div_ceil(2), p));
+    while t != 1 {
+        // The order of t is 2^i for some i < m.
+        let (mut i, mut square) = (0, t);
+        while square != 1 {
+            square = mul_mod(square, square, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        root = mul_mod(root, b, p);
+    }
+    Some(root)
+}
+
+#[cfg(test)]
+mod sqrt_mod_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_small_primes() {
+        let root = sqrt_mod(4, 7).unwrap();
+        assert!(root == 2 || root == 5);
+        assert_eq!(sqrt_mod(2, 7), Some(4));
+        assert_eq!(sqrt_mod(2, 7).map(|root| 7 - root), Some(3));
+        assert_eq!(sqrt_mod(0, 7), Some(0));
+        assert_eq!(sqrt_mod(14, 7), Some(0));
+        assert_eq!(sqrt_mod(1, 2), Some(1));
+        assert_eq!(sqrt_mod(3, 7), None);
+        assert_eq!(sqrt_mod(5, 7), None);
+        assert_eq!(sqrt_mod(6, 7), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        // 17, 97 and 257 have p - 1 divisible by high powers of 2.
+        for &p in &[2, 3, 5, 7, 11, 13, 17, 41, 97, 257, 641] {
+            for n in 0..p {
+                let has_root = (0..p).any(|x| x * x % p == n);
+                match sqrt_mod(n, p) {
+                    Some(root) => assert_eq!(mul_mod(root, root, p), n),
+                    None => assert!(!has_root, "{} has a root mod {}", n, p),
+                }
+                assert_eq!(sqrt_mod(n, p).is_some(), has_root);
+            }
+        }
+    }
+
+    #[test]
+    fn receives_random_residues() {
+        let primes = [
+            1_000_000_007,
+            998_244_353,
+            4_294_967_291,
+            18_446_744_069_414_584_321,
+            18_446_744_073_709_551_557,
+        ];
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let p = *rng.choose(&primes).unwrap();
+            let x = rng.gen_range(0, p);
+            let n = mul_mod(x, x, p);
+            let root = sqrt_mod(n, p).unwrap();
+            assert_eq!(mul_mod(root, root, p), n);
+            assert!(root == x || root == (p - x) % p);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "p should be larger than 1")]
+    fn receives_p_of_one() {
+        sqrt_mod(0, 1);
+    }
+}
+
+/// # [Berlekamp–Massey Algorithm](https://en.wikipedia.org/wiki/Berlekamp%E2%80%93Massey_algorithm)
+///
+/// Finds the shortest linear recurrence a<sub>n</sub> = c<sub>1</sub>·a<sub>n-1</sub> + ... +
+/// c<sub>L</sub>·a<sub>n-L</sub> (mod modulus) that generates a sequence, returning its
+/// coefficients [c<sub>1</sub>, ..., c<sub>L</sub>] in O(n²) time. The recurrence found so far
+/// is corrected whenever it mispredicts the next term, by subtracting a multiple of the
+/// recurrence from before its last change in length. A recurrence of length L is determined by
+/// 2L terms, so the sequence should be at least that long for the result to be the intended one.
+///
+/// The modulus should be a prime, so that the mispredictions can be divided by.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// // Fibonacci: a(n) = a(n-1) + a(n-2)
+/// assert_eq!(number_theory::berlekamp_massey(&[0, 1, 1, 2, 3, 5, 8], 1_000_000_007), vec![1, 1]);
+/// // a(n) = 2·a(n-1) - a(n-2), with -1 as 6 mod 7
+/// assert_eq!(number_theory::berlekamp_massey(&[1, 2, 3, 4, 5], 7), vec![2, 6]);
+/// ```
+///
+/// # Panics
+///
+/// * modulus should be larger than 1
+pub fn berlekamp_massey(sequence: &[i64], modulus: i64) -> Vec<i64> {
+    if modulus <= 1 {
+        panic!("modulus should be larger than 1");
+    }
+    let m = modulus as u64;
+    let a: Vec<u64> = sequence
+        .iter()
+        .map(|&x| x.rem_euclid(modulus) as u64)
+        .collect();
+    let n = a.len();
+    // connection: 1 - c1·x - ... - cL·x^L, and previous: the connection before the last change
+    // in length, which mispredicted by previous_discrepancy `shift` terms ago.
+    let (mut connection, mut previous) = (vec![0; n + 1], vec![0; n + 1]);
+    connection[0] = 1;
+    previous[0] = 1;
+    let (mut len, mut shift, mut previous_discrepancy) = (0, 1, 1);
+    for i in 0..n {
+        let discrepancy =
+            (0..=len).fold(0, |sum, j| (sum + mul_mod(connection[j], a[i - j], m)) % m);
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let factor = mul_mod(discrepancy, pow_mod(previous_discrepancy, m - 2, m), m);
+        let last = connection.clone();
+        for j in shift..=n {
+            connection[j] = (connection[j] + m - mul_mod(factor, previous[j - shift], m)) % m;
+        }
+        if 2 * len <= i {
+            len = i + 1 - len;
+            previous = last;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    connection[1..=len]
+        .iter()
+        .map(|&c| ((m - c) % m) as i64)
+        .collect()
+}
+
+#[cfg(test)]
+mod berlekamp_massey_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    const MODULUS: i64 = 1_000_000_007;
+
+    fn generates(coefficients: &[i64], sequence: &[i64], modulus: i64) -> bool {
+        (coefficients.len()..sequence.len()).all(|n| {
+            let predicted = coefficients
+                .iter()
+                .enumerate()
+                .fold(0, |sum, (i, &c)| (sum + c * sequence[n - 1 - i]) % modulus);
+            predicted == sequence[n].rem_euclid(modulus)
+        })
+    }
+
+    #[test]
+    fn finds_fibonacci() {
+        assert_eq!(
+            berlekamp_massey(&[0, 1, 1, 2, 3, 5, 8], MODULUS),
+            vec![1, 1]
+        );
+        assert_eq!(
+            berlekamp_massey(&[2, 1, 3, 4, 7, 11, 18], MODULUS),
+            vec![1, 1]
+        );
+    }
+
+    #[test]
+    fn finds_progressions() {
+        assert_eq!(berlekamp_massey(&[4, 4, 4, 4, 4], MODULUS), vec![1]);
+        assert_eq!(berlekamp_massey(&[1, 3, 9, 27, 81], MODULUS), vec![3]);
+        // An arithmetic progression with a nonzero difference needs two terms.
+        assert_eq!(
+            berlekamp_massey(&[1, 4, 7, 10, 13], MODULUS),
+            vec![2, MODULUS - 1]
+        );
+        assert_eq!(
+            berlekamp_massey(&[-1, -2, -3, -4], MODULUS),
+            vec![2, MODULUS - 1]
+        );
+    }
+
+    #[test]
+    fn receives_short_sequences() {
+        let x = test_util::rng().gen_range(1, MODULUS);
+        assert_eq!(berlekamp_massey(&[x], MODULUS).len(), 1);
+        assert_eq!(berlekamp_massey(&[], MODULUS), vec![]);
+        assert_eq!(berlekamp_massey(&[0, 0, 0], MODULUS), vec![]);
+        assert_eq!(berlekamp_massey(&[0, 0, 5], MODULUS).len(), 3);
+    }
+
+    #[test]
+    fn recovers_random_recurrences() {
+        let mut rng = test_util::rng();
+        for &modulus in &[2, 7, 998_244_353, MODULUS] {
+            for _ in 0..50 {
+                let len = rng.gen_range(1, 8);
+                let coefficients: Vec<i64> = (0..len).map(|_| rng.gen_range(0, modulus)).collect();
+                let mut sequence: Vec<i64> = (0..len).map(|_| rng.gen_range(0, modulus)).collect();
+                for n in len..3 * len {
+                    let next = (0..len).fold(0, |sum, i| {
+                        (sum + coefficients[i] * sequence[n - 1 - i] % modulus) % modulus
+                    });
+                    sequence.push(next);
+                }
+                let found = berlekamp_massey(&sequence, modulus);
+                assert!(found.len() <= len);
+                assert!(generates(&found, &sequence, modulus));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus should be larger than 1")]
+    fn receives_modulus_of_one() {
+        berlekamp_massey(&[1, 2], 1);
+    }
+}
+
+/// # [Number-theoretic Transform](https://en.wikipedia.org/wiki/Discrete_Fourier_transform_over_a_ring#Number-theoretic_transform)
+///
+/// Transforms the coefficients of a polynomial into its values at the powers of a root of unity
+/// modulo a prime in place, or back if `invert` is set, with the iterative Cooley–Tukey
+/// algorithm in O(n log n) time. The root of unity of order n is derived from the primitive
+/// root of the modulus. Coefficients are reduced modulo the modulus first.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// let mut a = vec![1, 2, 3, 4];
+/// number_theory::ntt(&mut a, false, 998_244_353, 3);
+/// assert_eq!(a[0], 10); // the value at 1 is the sum of the coefficients
+/// number_theory::ntt(&mut a, true, 998_244_353, 3);
+/// assert_eq!(a, vec![1, 2, 3, 4]);
+/// ```
+///
+/// # Panics
+///
+/// * modulus is smaller than 2
+/// * the length of a is not a power of two
+/// * the length of a does not divide modulus - 1
+/// * primitive_root does not give a root of unity whose order is the length of a
+pub fn ntt(a: &mut [u64], invert: bool, modulus: u64, primitive_root: u64) {
+    if modulus < 2 {
+        panic!("modulus should be larger than 1");
+    }
+    let n = a.len();
+    if !n.is_power_of_two() {
+        panic!("length should be a power of two");
+    }
+    if !(modulus - 1).is_multiple_of(n as u64) {
+        panic!("length should divide modulus - 1");
+    }
+    // A root of unity of a power-of-two order n has exactly that order if its (n/2)-th power is -1.
+    let root_of_unity = pow_mod(primitive_root, (modulus - 1) / n as u64, modulus);
+    if n > 1 && pow_mod(root_of_unity, n as u64 / 2, modulus) != modulus - 1 {
+        panic!("primitive_root should give a root of unity whose order is the length");
+    }
+    for x in a.iter_mut() {
+        *x %= modulus;
+    }
+
+    // Reorder by bit-reversed index, so that each pass combines adjacent halves.
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut root = pow_mod(primitive_root, (modulus - 1) / len as u64, modulus);
+        if invert {
+            root = pow_mod(root, modulus - 2, modulus);
+        }
+        for start in (0..n).step_by(len) {
+            let mut w = 1;
+            for k in start..start + len / 2 {
+                let (u, v) = (a[k], mul_mod(a[k + len / 2], w, modulus));
+                a[k] = add_mod(u, v, modulus);
+                a[k + len / 2] = sub_mod(u, v, modulus);
+                w = mul_mod(w, root, modulus);
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let n_inverse = pow_mod(n as u64, modulus - 2, modulus);
+        for x in a.iter_mut() {
+            *x = mul_mod(*x, n_inverse, modulus);
+        }
+    }
+}
+
+/// Multiplies two polynomials with coefficients modulo a prime by transforming both with `ntt`,
+/// multiplying their values pointwise and transforming back, in O(n log n) time. The modulus
+/// should be a prime p where 2<sup>k</sup> divides p - 1 for a power of two 2<sup>k</sup> at
+/// least the length of the product, such as 998244353 = 119 · 2<sup>23</sup> + 1. Any
+/// quadratic non-residue serves as the primitive root, as only roots of unity whose order is a
+/// power of two are needed.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::number_theory;
+///
+/// // (1 + 2x + 3x²)(4 + 5x) = 4 + 13x + 22x² + 15x³
+/// let product = number_theory::poly_mul_mod(&[1, 2, 3], &[4, 5], 998_244_353);
+/// assert_eq!(product, vec![4, 13, 22, 15]);
+/// ```
+///
+/// # Panics
+///
+/// * modulus is not a prime
+/// * the power of two covering the length of the product does not divide modulus - 1
+pub fn poly_mul_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    if !is_prime(modulus) {
+        panic!("modulus should be a prime");
+    }
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    // Half of the numbers are non-residues by Euler's criterion, so one is found after a few tries.
+    let root = match modulus {
+        2 => 1,
+        _ => (2..modulus)
+            .find(|&g| pow_mod(g, (modulus - 1) / 2, modulus) == modulus - 1)
+            .unwrap(),
+    };
+
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(n, 0);
+    fb.resize(n, 0);
+    ntt(&mut fa, false, modulus, root);
+    ntt(&mut fb, false, modulus, root);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = mul_mod(*x, *y, modulus);
+    }
+    ntt(&mut fa, true, modulus, root);
+    fa.truncate(len);
+    fa
+}
+
+#[cfg(test)]
+mod ntt_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    const MODULUS: u64 = 998_244_353;
+
+    fn naive(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let mut product = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] = add_mod(product[i + j], mul_mod(x, y, modulus), modulus);
+            }
+        }
+        product
+    }
+
+    #[test]
+    fn inverts_transform() {
+        let mut rng = test_util::rng();
+        for &n in &[1, 2, 8, 1024] {
+            let original: Vec<u64> = (0..n).map(|_| rng.gen_range(0, MODULUS)).collect();
+            let mut a = original.clone();
+            ntt(&mut a, false, MODULUS, 3);
+            ntt(&mut a, true, MODULUS, 3);
+            assert_eq!(a, original);
+        }
+    }
+
+    #[test]
+    fn multiplies_known_polynomials() {
+        assert_eq!(
+            poly_mul_mod(&[1, 2, 3], &[4, 5], MODULUS),
+            vec![4, 13, 22, 15]
+        );
+        assert_eq!(poly_mul_mod(&[7], &[6], MODULUS), vec![42]);
+        assert_eq!(poly_mul_mod(&[], &[6], MODULUS), vec![]);
+        // Coefficients wrap around the modulus.
+        assert_eq!(
+            poly_mul_mod(&[MODULUS - 1, 1], &[MODULUS - 1, 1], MODULUS),
+            vec![1, MODULUS - 2, 1]
+        );
+    }
+
+    #[test]
+    fn agrees_with_naive_multiplication() {
+        let mut rng = test_util::rng();
+        // The last modulus is 2^64 - 2^32 + 1, where sums of two residues overflow u64.
+        for &modulus in &[MODULUS, 17, 7_340_033, 18_446_744_069_414_584_321] {
+            for _ in 0..50 {
+                let a: Vec<u64> = test_util::random_vec(&mut rng, 1..5, 0..modulus);
+                let b: Vec<u64> = test_util::random_vec(&mut rng, 1..5, 0..modulus);
+                assert_eq!(poly_mul_mod(&a, &b, modulus), naive(&a, &b, modulus));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "length should be a power of two")]
+    fn receives_invalid_length() {
+        ntt(&mut [1, 2, 3], false, MODULUS, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "length should divide modulus - 1")]
+    fn receives_unsupported_modulus() {
+        poly_mul_mod(&[1, 2, 3], &[4, 5], 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus should be a prime")]
+    fn receives_composite_modulus() {
+        // 17 · 257 - 1 is a multiple of 4, so only the primality check can catch it.
+        poly_mul_mod(&[1, 2, 3], &[4, 5], 17 * 257);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus should be larger than 1")]
+    fn receives_zero_modulus() {
+        ntt(&mut [1, 2], false, 0, 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "primitive_root should give a root of unity whose order is the length"
+    )]
+    fn receives_invalid_primitive_root() {
+        // 4 is a square, so it gives 1 instead of -1 as the root of unity of order 2.
+        ntt(&mut [1, 2], false, MODULUS, 4);
+    }
+
+    #[test]
+    fn tests_primality() {
+        let primes: Vec<u64> = (0..200).filter(|&n| is_prime(n)).collect();
+        let expected: Vec<u64> = (2..200u64)
+            .filter(|&n| (2..n).all(|d| !n.is_multiple_of(d)))
+            .collect();
+        assert_eq!(primes, expected);
+        assert!(is_prime(MODULUS));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // A strong pseudoprime to the bases 2, 3, 5, 7, 11, 13 and 17.
+        assert!(!is_prime(341_550_071_728_321));
+    }
+}
+
+/// Calculates the greatest common divisor with Euclid's algorithm.
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Calculates a · b mod modulus without overflowing.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Calculates a + b mod modulus without overflowing, for a and b smaller than modulus.
+fn add_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// Calculates a - b mod modulus without overflowing, for a and b smaller than modulus.
+fn sub_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (modulus - b)
+    }
+}
+
+/// Tests whether n is a prime with the
+/// [Miller–Rabin test](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test).
+/// Testing the first 12 primes as bases makes it exact for every u64.
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+    let (mut d, mut s) = (n - 1, 0);
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    BASES.iter().all(|&base| {
+        let mut x = pow_mod(base, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Calculates base<sup>exponent</sup> mod modulus by repeated squaring.
+fn pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let (mut base, mut exponent, mut power) = (base % modulus, exponent, 1 % modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            power = mul_mod(power, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    power
+}
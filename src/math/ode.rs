@@ -0,0 +1,198 @@
+//! # Ordinary Differential Equations
+//!
+//! Numerical methods to solve initial value problems.
+
+/// # [Runge–Kutta Method](https://en.wikipedia.org/wiki/Runge%E2%80%93Kutta_methods) (RK4)
+///
+/// Solves dy/dt = f(t, y) from y(t0) = y0 up to t_end with the classic 4th-order Runge–Kutta
+/// method, returning (t, y) at t0 and after every step of size h. The last step is shortened
+/// to end exactly at t_end. The global error is O(h⁴), so halving h divides it by about 16.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::ode;
+///
+/// // dy/dt = y, y(0) = 1, so y(1) = e.
+/// let steps = ode::rk4(|_, y| y, 1.0, 0.0, 1.0, 0.01);
+/// let (t, y) = *steps.last().unwrap();
+/// assert_eq!(t, 1.0);
+/// assert!((y - std::f64::consts::E).abs() < 1e-8);
+/// ```
+///
+/// # Panics
+///
+/// * h should be larger than 0
+/// * t_end should not be smaller than t0
+pub fn rk4<F>(f: F, y0: f64, t0: f64, t_end: f64, h: f64) -> Vec<(f64, f64)>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    if h <= 0.0 || h.is_nan() {
+        panic!("h should be larger than 0");
+    }
+    if t_end < t0 {
+        panic!("t_end should not be smaller than t0");
+    }
+    // Do not take an extra tiny step because of rounding in (t_end - t0) / h.
+    let steps = ((t_end - t0) / h * (1.0 - 1e-12)).ceil() as usize;
+
+    let mut solution = Vec::with_capacity(steps + 1);
+    let (mut t, mut y) = (t0, y0);
+    solution.push((t, y));
+    for step in 1..steps + 1 {
+        let t_next = if step == steps {
+            t_end
+        } else {
+            t0 + step as f64 * h
+        };
+        let h = t_next - t;
+        let k1 = f(t, y);
+        let k2 = f(t + h / 2.0, y + k1 * h / 2.0);
+        let k3 = f(t + h / 2.0, y + k2 * h / 2.0);
+        let k4 = f(t + h, y + k3 * h);
+        y += h / 6.0 * (k1 + 2.0 * k2 + 2.0 * k3 + k4);
+        t = t_next;
+        solution.push((t, y));
+    }
+    solution
+}
+
+/// Solves a system of ODEs dy/dt = f(t, y), where y is a vector, with the classic 4th-order
+/// Runge–Kutta method, like `rk4`. Higher-order equations can be solved as systems, such as
+/// x'' = -x as (x, v)' = (v, -x).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::ode;
+///
+/// // A harmonic oscillator is back where it started after a period of 2π.
+/// let period = 2.0 * std::f64::consts::PI;
+/// let steps = ode::rk4_system(|_, y| vec![y[1], -y[0]], &[1.0, 0.0], 0.0, period, 0.01);
+/// let (_, ref y) = steps[steps.len() - 1];
+/// assert!((y[0] - 1.0).abs() < 1e-8 && y[1].abs() < 1e-8);
+/// ```
+///
+/// # Panics
+///
+/// * h should be larger than 0
+/// * t_end should not be smaller than t0
+/// * f should return as many derivatives as there are values in y
+pub fn rk4_system<F>(f: F, y0: &[f64], t0: f64, t_end: f64, h: f64) -> Vec<(f64, Vec<f64>)>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    if h <= 0.0 || h.is_nan() {
+        panic!("h should be larger than 0");
+    }
+    if t_end < t0 {
+        panic!("t_end should not be smaller than t0");
+    }
+    // Do not take an extra tiny step because of rounding in (t_end - t0) / h.
+    let steps = ((t_end - t0) / h * (1.0 - 1e-12)).ceil() as usize;
+    let derivatives = |t: f64, y: &[f64]| {
+        let dy = f(t, y);
+        if dy.len() != y.len() {
+            panic!("f should return as many derivatives as there are values in y");
+        }
+        dy
+    };
+    // y + dy·h, the values after moving along the derivatives for a time h
+    let advance = |y: &[f64], dy: &[f64], h: f64| -> Vec<f64> {
+        y.iter().zip(dy).map(|(y, dy)| y + dy * h).collect()
+    };
+
+    let mut solution = Vec::with_capacity(steps + 1);
+    let (mut t, mut y) = (t0, y0.to_vec());
+    solution.push((t, y.clone()));
+    for step in 1..steps + 1 {
+        let t_next = if step == steps {
+            t_end
+        } else {
+            t0 + step as f64 * h
+        };
+        let h = t_next - t;
+        let k1 = derivatives(t, &y);
+        let k2 = derivatives(t + h / 2.0, &advance(&y, &k1, h / 2.0));
+        let k3 = derivatives(t + h / 2.0, &advance(&y, &k2, h / 2.0));
+        let k4 = derivatives(t + h, &advance(&y, &k3, h));
+        for (i, y) in y.iter_mut().enumerate() {
+            *y += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+        t = t_next;
+        solution.push((t, y.clone()));
+    }
+    solution
+}
+
+#[cfg(test)]
+mod rk4_tests {
+    use super::*;
+    use std::f64::consts::{E, PI};
+
+    fn error_at_one(h: f64) -> f64 {
+        let (_, y) = *rk4(|_, y| y, 1.0, 0.0, 1.0, h).last().unwrap();
+        (y - E).abs()
+    }
+
+    #[test]
+    fn solves_exponential_growth() {
+        let steps = rk4(|_, y| y, 1.0, 0.0, 1.0, 0.01);
+        assert_eq!(steps.len(), 101);
+        assert_eq!(steps[0], (0.0, 1.0));
+        assert_eq!(steps[100].0, 1.0);
+        assert!(error_at_one(0.01) < 1e-8);
+        for &(t, y) in steps.iter() {
+            assert!((y - t.exp()).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn solves_harmonic_oscillator() {
+        // x'' = -x with x(0) = 0 and x'(0) = 1 is solved by x = sin(t), with velocity cos(t).
+        let steps = rk4_system(|_, y| vec![y[1], -y[0]], &[0.0, 1.0], 0.0, 3.0 * PI, 0.001);
+        for (t, y) in steps {
+            assert!((y[0] - t.sin()).abs() < 1e-9);
+            assert!((y[1] - t.cos()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn converges_with_fourth_order() {
+        for &h in [0.2, 0.1, 0.05].iter() {
+            let ratio = error_at_one(h) / error_at_one(h / 2.0);
+            assert!(ratio > 14.0 && ratio < 18.0, "ratio {} for h={}", ratio, h);
+        }
+    }
+
+    #[test]
+    fn shortens_last_step() {
+        let steps = rk4(|t, _| 2.0 * t, 0.0, 0.0, 1.0, 0.3);
+        let times: Vec<f64> = steps.iter().map(|&(t, _)| t).collect();
+        assert_eq!(times.len(), 5);
+        assert_eq!(times[4], 1.0);
+        assert!((times[3] - 0.9).abs() < 1e-12);
+        // RK4 is exact for y = t².
+        assert!(steps.iter().all(|&(t, y)| (y - t * t).abs() < 1e-12));
+        assert_eq!(rk4(|_, y| y, 2.0, 5.0, 5.0, 0.1), vec![(5.0, 2.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "h should be larger than 0")]
+    fn receives_zero_step() {
+        rk4(|_, y| y, 1.0, 0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "t_end should not be smaller than t0")]
+    fn receives_reversed_interval() {
+        rk4(|_, y| y, 1.0, 1.0, 0.0, 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "f should return as many derivatives as there are values in y")]
+    fn receives_mismatched_derivatives() {
+        rk4_system(|_, y| vec![y[0]], &[1.0, 0.0], 0.0, 1.0, 0.1);
+    }
+}
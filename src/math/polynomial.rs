@@ -0,0 +1,204 @@
+//! # Polynomial
+//!
+//! Polynomials in one variable over a generic coefficient type.
+
+use std::ops::{Add, Mul, Neg};
+
+/// # [Polynomial](https://en.wikipedia.org/wiki/Polynomial)
+///
+/// A polynomial stored as its coefficients, where the coefficient at index i belongs to xⁱ. The
+/// zero polynomial has no coefficients. Trailing zero coefficients are kept as given, since the
+/// coefficients need not be comparable.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::math::polynomial::Polynomial;
+///
+/// // (1 + x)(1 - x) = 1 - x²
+/// let p = Polynomial::new(vec![1, 1]) * Polynomial::new(vec![1, -1]);
+/// assert_eq!(p.coefficients(), &[1, 0, -1]);
+/// assert_eq!(p.evaluate(3), -8);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial<T> {
+    coefficients: Vec<T>,
+}
+
+impl<T> Polynomial<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    /// Creates the polynomial with the coefficients from the constant term up.
+    pub fn new(coefficients: Vec<T>) -> Polynomial<T> {
+        Polynomial { coefficients }
+    }
+
+    /// Creates the zero polynomial.
+    pub fn zero() -> Polynomial<T> {
+        Polynomial::new(Vec::new())
+    }
+
+    /// Returns the coefficients from the constant term up.
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    /// Evaluates the polynomial at x with [Horner's method](https://en.wikipedia.org/wiki/Horner%27s_method),
+    /// using n multiplications and additions. The zero polynomial evaluates to `T::default()`.
+    pub fn evaluate(&self, x: T) -> T {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(T::default(), |acc, &coefficient| acc * x + coefficient)
+    }
+
+    /// Returns the derivative, where the coefficient of xⁱ⁻¹ is i times the coefficient of xⁱ.
+    /// Multiplying by i is done by adding the coefficient i times, since `T` need not be
+    /// convertible from an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::math::polynomial::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![4, 3, 2, 1]);
+    /// assert_eq!(p.derivative().coefficients(), &[3, 4, 3]);
+    /// ```
+    pub fn derivative(&self) -> Polynomial<T> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &coefficient)| (1..i).fold(coefficient, |acc, _| acc + coefficient))
+            .collect();
+        Polynomial::new(coefficients)
+    }
+}
+
+impl<T> Add for Polynomial<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Polynomial<T>) -> Polynomial<T> {
+        let (mut long, short) = if self.coefficients.len() < other.coefficients.len() {
+            (other.coefficients, self.coefficients)
+        } else {
+            (self.coefficients, other.coefficients)
+        };
+        for (a, b) in long.iter_mut().zip(short) {
+            *a = *a + b;
+        }
+        Polynomial::new(long)
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: Polynomial<T>) -> Polynomial<T> {
+        let (a, b) = (&self.coefficients, &other.coefficients);
+        if a.is_empty() || b.is_empty() {
+            return Polynomial::zero();
+        }
+        let mut product = vec![T::default(); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] = product[i + j] + x * y;
+            }
+        }
+        Polynomial::new(product)
+    }
+}
+
+impl<T> Neg for Polynomial<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Neg<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coefficients.into_iter().map(Neg::neg).collect())
+    }
+}
+
+#[cfg(test)]
+mod polynomial_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn adds_polynomials() {
+        let p = Polynomial::new(vec![1, 2, 3]) + Polynomial::new(vec![4, 5]);
+        assert_eq!(p.coefficients(), &[5, 7, 3]);
+        let p = Polynomial::new(vec![4]) + Polynomial::new(vec![1, 0, -2]);
+        assert_eq!(p.coefficients(), &[5, 0, -2]);
+    }
+
+    #[test]
+    fn multiplies_polynomials() {
+        // (1 + 2x + 3x²)(4 + 5x) = 4 + 13x + 22x² + 15x³
+        let p = Polynomial::new(vec![1, 2, 3]) * Polynomial::new(vec![4, 5]);
+        assert_eq!(p.coefficients(), &[4, 13, 22, 15]);
+        // (x - 1)(x + 1) = x² - 1
+        let p = Polynomial::new(vec![-1.0, 1.0]) * Polynomial::new(vec![1.0, 1.0]);
+        assert_eq!(p.coefficients(), &[-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn negates_polynomial() {
+        let p = -Polynomial::new(vec![1, -2, 3]);
+        assert_eq!(p.coefficients(), &[-1, 2, -3]);
+    }
+
+    #[test]
+    fn evaluates_like_naive_evaluation() {
+        test_util::agrees(
+            100,
+            |rng| {
+                let coefficients: Vec<i64> = test_util::random_vec(rng, 0..8, -10..10);
+                (coefficients, rng.gen_range(-5, 5))
+            },
+            |&(ref coefficients, x)| Polynomial::new(coefficients.clone()).evaluate(x),
+            |&(ref coefficients, x): &(Vec<i64>, i64)| {
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &c)| c * x.pow(i as u32))
+                    .sum::<i64>()
+            },
+        );
+    }
+
+    #[test]
+    fn differentiates_polynomial() {
+        // x³ + 2x² + 3x + 4 becomes 3x² + 4x + 3
+        let p = Polynomial::new(vec![4, 3, 2, 1]);
+        assert_eq!(p.derivative().coefficients(), &[3, 4, 3]);
+        assert_eq!(Polynomial::new(vec![7]).derivative(), Polynomial::zero());
+    }
+
+    #[test]
+    fn receives_zero_polynomial() {
+        let zero = Polynomial::<i32>::zero();
+        assert!(zero.coefficients().is_empty());
+        assert_eq!(zero.evaluate(10), 0);
+        assert_eq!(zero.derivative(), Polynomial::zero());
+        assert_eq!(
+            zero.clone() * Polynomial::new(vec![1, 2]),
+            Polynomial::zero()
+        );
+        assert_eq!(
+            zero.clone() + Polynomial::new(vec![1, 2]),
+            Polynomial::new(vec![1, 2])
+        );
+        assert_eq!(-zero, Polynomial::zero());
+    }
+}
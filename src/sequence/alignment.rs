@@ -0,0 +1,879 @@
+//! # Alignment
+//!
+//! A collection of functions to align two sequences.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// The scores of aligning two items that are equal, two items that differ, and an item with a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scoring {
+    pub match_: i64,
+    pub mismatch: i64,
+    pub gap: i64,
+}
+
+/// The scores of aligning each item of sequence a with each item of sequence b, such as a BLOSUM
+/// matrix for proteins or a table of confusion costs.
+pub trait SubstitutionMatrix<T> {
+    /// Returns the score of aligning the item `a` of sequence a with the item `b` of sequence b.
+    fn score(&self, a: &T, b: &T) -> i64;
+}
+
+impl<T: PartialEq> SubstitutionMatrix<T> for Scoring {
+    fn score(&self, a: &T, b: &T) -> i64 {
+        if a == b {
+            self.match_
+        } else {
+            self.mismatch
+        }
+    }
+}
+
+/// A substitution matrix that looks the ordered pairs of items up in a map, falling back to a
+/// default score for the pairs it does not contain.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{SubstitutionMatrix, SubstitutionTable};
+///
+/// let mut table = SubstitutionTable::new(-1);
+/// table.insert('A', 'A', 2);
+/// table.insert('A', 'G', 1);
+/// assert_eq!(table.score(&'A', &'G'), 1);
+/// assert_eq!(table.score(&'G', &'A'), -1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubstitutionTable<T: Eq + Hash> {
+    scores: HashMap<(T, T), i64>,
+    default: i64,
+}
+
+impl<T: Eq + Hash> SubstitutionTable<T> {
+    /// Creates an empty table, where every pair has the default score.
+    pub fn new(default: i64) -> SubstitutionTable<T> {
+        SubstitutionTable {
+            scores: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Sets the score of aligning `a` of sequence a with `b` of sequence b.
+    pub fn insert(&mut self, a: T, b: T, score: i64) {
+        self.scores.insert((a, b), score);
+    }
+}
+
+impl<T: Eq + Hash + Clone> SubstitutionMatrix<T> for SubstitutionTable<T> {
+    fn score(&self, a: &T, b: &T) -> i64 {
+        *self
+            .scores
+            .get(&(a.clone(), b.clone()))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// An alignment of two sequences, each padded with gaps (`None`) to the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment<T> {
+    pub score: i64,
+    pub a: Vec<Option<T>>,
+    pub b: Vec<Option<T>>,
+}
+
+impl<T> Alignment<T> {
+    /// Returns the aligned pairs of items, where `None` is a gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::sequence::alignment::{self, Scoring};
+    ///
+    /// let scores = Scoring { match_: 1, mismatch: -1, gap: -1 };
+    /// let alignment = alignment::global(b"AC", b"C", &scores);
+    /// assert_eq!(alignment.as_pairs(), vec![(Some(&b'A'), None), (Some(&b'C'), Some(&b'C'))]);
+    /// ```
+    pub fn as_pairs(&self) -> Vec<(Option<&T>, Option<&T>)> {
+        self.a
+            .iter()
+            .zip(&self.b)
+            .map(|(x, y)| (x.as_ref(), y.as_ref()))
+            .collect()
+    }
+
+    /// Returns a view that displays the alignment as three lines: the gapped sequence a, a
+    /// midline with `|` for matches, `.` for mismatches and a space for gaps, and the gapped
+    /// sequence b. Gaps are shown as `-`, and two items match if they are displayed the same.
+    /// The lines wrap at 60 characters unless another width is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::sequence::alignment::{self, Scoring};
+    ///
+    /// let scores = Scoring { match_: 1, mismatch: -1, gap: -1 };
+    /// let a: Vec<char> = "GATTACA".chars().collect();
+    /// let b: Vec<char> = "GCATGCU".chars().collect();
+    /// let alignment = alignment::global(&a, &b, &scores);
+    /// assert_eq!(
+    ///     alignment.render().width(4).to_string(),
+    ///     "G-AT\n| | \nGCA-\n\nTACA\n|.|.\nTGCU\n"
+    /// );
+    /// ```
+    pub fn render(&self) -> AlignmentDisplay<'_, T> {
+        AlignmentDisplay {
+            alignment: self,
+            width: 60,
+        }
+    }
+}
+
+/// A view of an alignment for display, created by `Alignment::render`.
+#[derive(Debug, Clone, Copy)]
+pub struct AlignmentDisplay<'a, T> {
+    alignment: &'a Alignment<T>,
+    width: usize,
+}
+
+impl<'a, T> AlignmentDisplay<'a, T> {
+    /// Sets the number of characters after which the lines wrap; a column is never split, so a
+    /// line holds at least one column.
+    pub fn width(mut self, width: usize) -> AlignmentDisplay<'a, T> {
+        self.width = width;
+        self
+    }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for AlignmentDisplay<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = [String::new(), String::new(), String::new()];
+        for (x, y) in self.alignment.as_pairs() {
+            let x = x.map_or_else(|| String::from("-"), |x| x.to_string());
+            let y = y.map_or_else(|| String::from("-"), |y| y.to_string());
+            let column_width = x.chars().count().max(y.chars().count());
+            if lines[0].chars().count() + column_width > self.width && !lines[0].is_empty() {
+                // The previous block is followed by a blank line.
+                for line in &mut lines {
+                    writeln!(f, "{}", line)?;
+                    line.clear();
+                }
+                writeln!(f)?;
+            }
+
+            let mid = match (x.as_str(), y.as_str()) {
+                ("-", _) | (_, "-") => ' ',
+                (x, y) if x == y => '|',
+                _ => '.',
+            };
+            lines[0].push_str(&format!("{:<1$}", x, column_width));
+            lines[1].extend((0..column_width).map(|_| mid));
+            lines[2].push_str(&format!("{:<1$}", y, column_width));
+        }
+        if !lines[0].is_empty() {
+            for line in &lines {
+                writeln!(f, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Alignment<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod alignment_tests {
+    use super::*;
+
+    const SCORES: Scoring = Scoring {
+        match_: 1,
+        mismatch: -1,
+        gap: -1,
+    };
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn renders_classic_example() {
+        let alignment = global(&chars("GATTACA"), &chars("GCATGCU"), &SCORES);
+        assert_eq!(alignment.to_string(), "G-ATTACA\n| | |.|.\nGCA-TGCU\n");
+        assert_eq!(
+            alignment.render().width(3).to_string(),
+            "G-A\n| |\nGCA\n\nTTA\n |.\n-TG\n\nCA\n|.\nCU\n"
+        );
+    }
+
+    #[test]
+    fn renders_wide_items() {
+        let alignment = global(&[1, 100, 7], &[1, 7], &SCORES);
+        assert_eq!(alignment.to_string(), "11007\n|   |\n1-  7\n");
+    }
+
+    #[test]
+    fn renders_empty_alignment() {
+        let alignment = global::<char>(&[], &[], &SCORES);
+        assert_eq!(alignment.to_string(), "");
+    }
+
+    #[test]
+    fn pairs_round_trip() {
+        let (a, b) = (chars("AGGCTATCACCTGAC"), chars("TAGCTATCACGACCG"));
+        let alignment = global(&a, &b, &SCORES);
+        let pairs = alignment.as_pairs();
+        assert_eq!(pairs.len(), alignment.a.len());
+        let a_: Vec<char> = pairs.iter().filter_map(|&(x, _)| x).cloned().collect();
+        let b_: Vec<char> = pairs.iter().filter_map(|&(_, y)| y).cloned().collect();
+        assert_eq!((a_, b_), (a, b));
+    }
+}
+
+/// # [Needleman–Wunsch Algorithm](https://en.wikipedia.org/wiki/Needleman%E2%80%93Wunsch_algorithm)
+///
+/// Finds an alignment of the whole of both sequences with the highest score, in O(mn) time and
+/// space.
+///
+/// When several alignments have the highest score, the backtracking prefers, from the end of the
+/// sequences, aligning the two items with each other, then aligning the item of `a` with a gap,
+/// then aligning the item of `b` with a gap.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, Scoring};
+///
+/// let scores = Scoring { match_: 1, mismatch: -1, gap: -1 };
+/// let alignment = alignment::global(b"ACGT", b"AGT", &scores);
+/// assert_eq!(alignment.score, 2);
+/// assert_eq!(alignment.a, vec![Some(b'A'), Some(b'C'), Some(b'G'), Some(b'T')]);
+/// assert_eq!(alignment.b, vec![Some(b'A'), None, Some(b'G'), Some(b'T')]);
+/// ```
+pub fn global<T: PartialEq + Clone>(a: &[T], b: &[T], scores: &Scoring) -> Alignment<T> {
+    global_with_matrix(a, b, scores, scores.gap)
+}
+
+/// # [Needleman–Wunsch Algorithm](https://en.wikipedia.org/wiki/Needleman%E2%80%93Wunsch_algorithm) with a Substitution Matrix
+///
+/// Finds an alignment of the whole of both sequences with the highest score like `global`, where
+/// aligning two items scores what the matrix looks up for them (the item of `a` first) and
+/// aligning an item with a gap scores `gap`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, SubstitutionTable};
+///
+/// // Transitions (A <-> G, C <-> T) are more likely than transversions.
+/// let mut matrix = SubstitutionTable::new(-2);
+/// for &(x, y) in [(b'A', b'G'), (b'G', b'A'), (b'C', b'T'), (b'T', b'C')].iter() {
+///     matrix.insert(x, y, 0);
+/// }
+/// for &x in b"ACGT" {
+///     matrix.insert(x, x, 2);
+/// }
+/// let alignment = alignment::global_with_matrix(b"GATC", b"AACT", &matrix, -3);
+/// assert_eq!(alignment.score, 2);
+/// assert_eq!(alignment.a, vec![Some(b'G'), Some(b'A'), Some(b'T'), Some(b'C')]);
+/// ```
+pub fn global_with_matrix<T, M>(a: &[T], b: &[T], matrix: &M, gap: i64) -> Alignment<T>
+where
+    T: Clone,
+    M: SubstitutionMatrix<T>,
+{
+    let (m, n) = (a.len(), b.len());
+    // score[i][j] is the best score of aligning a[..i] with b[..j].
+    let mut score = vec![vec![0; n + 1]; m + 1];
+    for i in 1..=m {
+        score[i][0] = score[i - 1][0] + gap;
+    }
+    for j in 1..=n {
+        score[0][j] = score[0][j - 1] + gap;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let diagonal = score[i - 1][j - 1] + matrix.score(&a[i - 1], &b[j - 1]);
+            let up = score[i - 1][j] + gap;
+            let left = score[i][j - 1] + gap;
+            score[i][j] = diagonal.max(up).max(left);
+        }
+    }
+
+    let (gapped_a, gapped_b) = backtrack(a, b, &score, matrix, gap, false, (m, n));
+    Alignment {
+        score: score[m][n],
+        a: gapped_a,
+        b: gapped_b,
+    }
+}
+
+/// Follows the moves of the table back from the end cell to the start of both sequences, or in a local
+/// alignment to the first cell whose score is 0, returning the gapped sequences.
+fn backtrack<T, M>(
+    a: &[T],
+    b: &[T],
+    score: &[Vec<i64>],
+    matrix: &M,
+    gap: i64,
+    local: bool,
+    end: (usize, usize),
+) -> (Vec<Option<T>>, Vec<Option<T>>)
+where
+    T: Clone,
+    M: SubstitutionMatrix<T>,
+{
+    let (mut i, mut j) = end;
+    let (mut gapped_a, mut gapped_b) = (Vec::new(), Vec::new());
+    while (i > 0 || j > 0) && !(local && score[i][j] == 0) {
+        if i > 0 && j > 0 && score[i][j] == score[i - 1][j - 1] + matrix.score(&a[i - 1], &b[j - 1])
+        {
+            i -= 1;
+            j -= 1;
+            gapped_a.push(Some(a[i].clone()));
+            gapped_b.push(Some(b[j].clone()));
+        } else if i > 0 && score[i][j] == score[i - 1][j] + gap {
+            i -= 1;
+            gapped_a.push(Some(a[i].clone()));
+            gapped_b.push(None);
+        } else {
+            j -= 1;
+            gapped_a.push(None);
+            gapped_b.push(Some(b[j].clone()));
+        }
+    }
+    gapped_a.reverse();
+    gapped_b.reverse();
+    (gapped_a, gapped_b)
+}
+
+#[cfg(test)]
+mod global_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    const SCORES: Scoring = Scoring {
+        match_: 1,
+        mismatch: -1,
+        gap: -1,
+    };
+
+    fn rescore<T: PartialEq>(alignment: &Alignment<T>, scores: &Scoring) -> i64 {
+        alignment
+            .a
+            .iter()
+            .zip(&alignment.b)
+            .map(|pair| match pair {
+                (Some(x), Some(y)) => scores.score(x, y),
+                (Some(_), None) | (None, Some(_)) => scores.gap,
+                (None, None) => panic!("two gaps should never be aligned"),
+            })
+            .sum()
+    }
+
+    fn ungapped<T: Clone>(gapped: &[Option<T>]) -> Vec<T> {
+        gapped.iter().flatten().cloned().collect()
+    }
+
+    #[test]
+    fn receives_classic_example() {
+        let alignment = global(b"GATTACA", b"GCATGCU", &SCORES);
+        assert_eq!(alignment.score, 0);
+        assert_eq!(rescore(&alignment, &SCORES), 0);
+        assert_eq!(ungapped(&alignment.a), b"GATTACA");
+        assert_eq!(ungapped(&alignment.b), b"GCATGCU");
+
+        let render = |gapped: &[Option<u8>]| -> String {
+            gapped.iter().map(|x| x.map_or('-', char::from)).collect()
+        };
+        assert_eq!(render(&alignment.a), "G-ATTACA");
+        assert_eq!(render(&alignment.b), "GCA-TGCU");
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let alignment = global::<u8>(b"", b"", &SCORES);
+        assert_eq!(alignment.score, 0);
+        assert!(alignment.a.is_empty() && alignment.b.is_empty());
+
+        let alignment = global(b"ABC", b"", &SCORES);
+        assert_eq!(alignment.score, -3);
+        assert_eq!(alignment.a, vec![Some(b'A'), Some(b'B'), Some(b'C')]);
+        assert_eq!(alignment.b, vec![None; 3]);
+
+        let alignment = global(b"", b"AB", &SCORES);
+        assert_eq!(alignment.score, -2);
+        assert_eq!(alignment.a, vec![None; 2]);
+        assert_eq!(alignment.b, vec![Some(b'A'), Some(b'B')]);
+    }
+
+    #[test]
+    fn breaks_ties_deterministically() {
+        // Both gaps on the last items and mismatching them score -2; the mismatch comes first.
+        let alignment = global(b"A", b"B", &SCORES);
+        assert_eq!(alignment.a, vec![Some(b'A')]);
+        assert_eq!(alignment.b, vec![Some(b'B')]);
+
+        // Backtracking from the end, a gap in b is preferred to a gap in a.
+        let scores = Scoring {
+            match_: 1,
+            mismatch: -3,
+            gap: -1,
+        };
+        let alignment = global(b"A", b"B", &scores);
+        assert_eq!(alignment.a, vec![None, Some(b'A')]);
+        assert_eq!(alignment.b, vec![Some(b'B'), None]);
+    }
+
+    #[test]
+    fn agrees_with_recomputed_score() {
+        let mut rng = test_util::rng();
+        let scorings = [
+            SCORES,
+            Scoring {
+                match_: 2,
+                mismatch: -1,
+                gap: -2,
+            },
+            Scoring {
+                match_: 5,
+                mismatch: -4,
+                gap: -1,
+            },
+        ];
+        for _ in 0..200 {
+            let a: Vec<u8> = test_util::random_vec(&mut rng, 0..15, b'a'..b'e');
+            let b: Vec<u8> = test_util::random_vec(&mut rng, 0..15, b'a'..b'e');
+            let scores = &scorings[rng.gen_range(0, scorings.len())];
+            let alignment = global(&a, &b, scores);
+            assert_eq!(alignment.a.len(), alignment.b.len());
+            assert_eq!(rescore(&alignment, scores), alignment.score);
+            assert_eq!(ungapped(&alignment.a), a);
+            assert_eq!(ungapped(&alignment.b), b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod global_with_matrix_tests {
+    use super::*;
+    use test_util;
+
+    #[test]
+    fn looks_up_pairs_in_order() {
+        let mut matrix = SubstitutionTable::new(-1);
+        matrix.insert('A', 'B', 5);
+        matrix.insert('B', 'A', -5);
+        let alignment = global_with_matrix(&['A'], &['B'], &matrix, -10);
+        assert_eq!(alignment.score, 5);
+        assert_eq!(alignment.a, vec![Some('A')]);
+        assert_eq!(alignment.b, vec![Some('B')]);
+
+        // Swapping the sequences looks up (B, A), which is worse than two gaps.
+        let alignment = global_with_matrix(&['B'], &['A'], &matrix, -2);
+        assert_eq!(alignment.score, -4);
+        assert_eq!(alignment.a, vec![None, Some('B')]);
+        assert_eq!(alignment.b, vec![Some('A'), None]);
+    }
+
+    #[test]
+    fn agrees_with_uniform_scores() {
+        let scores = Scoring {
+            match_: 3,
+            mismatch: -2,
+            gap: -4,
+        };
+        let mut matrix = SubstitutionTable::new(scores.mismatch);
+        for x in b'a'..b'e' {
+            matrix.insert(x, x, scores.match_);
+        }
+        test_util::agrees(
+            200,
+            |rng| {
+                let a: Vec<u8> = test_util::random_vec(rng, 0..15, b'a'..b'e');
+                (a, test_util::random_vec(rng, 0..15, b'a'..b'e'))
+            },
+            |(a, b)| {
+                (
+                    global_with_matrix(a, b, &matrix, scores.gap),
+                    local_with_matrix(a, b, &matrix, scores.gap),
+                )
+            },
+            |(a, b)| (global(a, b, &scores), local(a, b, &scores)),
+        );
+    }
+}
+
+/// # [Hirschberg's Algorithm](https://en.wikipedia.org/wiki/Hirschberg%27s_algorithm)
+///
+/// Finds an alignment of the whole of both sequences with the highest score, like `global`, in
+/// O(mn) time but only O(min(m, n)) extra space besides the alignment itself.
+///
+/// The longer sequence is halved, and the shorter one is split where the scores of aligning the
+/// first half forwards and the second half backwards add up to the highest score; both parts are
+/// then aligned recursively. The alignment equals the one from `global` when there is only one
+/// alignment with the highest score; otherwise it may be a different one with the same score.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, Scoring};
+///
+/// let scores = Scoring { match_: 1, mismatch: -1, gap: -1 };
+/// let alignment = alignment::global_linear_space(b"ACGT", b"AGT", &scores);
+/// assert_eq!(alignment, alignment::global(b"ACGT", b"AGT", &scores));
+/// ```
+pub fn global_linear_space<T: PartialEq + Clone>(
+    a: &[T],
+    b: &[T],
+    scores: &Scoring,
+) -> Alignment<T> {
+    let (mut gapped_a, mut gapped_b) = (Vec::new(), Vec::new());
+    if a.len() < b.len() {
+        hirschberg(b, a, scores, &mut gapped_b, &mut gapped_a);
+    } else {
+        hirschberg(a, b, scores, &mut gapped_a, &mut gapped_b);
+    }
+
+    let score = gapped_a
+        .iter()
+        .zip(&gapped_b)
+        .map(|pair| match pair {
+            (Some(x), Some(y)) => scores.score(x, y),
+            _ => scores.gap,
+        })
+        .sum();
+    Alignment {
+        score,
+        a: gapped_a,
+        b: gapped_b,
+    }
+}
+
+/// Appends the alignment of a and b, where b is the shorter sequence, to the gapped sequences.
+fn hirschberg<T: PartialEq + Clone>(
+    a: &[T],
+    b: &[T],
+    scores: &Scoring,
+    gapped_a: &mut Vec<Option<T>>,
+    gapped_b: &mut Vec<Option<T>>,
+) {
+    if a.len() <= 1 || b.len() <= 1 {
+        // The table of one of the sequences against one item or none is linear already.
+        let alignment = global(a, b, scores);
+        gapped_a.extend(alignment.a);
+        gapped_b.extend(alignment.b);
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let forward = last_row(&a[..mid], b, false, scores);
+    let backward = last_row(&a[mid..], b, true, scores);
+    // forward[k] + backward[n - k] is the best score of aligning a[..mid] with b[..k] and
+    // a[mid..] with b[k..].
+    let n = b.len();
+    let split = (0..=n)
+        .max_by_key(|&k| forward[k] + backward[n - k])
+        .unwrap_or(0);
+
+    hirschberg(&a[..mid], &b[..split], scores, gapped_a, gapped_b);
+    hirschberg(&a[mid..], &b[split..], scores, gapped_a, gapped_b);
+}
+
+/// Returns the best scores of aligning the whole of a with each prefix of b (or, in reverse,
+/// each suffix of b with the whole of a), keeping one row of the table.
+fn last_row<T: PartialEq>(a: &[T], b: &[T], reverse: bool, scores: &Scoring) -> Vec<i64> {
+    let index = |len: usize, i: usize| if reverse { len - 1 - i } else { i };
+    let mut row: Vec<i64> = (0..=b.len() as i64).map(|j| j * scores.gap).collect();
+    for i in 0..a.len() {
+        let x = &a[index(a.len(), i)];
+        let mut diagonal = row[0];
+        row[0] += scores.gap;
+        for j in 1..=b.len() {
+            let up = row[j];
+            row[j] = (diagonal + scores.score(x, &b[index(b.len(), j - 1)]))
+                .max(up + scores.gap)
+                .max(row[j - 1] + scores.gap);
+            diagonal = up;
+        }
+    }
+    row
+}
+
+/// Returns the score of the alignment found by `global`, keeping only one row of the table of
+/// the shorter sequence in O(min(m, n)) space.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, Scoring};
+///
+/// let scores = Scoring { match_: 1, mismatch: -1, gap: -1 };
+/// assert_eq!(alignment::global_score_only(b"GATTACA", b"GCATGCU", &scores), 0);
+/// ```
+pub fn global_score_only<T: PartialEq>(a: &[T], b: &[T], scores: &Scoring) -> i64 {
+    if a.len() < b.len() {
+        last_row(b, a, false, scores)[a.len()]
+    } else {
+        last_row(a, b, false, scores)[b.len()]
+    }
+}
+
+#[cfg(test)]
+mod global_linear_space_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    const SCORES: Scoring = Scoring {
+        match_: 1,
+        mismatch: -1,
+        gap: -1,
+    };
+
+    /// Counts the alignments with the highest score.
+    fn count_best_alignments(a: &[u8], b: &[u8], scores: &Scoring) -> u64 {
+        let (m, n) = (a.len(), b.len());
+        let mut score = vec![vec![0; n + 1]; m + 1];
+        let mut count = vec![vec![1u64; n + 1]; m + 1];
+        for i in 0..=m {
+            for j in 0..=n {
+                if i == 0 && j == 0 {
+                    continue;
+                }
+                let mut moves = Vec::new();
+                if i > 0 && j > 0 {
+                    let s = scores.score(&a[i - 1], &b[j - 1]);
+                    moves.push((score[i - 1][j - 1] + s, count[i - 1][j - 1]));
+                }
+                if i > 0 {
+                    moves.push((score[i - 1][j] + scores.gap, count[i - 1][j]));
+                }
+                if j > 0 {
+                    moves.push((score[i][j - 1] + scores.gap, count[i][j - 1]));
+                }
+                score[i][j] = moves.iter().map(|&(s, _)| s).max().unwrap();
+                count[i][j] = moves
+                    .iter()
+                    .filter(|&&(s, _)| s == score[i][j])
+                    .map(|&(_, c)| c)
+                    .sum();
+            }
+        }
+        count[m][n]
+    }
+
+    #[test]
+    fn receives_classic_example() {
+        let alignment = global_linear_space(b"GATTACA", b"GCATGCU", &SCORES);
+        assert_eq!(alignment.score, 0);
+        assert_eq!(global_score_only(b"GATTACA", b"GCATGCU", &SCORES), 0);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        assert_eq!(
+            global_linear_space::<u8>(b"", b"", &SCORES),
+            global(b"", b"", &SCORES)
+        );
+        assert_eq!(
+            global_linear_space(b"ABC", b"", &SCORES),
+            global(b"ABC", b"", &SCORES)
+        );
+        assert_eq!(
+            global_linear_space(b"", b"AB", &SCORES),
+            global(b"", b"AB", &SCORES)
+        );
+        assert_eq!(global_score_only(b"", b"AB", &SCORES), -2);
+    }
+
+    #[test]
+    fn agrees_with_quadratic_space() {
+        let mut rng = test_util::rng();
+        let scorings = [
+            SCORES,
+            Scoring {
+                match_: 3,
+                mismatch: -2,
+                gap: -5,
+            },
+            Scoring {
+                match_: 5,
+                mismatch: -4,
+                gap: -1,
+            },
+        ];
+        let mut unique = 0;
+        for _ in 0..500 {
+            let a: Vec<u8> = test_util::random_vec(&mut rng, 0..20, b'a'..b'e');
+            let b: Vec<u8> = test_util::random_vec(&mut rng, 0..20, b'a'..b'e');
+            let scores = &scorings[rng.gen_range(0, scorings.len())];
+            let expected = global(&a, &b, scores);
+            let alignment = global_linear_space(&a, &b, scores);
+            assert_eq!(alignment.score, expected.score);
+            assert_eq!(global_score_only(&a, &b, scores), expected.score);
+            assert_eq!(alignment.a.iter().flatten().cloned().collect::<Vec<_>>(), a);
+            assert_eq!(alignment.b.iter().flatten().cloned().collect::<Vec<_>>(), b);
+            if count_best_alignments(&a, &b, scores) == 1 {
+                assert_eq!(alignment, expected);
+                unique += 1;
+            }
+        }
+        assert!(unique > 0);
+    }
+
+    #[test]
+    fn receives_long_sequences() {
+        let mut rng = test_util::rng();
+        let a: Vec<u8> = (0..2000).map(|_| rng.gen_range(b'a', b'e')).collect();
+        let mut b = a.clone();
+        for _ in 0..100 {
+            let i = rng.gen_range(0, b.len());
+            b[i] = rng.gen_range(b'a', b'e');
+        }
+        let expected = global_score_only(&a, &b, &SCORES);
+        assert_eq!(global_linear_space(&a, &b, &SCORES).score, expected);
+        assert_eq!(global(&a, &b, &SCORES).score, expected);
+    }
+}
+
+/// # [Smith–Waterman Algorithm](https://en.wikipedia.org/wiki/Smith%E2%80%93Waterman_algorithm)
+///
+/// Finds the pair of substrings of the two sequences whose alignment has the highest score, in
+/// O(mn) time and space. Only the aligned substrings are returned; when no pair of items scores
+/// above 0, the alignment is empty with a score of 0.
+///
+/// When several alignments have the highest score, the one ending first in `a`, then first in
+/// `b`, is returned, and its backtracking breaks ties like `global`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, Scoring};
+///
+/// let scores = Scoring { match_: 3, mismatch: -3, gap: -2 };
+/// let alignment = alignment::local(b"TGTTACGG", b"GGTTGACTA", &scores);
+/// assert_eq!(alignment.score, 13);
+/// assert_eq!(alignment.a, vec![Some(b'G'), Some(b'T'), Some(b'T'), None, Some(b'A'), Some(b'C')]);
+/// assert_eq!(alignment.b, b"GTTGAC".iter().cloned().map(Some).collect::<Vec<_>>());
+/// ```
+pub fn local<T: PartialEq + Clone>(a: &[T], b: &[T], scores: &Scoring) -> Alignment<T> {
+    local_with_matrix(a, b, scores, scores.gap)
+}
+
+/// # [Smith–Waterman Algorithm](https://en.wikipedia.org/wiki/Smith%E2%80%93Waterman_algorithm) with a Substitution Matrix
+///
+/// Finds the pair of substrings with the highest scoring alignment like `local`, where aligning
+/// two items scores what the matrix looks up for them (the item of `a` first) and aligning an
+/// item with a gap scores `gap`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::alignment::{self, SubstitutionTable};
+///
+/// let mut matrix = SubstitutionTable::new(-4);
+/// matrix.insert('x', 'y', 2);
+/// let a: Vec<char> = "axxb".chars().collect();
+/// let b: Vec<char> = "cyyd".chars().collect();
+/// let alignment = alignment::local_with_matrix(&a, &b, &matrix, -4);
+/// assert_eq!(alignment.score, 4);
+/// assert_eq!(alignment.a, vec![Some('x'), Some('x')]);
+/// assert_eq!(alignment.b, vec![Some('y'), Some('y')]);
+/// ```
+pub fn local_with_matrix<T, M>(a: &[T], b: &[T], matrix: &M, gap: i64) -> Alignment<T>
+where
+    T: Clone,
+    M: SubstitutionMatrix<T>,
+{
+    let (m, n) = (a.len(), b.len());
+    // score[i][j] is the best score of aligning a suffix of a[..i] with a suffix of b[..j].
+    let mut score = vec![vec![0; n + 1]; m + 1];
+    let mut end = (0, 0);
+    for i in 1..=m {
+        for j in 1..=n {
+            let diagonal = score[i - 1][j - 1] + matrix.score(&a[i - 1], &b[j - 1]);
+            let up = score[i - 1][j] + gap;
+            let left = score[i][j - 1] + gap;
+            score[i][j] = diagonal.max(up).max(left).max(0);
+            if score[i][j] > score[end.0][end.1] {
+                end = (i, j);
+            }
+        }
+    }
+
+    let (gapped_a, gapped_b) = backtrack(a, b, &score, matrix, gap, true, end);
+    Alignment {
+        score: score[end.0][end.1],
+        a: gapped_a,
+        b: gapped_b,
+    }
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use test_util;
+
+    const SCORES: Scoring = Scoring {
+        match_: 2,
+        mismatch: -1,
+        gap: -2,
+    };
+
+    /// Finds the highest score of aligning any substrings by aligning them all globally.
+    fn best_substring_score(a: &[u8], b: &[u8], scores: &Scoring) -> i64 {
+        let mut best = 0;
+        for i in 0..a.len() {
+            for k in i + 1..=a.len() {
+                for j in 0..b.len() {
+                    for l in j + 1..=b.len() {
+                        best = best.max(global_score_only(&a[i..k], &b[j..l], scores));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn receives_unrelated_sequences() {
+        let alignment = local(b"AAA", b"BBB", &SCORES);
+        assert_eq!(alignment.score, 0);
+        assert!(alignment.a.is_empty() && alignment.b.is_empty());
+        assert_eq!(local::<u8>(b"", b"AB", &SCORES).score, 0);
+    }
+
+    #[test]
+    fn looks_up_pairs_in_order() {
+        let mut matrix = SubstitutionTable::new(-3);
+        matrix.insert('a', 'b', 4);
+        matrix.insert('b', 'a', 1);
+        let alignment = local_with_matrix(&['b', 'a'], &['b', 'a'], &matrix, -3);
+        // (b, a) scores 1 and (a, b) 4, so the best substrings are "a" and "b".
+        assert_eq!(alignment.score, 4);
+        assert_eq!(alignment.a, vec![Some('a')]);
+        assert_eq!(alignment.b, vec![Some('b')]);
+    }
+
+    #[test]
+    fn agrees_with_every_substring() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let a: Vec<u8> = test_util::random_vec(&mut rng, 0..9, b'a'..b'd');
+            let b: Vec<u8> = test_util::random_vec(&mut rng, 0..9, b'a'..b'd');
+            let alignment = local(&a, &b, &SCORES);
+            assert_eq!(alignment.score, best_substring_score(&a, &b, &SCORES));
+            let a_: Vec<u8> = alignment.a.iter().flatten().cloned().collect();
+            let b_: Vec<u8> = alignment.b.iter().flatten().cloned().collect();
+            assert!(a.windows(a_.len().max(1)).any(|w| w == &a_[..]) || a_.is_empty());
+            assert!(b.windows(b_.len().max(1)).any(|w| w == &b_[..]) || b_.is_empty());
+            assert_eq!(global(&a_, &b_, &SCORES).score, alignment.score);
+        }
+    }
+}
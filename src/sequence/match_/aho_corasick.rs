@@ -0,0 +1,512 @@
+//! # Aho–Corasick
+//!
+//! Matching many patterns at once with an automaton built from their trie.
+
+use sequence::match_::Alphabet;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::io::{self, Read};
+
+/// An occurrence of a pattern, which covers `start..end` of the sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// The index of the pattern
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// # [Aho–Corasick Algorithm](https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm)
+///
+/// An automaton that finds every occurrence of many patterns in one pass over a sequence, in
+/// O(n + m + z) time for a sequence of length n, patterns of total length m and z occurrences.
+///
+/// The states are the prefixes of the patterns, and each state links to the state of its
+/// longest proper suffix that is also a prefix, which is followed on a mismatch. Built with
+/// `new`, the transitions are hash maps that may follow several suffix links per item; built
+/// with `with_alphabet`, they are resolved up front into a table indexed by the alphabet, so
+/// that every item takes one lookup.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::aho_corasick::{AhoCorasick, Match};
+///
+/// let patterns = vec![b"he".to_vec(), b"she".to_vec(), b"hers".to_vec()];
+/// let automaton = AhoCorasick::new(&patterns);
+/// assert_eq!(
+///     automaton.find_all(b"ushers"),
+///     vec![
+///         Match { pattern: 1, start: 1, end: 4 },
+///         Match { pattern: 0, start: 2, end: 4 },
+///         Match { pattern: 2, start: 2, end: 6 },
+///     ]
+/// );
+/// ```
+pub struct AhoCorasick<T> {
+    pattern_lens: Vec<usize>,
+    children: Vec<HashMap<T, usize>>,
+    fail: Vec<usize>,
+    /// The patterns that end exactly at each state
+    outputs: Vec<Vec<usize>>,
+    /// The nearest state along the suffix links with an output
+    output_link: Vec<Option<usize>>,
+    dense: Option<Dense<T>>,
+}
+
+struct Dense<T> {
+    alphabet: Box<dyn Alphabet<T>>,
+    /// The transition of state s over the symbol of index c is at `s * size + c`.
+    table: Vec<usize>,
+}
+
+const ROOT: usize = 0;
+
+impl<T: Eq + Hash + Clone> AhoCorasick<T> {
+    /// Builds the automaton of the patterns with hash map transitions.
+    pub fn new(patterns: &[Vec<T>]) -> AhoCorasick<T> {
+        let mut automaton = AhoCorasick {
+            pattern_lens: patterns.iter().map(Vec::len).collect(),
+            children: vec![HashMap::new()],
+            fail: vec![ROOT],
+            outputs: vec![Vec::new()],
+            output_link: vec![None],
+            dense: None,
+        };
+
+        for (i, pattern) in patterns.iter().enumerate() {
+            let mut state = ROOT;
+            for symbol in pattern {
+                state = match automaton.children[state].get(symbol) {
+                    Some(&child) => child,
+                    None => {
+                        let child = automaton.children.len();
+                        automaton.children.push(HashMap::new());
+                        automaton.fail.push(ROOT);
+                        automaton.outputs.push(Vec::new());
+                        automaton.output_link.push(None);
+                        automaton.children[state].insert(symbol.clone(), child);
+                        child
+                    }
+                };
+            }
+            automaton.outputs[state].push(i);
+        }
+
+        // Link the states breadth first, so that every shorter suffix is linked already.
+        for state in automaton.breadth_first() {
+            for (symbol, &child) in &automaton.children[state] {
+                if state != ROOT {
+                    let mut suffix = automaton.fail[state];
+                    automaton.fail[child] = loop {
+                        if let Some(&next) = automaton.children[suffix].get(symbol) {
+                            break next;
+                        }
+                        if suffix == ROOT {
+                            break ROOT;
+                        }
+                        suffix = automaton.fail[suffix];
+                    };
+                }
+                let suffix = automaton.fail[child];
+                automaton.output_link[child] = if automaton.outputs[suffix].is_empty() {
+                    automaton.output_link[suffix]
+                } else {
+                    Some(suffix)
+                };
+            }
+        }
+        automaton
+    }
+
+    /// Builds the automaton of the patterns with a transition table indexed by the alphabet.
+    /// Items of the sequence outside the alphabet never match, and lead back to the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::sequence::match_::aho_corasick::AhoCorasick;
+    /// use ult_algo::sequence::match_::ByteAlphabet;
+    ///
+    /// let patterns = vec![b"abc".to_vec(), b"bc".to_vec()];
+    /// let automaton = AhoCorasick::with_alphabet(&patterns, ByteAlphabet);
+    /// assert_eq!(automaton.find_all(b"xabcx").len(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * patterns should only contain symbols of the alphabet
+    pub fn with_alphabet<A>(patterns: &[Vec<T>], alphabet: A) -> AhoCorasick<T>
+    where
+        A: Alphabet<T> + 'static,
+    {
+        let mut automaton = AhoCorasick::new(patterns);
+        let size = alphabet.size();
+        let mut table = vec![ROOT; automaton.children.len() * size];
+        for state in automaton.breadth_first() {
+            // Inherit the transitions of the suffix, which is resolved already, then override
+            // them with the children.
+            if state != ROOT {
+                let suffix = automaton.fail[state];
+                for c in 0..size {
+                    table[state * size + c] = table[suffix * size + c];
+                }
+            }
+            for (symbol, &child) in &automaton.children[state] {
+                let c = alphabet
+                    .index(symbol)
+                    .expect("patterns should only contain symbols of the alphabet");
+                table[state * size + c] = child;
+            }
+        }
+        automaton.dense = Some(Dense {
+            alphabet: Box::new(alphabet),
+            table,
+        });
+        automaton
+    }
+
+    /// Returns the states in breadth first order from the root.
+    fn breadth_first(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.children.len());
+        let mut queue = VecDeque::new();
+        queue.push_back(ROOT);
+        while let Some(state) = queue.pop_front() {
+            order.push(state);
+            queue.extend(self.children[state].values().cloned());
+        }
+        order
+    }
+
+    /// Returns the state after reading the symbol in the state.
+    fn next_state(&self, mut state: usize, symbol: &T) -> usize {
+        if let Some(ref dense) = self.dense {
+            return match dense.alphabet.index(symbol) {
+                Some(c) => dense.table[state * dense.alphabet.size() + c],
+                None => ROOT,
+            };
+        }
+        loop {
+            if let Some(&next) = self.children[state].get(symbol) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    /// Appends the occurrences of the patterns ending at the given end in the state, longest
+    /// first.
+    fn push_matches(&self, state: usize, end: usize, matches: &mut Vec<Match>) {
+        let mut current = Some(state);
+        while let Some(state) = current {
+            for &pattern in &self.outputs[state] {
+                matches.push(Match {
+                    pattern,
+                    start: end - self.pattern_lens[pattern],
+                    end,
+                });
+            }
+            current = self.output_link[state];
+        }
+    }
+
+    /// Finds every occurrence of the patterns, including overlapping ones, ordered by end and
+    /// then from the longest to the shortest. An empty pattern occurs at every position.
+    pub fn find_all(&self, sequence: &[T]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut state = ROOT;
+        self.push_matches(state, 0, &mut matches);
+        for (i, symbol) in sequence.iter().enumerate() {
+            state = self.next_state(state, symbol);
+            self.push_matches(state, i + 1, &mut matches);
+        }
+        matches
+    }
+}
+
+/// The size of the buffer that `find_in_reader` reads into.
+const READER_BUFFER_SIZE: usize = 8 * 1024;
+
+impl AhoCorasick<u8> {
+    /// Finds every occurrence of the patterns in the bytes of a reader, in the same order as
+    /// `find_all`, without holding more of them than a fixed-size buffer. The automaton state is
+    /// carried across reads, so occurrences spanning two reads are found, and the offsets count
+    /// from the first byte read.
+    ///
+    /// An error from the reader (other than `ErrorKind::Interrupted`, which is retried) is
+    /// yielded once, after which the iterator ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::sequence::match_::aho_corasick::{AhoCorasick, Match};
+    ///
+    /// let automaton = AhoCorasick::new(&[b"needle".to_vec()]);
+    /// let reader: &[u8] = b"haystack with a needle";
+    /// let matches: Vec<Match> = automaton
+    ///     .find_in_reader(reader)
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(matches, vec![Match { pattern: 0, start: 16, end: 22 }]);
+    /// ```
+    pub fn find_in_reader<R: Read>(&self, reader: R) -> ReaderMatches<'_, R> {
+        self.find_in_reader_with_buffer(reader, READER_BUFFER_SIZE)
+    }
+
+    fn find_in_reader_with_buffer<R: Read>(
+        &self,
+        reader: R,
+        buffer_size: usize,
+    ) -> ReaderMatches<'_, R> {
+        let mut pending = Vec::new();
+        self.push_matches(ROOT, 0, &mut pending);
+        pending.reverse();
+        ReaderMatches {
+            automaton: self,
+            reader,
+            buffer: vec![0; buffer_size],
+            filled: 0,
+            position: 0,
+            offset: 0,
+            state: ROOT,
+            pending,
+            is_done: false,
+        }
+    }
+}
+
+/// An iterator over the occurrences of patterns in a reader, created by
+/// `AhoCorasick::find_in_reader`.
+pub struct ReaderMatches<'a, R> {
+    automaton: &'a AhoCorasick<u8>,
+    reader: R,
+    buffer: Vec<u8>,
+    /// The number of bytes of the buffer from the last read
+    filled: usize,
+    /// The index of the next byte of the buffer to feed
+    position: usize,
+    /// The number of bytes fed so far
+    offset: usize,
+    state: usize,
+    /// The occurrences not yielded yet, in reverse
+    pending: Vec<Match>,
+    is_done: bool,
+}
+
+impl<'a, R: Read> Iterator for ReaderMatches<'a, R> {
+    type Item = io::Result<Match>;
+
+    fn next(&mut self) -> Option<io::Result<Match>> {
+        loop {
+            if let Some(found) = self.pending.pop() {
+                return Some(Ok(found));
+            }
+            if self.is_done {
+                return None;
+            }
+            if self.position == self.filled {
+                match self.reader.read(&mut self.buffer) {
+                    Ok(0) => self.is_done = true,
+                    Ok(n) => {
+                        self.filled = n;
+                        self.position = 0;
+                    }
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        self.is_done = true;
+                        return Some(Err(e));
+                    }
+                }
+                continue;
+            }
+
+            let byte = self.buffer[self.position];
+            self.position += 1;
+            self.offset += 1;
+            self.state = self.automaton.next_state(self.state, &byte);
+            self.automaton
+                .push_matches(self.state, self.offset, &mut self.pending);
+            self.pending.reverse();
+        }
+    }
+}
+
+#[cfg(test)]
+mod aho_corasick_tests {
+    use super::*;
+    use rand::Rng;
+    use sequence::match_::{ByteAlphabet, MappedAlphabet};
+    use test_util;
+
+    fn brute_force(patterns: &[Vec<u8>], sequence: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for end in 0..=sequence.len() {
+            let mut ending: Vec<Match> = patterns
+                .iter()
+                .enumerate()
+                .filter(|&(_, pattern)| sequence[..end].ends_with(pattern))
+                .map(|(pattern, p)| Match {
+                    pattern,
+                    start: end - p.len(),
+                    end,
+                })
+                .collect();
+            ending.sort_by_key(|m| (m.start, m.pattern));
+            matches.extend(ending);
+        }
+        matches
+    }
+
+    fn sorted(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|m| (m.end, m.start, m.pattern));
+        matches
+    }
+
+    #[test]
+    fn receives_classic_example() {
+        let patterns: Vec<Vec<u8>> = ["he", "she", "his", "hers"]
+            .iter()
+            .map(|p| p.as_bytes().to_vec())
+            .collect();
+        let expected = brute_force(&patterns, b"ahishers");
+        assert_eq!(expected.len(), 4);
+        assert_eq!(AhoCorasick::new(&patterns).find_all(b"ahishers"), expected);
+        assert_eq!(
+            AhoCorasick::with_alphabet(&patterns, ByteAlphabet).find_all(b"ahishers"),
+            expected
+        );
+    }
+
+    #[test]
+    fn receives_empty_and_duplicate_patterns() {
+        let patterns = vec![vec![], b"a".to_vec(), b"a".to_vec()];
+        let matches = AhoCorasick::new(&patterns).find_all(b"aa");
+        assert_eq!(sorted(matches), sorted(brute_force(&patterns, b"aa")));
+        assert_eq!(AhoCorasick::new(&[]).find_all(b"abc"), vec![]);
+    }
+
+    #[test]
+    fn ignores_symbols_outside_alphabet() {
+        let patterns: Vec<Vec<char>> = vec!["ab".chars().collect(), "b".chars().collect()];
+        let alphabet = MappedAlphabet::new(patterns.iter().flat_map(|p| p.clone()));
+        let automaton = AhoCorasick::with_alphabet(&patterns, alphabet);
+        let sequence: Vec<char> = "azb!ab".chars().collect();
+        assert_eq!(
+            automaton.find_all(&sequence),
+            vec![
+                Match {
+                    pattern: 1,
+                    start: 2,
+                    end: 3
+                },
+                Match {
+                    pattern: 0,
+                    start: 4,
+                    end: 6
+                },
+                Match {
+                    pattern: 1,
+                    start: 5,
+                    end: 6
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "patterns should only contain symbols of the alphabet")]
+    fn receives_pattern_outside_alphabet() {
+        let alphabet = MappedAlphabet::new(vec!['a']);
+        AhoCorasick::with_alphabet(&[vec!['a', 'b']], alphabet);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        test_util::agrees(
+            200,
+            |rng| {
+                let patterns: Vec<Vec<u8>> = (0..rng.gen_range(1, 6))
+                    .map(|_| test_util::random_vec(rng, 0..5, b'a'..b'd'))
+                    .collect();
+                (patterns, test_util::random_vec(rng, 0..40, b'a'..b'e'))
+            },
+            |(patterns, sequence)| {
+                let sparse = AhoCorasick::new(patterns).find_all(sequence);
+                let dense = AhoCorasick::with_alphabet(patterns, ByteAlphabet).find_all(sequence);
+                assert_eq!(sparse, dense);
+                sorted(sparse)
+            },
+            |(patterns, sequence)| sorted(brute_force(patterns, sequence)),
+        );
+    }
+
+    /// Reads from the bytes, then fails.
+    struct FailingReader<'a> {
+        bytes: &'a [u8],
+    }
+
+    impl<'a> Read for FailingReader<'a> {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.bytes.is_empty() {
+                return Err(io::Error::other("disconnected"));
+            }
+            self.bytes.read(buffer)
+        }
+    }
+
+    #[test]
+    fn finds_matches_across_buffers() {
+        let patterns = vec![b"abcdefgh".to_vec(), b"defg".to_vec(), b"ha".to_vec()];
+        let automaton = AhoCorasick::new(&patterns);
+        let sequence = b"xxabcdefghabcdefgh";
+        let matches: Vec<Match> = automaton
+            .find_in_reader_with_buffer(&sequence[..], 3)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(matches, automaton.find_all(sequence));
+        assert_eq!(matches.len(), 5);
+        assert_eq!(
+            matches.last(),
+            Some(&Match {
+                pattern: 0,
+                start: 10,
+                end: 18
+            })
+        );
+    }
+
+    #[test]
+    fn agrees_with_find_all_on_any_buffer_size() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let patterns: Vec<Vec<u8>> = (0..rng.gen_range(1, 4))
+                .map(|_| test_util::random_vec(&mut rng, 0..7, b'a'..b'c'))
+                .collect();
+            let sequence: Vec<u8> = test_util::random_vec(&mut rng, 0..50, b'a'..b'c');
+            let automaton = AhoCorasick::with_alphabet(&patterns, ByteAlphabet);
+            let expected = automaton.find_all(&sequence);
+            for buffer_size in 1..5 {
+                let matches: Vec<Match> = automaton
+                    .find_in_reader_with_buffer(&sequence[..], buffer_size)
+                    .collect::<io::Result<_>>()
+                    .unwrap();
+                assert_eq!(matches, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn yields_reader_errors() {
+        let automaton = AhoCorasick::new(&[b"ab".to_vec()]);
+        let reader = FailingReader { bytes: b"xabab" };
+        let results: Vec<io::Result<Match>> =
+            automaton.find_in_reader_with_buffer(reader, 3).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().start, 1);
+        assert_eq!(results[1].as_ref().unwrap().start, 3);
+        assert_eq!(results[2].as_ref().unwrap_err().to_string(), "disconnected");
+    }
+}
@@ -0,0 +1,4573 @@
+//! # Match
+//!
+//! A collection of functions to approximately or exactly match a pattern over a sequence.
+
+use bit_vec::BitVec;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::ops::Range;
+
+pub mod aho_corasick;
+pub mod regex;
+pub mod str;
+
+/// # [Bitap Algorithm](https://en.wikipedia.org/wiki/Bitap_algorithm)
+///
+/// Exactly matches a pattern over the given sequence using bitwise operations.
+///
+/// # Examples
+///
+/// It returns the index of the matching pattern in the sequence.
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "hello, world".chars().collect();
+/// let pattern: Vec<char> = "wor".chars().collect();
+/// assert_eq!(match_::bitap(&sequence, &pattern).unwrap(), 7);
+/// ```
+///
+/// If the pattern does not match, it returns -1.
+pub fn bitap<T: PartialEq>(sequence: &[T], pattern: &[T]) -> Option<usize> {
+    bitap_by(sequence, pattern, PartialEq::eq)
+}
+
+#[cfg(test)]
+mod bitap_tests {
+    use super::bitap;
+
+    #[test]
+    fn receives_char_vectors() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        let pattern: Vec<char> = "wor".chars().collect();
+        assert_eq!(bitap(&sequence, &pattern), Some(7));
+    }
+
+    #[test]
+    fn receives_integer_vectors() {
+        let sequence = vec![3, 4, 5, 7, 3, 2, 1];
+        let pattern = vec![4, 5, 7, 3];
+        assert_eq!(bitap(&sequence, &pattern), Some(1));
+    }
+
+    #[test]
+    fn receives_different_types_of_sequences() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        let pattern = ['w', 'o', 'r'];
+        assert_eq!(bitap(&sequence, &pattern), Some(7));
+    }
+
+    #[test]
+    fn receives_empty_pattern_returns_index_0() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        let pattern = vec![];
+        assert_eq!(bitap(&sequence, &pattern), Some(0));
+    }
+
+    #[test]
+    fn receives_pattern_equal_to_sequence() {
+        let sequence = [3, 4, 5];
+        assert_eq!(bitap(&sequence, &sequence), Some(0));
+    }
+
+    #[test]
+    fn receives_longer_pattern_returns_invalid_index() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        let pattern: Vec<char> = "hello, world! Here I am looking at nothing"
+            .chars()
+            .collect();
+        assert_eq!(bitap(&sequence, &pattern), None);
+    }
+
+    #[test]
+    fn receives_non_matching_pattern_returns_invalid_index() {
+        let sequence = vec![3, 4, 5, 7, 3, 2, 1];
+        let pattern = vec![4, 5, 7, 5];
+        assert_eq!(bitap(&sequence, &pattern), None);
+    }
+}
+
+/// # [Bitap Algorithm](https://en.wikipedia.org/wiki/Bitap_algorithm)
+///
+/// Exactly matches a pattern over the given sequence, deciding whether two items are equal with
+/// a custom predicate. The predicate always receives (sequence item, pattern item) in that order,
+/// so it does not need to be symmetric.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "Hello, World".chars().collect();
+/// let pattern: Vec<char> = "wor".chars().collect();
+/// let found = match_::bitap_by(&sequence, &pattern, |x, y| x.eq_ignore_ascii_case(y));
+/// assert_eq!(found, Some(7));
+/// ```
+pub fn bitap_by<T, F>(sequence: &[T], pattern: &[T], eq: F) -> Option<usize>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let (pat_len, seq_len) = (pattern.len(), sequence.len());
+    if pat_len == 0 {
+        return Some(0); // empty pattern matches everything
+    } else if pat_len > seq_len {
+        return None; // longer pattern matches nothing
+    }
+
+    let mut bit_arr = BitVec::from_elem(pat_len + 1, false); // init bit array
+    bit_arr.set(0, true);
+    for (i, item) in sequence.iter().enumerate() {
+        // Update the bit array.
+        for k in (1..pat_len + 1).rev() {
+            let prev_bit = bit_arr[k - 1];
+            bit_arr.set(k, prev_bit & eq(item, &pattern[k - 1]));
+        }
+
+        if bit_arr[pat_len] {
+            return Some(i + 1 - pat_len); // found a match
+        }
+    }
+    None // no match found
+}
+
+#[cfg(test)]
+mod bitap_by_tests {
+    use super::bitap_by;
+
+    #[derive(Debug)]
+    struct Token {
+        id: u32,
+        text: &'static str,
+    }
+
+    #[test]
+    fn matches_case_insensitively() {
+        let sequence: Vec<char> = "HeLLo, WoRLD".chars().collect();
+        let pattern: Vec<char> = "world".chars().collect();
+        let found = bitap_by(&sequence, &pattern, |x, y| x.eq_ignore_ascii_case(y));
+        assert_eq!(found, Some(7));
+    }
+
+    #[test]
+    fn matches_structs_by_field() {
+        let sequence = [
+            Token { id: 1, text: "a" },
+            Token { id: 2, text: "b" },
+            Token { id: 3, text: "c" },
+        ];
+        let pattern = [Token { id: 2, text: "x" }, Token { id: 3, text: "y" }];
+        assert_eq!(bitap_by(&sequence, &pattern, |x, y| x.id == y.id), Some(1));
+        assert_eq!(bitap_by(&sequence, &pattern, |x, y| x.text == y.text), None);
+    }
+
+    #[test]
+    fn matches_wildcard_pattern_items() {
+        let sequence: Vec<char> = "a cat and a cot".chars().collect();
+        let pattern: Vec<char> = "c?t".chars().collect();
+        // Only pattern items may be wildcards, so the predicate is asymmetric.
+        let eq = |x: &char, y: &char| *y == '?' || x == y;
+        assert_eq!(bitap_by(&sequence, &pattern, eq), Some(2));
+        let sequence: Vec<char> = "a c?t".chars().collect();
+        let pattern: Vec<char> = "cat".chars().collect();
+        assert_eq!(bitap_by(&sequence, &pattern, eq), None);
+    }
+}
+
+/// # [Bitap Algorithm](https://en.wikipedia.org/wiki/Bitap_algorithm) with Character Classes
+///
+/// Matches a pattern over the given sequence where every pattern position is a class of
+/// acceptable items, like the degenerate symbols of the IUPAC nucleotide codes.
+///
+/// Bit i of the mask of an item is set if the class at position i contains the item, so a class
+/// costs nothing more than a single item; the state of every pattern prefix is shifted and
+/// masked a machine word at a time.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "GATTACA".chars().collect();
+/// // R is A or G, Y is C or T.
+/// let pattern = vec![vec!['A', 'G'], vec!['C', 'T'], vec!['A']];
+/// assert_eq!(match_::bitap_classes(&sequence, &pattern), Ok(Some(4)));
+/// ```
+///
+/// # Errors
+///
+/// * `PatternError::EmptyClass` if a class is empty, since it can never match
+pub fn bitap_classes<T: Eq + Hash>(
+    sequence: &[T],
+    pattern: &[Vec<T>],
+) -> Result<Option<usize>, PatternError> {
+    if let Some(position) = pattern.iter().position(|class| class.is_empty()) {
+        return Err(PatternError::EmptyClass(position));
+    }
+    let m = pattern.len();
+    if m == 0 {
+        return Ok(Some(0)); // empty pattern matches everything
+    }
+
+    let words = m.div_ceil(64);
+    let mut masks: HashMap<&T, Vec<u64>> = HashMap::new();
+    for (i, class) in pattern.iter().enumerate() {
+        for x in class {
+            masks.entry(x).or_insert_with(|| vec![0; words])[i / 64] |= 1 << (i % 64);
+        }
+    }
+
+    // Bit i of the state is set if pattern[..=i] matches the items ending at the current one.
+    let mut state = vec![0u64; words];
+    let last_bit = 1u64 << ((m - 1) % 64);
+    for (j, x) in sequence.iter().enumerate() {
+        let mask = match masks.get(x) {
+            Some(mask) => mask,
+            None => {
+                state.iter_mut().for_each(|word| *word = 0);
+                continue;
+            }
+        };
+        // Shift in a set bit, since the empty prefix always matches.
+        let mut carry = 1;
+        for (word, &mask_word) in state.iter_mut().zip(mask.iter()) {
+            let shifted = (*word << 1) | carry;
+            carry = *word >> 63;
+            *word = shifted & mask_word;
+        }
+        if state[words - 1] & last_bit != 0 {
+            return Ok(Some(j + 1 - m));
+        }
+    }
+    Ok(None)
+}
+
+/// Errors from invalid patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternError {
+    /// The class at the given position contains no items
+    EmptyClass(usize),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PatternError::EmptyClass(position) => {
+                write!(f, "class at position {} is empty", position)
+            }
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+#[cfg(test)]
+mod bitap_classes_tests {
+    use super::*;
+    use test_util;
+
+    #[test]
+    fn agrees_with_bitap_on_single_item_classes() {
+        test_util::agrees(
+            200,
+            |rng| {
+                let sequence: Vec<u8> = test_util::random_vec(rng, 0..300, 0..3);
+                (sequence, test_util::random_vec(rng, 1..150, 0..3))
+            },
+            |(sequence, pattern)| {
+                let classes: Vec<Vec<u8>> = pattern.iter().map(|&x| vec![x]).collect();
+                bitap_classes(sequence, &classes)
+            },
+            |(sequence, pattern)| Ok(bitap(sequence, pattern)),
+        );
+    }
+
+    #[test]
+    fn treats_full_class_as_wildcard() {
+        let sequence: Vec<char> = "ACGTTGCA".chars().collect();
+        let any = vec!['A', 'C', 'G', 'T'];
+        let pattern = vec![vec!['T'], any.clone(), any, vec!['C']];
+        assert_eq!(bitap_classes(&sequence, &pattern), Ok(Some(3)));
+    }
+
+    #[test]
+    fn receives_integer_classes() {
+        let sequence = [10, 20, 30, 11, 21, 31, 12];
+        let pattern = vec![vec![11, 12], vec![20, 21, 22], vec![31]];
+        assert_eq!(bitap_classes(&sequence, &pattern), Ok(Some(3)));
+        let pattern = vec![vec![10, 11], vec![21], vec![30]];
+        assert_eq!(bitap_classes(&sequence, &pattern), Ok(None));
+    }
+
+    #[test]
+    fn receives_patterns_longer_than_a_word() {
+        let sequence: Vec<u32> = (0..500).collect();
+        let pattern: Vec<Vec<u32>> = (200..330).map(|x| vec![x, x + 1000]).collect();
+        assert_eq!(bitap_classes(&sequence, &pattern), Ok(Some(200)));
+    }
+
+    #[test]
+    fn receives_empty_pattern_or_class() {
+        assert_eq!(bitap_classes(&[1, 2], &[]), Ok(Some(0)));
+        let pattern = vec![vec![1], vec![]];
+        assert_eq!(
+            bitap_classes(&[1, 2], &pattern),
+            Err(PatternError::EmptyClass(1))
+        );
+        assert_eq!(
+            PatternError::EmptyClass(1).to_string(),
+            "class at position 1 is empty"
+        );
+    }
+}
+
+/// # [Bitap Algorithm](https://en.wikipedia.org/wiki/Bitap_algorithm)
+///
+/// Exactly matches a pattern over the given sequence, lazily yielding the index of every
+/// occurrence in ascending order, including overlapping ones. An empty pattern occurs at every
+/// index from 0 to the sequence length.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abababa".chars().collect();
+/// let pattern: Vec<char> = "aba".chars().collect();
+/// let found: Vec<usize> = match_::bitap_all(&sequence, &pattern).collect();
+/// assert_eq!(found, vec![0, 2, 4]);
+/// ```
+pub fn bitap_all<'a, T: PartialEq>(sequence: &'a [T], pattern: &'a [T]) -> BitapAll<'a, T> {
+    let mut bit_arr = BitVec::from_elem(pattern.len() + 1, false);
+    bit_arr.set(0, true);
+    BitapAll {
+        sequence,
+        pattern,
+        bit_arr,
+        position: 0,
+    }
+}
+
+/// An iterator over the indices of every occurrence of a pattern, created by `bitap_all`.
+pub struct BitapAll<'a, T: 'a> {
+    sequence: &'a [T],
+    pattern: &'a [T],
+    bit_arr: BitVec,
+    position: usize,
+}
+
+impl<'a, T: PartialEq> Iterator for BitapAll<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let pat_len = self.pattern.len();
+        if pat_len == 0 {
+            // Empty pattern matches at every index, including the end.
+            self.position += 1;
+            return if self.position <= self.sequence.len() + 1 {
+                Some(self.position - 1)
+            } else {
+                None
+            };
+        }
+
+        while self.position < self.sequence.len() {
+            let item = &self.sequence[self.position];
+            self.position += 1;
+            // Update the bit array.
+            for k in (1..pat_len + 1).rev() {
+                let prev_bit = self.bit_arr[k - 1];
+                self.bit_arr
+                    .set(k, prev_bit & (*item == self.pattern[k - 1]));
+            }
+
+            if self.bit_arr[pat_len] {
+                return Some(self.position - pat_len); // found a match
+            }
+        }
+        None // no more matches
+    }
+}
+
+#[cfg(test)]
+mod bitap_all_tests {
+    use super::*;
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let sequence = [1, 1, 1, 1];
+        let found: Vec<usize> = bitap_all(&sequence, &[1, 1]).collect();
+        assert_eq!(found, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn agrees_with_bitap() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        for pattern in &["o", "l", "wor", "xyz", "hello, world"] {
+            let pattern: Vec<char> = pattern.chars().collect();
+            let mut found = bitap_all(&sequence, &pattern);
+            assert_eq!(found.next(), bitap(&sequence, &pattern));
+        }
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        let found: Vec<usize> = bitap_all(&['a', 'b'], &[]).collect();
+        assert_eq!(found, vec![0, 1, 2]);
+        let found: Vec<usize> = bitap_all::<char>(&[], &[]).collect();
+        assert_eq!(found, vec![0]);
+    }
+
+    #[test]
+    fn receives_longer_pattern() {
+        assert_eq!(bitap_all(&[1, 2], &[1, 2, 3]).next(), None);
+    }
+}
+
+/// # Replace All
+///
+/// Returns a copy of the sequence where every non-overlapping occurrence of the pattern, found
+/// from left to right, is replaced. The replacement is never scanned for further occurrences.
+/// An empty pattern replaces nothing, so a copy of the original sequence is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "aaa".chars().collect();
+/// let pattern: Vec<char> = "aa".chars().collect();
+/// let replacement: Vec<char> = "b".chars().collect();
+/// let replaced = match_::replace_all(&sequence, &pattern, &replacement);
+/// assert_eq!(replaced.iter().collect::<String>(), "ba");
+/// ```
+pub fn replace_all<T: Clone + PartialEq>(
+    sequence: &[T],
+    pattern: &[T],
+    replacement: &[T],
+) -> Vec<T> {
+    if pattern.is_empty() {
+        return sequence.to_vec();
+    }
+    let mut replaced = Vec::with_capacity(sequence.len());
+    let mut end = 0; // end of the last replaced occurrence
+    for start in bitap_all(sequence, pattern) {
+        if start < end {
+            continue; // overlaps with the last replaced occurrence
+        }
+        replaced.extend_from_slice(&sequence[end..start]);
+        replaced.extend_from_slice(replacement);
+        end = start + pattern.len();
+    }
+    replaced.extend_from_slice(&sequence[end..]);
+    replaced
+}
+
+/// # Replace First
+///
+/// Returns a copy of the sequence where the first occurrence of the pattern is replaced.
+/// An empty pattern replaces nothing, so a copy of the original sequence is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let replaced = match_::replace_first(&[1, 2, 3, 1, 2], &[1, 2], &[0]);
+/// assert_eq!(replaced, vec![0, 3, 1, 2]);
+/// ```
+pub fn replace_first<T: Clone + PartialEq>(
+    sequence: &[T],
+    pattern: &[T],
+    replacement: &[T],
+) -> Vec<T> {
+    match bitap(sequence, pattern) {
+        Some(start) if !pattern.is_empty() => {
+            let mut replaced = sequence[..start].to_vec();
+            replaced.extend_from_slice(replacement);
+            replaced.extend_from_slice(&sequence[start + pattern.len()..]);
+            replaced
+        }
+        _ => sequence.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod replace_tests {
+    use super::*;
+
+    fn replace_all_str(sequence: &str, pattern: &str, replacement: &str) -> String {
+        let chars = |s: &str| s.chars().collect::<Vec<char>>();
+        replace_all(&chars(sequence), &chars(pattern), &chars(replacement))
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn replaces_adjacent_occurrences() {
+        assert_eq!(replace_all_str("abab", "ab", "x"), "xx");
+        assert_eq!(replace_all_str("aaaa", "aa", "b"), "bb");
+        assert_eq!(replace_all_str("aaaaa", "aa", "b"), "bba");
+    }
+
+    #[test]
+    fn replaces_occurrences_at_both_ends() {
+        assert_eq!(replace_all_str("cat and cat", "cat", "dog"), "dog and dog");
+        assert_eq!(replace_all_str("cat", "cat", ""), "");
+    }
+
+    #[test]
+    fn replaces_with_different_lengths() {
+        assert_eq!(replace_all_str("a-b-c", "-", "--"), "a--b--c");
+        assert_eq!(replace_all_str("a--b--c", "--", ""), "abc");
+    }
+
+    #[test]
+    fn does_not_rescan_replacement() {
+        assert_eq!(replace_all_str("ab", "a", "aa"), "aab");
+        assert_eq!(replace_all_str("xax", "a", "bab"), "xbabx");
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        assert_eq!(replace_all_str("abc", "", "x"), "abc");
+        assert_eq!(replace_first(&[1, 2], &[], &[0]), vec![1, 2]);
+    }
+
+    #[test]
+    fn replaces_first_occurrence_only() {
+        assert_eq!(
+            replace_first(&[1, 2, 1, 2], &[2], &[3, 3]),
+            vec![1, 3, 3, 1, 2]
+        );
+        assert_eq!(replace_first(&[1, 2], &[3], &[0]), vec![1, 2]);
+    }
+}
+
+/// Returns the length of the longest common prefix of two sequences.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::common_prefix_len(b"interview", b"internet"), 5);
+/// ```
+pub fn common_prefix_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    common_prefix_len_by(a, b, &PartialEq::eq)
+}
+
+fn common_prefix_len_by<T, F>(a: &[T], b: &[T], eq: &F) -> usize
+where
+    F: Fn(&T, &T) -> bool,
+{
+    a.iter().zip(b).take_while(|&(x, y)| eq(x, y)).count()
+}
+
+/// Returns the length of the longest common suffix of two sequences.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::common_suffix_len(b"running", b"jumping"), 3);
+/// ```
+pub fn common_suffix_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    common_suffix_len_by(a, b, &PartialEq::eq)
+}
+
+fn common_suffix_len_by<T, F>(a: &[T], b: &[T], eq: &F) -> usize
+where
+    F: Fn(&T, &T) -> bool,
+{
+    a.iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|&(x, y)| eq(x, y))
+        .count()
+}
+
+/// Strips the longest common prefix of two sequences, then the longest common suffix of what
+/// remains, returning (prefix length, suffix length, core of a, core of b). The suffix never
+/// overlaps the prefix, so e.g. "aaaa" and "aaa" have a prefix of 3 and a suffix of 0.
+///
+/// Edits are only ever needed in the cores, so distance functions can work on them alone.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let (prefix, suffix, _, _) = match_::trim_common(b"kitten", b"sitting");
+/// assert_eq!((prefix, suffix), (0, 0));
+/// let (prefix, suffix, a, b) = match_::trim_common(b"the cat sat", b"the rat sat");
+/// assert_eq!((prefix, suffix, a, b), (4, 6, &b"c"[..], &b"r"[..]));
+/// ```
+pub fn trim_common<'a, 'b, T: PartialEq>(
+    a: &'a [T],
+    b: &'b [T],
+) -> (usize, usize, &'a [T], &'b [T]) {
+    trim_common_by(a, b, &PartialEq::eq)
+}
+
+fn trim_common_by<'a, 'b, T, F>(a: &'a [T], b: &'b [T], eq: &F) -> (usize, usize, &'a [T], &'b [T])
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let prefix = common_prefix_len_by(a, b, eq);
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = common_suffix_len_by(a, b, eq);
+    (
+        prefix,
+        suffix,
+        &a[..a.len() - suffix],
+        &b[..b.len() - suffix],
+    )
+}
+
+#[cfg(test)]
+mod trim_common_tests {
+    use super::*;
+
+    #[test]
+    fn counts_prefix_and_suffix() {
+        assert_eq!(common_prefix_len(b"abcx", b"abcy"), 3);
+        assert_eq!(common_suffix_len(b"xabc", b"yabc"), 3);
+        assert_eq!(common_prefix_len(b"abc", b"xyz"), 0);
+        assert_eq!(common_prefix_len::<u8>(b"", b"abc"), 0);
+    }
+
+    #[test]
+    fn does_not_double_count_overlap() {
+        assert_eq!(common_prefix_len(b"aaaa", b"aaa"), 3);
+        assert_eq!(common_suffix_len(b"aaaa", b"aaa"), 3);
+        let (prefix, suffix, a, b) = trim_common(b"aaaa", b"aaa");
+        assert_eq!((prefix, suffix, a, b), (3, 0, &b"a"[..], &b""[..]));
+
+        let (prefix, suffix, a, b) = trim_common(b"abab", b"ab");
+        assert_eq!((prefix, suffix, a, b), (2, 0, &b"ab"[..], &b""[..]));
+    }
+
+    #[test]
+    fn trims_identical_sequences() {
+        let (prefix, suffix, a, b) = trim_common(b"same", b"same");
+        assert_eq!((prefix, suffix), (4, 0));
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    fn trims_both_ends() {
+        let (prefix, suffix, a, b) = trim_common(b"prefix-ab-suffix", b"prefix-xyz-suffix");
+        assert_eq!((prefix, suffix, a, b), (7, 7, &b"ab"[..], &b"xyz"[..]));
+    }
+}
+
+/// # [Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+///
+/// Calculates the minimum edit distance between two sequences.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "sitting".chars().collect();
+/// let target: Vec<char> = "kitten".chars().collect();
+/// assert_eq!(match_::levenshtein_distance(&source, &target), 3);
+/// ```
+pub fn levenshtein_distance<T: PartialEq>(source: &[T], target: &[T]) -> u64 {
+    levenshtein_distance_by(source, target, PartialEq::eq)
+}
+
+#[cfg(test)]
+mod levenshtein_distance_tests {
+    use super::levenshtein_distance;
+
+    #[test]
+    fn receives_longer_source() {
+        let source: Vec<char> = "sitting".chars().collect();
+        let target: Vec<char> = "kitten".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), 3);
+    }
+
+    #[test]
+    fn receives_longer_target() {
+        let source: Vec<char> = "kite".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), 5);
+    }
+
+    #[test]
+    fn receives_integer_vectors() {
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(levenshtein_distance(&source, &target), 13);
+    }
+
+    #[test]
+    fn receives_empty_source() {
+        let source: Vec<char> = vec![];
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), target.len() as u64);
+    }
+
+    #[test]
+    fn receives_empty_target() {
+        let source: Vec<char> = "sitting".chars().collect();
+        let target: Vec<char> = vec![];
+        assert_eq!(levenshtein_distance(&source, &target), source.len() as u64);
+    }
+
+    #[test]
+    fn receives_swapped_sequences() {
+        let source: Vec<char> = "intention".chars().collect();
+        let target: Vec<char> = "execution".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), 5);
+        assert_eq!(levenshtein_distance(&target, &source), 5);
+    }
+
+    #[test]
+    fn trims_common_ends() {
+        let source: Vec<char> = "aaaa".chars().collect();
+        let target: Vec<char> = "aaa".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), 1);
+        let source: Vec<char> = "the cat sat on the mat".chars().collect();
+        let target: Vec<char> = "the cart sat on the hat".chars().collect();
+        assert_eq!(levenshtein_distance(&source, &target), 2);
+    }
+
+    #[test]
+    fn receives_long_sequences() {
+        let source: Vec<u32> = (0..5000).collect();
+        let target: Vec<u32> = (0..5000)
+            .map(|x| if x % 100 == 0 { 0 } else { x })
+            .collect();
+        assert_eq!(levenshtein_distance(&source, &target), 49);
+    }
+}
+
+/// # [Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+///
+/// Calculates the minimum edit distance between two sequences, deciding whether two items are
+/// equal with a custom predicate. The predicate always receives (source item, target item) in
+/// that order, so it does not need to be symmetric.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "Sitting".chars().collect();
+/// let target: Vec<char> = "kitten".chars().collect();
+/// let distance = match_::levenshtein_distance_by(&source, &target, |x, y| {
+///     x.eq_ignore_ascii_case(y)
+/// });
+/// assert_eq!(distance, 3);
+/// ```
+pub fn levenshtein_distance_by<T, F>(source: &[T], target: &[T], eq: F) -> u64
+where
+    F: Fn(&T, &T) -> bool,
+{
+    edit_distance_by(source, target, eq, false)
+}
+
+/// Calculates the minimum edit distance between two sequences, counting a transposition of two
+/// adjacent items as one edit if `transpositions` is set, where eq receives (source item, target
+/// item).
+fn edit_distance_by<T, F>(source: &[T], target: &[T], eq: F, transpositions: bool) -> u64
+where
+    F: Fn(&T, &T) -> bool,
+{
+    // Matching items at either end never need an edit, so only the cores are compared.
+    let (_, _, source, target) = trim_common_by(source, target, &eq);
+    // The edit distance is symmetric, so the shorter sequence goes into the inner dimension
+    // to keep the rows as short as possible; the predicate is flipped back when they swap.
+    if source.len() >= target.len() {
+        edit_distance_rows(source, target, eq, transpositions)
+    } else {
+        edit_distance_rows(target, source, |x, y| eq(y, x), transpositions)
+    }
+}
+
+/// Calculates the minimum edit distance between a longer and a shorter sequence, where eq
+/// receives (longer item, shorter item).
+fn edit_distance_rows<T, F>(longer: &[T], shorter: &[T], eq: F, transpositions: bool) -> u64
+where
+    F: Fn(&T, &T) -> bool,
+{
+    // previous[j] holds the edit distance for the first i-1 longer items and j shorter items,
+    // while current[j] holds the one for the first i longer items and j shorter items;
+    // only these two rows of the m*n distances matrix are ever needed, plus the row for the
+    // first i-2 longer items when transpositions are counted.
+    let n = shorter.len() + 1;
+    // Empty sequence can be transformed into shorter prefixes by inserting every item.
+    let mut previous: Vec<u64> = (0..n as u64).collect();
+    let mut current = vec![0u64; n];
+    let mut before_previous = if transpositions {
+        vec![0u64; n]
+    } else {
+        Vec::new()
+    };
+
+    for (i, x) in longer.iter().enumerate() {
+        // Longer prefixes can be transformed into empty sequence by dropping all items.
+        current[0] = i as u64 + 1;
+        for (j, y) in shorter.iter().enumerate() {
+            let substitution_cost = if eq(x, y) { 0 } else { 1 };
+
+            // Find the minimum of 3 different edit operation costs.
+            current[j + 1] = cmp::min(
+                previous[j + 1] + 1, // deletion
+                cmp::min(
+                    current[j] + 1,                  // insertion
+                    previous[j] + substitution_cost, // substitution
+                ),
+            );
+            // Swapping two adjacent items is a single edit too.
+            if transpositions && i > 0 && j > 0 && eq(x, &shorter[j - 1]) && eq(&longer[i - 1], y) {
+                current[j + 1] = cmp::min(current[j + 1], before_previous[j - 1] + 1);
+            }
+        }
+        if transpositions {
+            mem::swap(&mut before_previous, &mut previous);
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+    previous[n - 1] // the last element is the min. edit distance
+}
+
+#[cfg(test)]
+mod levenshtein_distance_by_tests {
+    use super::levenshtein_distance_by;
+
+    struct Token {
+        id: u32,
+    }
+
+    #[test]
+    fn compares_case_insensitively() {
+        let source: Vec<char> = "KITTEN".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        let eq = |x: &char, y: &char| x.eq_ignore_ascii_case(y);
+        assert_eq!(levenshtein_distance_by(&source, &target, eq), 3);
+        assert_eq!(levenshtein_distance_by(&target, &source, eq), 3);
+    }
+
+    #[test]
+    fn compares_structs_by_field() {
+        let source: Vec<Token> = [1, 2, 3, 4].iter().map(|&id| Token { id }).collect();
+        let target: Vec<Token> = [1, 3, 4, 5].iter().map(|&id| Token { id }).collect();
+        assert_eq!(
+            levenshtein_distance_by(&source, &target, |x, y| x.id == y.id),
+            2
+        );
+    }
+
+    #[test]
+    fn passes_source_item_first() {
+        // Only target items may be wildcards, whichever sequence is longer.
+        let eq = |x: &char, y: &char| *y == '?' || x == y;
+        let source: Vec<char> = "cat".chars().collect();
+        let target: Vec<char> = "c?ts".chars().collect();
+        assert_eq!(levenshtein_distance_by(&source, &target, eq), 1);
+        assert_eq!(levenshtein_distance_by(&target, &source, eq), 2);
+        let target: Vec<char> = "??".chars().collect();
+        assert_eq!(levenshtein_distance_by(&source, &target, eq), 1);
+        assert_eq!(levenshtein_distance_by(&target, &source, eq), 3);
+    }
+}
+
+/// # [Bounded Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+///
+/// Calculates the minimum edit distance between two sequences only if it does not exceed `max`.
+/// Only the diagonal band of width 2*max+1 in the distances matrix is computed, and the
+/// computation stops as soon as every cell in a row exceeds `max`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "sitting".chars().collect();
+/// let target: Vec<char> = "kitten".chars().collect();
+/// assert_eq!(match_::levenshtein_within(&source, &target, 3), Some(3));
+/// assert_eq!(match_::levenshtein_within(&source, &target, 2), None);
+/// ```
+pub fn levenshtein_within<T: PartialEq>(source: &[T], target: &[T], max: u64) -> Option<u64> {
+    // Matching items at either end never need an edit, so only the cores are compared.
+    let (_, _, source, target) = trim_common(source, target);
+    let (longer, shorter) = if source.len() >= target.len() {
+        (source, target)
+    } else {
+        (target, source)
+    };
+    let (m, n) = (longer.len(), shorter.len());
+    // Every extra item of the longer sequence needs at least one insertion.
+    if (m - n) as u64 > max {
+        return None;
+    }
+
+    // Cells farther than band from the diagonal always exceed max, so they are never computed;
+    // any value larger than max is as good as infinity.
+    let band = cmp::min(max, m as u64) as usize;
+    let out_of_band = max.saturating_add(1);
+    let mut previous: Vec<u64> = (0..n + 1)
+        .map(|j| if j <= band { j as u64 } else { out_of_band })
+        .collect();
+    let mut current = vec![out_of_band; n + 1];
+
+    for (i, x) in longer.iter().enumerate().map(|(i, x)| (i + 1, x)) {
+        let low = if i > band { i - band } else { 1 };
+        let high = cmp::min(n, i + band);
+
+        // The cell left of the band is either the first column or out of the band.
+        current[low - 1] = if low == 1 { i as u64 } else { out_of_band };
+        let mut row_min = current[low - 1];
+        for j in low..high + 1 {
+            let substitution_cost = if *x == shorter[j - 1] { 0 } else { 1 };
+            let distance = cmp::min(
+                previous[j] + 1, // deletion
+                cmp::min(
+                    current[j - 1] + 1,                  // insertion
+                    previous[j - 1] + substitution_cost, // substitution
+                ),
+            );
+            current[j] = cmp::min(distance, out_of_band);
+            row_min = cmp::min(row_min, current[j]);
+        }
+        // The cell right of the band is read by the next row.
+        if high < n {
+            current[high + 1] = out_of_band;
+        }
+
+        if row_min > max {
+            return None; // the distance can only grow from here
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+
+    if previous[n] <= max {
+        Some(previous[n])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod levenshtein_within_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_distance_within_max() {
+        let source: Vec<char> = "sitting".chars().collect();
+        let target: Vec<char> = "kitten".chars().collect();
+        assert_eq!(levenshtein_within(&source, &target, 3), Some(3));
+        assert_eq!(levenshtein_within(&source, &target, 10), Some(3));
+    }
+
+    #[test]
+    fn receives_distance_exceeding_max() {
+        let source: Vec<char> = "kite".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_within(&source, &target, 4), None);
+    }
+
+    #[test]
+    fn receives_zero_max() {
+        let source: Vec<char> = "kitten".chars().collect();
+        let target: Vec<char> = "kitten".chars().collect();
+        assert_eq!(levenshtein_within(&source, &target, 0), Some(0));
+        assert_eq!(levenshtein_within(&source, &target[1..], 0), None);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let source: Vec<char> = vec![];
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_within(&source, &source, 0), Some(0));
+        assert_eq!(levenshtein_within(&source, &target, 7), Some(7));
+        assert_eq!(levenshtein_within(&target, &source, 6), None);
+    }
+
+    #[test]
+    fn receives_wildly_different_lengths() {
+        let source: Vec<u32> = (0..10).collect();
+        let target: Vec<u32> = (0..1_000_000).collect();
+        assert_eq!(levenshtein_within(&source, &target, 2), None);
+    }
+
+    #[test]
+    fn agrees_with_levenshtein_distance() {
+        test_util::agrees(
+            500,
+            |rng| {
+                let source: Vec<u8> = test_util::random_vec(rng, 0..20, 0..4);
+                let target: Vec<u8> = test_util::random_vec(rng, 0..20, 0..4);
+                (source, target, rng.gen_range(0, 12))
+            },
+            |&(ref source, ref target, max)| levenshtein_within(source, target, max),
+            |&(ref source, ref target, max)| {
+                let distance = levenshtein_distance(source, target);
+                if distance <= max {
+                    Some(distance)
+                } else {
+                    None
+                }
+            },
+        );
+    }
+}
+
+/// # [Bounded Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+///
+/// Calculates the minimum edit distance between two sequences, giving up as soon as every active
+/// diagonal in the distances matrix exceeds `max_distance`. This is the same computation as
+/// `levenshtein_within`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "kitten".chars().collect();
+/// let target: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(match_::levenshtein_distance_bounded(&source, &target, 3), Some(3));
+/// assert_eq!(match_::levenshtein_distance_bounded(&source, &target, 1), None);
+/// ```
+pub fn levenshtein_distance_bounded<T: PartialEq>(
+    source: &[T],
+    target: &[T],
+    max_distance: u64,
+) -> Option<u64> {
+    levenshtein_within(source, target, max_distance)
+}
+
+#[cfg(test)]
+mod levenshtein_distance_bounded_tests {
+    use super::*;
+
+    #[test]
+    fn receives_distance_within_max() {
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(levenshtein_distance_bounded(&source, &target, 13), Some(13));
+        assert_eq!(levenshtein_distance_bounded(&source, &target, 20), Some(13));
+    }
+
+    #[test]
+    fn receives_distance_exceeding_max() {
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(levenshtein_distance_bounded(&source, &target, 12), None);
+    }
+
+    #[test]
+    fn receives_far_apart_sequences() {
+        let source: Vec<u32> = (0..1000).collect();
+        let target: Vec<u32> = (0..1000)
+            .map(|x| if x % 10 == 0 { x + 1 } else { x })
+            .collect();
+        assert_eq!(levenshtein_distance(&source, &target), 100);
+        assert_eq!(levenshtein_distance_bounded(&source, &target, 3), None);
+        assert_eq!(
+            levenshtein_distance_bounded(&source, &target, 100),
+            Some(100)
+        );
+    }
+}
+
+/// # [Myers' Bit-Parallel Edit Distance](https://doi.org/10.1145/316542.316550)
+///
+/// Calculates the same minimum edit distance as `levenshtein_distance` in O(m*n/64) time by
+/// encoding the vertical and horizontal differences of a whole distances matrix column in
+/// machine words. Sequences longer than 64 items are split into multiple words, as described by
+/// Heikki Hyyrö.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "sitting".chars().collect();
+/// let target: Vec<char> = "kitten".chars().collect();
+/// assert_eq!(match_::levenshtein_myers(&source, &target), 3);
+/// ```
+pub fn levenshtein_myers<T: Eq + Hash>(source: &[T], target: &[T]) -> u64 {
+    // The shorter sequence is encoded into bit vectors, the longer one is scanned.
+    let (text, pattern) = if source.len() >= target.len() {
+        (source, target)
+    } else {
+        (target, source)
+    };
+    let m = pattern.len();
+    if m == 0 {
+        return text.len() as u64;
+    }
+
+    // Bit i of a word block tells whether pattern[i] equals the symbol.
+    let words = m.div_ceil(64);
+    let mut peq: HashMap<&T, Vec<u64>> = HashMap::new();
+    for (i, x) in pattern.iter().enumerate() {
+        peq.entry(x).or_insert_with(|| vec![0; words])[i / 64] |= 1 << (i % 64);
+    }
+
+    // Positive and negative vertical differences; the first column is 0, 1, 2, ..., m.
+    let mut pv = vec![!0u64; words];
+    let mut mv = vec![0u64; words];
+    let mut score = m as u64;
+    let last_bit = 1u64 << ((m - 1) % 64);
+
+    for y in text {
+        let eqs = peq.get(y);
+        // Horizontal difference entering the top of the block; the first row is 0, 1, 2, ..., n.
+        let mut h_in: i8 = 1;
+        for w in 0..words {
+            let eq = eqs.map_or(0, |eqs| eqs[w]);
+            let (pv_w, mv_w) = (pv[w], mv[w]);
+
+            let xv = eq | mv_w;
+            let eq = eq | (h_in < 0) as u64;
+            let xh = ((eq & pv_w).wrapping_add(pv_w) ^ pv_w) | eq;
+            let mut ph = mv_w | !(xh | pv_w);
+            let mut mh = pv_w & xh;
+
+            if w == words - 1 {
+                // Track the last row of the pattern, which holds the edit distance.
+                if ph & last_bit != 0 {
+                    score += 1;
+                } else if mh & last_bit != 0 {
+                    score -= 1;
+                }
+            }
+            let h_out = (ph >> 63) as i8 - (mh >> 63) as i8;
+
+            ph = (ph << 1) | (h_in > 0) as u64;
+            mh = (mh << 1) | (h_in < 0) as u64;
+            pv[w] = mh | !(xv | ph);
+            mv[w] = ph & xv;
+            h_in = h_out;
+        }
+    }
+    score
+}
+
+#[cfg(test)]
+mod levenshtein_myers_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_longer_source() {
+        let source: Vec<char> = "sitting".chars().collect();
+        let target: Vec<char> = "kitten".chars().collect();
+        assert_eq!(levenshtein_myers(&source, &target), 3);
+    }
+
+    #[test]
+    fn receives_longer_target() {
+        let source: Vec<char> = "kite".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_myers(&source, &target), 5);
+    }
+
+    #[test]
+    fn receives_integer_vectors() {
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(levenshtein_myers(&source, &target), 13);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let source: Vec<char> = vec![];
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_myers(&source, &source), 0);
+        assert_eq!(levenshtein_myers(&source, &target), 7);
+        assert_eq!(levenshtein_myers(&target, &source), 7);
+    }
+
+    #[test]
+    fn receives_sequences_longer_than_a_word() {
+        let source: Vec<u32> = (0..300).collect();
+        let target: Vec<u32> = (0..300).map(|x| if x % 7 == 0 { 0 } else { x }).collect();
+        assert_eq!(
+            levenshtein_myers(&source, &target),
+            levenshtein_distance(&source, &target)
+        );
+    }
+
+    #[test]
+    fn agrees_with_levenshtein_distance() {
+        test_util::agrees(
+            300,
+            |rng| {
+                let alphabet: u8 = rng.gen_range(1, 6);
+                let source: Vec<u8> = test_util::random_vec(rng, 0..200, 0..alphabet);
+                (source, test_util::random_vec(rng, 0..200, 0..alphabet))
+            },
+            |(source, target)| levenshtein_myers(source, target),
+            |(source, target)| levenshtein_distance(source, target),
+        );
+    }
+}
+
+/// An edit operation that transforms a source sequence into a target sequence
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditOp<T> {
+    /// Insert the target item
+    Insert(T),
+    /// Delete the source item
+    Delete(T),
+    /// Replace the source item with the target item
+    Substitute { from: T, to: T },
+    /// Keep the source item as it is
+    Keep(T),
+}
+
+/// # [Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance) with Edits
+///
+/// Calculates the minimum edit distance between two sequences, along with the edit operations
+/// that transform the source into the target. The edits are found by backtracking through the
+/// full distances matrix, preferring keeps/substitutions, then deletions, then insertions.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{self, EditOp};
+///
+/// let source: Vec<char> = "kitten".chars().collect();
+/// let target: Vec<char> = "sitting".chars().collect();
+/// let (distance, edits) = match_::levenshtein_edits(&source, &target);
+/// assert_eq!(distance, 3);
+/// assert_eq!(edits[0], EditOp::Substitute { from: 'k', to: 's' });
+/// assert_eq!(edits[6], EditOp::Insert('g'));
+/// ```
+pub fn levenshtein_edits<T: PartialEq + Clone>(
+    source: &[T],
+    target: &[T],
+) -> (u64, Vec<EditOp<T>>) {
+    let distances = levenshtein_matrix(source, target);
+    let (mut i, mut j) = (source.len(), target.len());
+    let mut edits = Vec::with_capacity(cmp::max(i, j));
+
+    // Walk back from the last cell to the first one, undoing one edit operation at a time.
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let is_equal = source[i - 1] == target[j - 1];
+            let substitution_cost = if is_equal { 0 } else { 1 };
+            if distances[i][j] == distances[i - 1][j - 1] + substitution_cost {
+                edits.push(if is_equal {
+                    EditOp::Keep(source[i - 1].clone())
+                } else {
+                    EditOp::Substitute {
+                        from: source[i - 1].clone(),
+                        to: target[j - 1].clone(),
+                    }
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && distances[i][j] == distances[i - 1][j] + 1 {
+            edits.push(EditOp::Delete(source[i - 1].clone()));
+            i -= 1;
+        } else {
+            edits.push(EditOp::Insert(target[j - 1].clone()));
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    (distances[source.len()][target.len()], edits)
+}
+
+/// Builds the full (m+1)*(n+1) Levenshtein distances matrix, where distances[i][j] holds
+/// the edit distance for the first i source items and j target items.
+fn levenshtein_matrix<T: PartialEq>(source: &[T], target: &[T]) -> Vec<Vec<u64>> {
+    let (m, n) = (source.len() + 1, target.len() + 1);
+    let mut distances = vec![vec![0u64; n]; m];
+
+    // Source prefixes can be transformed into empty sequence by dropping all items.
+    for (i, row) in distances.iter_mut().enumerate().skip(1) {
+        row[0] = i as u64;
+    }
+    // Empty sequence can be transformed into target prefixes by inserting every item.
+    for (j, distance) in distances[0].iter_mut().enumerate().skip(1) {
+        *distance = j as u64;
+    }
+
+    for i in 1..m {
+        for j in 1..n {
+            let substitution_cost = if source[i - 1] == target[j - 1] { 0 } else { 1 };
+
+            // Find the minimum of 3 different edit operation costs.
+            distances[i][j] = cmp::min(
+                distances[i - 1][j] + 1, // deletion
+                cmp::min(
+                    distances[i][j - 1] + 1,                     // insertion
+                    distances[i - 1][j - 1] + substitution_cost, // substitution
+                ),
+            );
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+mod levenshtein_edits_tests {
+    use super::*;
+
+    /// Replays the edits on the source sequence.
+    fn apply_edits<T: PartialEq + Clone>(source: &[T], edits: &[EditOp<T>]) -> Vec<T> {
+        let mut source = source.iter();
+        let mut result = vec![];
+        for edit in edits {
+            match *edit {
+                EditOp::Insert(ref x) => result.push(x.clone()),
+                EditOp::Delete(ref x) => assert!(source.next() == Some(x)),
+                EditOp::Substitute { ref from, ref to } => {
+                    assert!(source.next() == Some(from));
+                    result.push(to.clone());
+                }
+                EditOp::Keep(ref x) => {
+                    assert!(source.next() == Some(x));
+                    result.push(x.clone());
+                }
+            }
+        }
+        assert!(source.next().is_none());
+        result
+    }
+
+    #[test]
+    fn receives_kitten_and_sitting() {
+        let source: Vec<char> = "kitten".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        let (distance, edits) = levenshtein_edits(&source, &target);
+        assert_eq!(distance, 3);
+        assert_eq!(
+            edits,
+            vec![
+                EditOp::Substitute { from: 'k', to: 's' },
+                EditOp::Keep('i'),
+                EditOp::Keep('t'),
+                EditOp::Keep('t'),
+                EditOp::Substitute { from: 'e', to: 'i' },
+                EditOp::Keep('n'),
+                EditOp::Insert('g'),
+            ]
+        );
+    }
+
+    #[test]
+    fn receives_empty_source() {
+        let source: Vec<char> = vec![];
+        let target: Vec<char> = "abc".chars().collect();
+        let (distance, edits) = levenshtein_edits(&source, &target);
+        assert_eq!(distance, 3);
+        assert_eq!(
+            edits,
+            vec![
+                EditOp::Insert('a'),
+                EditOp::Insert('b'),
+                EditOp::Insert('c')
+            ]
+        );
+    }
+
+    #[test]
+    fn receives_empty_target() {
+        let source: Vec<char> = "abc".chars().collect();
+        let target: Vec<char> = vec![];
+        let (distance, edits) = levenshtein_edits(&source, &target);
+        assert_eq!(distance, 3);
+        assert_eq!(
+            edits,
+            vec![
+                EditOp::Delete('a'),
+                EditOp::Delete('b'),
+                EditOp::Delete('c')
+            ]
+        );
+    }
+
+    #[test]
+    fn replays_edits_into_target() {
+        let pairs = [
+            ("sitting in my kitchen", "kittens love sitting"),
+            ("intention", "execution"),
+            ("", ""),
+            ("abc", "abc"),
+        ];
+        for &(source, target) in pairs.iter() {
+            let source: Vec<char> = source.chars().collect();
+            let target: Vec<char> = target.chars().collect();
+            let (distance, edits) = levenshtein_edits(&source, &target);
+            assert_eq!(distance, levenshtein_distance(&source, &target));
+            assert_eq!(apply_edits(&source, &edits), target);
+
+            let cost = edits
+                .iter()
+                .filter(|edit| !matches!(**edit, EditOp::Keep(_)));
+            assert_eq!(cost.count() as u64, distance);
+        }
+    }
+}
+
+/// # Normalized Levenshtein Similarity
+///
+/// Calculates a similarity score in [0, 1] between two sequences, defined as
+/// `1 - levenshtein_distance(a, b) / max(a.len(), b.len())`. Two empty sequences are
+/// identical, so their similarity is 1.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "kitten".chars().collect();
+/// let b: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(match_::levenshtein_similarity(&a, &b), 1.0 - 3.0 / 7.0);
+/// ```
+pub fn levenshtein_similarity<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    normalized_similarity(levenshtein_distance(a, b), a.len(), b.len())
+}
+
+/// Turns a distance into a similarity score in [0, 1] by dividing it by the length of
+/// the longer sequence (which bounds every edit distance) and subtracting it from 1.
+fn normalized_similarity(distance: u64, a_len: usize, b_len: usize) -> f64 {
+    let max_len = cmp::max(a_len, b_len);
+    if max_len == 0 {
+        1.0 // 0/0: two empty sequences are identical
+    } else {
+        1.0 - distance as f64 / max_len as f64
+    }
+}
+
+#[cfg(test)]
+mod levenshtein_similarity_tests {
+    use super::*;
+
+    #[test]
+    fn receives_identical_sequences() {
+        let a: Vec<char> = "kitten".chars().collect();
+        assert_eq!(levenshtein_similarity(&a, &a), 1.0);
+    }
+
+    #[test]
+    fn receives_disjoint_sequences() {
+        let a: Vec<char> = "abc".chars().collect();
+        let b: Vec<char> = "xyz".chars().collect();
+        assert_eq!(levenshtein_similarity(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn receives_similar_sequences() {
+        let a: Vec<u64> = (0..10).collect();
+        let b: Vec<u64> = (0..8).collect();
+        assert_eq!(levenshtein_similarity(&a, &b), 0.8);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<char> = vec![];
+        let a: Vec<char> = "abc".chars().collect();
+        assert_eq!(levenshtein_similarity(&empty, &empty), 1.0);
+        assert_eq!(levenshtein_similarity(&empty, &a), 0.0);
+        assert_eq!(levenshtein_similarity(&a, &empty), 0.0);
+    }
+}
+
+/// # [Levenshtein Edit Distance](https://en.wikipedia.org/wiki/Levenshtein_distance) in O(min(m, n)) Space
+///
+/// Calculates the minimum edit distance between two sequences while keeping only two rows of
+/// the distances matrix, each as long as the shorter sequence. This is what
+/// `levenshtein_distance` does as well; the name is kept for callers who want to be explicit.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "sitting".chars().collect();
+/// let target: Vec<char> = "kitten".chars().collect();
+/// assert_eq!(match_::levenshtein_distance_optimised(&source, &target), 3);
+/// ```
+pub fn levenshtein_distance_optimised<T: PartialEq>(source: &[T], target: &[T]) -> u64 {
+    levenshtein_distance(source, target)
+}
+
+#[cfg(test)]
+mod levenshtein_distance_optimised_tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_levenshtein_distance() {
+        let pairs = [
+            ("sitting", "kitten"),
+            ("kite", "sitting"),
+            ("", "sitting"),
+            ("sitting", ""),
+            ("", ""),
+        ];
+        for &(source, target) in pairs.iter() {
+            let source: Vec<char> = source.chars().collect();
+            let target: Vec<char> = target.chars().collect();
+            assert_eq!(
+                levenshtein_distance_optimised(&source, &target),
+                levenshtein_distance(&source, &target)
+            );
+        }
+
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(levenshtein_distance_optimised(&source, &target), 13);
+    }
+}
+
+/// # [Optimal String Alignment Distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Optimal_string_alignment_distance)
+///
+/// Calculates the minimum number of insertions, deletions, substitutions, and transpositions of
+/// two adjacent items between two sequences, with the restriction that no substring is edited
+/// more than once. It extends the rows of `levenshtein_distance` with one more case, keeping a
+/// third row as long as the shorter sequence.
+///
+/// This is what many libraries call the "Damerau–Levenshtein distance", but it differs from
+/// `damerau_levenshtein_distance`, which lets a transposed pair be edited again: "CA" becomes
+/// "ABC" in 2 edits there (transpose to "AC", insert "B") but in 3 here. Because of that, this
+/// distance does not satisfy the triangle inequality, while being cheaper to compute and
+/// needing only `PartialEq`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "CA".chars().collect();
+/// let target: Vec<char> = "ABC".chars().collect();
+/// assert_eq!(match_::osa_distance(&source, &target), 3);
+/// assert_eq!(match_::damerau_levenshtein_distance(&source, &target), 2);
+/// ```
+pub fn osa_distance<T: PartialEq>(source: &[T], target: &[T]) -> u64 {
+    edit_distance_by(source, target, PartialEq::eq, true)
+}
+
+#[cfg(test)]
+mod osa_distance_tests {
+    use super::*;
+    use test_util;
+
+    #[test]
+    fn receives_transposed_items() {
+        let source: Vec<char> = "ab".chars().collect();
+        let target: Vec<char> = "ba".chars().collect();
+        assert_eq!(osa_distance(&source, &target), 1);
+        assert_eq!(levenshtein_distance(&source, &target), 2);
+    }
+
+    #[test]
+    fn differs_from_damerau_levenshtein() {
+        let source: Vec<char> = "CA".chars().collect();
+        let target: Vec<char> = "ABC".chars().collect();
+        assert_eq!(osa_distance(&source, &target), 3);
+        assert_eq!(damerau_levenshtein_distance(&source, &target), 2);
+    }
+
+    #[test]
+    fn agrees_with_levenshtein_without_transpositions() {
+        let pairs = [
+            ("sitting", "kitten"),
+            ("kite", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+        ];
+        for &(source, target) in pairs.iter() {
+            let source: Vec<char> = source.chars().collect();
+            let target: Vec<char> = target.chars().collect();
+            assert_eq!(
+                osa_distance(&source, &target),
+                levenshtein_distance(&source, &target)
+            );
+        }
+    }
+
+    #[test]
+    fn agrees_with_full_matrix() {
+        let full = |source: &[u8], target: &[u8]| {
+            let (m, n) = (source.len(), target.len());
+            let mut d = vec![vec![0u64; n + 1]; m + 1];
+            for i in 0..=m {
+                for j in 0..=n {
+                    d[i][j] = if i == 0 || j == 0 {
+                        (i + j) as u64
+                    } else {
+                        let cost = (source[i - 1] != target[j - 1]) as u64;
+                        cmp::min(
+                            cmp::min(d[i - 1][j] + 1, d[i][j - 1] + 1),
+                            d[i - 1][j - 1] + cost,
+                        )
+                    };
+                    if i > 1
+                        && j > 1
+                        && source[i - 1] == target[j - 2]
+                        && source[i - 2] == target[j - 1]
+                    {
+                        d[i][j] = cmp::min(d[i][j], d[i - 2][j - 2] + 1);
+                    }
+                }
+            }
+            d[m][n]
+        };
+        test_util::agrees(
+            500,
+            |rng| {
+                let source: Vec<u8> = test_util::random_vec(rng, 0..10, 0..3);
+                (source, test_util::random_vec(rng, 0..10, 0..3))
+            },
+            |(source, target)| osa_distance(source, target),
+            |(source, target)| full(source, target),
+        );
+    }
+
+    #[test]
+    fn lies_between_damerau_levenshtein_and_levenshtein() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            let source: Vec<u8> = test_util::random_vec(&mut rng, 0..12, 0..4);
+            let target: Vec<u8> = test_util::random_vec(&mut rng, 0..12, 0..4);
+            let distance = osa_distance(&source, &target);
+            assert!(distance <= levenshtein_distance(&source, &target));
+            assert!(distance >= damerau_levenshtein_distance(&source, &target));
+        }
+    }
+}
+
+/// # [Damerau–Levenshtein Distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance)
+///
+/// Calculates the minimum number of insertions, deletions, substitutions, and transpositions of
+/// two adjacent items between two sequences. Unlike `osa_distance`, a transposed pair may be
+/// edited again, e.g. "CA" becomes "ABC" by transposing to "AC" and inserting "B".
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let source: Vec<char> = "CA".chars().collect();
+/// let target: Vec<char> = "ABC".chars().collect();
+/// assert_eq!(match_::damerau_levenshtein_distance(&source, &target), 2);
+/// ```
+pub fn damerau_levenshtein_distance<T: Eq + Hash>(source: &[T], target: &[T]) -> u64 {
+    let (m, n) = (source.len(), target.len());
+    let infinity = (m + n) as u64;
+    // distances[i+1][j+1] holds the distance for the first i source items and j target items;
+    // the extra first row and column hold infinity so that transpositions never start there.
+    let mut distances = vec![vec![0u64; n + 2]; m + 2];
+    distances[0][0] = infinity;
+    for i in 0..m + 1 {
+        distances[i + 1][0] = infinity;
+        distances[i + 1][1] = i as u64;
+    }
+    for j in 0..n + 1 {
+        distances[0][j + 1] = infinity;
+        distances[1][j + 1] = j as u64;
+    }
+
+    // The last source row in which every item has been seen.
+    let mut last_rows: HashMap<&T, usize> = HashMap::new();
+    for i in 1..m + 1 {
+        // The last target column in which the current source item has been seen.
+        let mut last_column = 0;
+        for j in 1..n + 1 {
+            let k = last_rows.get(&target[j - 1]).cloned().unwrap_or(0);
+            let l = last_column;
+            let substitution_cost = if source[i - 1] == target[j - 1] {
+                last_column = j;
+                0
+            } else {
+                1
+            };
+
+            distances[i + 1][j + 1] = cmp::min(
+                cmp::min(
+                    distances[i][j + 1] + 1, // deletion
+                    distances[i + 1][j] + 1, // insertion
+                ),
+                cmp::min(
+                    distances[i][j] + substitution_cost, // substitution
+                    // transposition with the items in between deleted or inserted
+                    distances[k][l] + (i - k - 1) as u64 + 1 + (j - l - 1) as u64,
+                ),
+            );
+        }
+        last_rows.insert(&source[i - 1], i);
+    }
+    distances[m + 1][n + 1]
+}
+
+/// # Normalized Damerau–Levenshtein Similarity
+///
+/// Calculates a similarity score in [0, 1] between two sequences, defined as
+/// `1 - damerau_levenshtein_distance(a, b) / max(a.len(), b.len())`. Two empty sequences are
+/// identical, so their similarity is 1.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "abcd".chars().collect();
+/// let b: Vec<char> = "bacd".chars().collect();
+/// assert_eq!(match_::damerau_levenshtein_similarity(&a, &b), 0.75);
+/// ```
+pub fn damerau_levenshtein_similarity<T: Eq + Hash>(a: &[T], b: &[T]) -> f64 {
+    normalized_similarity(damerau_levenshtein_distance(a, b), a.len(), b.len())
+}
+
+#[cfg(test)]
+mod damerau_levenshtein_distance_tests {
+    use super::*;
+
+    #[test]
+    fn receives_transposed_items() {
+        let source: Vec<char> = "abcdef".chars().collect();
+        let target: Vec<char> = "badcfe".chars().collect();
+        assert_eq!(damerau_levenshtein_distance(&source, &target), 3);
+    }
+
+    #[test]
+    fn receives_transposition_with_insertion() {
+        let source: Vec<char> = "CA".chars().collect();
+        let target: Vec<char> = "ABC".chars().collect();
+        assert_eq!(damerau_levenshtein_distance(&source, &target), 2);
+    }
+
+    #[test]
+    fn agrees_with_levenshtein_without_transpositions() {
+        let source: Vec<u64> = (1..50).collect();
+        let target: Vec<u64> = (4..40).collect();
+        assert_eq!(damerau_levenshtein_distance(&source, &target), 13);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<char> = vec![];
+        let target: Vec<char> = "sitting".chars().collect();
+        assert_eq!(damerau_levenshtein_distance(&empty, &empty), 0);
+        assert_eq!(damerau_levenshtein_distance(&empty, &target), 7);
+        assert_eq!(damerau_levenshtein_distance(&target, &empty), 7);
+    }
+
+    #[test]
+    fn computes_similarity() {
+        let a: Vec<char> = "ab".chars().collect();
+        let b: Vec<char> = "ba".chars().collect();
+        let empty: Vec<char> = vec![];
+        assert_eq!(damerau_levenshtein_similarity(&a, &b), 0.5);
+        assert_eq!(damerau_levenshtein_similarity(&a, &a), 1.0);
+        assert_eq!(damerau_levenshtein_similarity(&empty, &empty), 1.0);
+    }
+}
+
+/// # [Hamming Distance](https://en.wikipedia.org/wiki/Hamming_distance)
+///
+/// Counts the positions at which two sequences of the same length differ, or returns `None` if
+/// their lengths differ.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "karolin".chars().collect();
+/// let b: Vec<char> = "kathrin".chars().collect();
+/// assert_eq!(match_::hamming_distance(&a, &b), Some(3));
+/// assert_eq!(match_::hamming_distance(&a, &b[1..]), None);
+/// ```
+pub fn hamming_distance<T: PartialEq>(a: &[T], b: &[T]) -> Option<u64> {
+    if a.len() != b.len() {
+        return None;
+    }
+    Some(a.iter().zip(b).filter(|&(x, y)| x != y).count() as u64)
+}
+
+#[cfg(test)]
+mod hamming_distance_tests {
+    use super::*;
+
+    #[test]
+    fn receives_equal_lengths() {
+        assert_eq!(
+            hamming_distance(&[1, 0, 1, 1, 1, 0, 1], &[1, 0, 0, 1, 0, 0, 1]),
+            Some(2)
+        );
+        assert_eq!(hamming_distance(&[1, 2, 3], &[1, 2, 3]), Some(0));
+        assert_eq!(hamming_distance::<u8>(&[], &[]), Some(0));
+    }
+
+    #[test]
+    fn receives_different_lengths() {
+        assert_eq!(hamming_distance(&[1, 2, 3], &[1, 2]), None);
+        assert_eq!(hamming_distance(&[], &[1]), None);
+    }
+}
+
+/// # [Jaro Similarity](https://en.wikipedia.org/wiki/Jaro%E2%80%93Winkler_distance#Jaro_similarity)
+///
+/// Calculates a similarity score in [0, 1] between two sequences from their m matching items
+/// and t transpositions, as `(m / |a| + m / |b| + (m - t) / m) / 3`. Two items match if they are
+/// equal and no farther apart than `max(|a|, |b|) / 2 - 1`, and each item matches at most once;
+/// t is half the number of matching items that are out of order. Two empty sequences are
+/// identical, so their similarity is 1.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "FAREMVIEL".chars().collect();
+/// let b: Vec<char> = "FARMVILLE".chars().collect();
+/// assert!((match_::jaro_similarity(&a, &b) - 0.8842592592592592).abs() < 1e-12);
+/// ```
+pub fn jaro_similarity<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let window = (cmp::max(a.len(), b.len()) / 2).saturating_sub(1);
+
+    let mut b_matched = vec![false; b.len()];
+    // The matched items of a, in order.
+    let mut a_matches = Vec::new();
+    for (i, x) in a.iter().enumerate() {
+        let low = i.saturating_sub(window);
+        let high = cmp::min(i + window + 1, b.len());
+        if let Some(j) = (low..high).find(|&j| !b_matched[j] && b[j] == *x) {
+            b_matched[j] = true;
+            a_matches.push(x);
+        }
+    }
+    let m = a_matches.len();
+    if m == 0 {
+        return 0.0;
+    }
+
+    let b_matches = b.iter().zip(&b_matched).filter(|&(_, &matched)| matched);
+    let half_transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|&(&x, (y, _))| x != y)
+        .count();
+    let (m, t) = (m as f64, half_transpositions as f64 / 2.0);
+    (m / a.len() as f64 + m / b.len() as f64 + (m - t) / m) / 3.0
+}
+
+#[cfg(test)]
+mod jaro_similarity_tests {
+    use super::*;
+
+    fn jaro(a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        jaro_similarity(&a, &b)
+    }
+
+    #[test]
+    fn receives_known_pairs() {
+        assert!((jaro("MARTHA", "MARHTA") - 0.9444444444444445).abs() < 1e-12);
+        assert!((jaro("DIXON", "DICKSONX") - 0.7666666666666666).abs() < 1e-12);
+        assert!((jaro("CRATE", "TRACE") - 0.7333333333333334).abs() < 1e-12);
+    }
+
+    #[test]
+    fn counts_half_transpositions() {
+        // a, b and c are matched out of order three times, so t = 1.5.
+        assert!((jaro("abcxyz", "bcaxyz") - (2.0 + 4.5 / 6.0) / 3.0).abs() < 1e-12);
+        assert_eq!(jaro("abcxyz", "bcaxyz"), jaro("bcaxyz", "abcxyz"));
+    }
+
+    #[test]
+    fn receives_identical_and_disjoint_sequences() {
+        assert_eq!(jaro("abc", "abc"), 1.0);
+        assert_eq!(jaro("abc", "xyz"), 0.0);
+        assert_eq!(jaro("", ""), 1.0);
+        assert_eq!(jaro("abc", ""), 0.0);
+    }
+
+    #[test]
+    fn is_symmetric() {
+        for &(a, b) in &[("MARTHA", "MARHTA"), ("DIXON", "DICKSONX"), ("ab", "ba")] {
+            assert_eq!(jaro(a, b), jaro(b, a));
+        }
+    }
+}
+
+/// # [N-gram Similarity](https://en.wikipedia.org/wiki/S%C3%B8rensen%E2%80%93Dice_coefficient)
+///
+/// Calculates the Sørensen–Dice coefficient `2 * |A ∩ B| / (|A| + |B|)` between the multisets
+/// A and B of n-grams (windows of length n) of two sequences. A repeated n-gram counts as many
+/// times as it occurs in both sequences.
+///
+/// A sequence shorter than n has no n-grams; in that case, the similarity is 1 if both
+/// sequences are equal, and 0 otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "night".chars().collect();
+/// let b: Vec<char> = "nacht".chars().collect();
+/// assert_eq!(match_::ngram_similarity(&a, &b, 2), 0.25);
+/// ```
+///
+/// # Panics
+///
+/// * n is 0
+pub fn ngram_similarity<T: Eq + Hash>(a: &[T], b: &[T], n: usize) -> f64 {
+    if n == 0 {
+        panic!("n should be larger than 0");
+    }
+    if a.len() < n || b.len() < n {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    // Count the n-grams of a, then take away those shared with b.
+    let mut counts: HashMap<&[T], usize> = HashMap::new();
+    for ngram in a.windows(n) {
+        *counts.entry(ngram).or_insert(0) += 1;
+    }
+    let mut intersection = 0;
+    for ngram in b.windows(n) {
+        if let Some(count) = counts.get_mut(ngram) {
+            if *count > 0 {
+                *count -= 1;
+                intersection += 1;
+            }
+        }
+    }
+
+    let total = (a.len() - n + 1) + (b.len() - n + 1);
+    2.0 * intersection as f64 / total as f64
+}
+
+/// # [Sørensen–Dice Coefficient](https://en.wikipedia.org/wiki/S%C3%B8rensen%E2%80%93Dice_coefficient)
+///
+/// Calculates the n-gram similarity of two sequences using bigrams (n = 2).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "night".chars().collect();
+/// let b: Vec<char> = "nacht".chars().collect();
+/// assert_eq!(match_::dice_coefficient(&a, &b), 0.25);
+/// ```
+pub fn dice_coefficient<T: Eq + Hash>(a: &[T], b: &[T]) -> f64 {
+    ngram_similarity(a, b, 2)
+}
+
+#[cfg(test)]
+mod ngram_similarity_tests {
+    use super::*;
+
+    #[test]
+    fn receives_identical_sequences() {
+        let a: Vec<char> = "hello".chars().collect();
+        assert_eq!(ngram_similarity(&a, &a, 3), 1.0);
+        assert_eq!(dice_coefficient(&a, &a), 1.0);
+    }
+
+    #[test]
+    fn receives_disjoint_sequences() {
+        let a: Vec<char> = "abcd".chars().collect();
+        let b: Vec<char> = "wxyz".chars().collect();
+        assert_eq!(dice_coefficient(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn receives_repeated_ngrams() {
+        // a has "aa" three times, b only once.
+        let a: Vec<char> = "aaaa".chars().collect();
+        let b: Vec<char> = "aab".chars().collect();
+        assert_eq!(dice_coefficient(&a, &b), 2.0 * 1.0 / 5.0);
+    }
+
+    #[test]
+    fn receives_unigrams() {
+        // Symbol frequencies: a={1:2, 2:1}, b={1:1, 2:1, 3:1}; overlap is {1, 2}.
+        let a = [1, 1, 2];
+        let b = [3, 2, 1];
+        assert_eq!(ngram_similarity(&a, &b, 1), 2.0 * 2.0 / 6.0);
+    }
+
+    #[test]
+    fn receives_sequences_shorter_than_n() {
+        let a: Vec<char> = "ab".chars().collect();
+        let b: Vec<char> = "abc".chars().collect();
+        assert_eq!(ngram_similarity(&a, &a, 3), 1.0);
+        assert_eq!(ngram_similarity(&a, &b, 3), 0.0);
+        let empty: Vec<char> = vec![];
+        assert_eq!(dice_coefficient(&empty, &empty), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "n should be larger than 0")]
+    fn receives_zero_n() {
+        ngram_similarity(&[1, 2], &[1, 2], 0);
+    }
+}
+
+/// # [Gestalt Pattern Matching](https://en.wikipedia.org/wiki/Gestalt_pattern_matching)
+///
+/// Calculates the Ratcliff/Obershelp similarity score in [0, 1] between two sequences, defined as
+/// `2 * matches / (a.len() + b.len())`. The matches are counted by finding the longest common
+/// substring, then recursively doing the same on the items to its left and to its right.
+/// This is the algorithm behind Python's `difflib.SequenceMatcher`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "WIKIMEDIA".chars().collect();
+/// let b: Vec<char> = "WIKIMANIA".chars().collect();
+/// // "WIKIM" and "IA" match: 2 * 7 / 18
+/// assert_eq!(match_::gestalt_similarity(&a, &b), 14.0 / 18.0);
+/// ```
+pub fn gestalt_similarity<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 1.0; // two empty sequences are identical
+    }
+    2.0 * gestalt_matches(a, b) as f64 / total as f64
+}
+
+/// Counts the matching items of two sequences according to the Ratcliff/Obershelp algorithm.
+fn gestalt_matches<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let (start_a, start_b, length) = longest_common_substring(a, b);
+    if length == 0 {
+        return 0;
+    }
+    length
+        + gestalt_matches(&a[..start_a], &b[..start_b]) // left side
+        + gestalt_matches(&a[start_a + length..], &b[start_b + length..]) // right side
+}
+
+#[cfg(test)]
+mod gestalt_similarity_tests {
+    use super::*;
+
+    #[test]
+    fn receives_identical_sequences() {
+        let a: Vec<char> = "WIKIMEDIA".chars().collect();
+        assert_eq!(gestalt_similarity(&a, &a), 1.0);
+    }
+
+    #[test]
+    fn receives_different_sequences() {
+        let a: Vec<char> = "abcdef".chars().collect();
+        let b: Vec<char> = "uvwxyz".chars().collect();
+        assert_eq!(gestalt_similarity(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn receives_wikimedia_and_wikimania() {
+        let a: Vec<char> = "WIKIMEDIA".chars().collect();
+        let b: Vec<char> = "WIKIMANIA".chars().collect();
+        assert!((gestalt_similarity(&a, &b) - 0.7777777777777778).abs() < 1e-12);
+    }
+
+    #[test]
+    fn agrees_with_python_difflib() {
+        let a: Vec<char> = "GESTALT PATTERN MATCHING".chars().collect();
+        let b: Vec<char> = "GESTALT PRACTICE".chars().collect();
+        assert!((gestalt_similarity(&a, &b) - 0.6).abs() < 1e-12);
+
+        let a: Vec<char> = "abcd".chars().collect();
+        let b: Vec<char> = "bcda".chars().collect();
+        assert_eq!(gestalt_similarity(&a, &b), 0.75);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<u8> = vec![];
+        assert_eq!(gestalt_similarity(&empty, &empty), 1.0);
+        assert_eq!(gestalt_similarity(&empty, &[1, 2]), 0.0);
+    }
+}
+
+/// # Token Sort Similarity
+///
+/// Calculates a similarity score in [0, 1] between two sequences of tokens that ignores the
+/// order of the tokens: both token lists are sorted, joined with a separator, and compared with
+/// `levenshtein_similarity`. The separator matches only another separator, so it counts as one
+/// item like the space between words. This is the `token_sort_ratio` of Python's fuzzywuzzy.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<Vec<char>> = vec!["smith".chars().collect(), "john".chars().collect()];
+/// let b: Vec<Vec<char>> = vec!["john".chars().collect(), "smith".chars().collect()];
+/// assert_eq!(match_::token_sort_similarity(&a, &b), 1.0);
+/// ```
+pub fn token_sort_similarity<T: Ord>(a: &[Vec<T>], b: &[Vec<T>]) -> f64 {
+    let mut a: Vec<&Vec<T>> = a.iter().collect();
+    let mut b: Vec<&Vec<T>> = b.iter().collect();
+    a.sort();
+    b.sort();
+    levenshtein_similarity(&join_tokens(&a), &join_tokens(&b))
+}
+
+/// # Token Set Similarity
+///
+/// Calculates a similarity score in [0, 1] between two sequences of tokens that ignores the
+/// order and the repetition of the tokens, and scores 1 when the tokens of one sequence are a
+/// subset of the other's. The distinct tokens are split into the sorted intersection, the sorted
+/// intersection followed by the sorted tokens only in a, and the same for b; the score is the
+/// best `levenshtein_similarity` among the three pairs, joined as in `token_sort_similarity`.
+/// This is the `token_set_ratio` of Python's fuzzywuzzy. Two empty sequences are identical, but
+/// an empty sequence shares nothing with a non-empty one, so their similarity is 0.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<Vec<char>> = vec!["new".chars().collect(), "york".chars().collect()];
+/// let b: Vec<Vec<char>> = vec![
+///     "new".chars().collect(),
+///     "york".chars().collect(),
+///     "mets".chars().collect(),
+/// ];
+/// assert_eq!(match_::token_set_similarity(&a, &b), 1.0);
+/// ```
+pub fn token_set_similarity<T: Ord>(a: &[Vec<T>], b: &[Vec<T>]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return if a.is_empty() && b.is_empty() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let mut a: Vec<&Vec<T>> = a.iter().collect();
+    let mut b: Vec<&Vec<T>> = b.iter().collect();
+    a.sort();
+    a.dedup();
+    b.sort();
+    b.dedup();
+
+    let intersection: Vec<&Vec<T>> = a
+        .iter()
+        .filter(|token| b.binary_search(token).is_ok())
+        .cloned()
+        .collect();
+    let mut combined_a = intersection.clone();
+    combined_a.extend(a.iter().filter(|token| b.binary_search(token).is_err()));
+    let mut combined_b = intersection.clone();
+    combined_b.extend(b.iter().filter(|token| a.binary_search(token).is_err()));
+    let (combined_a, combined_b) = (join_tokens(&combined_a), join_tokens(&combined_b));
+    let intersection = join_tokens(&intersection);
+
+    levenshtein_similarity(&intersection, &combined_a)
+        .max(levenshtein_similarity(&intersection, &combined_b))
+        .max(levenshtein_similarity(&combined_a, &combined_b))
+}
+
+/// Flattens tokens into one sequence, with `None` separating consecutive tokens.
+fn join_tokens<'a, T>(tokens: &[&'a Vec<T>]) -> Vec<Option<&'a T>> {
+    let mut joined = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            joined.push(None);
+        }
+        joined.extend(token.iter().map(Some));
+    }
+    joined
+}
+
+#[cfg(test)]
+mod token_similarity_tests {
+    use super::*;
+
+    fn tokens(s: &str) -> Vec<Vec<char>> {
+        s.split_whitespace().map(|t| t.chars().collect()).collect()
+    }
+
+    #[test]
+    fn ignores_token_order() {
+        let a = tokens("fuzzy wuzzy was a bear");
+        let b = tokens("wuzzy fuzzy was a bear");
+        assert_eq!(token_sort_similarity(&a, &b), 1.0);
+        assert_eq!(token_set_similarity(&a, &b), 1.0);
+        assert!(levenshtein_similarity(&a, &b) < 1.0);
+    }
+
+    #[test]
+    fn separates_tokens() {
+        // Both flatten to "abc" without a separator.
+        let a = tokens("ab c");
+        let b = tokens("a bc");
+        assert_eq!(token_sort_similarity(&a, &b), 0.5);
+    }
+
+    #[test]
+    fn scores_subsets_fully_under_token_set() {
+        let a = tokens("new york mets");
+        let b = tokens("new york mets vs atlanta braves");
+        assert_eq!(token_set_similarity(&a, &b), 1.0);
+        assert!(token_sort_similarity(&a, &b) < 0.5);
+    }
+
+    #[test]
+    fn receives_duplicate_tokens() {
+        let a = tokens("new new york york");
+        let b = tokens("york new");
+        assert_eq!(token_set_similarity(&a, &b), 1.0);
+        // token_sort keeps duplicates: "new new york york" vs "new york" is 9 deletions in 17.
+        assert_eq!(token_sort_similarity(&a, &b), 1.0 - 9.0 / 17.0);
+    }
+
+    #[test]
+    fn receives_partially_overlapping_tokens() {
+        // intersection "a", "a b", "a c": the pairs score 1/3, 1/3 and 2/3.
+        let a = tokens("a b");
+        let b = tokens("c a");
+        assert_eq!(token_set_similarity(&a, &b), 1.0 - 1.0 / 3.0);
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<Vec<char>> = vec![];
+        let a = tokens("a b");
+        assert_eq!(token_sort_similarity(&empty, &empty), 1.0);
+        assert_eq!(token_set_similarity(&empty, &empty), 1.0);
+        assert_eq!(token_sort_similarity(&empty, &a), 0.0);
+        assert_eq!(token_set_similarity(&a, &empty), 0.0);
+    }
+}
+
+/// # [Soundex](https://en.wikipedia.org/wiki/Soundex)
+///
+/// Encodes a word into its American Soundex code: the first letter followed by three digits that
+/// represent the following consonants. Adjacent letters with the same digit (also when separated
+/// by 'H' or 'W') are coded once, vowels are dropped, and the code is padded with zeros.
+/// Non-ASCII-alphabetic characters are ignored; a word without any letter has an empty code.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::soundex("Robert"), "R163");
+/// assert_eq!(match_::soundex("Ashcraft"), "A261");
+/// ```
+pub fn soundex(word: &str) -> String {
+    let mut letters = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase());
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    let mut last_digit = soundex_digit(first);
+    for letter in letters {
+        if code.len() == 4 {
+            break;
+        }
+        // 'H' and 'W' do not separate letters with the same digit, while vowels do.
+        if letter == 'H' || letter == 'W' {
+            continue;
+        }
+        let digit = soundex_digit(letter);
+        if digit != '0' && digit != last_digit {
+            code.push(digit);
+        }
+        last_digit = digit;
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// Returns the Soundex digit of an uppercase ASCII letter ('0' for vowels, 'H', 'W', and 'Y').
+fn soundex_digit(letter: char) -> char {
+    match letter {
+        'B' | 'F' | 'P' | 'V' => '1',
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+        'D' | 'T' => '3',
+        'L' => '4',
+        'M' | 'N' => '5',
+        'R' => '6',
+        _ => '0',
+    }
+}
+
+/// Checks whether two words have the same Soundex code.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert!(match_::soundex_similar("Robert", "Rupert"));
+/// assert!(!match_::soundex_similar("Robert", "Rubin"));
+/// ```
+pub fn soundex_similar(a: &str, b: &str) -> bool {
+    soundex(a) == soundex(b)
+}
+
+#[cfg(test)]
+mod soundex_tests {
+    use super::*;
+
+    #[test]
+    fn receives_similar_names() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        assert!(soundex_similar("Robert", "Rupert"));
+    }
+
+    #[test]
+    fn receives_h_and_w_between_same_digits() {
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Ashcroft"), "A261");
+        assert_eq!(soundex("Burroughs"), "B620");
+    }
+
+    #[test]
+    fn receives_vowel_between_same_digits() {
+        assert_eq!(soundex("Tymczak"), "T522");
+    }
+
+    #[test]
+    fn receives_first_letter_with_same_digit() {
+        assert_eq!(soundex("Pfister"), "P236");
+    }
+
+    #[test]
+    fn receives_short_names() {
+        assert_eq!(soundex("Lee"), "L000");
+        assert_eq!(soundex("Ng"), "N200");
+        assert_eq!(soundex("A"), "A000");
+    }
+
+    #[test]
+    fn receives_lowercase_and_non_alphabetic_characters() {
+        assert_eq!(soundex("o'hara"), "O600");
+        assert_eq!(soundex(" robert "), "R163");
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("123"), "");
+    }
+}
+
+/// # [Metaphone](https://en.wikipedia.org/wiki/Metaphone)
+///
+/// Encodes a word into its original Metaphone code (Lawrence Philips, 1990), which represents
+/// its English pronunciation with the consonant sounds "BFHJKLMNPRSTWXY0", where "X" is "sh" and
+/// "0" is "th". Unlike Soundex, letters are coded by their context ("PH" is "F", "C" before "I"
+/// is "S"), the code is not truncated, and vowels are only kept as the first letter ("A").
+/// Non-ASCII-alphabetic characters are ignored; a word without any letter has an empty code.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::metaphone("Thompson"), "0MPSN");
+/// assert_eq!(match_::metaphone("Knight"), "NT");
+/// assert_eq!(match_::metaphone("Philip"), match_::metaphone("Filip"));
+/// ```
+pub fn metaphone(word: &str) -> String {
+    let mut letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    // Adjacent duplicates sound once, except for "CC" as in "accident".
+    letters.dedup_by(|next, letter| next == letter && *letter != 'C');
+    match (letters.first(), letters.get(1)) {
+        (Some('A'), Some('E'))
+        | (Some('G'), Some('N'))
+        | (Some('K'), Some('N'))
+        | (Some('P'), Some('N'))
+        | (Some('W'), Some('R')) => {
+            letters.remove(0);
+        }
+        (Some('W'), Some('H')) => {
+            letters.remove(1);
+        }
+        (Some('X'), _) => letters[0] = 'S',
+        _ => {}
+    }
+
+    let at = |i: usize| letters.get(i).cloned().unwrap_or(' ');
+    let is_vowel = |c: char| "AEIOU".contains(c);
+    let mut code = String::with_capacity(letters.len());
+    let mut i = 0;
+    while i < letters.len() {
+        let (previous, letter, next) = (if i > 0 { at(i - 1) } else { ' ' }, at(i), at(i + 1));
+        let after_next = at(i + 2);
+        match letter {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(letter);
+                }
+            }
+            // "MB" at the end, as in "dumb"
+            'B' if previous == 'M' && i + 1 == letters.len() => {}
+            'C' => {
+                if next == 'I' && after_next == 'A' {
+                    code.push('X');
+                } else if next == 'H' {
+                    code.push(if previous == 'S' { 'K' } else { 'X' });
+                    i += 1;
+                } else if "IEY".contains(next) {
+                    if previous != 'S' {
+                        code.push('S');
+                    }
+                } else if next != 'K' {
+                    code.push('K');
+                }
+            }
+            'D' => {
+                if next == 'G' && "EIY".contains(after_next) {
+                    code.push('J');
+                    i += 1;
+                } else {
+                    code.push('T');
+                }
+            }
+            'G' => {
+                let silent_gh = next == 'H' && i + 2 < letters.len() && !is_vowel(after_next);
+                let silent_gn = next == 'N'
+                    && (i + 2 == letters.len()
+                        || (i + 4 == letters.len() && after_next == 'E' && at(i + 3) == 'D'));
+                if !silent_gh && !silent_gn {
+                    code.push(if "IEY".contains(next) { 'J' } else { 'K' });
+                }
+            }
+            'H' => {
+                // The "H" of "CH", "SH", "PH", "TH" and "GH" is coded with its first letter.
+                let digraph = "CSPTG".contains(previous);
+                if !digraph && (!is_vowel(previous) || is_vowel(next)) {
+                    code.push('H');
+                }
+            }
+            'K' if previous == 'C' => code.push('K'),
+            'P' => code.push(if next == 'H' { 'F' } else { 'P' }),
+            'Q' => code.push('K'),
+            'S' => {
+                if next == 'H' || (next == 'I' && "AO".contains(after_next)) {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            'T' => {
+                if next == 'I' && "AO".contains(after_next) {
+                    code.push('X');
+                } else if next == 'H' {
+                    code.push('0');
+                } else if !(next == 'C' && after_next == 'H') {
+                    code.push('T');
+                }
+            }
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    code.push(letter);
+                }
+            }
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(letter),
+        }
+        i += 1;
+    }
+    code
+}
+
+/// Checks whether two words sound alike, that is, whether they have the same Metaphone code or
+/// the same Soundex code. Soundex groups more spellings together while Metaphone tells more of
+/// them apart, so either agreeing is taken as a match. Words without any letter match nothing.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert!(match_::phonetic_match("Stephen", "Steven"));
+/// assert!(match_::phonetic_match("Catherine", "Kathryn"));
+/// assert!(!match_::phonetic_match("Robert", "Alice"));
+/// ```
+pub fn phonetic_match(a: &str, b: &str) -> bool {
+    let (metaphone_a, metaphone_b) = (metaphone(a), metaphone(b));
+    !metaphone_a.is_empty() && (metaphone_a == metaphone_b || soundex_similar(a, b))
+}
+
+#[cfg(test)]
+mod metaphone_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_reference_words() {
+        let words = [
+            ("Thompson", "0MPSN"),
+            ("Smith", "SM0"),
+            ("Schmidt", "SKMTT"),
+            ("Knight", "NT"),
+            ("Wright", "RT"),
+            ("Philip", "FLP"),
+            ("Xavier", "SFR"),
+            ("Aubrey", "ABR"),
+            ("Caesar", "KSR"),
+            ("Dodgy", "TJ"),
+            ("Thumb", "0M"),
+            ("Nation", "NXN"),
+            ("Science", "SNS"),
+            ("Whistle", "WSTL"),
+            ("Accident", "AKSTNT"),
+            ("Sign", "SN"),
+            ("Signed", "SNT"),
+        ];
+        for &(word, code) in words.iter() {
+            assert_eq!(metaphone(word), code, "{}", word);
+        }
+    }
+
+    #[test]
+    fn receives_lowercase_and_non_alphabetic_characters() {
+        assert_eq!(metaphone("o'neil"), metaphone("ONeil"));
+        assert_eq!(metaphone(" smith "), "SM0");
+        assert_eq!(metaphone(""), "");
+        assert_eq!(metaphone("123 !"), "");
+        assert_eq!(metaphone("Müller"), metaphone("Mller"));
+    }
+
+    #[test]
+    fn matches_phonetically() {
+        assert!(phonetic_match("Smith", "Smyth"));
+        assert!(phonetic_match("Robert", "Rupert"));
+        assert!(phonetic_match("Knight", "Night"));
+        assert!(!phonetic_match("Smith", "Jones"));
+        assert!(!phonetic_match("", ""));
+        assert!(!phonetic_match("42", "42"));
+    }
+
+    #[test]
+    fn codes_random_words() {
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let word: String = (0..rng.gen_range(0, 12))
+                .map(|_| rng.gen_range(b' ', b'~' + 1) as char)
+                .collect();
+            let letters = word.chars().filter(|c| c.is_ascii_alphabetic()).count();
+            let code = metaphone(&word);
+            assert!(code.chars().all(|c| "ABFHJKLMNPRSTWXY0AEIOU".contains(c)));
+            assert!(code.chars().skip(1).all(|c| !"AEIOU".contains(c)));
+            assert!(code.len() <= 2 * letters);
+
+            let code = soundex(&word);
+            assert_eq!(code.len(), if letters == 0 { 0 } else { 4 });
+            assert!(code.chars().take(1).all(|c| c.is_ascii_uppercase()));
+            assert!(code.chars().skip(1).all(|c| c.is_ascii_digit()));
+        }
+    }
+}
+
+/// # [Longest Common Substring](https://en.wikipedia.org/wiki/Longest_common_substring_problem)
+///
+/// Finds the longest contiguous run of items shared by two sequences, returned as
+/// (start in a, start in b, length). Ties prefer the earliest start in a, then the earliest
+/// start in b. Disjoint sequences return a length of 0.
+///
+/// Only one row of the lengths matrix is kept, so it uses O(min(m, n)) memory.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a: Vec<char> = "xabcdey".chars().collect();
+/// let b: Vec<char> = "abcdz".chars().collect();
+/// assert_eq!(match_::longest_common_substring(&a, &b), (1, 0, 4));
+/// ```
+pub fn longest_common_substring<T: PartialEq>(a: &[T], b: &[T]) -> (usize, usize, usize) {
+    // Keep the row as long as the shorter sequence.
+    let is_swapped = a.len() < b.len();
+    let (outer, inner) = if is_swapped { (b, a) } else { (a, b) };
+
+    // lengths[j+1] holds the length of the common suffix of outer[..=i] and inner[..=j];
+    // inner is scanned backwards so that lengths[j] still belongs to the previous row.
+    let mut lengths = vec![0usize; inner.len() + 1];
+    let mut longest = (0, 0, 0);
+    for (i, x) in outer.iter().enumerate() {
+        for (j, y) in inner.iter().enumerate().rev() {
+            if x != y {
+                lengths[j + 1] = 0;
+                continue;
+            }
+
+            let length = lengths[j] + 1;
+            lengths[j + 1] = length;
+            let (start_outer, start_inner) = (i + 1 - length, j + 1 - length);
+            let candidate = if is_swapped {
+                (start_inner, start_outer, length)
+            } else {
+                (start_outer, start_inner, length)
+            };
+            if length > longest.2
+                || (length == longest.2 && (candidate.0, candidate.1) < (longest.0, longest.1))
+            {
+                longest = candidate;
+            }
+        }
+    }
+    longest
+}
+
+/// Finds the longest contiguous run of items shared by two sequences, returned as a slice of a.
+/// See `longest_common_substring` for the tie rule.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let a = [1, 2, 3, 4, 5];
+/// let b = [9, 3, 4, 5, 9];
+/// assert_eq!(match_::longest_common_substring_slice(&a, &b), &[3, 4, 5]);
+/// ```
+pub fn longest_common_substring_slice<'a, T: PartialEq>(a: &'a [T], b: &[T]) -> &'a [T] {
+    let (start, _, length) = longest_common_substring(a, b);
+    &a[start..start + length]
+}
+
+#[cfg(test)]
+mod longest_common_substring_tests {
+    use super::*;
+
+    #[test]
+    fn receives_disjoint_sequences() {
+        let a: Vec<char> = "abc".chars().collect();
+        let b: Vec<char> = "xyz".chars().collect();
+        assert_eq!(longest_common_substring(&a, &b).2, 0);
+        assert!(longest_common_substring_slice(&a, &b).is_empty());
+    }
+
+    #[test]
+    fn receives_identical_sequences() {
+        let a: Vec<char> = "abcdef".chars().collect();
+        assert_eq!(longest_common_substring(&a, &a), (0, 0, 6));
+    }
+
+    #[test]
+    fn receives_equal_length_common_substrings() {
+        // "abc" and "xyz" are both common; "abc" starts earlier in a.
+        let a: Vec<char> = "-abc-xyz-".chars().collect();
+        let b: Vec<char> = "xyz+abc".chars().collect();
+        assert_eq!(longest_common_substring(&a, &b), (1, 4, 3));
+        assert_eq!(longest_common_substring_slice(&a, &b), &['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn receives_longer_b() {
+        let a = [5, 6, 7];
+        let b = [1, 2, 6, 7, 3, 5, 6, 7, 8];
+        assert_eq!(longest_common_substring(&a, &b), (0, 5, 3));
+        // The earliest start in b wins when the start in a is the same.
+        let a = [6, 7];
+        assert_eq!(longest_common_substring(&a, &b), (0, 2, 2));
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<u8> = vec![];
+        assert_eq!(longest_common_substring(&empty, &[1, 2]), (0, 0, 0));
+        assert_eq!(longest_common_substring(&[1, 2], &empty), (0, 0, 0));
+    }
+}
+
+/// A window of a sequence that approximately matches a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApproxMatch {
+    /// Position of the first item of the window
+    pub start: usize,
+    /// Position after the last item of the window
+    pub end: usize,
+    /// Edit distance between the window and the pattern
+    pub distance: u64,
+}
+
+/// # [Approximate String Matching](https://en.wikipedia.org/wiki/Approximate_string_matching)
+///
+/// Finds the window of the sequence with the minimum edit distance to the pattern. It uses the
+/// semi-global edit distance, where skipping items at the start and at the end of the sequence
+/// is free, and backtracks to recover the window boundaries. Ties prefer the earliest end.
+///
+/// An empty pattern matches at index 0, while an empty sequence has no window to match.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "the quick brown fox".chars().collect();
+/// let pattern: Vec<char> = "bruwn".chars().collect();
+/// let found = match_::best_match(&sequence, &pattern).unwrap();
+/// assert_eq!((found.start, found.end, found.distance), (10, 15, 1));
+/// ```
+pub fn best_match<T: PartialEq>(sequence: &[T], pattern: &[T]) -> Option<ApproxMatch> {
+    let (m, n) = (pattern.len() + 1, sequence.len() + 1);
+    if m == 1 {
+        return Some(ApproxMatch {
+            start: 0,
+            end: 0,
+            distance: 0,
+        }); // empty pattern matches everything
+    } else if n == 1 {
+        return None; // empty sequence has nothing to match
+    }
+
+    // distances[i][j] holds the edit distance between the first i pattern items
+    // and the best window ending before sequence[j].
+    let mut distances = vec![vec![0u64; n]; m];
+    for (i, row) in distances.iter_mut().enumerate().skip(1) {
+        row[0] = i as u64; // the window can start anywhere, so row 0 stays zero
+    }
+    for i in 1..m {
+        for j in 1..n {
+            let substitution_cost = if pattern[i - 1] == sequence[j - 1] {
+                0
+            } else {
+                1
+            };
+            distances[i][j] = cmp::min(
+                distances[i - 1][j] + 1, // pattern item is missing from the window
+                cmp::min(
+                    distances[i][j - 1] + 1,                     // extra item in the window
+                    distances[i - 1][j - 1] + substitution_cost, // substitution
+                ),
+            );
+        }
+    }
+
+    // The window can end anywhere too, so take the best cell of the last row.
+    let mut end = 0;
+    for j in 1..n {
+        if distances[m - 1][j] < distances[m - 1][end] {
+            end = j;
+        }
+    }
+
+    // Backtrack to the first row to find where the window starts.
+    let (mut i, mut j) = (m - 1, end);
+    while i > 0 {
+        if j > 0 {
+            let substitution_cost = if pattern[i - 1] == sequence[j - 1] {
+                0
+            } else {
+                1
+            };
+            if distances[i][j] == distances[i - 1][j - 1] + substitution_cost {
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if distances[i][j] == distances[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    Some(ApproxMatch {
+        start: j,
+        end,
+        distance: distances[m - 1][end],
+    })
+}
+
+/// Finds the window of the sequence with the minimum edit distance to the pattern,
+/// only if that distance does not exceed `max_distance`. See `best_match`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "the quick brown fox".chars().collect();
+/// let pattern: Vec<char> = "bruwn".chars().collect();
+/// assert!(match_::best_match_within(&sequence, &pattern, 1).is_some());
+/// assert!(match_::best_match_within(&sequence, &pattern, 0).is_none());
+/// ```
+pub fn best_match_within<T: PartialEq>(
+    sequence: &[T],
+    pattern: &[T],
+    max_distance: u64,
+) -> Option<ApproxMatch> {
+    best_match(sequence, pattern).and_then(|found| {
+        if found.distance <= max_distance {
+            Some(found)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod best_match_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_exact_occurrence() {
+        let sequence: Vec<char> = "hello, world".chars().collect();
+        let pattern: Vec<char> = "wor".chars().collect();
+        assert_eq!(
+            best_match(&sequence, &pattern),
+            Some(ApproxMatch {
+                start: 7,
+                end: 10,
+                distance: 0
+            })
+        );
+    }
+
+    #[test]
+    fn receives_approximate_occurrence() {
+        let sequence: Vec<char> = "the quick brown fox".chars().collect();
+        let pattern: Vec<char> = "quack".chars().collect();
+        assert_eq!(
+            best_match(&sequence, &pattern),
+            Some(ApproxMatch {
+                start: 4,
+                end: 9,
+                distance: 1
+            })
+        );
+    }
+
+    #[test]
+    fn receives_empty_sequences() {
+        let empty: Vec<char> = vec![];
+        let pattern: Vec<char> = "abc".chars().collect();
+        assert_eq!(best_match(&empty, &pattern), None);
+        assert_eq!(
+            best_match(&pattern, &empty),
+            Some(ApproxMatch {
+                start: 0,
+                end: 0,
+                distance: 0
+            })
+        );
+    }
+
+    #[test]
+    fn receives_max_distance() {
+        let sequence: Vec<char> = "the quick brown fox".chars().collect();
+        let pattern: Vec<char> = "qiuck".chars().collect();
+        assert_eq!(best_match_within(&sequence, &pattern, 1), None);
+        assert_eq!(
+            best_match_within(&sequence, &pattern, 2).map(|found| found.distance),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn locates_mutated_pattern_in_noise() {
+        let mut rng = test_util::rng();
+        for _ in 0..20 {
+            let mut sequence: Vec<u8> = (0..500).map(|_| rng.gen_range(0, 20)).collect();
+            let pattern: Vec<u8> = (0..30).map(|_| rng.gen_range(0, 20)).collect();
+
+            // Embed the pattern with 3 substitutions at a random offset.
+            let offset = rng.gen_range(0, 470);
+            sequence[offset..offset + 30].copy_from_slice(&pattern);
+            for _ in 0..3 {
+                let i = offset + rng.gen_range(0, 30);
+                sequence[i] = (sequence[i] + 1) % 20;
+            }
+
+            let found = best_match(&sequence, &pattern).unwrap();
+            assert!(found.distance <= 3);
+            assert!(found.start < offset + 30 && offset < found.end);
+        }
+    }
+}
+
+/// # [Distance Matrix](https://en.wikipedia.org/wiki/Distance_matrix)
+///
+/// Calculates the distances between every pair of items with the given metric, which can be any
+/// of the distances in this module. The metric is assumed to be symmetric and zero between equal
+/// items, so it is only called for pairs (i, j) with i < j, and only those distances are stored.
+///
+/// With the `parallel` feature, the rows are computed in parallel with
+///  [Rayon](https://github.com/rayon-rs/rayon).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let items: Vec<Vec<char>> = ["kitten", "sitting", "mitten"]
+///     .iter()
+///     .map(|s| s.chars().collect())
+///     .collect();
+/// let matrix = match_::distance_matrix(&items, match_::levenshtein_distance);
+/// assert_eq!(matrix.get(0, 1), 3);
+/// assert_eq!(matrix.get(2, 0), 1);
+/// assert_eq!(matrix.get(1, 1), 0);
+/// ```
+pub fn distance_matrix<T, F>(items: &[Vec<T>], metric: F) -> DistanceMatrix
+where
+    T: PartialEq + Sync,
+    F: Fn(&[T], &[T]) -> u64 + Sync,
+{
+    let n = items.len();
+    let metric = &metric;
+    let row = |i: usize| (i + 1..n).map(move |j| metric(&items[i], &items[j]));
+
+    #[cfg(feature = "parallel")]
+    let distances = (0..n).into_par_iter().flat_map_iter(row).collect();
+    #[cfg(not(feature = "parallel"))]
+    let distances = (0..n).flat_map(row).collect();
+
+    DistanceMatrix { n, distances }
+}
+
+/// A symmetric matrix of distances with a zero diagonal, stored in condensed form: only the
+/// distances of pairs (i, j) with i < j are kept, row by row, which halves the memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMatrix {
+    n: usize,
+    distances: Vec<u64>,
+}
+
+impl DistanceMatrix {
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns true if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the distance between items i and j, in any order.
+    ///
+    /// # Panics
+    ///
+    /// * i or j is out of bounds
+    pub fn get(&self, i: usize, j: usize) -> u64 {
+        if i >= self.n || j >= self.n {
+            panic!("indices should be less than the number of items");
+        }
+        match i.cmp(&j) {
+            cmp::Ordering::Less => self.distances[self.index(i, j)],
+            cmp::Ordering::Equal => 0,
+            cmp::Ordering::Greater => self.distances[self.index(j, i)],
+        }
+    }
+
+    /// Returns the condensed distances of pairs (i, j) with i < j, ordered by i then j.
+    pub fn condensed(&self) -> &[u64] {
+        &self.distances
+    }
+
+    /// Returns the position of pair (i, j) in the condensed distances.
+    ///
+    /// # Panics
+    ///
+    /// * i is not less than j
+    /// * j is out of bounds
+    pub fn index(&self, i: usize, j: usize) -> usize {
+        if i >= j || j >= self.n {
+            panic!("pair should satisfy i < j < number of items");
+        }
+        // Rows 0..i hold (n - 1) + (n - 2) + ... + (n - i) pairs.
+        i * (2 * self.n - i - 1) / 2 + (j - i - 1)
+    }
+
+    /// Returns the pair (i, j) at the given position in the condensed distances.
+    ///
+    /// # Panics
+    ///
+    /// * index is out of bounds
+    pub fn pair(&self, index: usize) -> (usize, usize) {
+        if index >= self.distances.len() {
+            panic!("index should be less than the number of pairs");
+        }
+        let (mut i, mut row_start) = (0, 0);
+        while row_start + (self.n - i - 1) <= index {
+            row_start += self.n - i - 1;
+            i += 1;
+        }
+        (i, i + 1 + index - row_start)
+    }
+}
+
+#[cfg(test)]
+mod distance_matrix_tests {
+    use super::*;
+
+    fn words() -> Vec<Vec<char>> {
+        ["kitten", "sitting", "mitten", "", "knitting", "kitten"]
+            .iter()
+            .map(|s| s.chars().collect())
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_direct_calls() {
+        let items = words();
+        let matrix = distance_matrix(&items, levenshtein_distance);
+        assert_eq!(matrix.len(), items.len());
+        assert_eq!(
+            matrix.condensed().len(),
+            items.len() * (items.len() - 1) / 2
+        );
+        for i in 0..items.len() {
+            assert_eq!(matrix.get(i, i), 0);
+            for j in 0..items.len() {
+                assert_eq!(matrix.get(i, j), matrix.get(j, i));
+                assert_eq!(matrix.get(i, j), levenshtein_distance(&items[i], &items[j]));
+            }
+        }
+    }
+
+    #[test]
+    fn receives_other_metrics() {
+        let items = words();
+        let matrix = distance_matrix(&items, osa_distance);
+        assert_eq!(matrix.get(4, 1), osa_distance(&items[4], &items[1]));
+        assert_eq!(matrix.get(0, 5), 0);
+    }
+
+    #[test]
+    fn round_trips_condensed_indices() {
+        for n in 0..20 {
+            let items: Vec<Vec<u8>> = (0..n).map(|i| vec![i]).collect();
+            let matrix = distance_matrix(&items, |a, b| (b[0] - a[0]) as u64);
+            let mut index = 0;
+            for i in 0..n as usize {
+                for j in i + 1..n as usize {
+                    assert_eq!(matrix.index(i, j), index);
+                    assert_eq!(matrix.pair(index), (i, j));
+                    assert_eq!(matrix.condensed()[index], (j - i) as u64);
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn receives_empty_items() {
+        let matrix = distance_matrix::<char, _>(&[], levenshtein_distance);
+        assert!(matrix.is_empty());
+        assert!(matrix.condensed().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "indices should be less than the number of items")]
+    fn receives_out_of_bounds_index() {
+        distance_matrix(&words(), levenshtein_distance).get(0, 6);
+    }
+}
+
+/// # [Manacher's Algorithm](https://en.wikipedia.org/wiki/Longest_palindromic_substring#Manacher's_algorithm)
+///
+/// Calculates the radius of the longest palindrome around every center of the sequence in O(n):
+///
+/// * odd[i] is the largest r such that sequence[i + 1 - r..i + r] is a palindrome centered at
+///   item i, so it is at least 1
+/// * even[i] is the largest r such that sequence[i - r..i + r] is a palindrome centered between
+///   items i - 1 and i, so even[0] is 0
+///
+/// Each radius starts from the one mirrored inside the rightmost palindrome found so far, so the
+/// right end of that palindrome only ever moves forward. The two kinds of centers are handled
+/// separately, which avoids interleaving the items with a separator that a generic T lacks.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abaab".chars().collect();
+/// let (odd, even) = match_::palindrome_radii(&sequence);
+/// assert_eq!(odd, vec![1, 2, 1, 1, 1]);
+/// assert_eq!(even, vec![0, 0, 0, 2, 0]);
+/// ```
+pub fn palindrome_radii<T: PartialEq>(sequence: &[T]) -> (Vec<usize>, Vec<usize>) {
+    let n = sequence.len();
+    let (mut odd, mut even) = (vec![0; n], vec![0; n]);
+
+    // [l, r) is the palindrome with the rightmost end found so far.
+    let (mut l, mut r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r {
+            cmp::min(odd[l + r - 1 - i], r - i)
+        } else {
+            1
+        };
+        while k <= i && i + k < n && sequence[i - k] == sequence[i + k] {
+            k += 1;
+        }
+        odd[i] = k;
+        if i + k > r {
+            l = i + 1 - k;
+            r = i + k;
+        }
+    }
+
+    let (mut l, mut r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r {
+            cmp::min(even[l + r - i], r - i)
+        } else {
+            0
+        };
+        while k < i && i + k < n && sequence[i - k - 1] == sequence[i + k] {
+            k += 1;
+        }
+        even[i] = k;
+        if i + k > r {
+            l = i - k;
+            r = i + k;
+        }
+    }
+    (odd, even)
+}
+
+/// # [Longest Palindromic Substring](https://en.wikipedia.org/wiki/Longest_palindromic_substring)
+///
+/// Finds the longest contiguous palindrome in the sequence with Manacher's algorithm in O(n),
+/// preferring the earliest one when several are equally long. The empty sequence gives 0..0.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "bananas".chars().collect();
+/// assert_eq!(match_::longest_palindrome(&sequence), 1..6);
+/// ```
+pub fn longest_palindrome<T: PartialEq>(sequence: &[T]) -> Range<usize> {
+    let (odd, even) = palindrome_radii(sequence);
+    let mut longest = 0..0;
+    for i in 0..sequence.len() {
+        for range in [i + 1 - odd[i]..i + odd[i], i - even[i]..i + even[i]] {
+            let is_longer = range.len() > longest.len()
+                || (range.len() == longest.len() && range.start < longest.start);
+            if is_longer {
+                longest = range;
+            }
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod palindrome_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn is_palindrome<T: PartialEq>(sequence: &[T]) -> bool {
+        sequence.iter().eq(sequence.iter().rev())
+    }
+
+    fn brute_force<T: PartialEq>(sequence: &[T]) -> Range<usize> {
+        let mut longest = 0..0;
+        for start in 0..sequence.len() {
+            for end in start + 1..=sequence.len() {
+                if end - start > longest.len() && is_palindrome(&sequence[start..end]) {
+                    longest = start..end;
+                }
+            }
+        }
+        longest
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let sequence = [7; 6];
+        assert_eq!(longest_palindrome(&sequence), 0..6);
+        let (odd, even) = palindrome_radii(&sequence);
+        assert_eq!(odd, vec![1, 2, 3, 3, 2, 1]);
+        assert_eq!(even, vec![0, 1, 2, 3, 2, 1]);
+    }
+
+    #[test]
+    fn receives_increasing_items() {
+        let sequence: Vec<u32> = (0..10).collect();
+        assert_eq!(longest_palindrome(&sequence), 0..1);
+    }
+
+    #[test]
+    fn finds_even_palindromes_at_ends() {
+        let sequence: Vec<char> = "abbacd".chars().collect();
+        assert_eq!(longest_palindrome(&sequence), 0..4);
+        let sequence: Vec<char> = "cdabba".chars().collect();
+        assert_eq!(longest_palindrome(&sequence), 2..6);
+    }
+
+    #[test]
+    fn prefers_earliest_palindrome() {
+        let sequence: Vec<char> = "abaxcdc".chars().collect();
+        assert_eq!(longest_palindrome(&sequence), 0..3);
+    }
+
+    #[test]
+    fn receives_empty_sequence() {
+        assert_eq!(longest_palindrome::<u8>(&[]), 0..0);
+        assert_eq!(palindrome_radii::<u8>(&[]), (vec![], vec![]));
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let len = rng.gen_range(0, 40);
+            let sequence: Vec<u8> = (0..len).map(|_| rng.gen_range(0, 3)).collect();
+            assert_eq!(longest_palindrome(&sequence), brute_force(&sequence));
+
+            let (odd, even) = palindrome_radii(&sequence);
+            for i in 0..sequence.len() {
+                assert!(is_palindrome(&sequence[i + 1 - odd[i]..i + odd[i]]));
+                assert!(
+                    i < odd[i] || i + odd[i] >= len || {
+                        sequence[i - odd[i]] != sequence[i + odd[i]]
+                    }
+                );
+                assert!(is_palindrome(&sequence[i - even[i]..i + even[i]]));
+                assert!(
+                    i == even[i] || i + even[i] >= len || {
+                        sequence[i - even[i] - 1] != sequence[i + even[i]]
+                    }
+                );
+            }
+        }
+    }
+}
+
+/// # [Suffix Array](https://en.wikipedia.org/wiki/Suffix_array)
+///
+/// Sorts all suffixes of a sequence once, so that every occurrence of any pattern can then be
+/// found with two binary searches over the suffixes in O(m log n).
+///
+/// The suffixes are sorted by prefix doubling in O(n log n): once they are ranked by their first
+/// k items, ranking them by their first 2k items only needs sorting the pairs of ranks at i and
+/// i + k, which two passes of counting sort do in O(n).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::SuffixArray;
+///
+/// let suffix_array = SuffixArray::new("banana".chars().collect());
+/// assert_eq!(suffix_array.suffixes(), &[5, 3, 1, 0, 4, 2]);
+/// assert_eq!(suffix_array.find(&['a', 'n']), Some(1));
+/// let mut found = suffix_array.find_all(&['a', 'n', 'a']).to_vec();
+/// found.sort();
+/// assert_eq!(found, vec![1, 3]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SuffixArray<T: Ord> {
+    sequence: Vec<T>,
+    suffixes: Vec<usize>,
+    // rank[i] is the position of the suffix at i in suffixes.
+    rank: Vec<usize>,
+}
+
+impl<T: Ord> SuffixArray<T> {
+    /// Builds the suffix array of the sequence.
+    pub fn new(sequence: Vec<T>) -> SuffixArray<T> {
+        let n = sequence.len();
+        let mut suffixes: Vec<usize> = (0..n).collect();
+        suffixes.sort_by(|&a, &b| sequence[a].cmp(&sequence[b]));
+
+        // rank[i] orders the suffix at i by its first k items; equal prefixes share a rank.
+        let mut rank = vec![0; n];
+        for w in 1..n {
+            let is_new = sequence[suffixes[w]] != sequence[suffixes[w - 1]];
+            rank[suffixes[w]] = rank[suffixes[w - 1]] + is_new as usize;
+        }
+
+        let mut k = 1;
+        let (mut order, mut counts, mut next_rank) = (vec![0; n], vec![0; n + 1], vec![0; n]);
+        while k < n && rank[suffixes[n - 1]] < n - 1 {
+            // Order by the rank at i + k: suffixes shorter than k + 1 come first, then the
+            // others follow the current order of the suffixes at i + k.
+            order.clear();
+            order.extend(n - k..n);
+            order.extend(suffixes.iter().filter(|&&j| j >= k).map(|&j| j - k));
+
+            // Stable counting sort by the rank at i.
+            counts.iter_mut().for_each(|count| *count = 0);
+            for &i in &order {
+                counts[rank[i] + 1] += 1;
+            }
+            for r in 1..=n {
+                counts[r] += counts[r - 1];
+            }
+            for &i in &order {
+                suffixes[counts[rank[i]]] = i;
+                counts[rank[i]] += 1;
+            }
+
+            let key = |i: usize| (rank[i], if i + k < n { rank[i + k] + 1 } else { 0 });
+            next_rank[suffixes[0]] = 0;
+            for w in 1..n {
+                let is_new = key(suffixes[w]) != key(suffixes[w - 1]);
+                next_rank[suffixes[w]] = next_rank[suffixes[w - 1]] + is_new as usize;
+            }
+            mem::swap(&mut rank, &mut next_rank);
+            k *= 2;
+        }
+        // Distinct suffixes end up with distinct ranks, so rank is the inverse of suffixes.
+        SuffixArray {
+            sequence,
+            suffixes,
+            rank,
+        }
+    }
+
+    /// Returns the sequence.
+    pub fn sequence(&self) -> &[T] {
+        &self.sequence
+    }
+
+    /// Returns the starting indices of the suffixes in lexicographic order.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// Returns the position of every suffix in the lexicographic order, the inverse of
+    /// `suffixes`.
+    pub fn rank(&self) -> &[usize] {
+        &self.rank
+    }
+
+    /// # [LCP Array](https://en.wikipedia.org/wiki/LCP_array)
+    ///
+    /// Calculates the length of the longest common prefix of every suffix and the one before it
+    /// in lexicographic order, so lcp[0] is 0, with Kasai's algorithm in O(n).
+    ///
+    /// Going through the suffixes from the longest, dropping the first item of a suffix drops at
+    /// most one item of its common prefix with the previous suffix, so the prefix is only ever
+    /// extended O(n) times overall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ult_algo::sequence::match_::SuffixArray;
+    ///
+    /// let suffix_array = SuffixArray::new("banana".chars().collect());
+    /// // a, ana, anana, banana, na, nana
+    /// assert_eq!(suffix_array.lcp(), vec![0, 1, 3, 0, 0, 2]);
+    /// ```
+    pub fn lcp(&self) -> Vec<usize> {
+        let n = self.sequence.len();
+        let mut lcp = vec![0; n];
+        let mut common = 0;
+        for i in 0..n {
+            if self.rank[i] == 0 {
+                common = 0;
+                continue;
+            }
+            let j = self.suffixes[self.rank[i] - 1];
+            while i + common < n
+                && j + common < n
+                && self.sequence[i + common] == self.sequence[j + common]
+            {
+                common += 1;
+            }
+            lcp[self.rank[i]] = common;
+            common = common.saturating_sub(1);
+        }
+        lcp
+    }
+
+    /// Returns the index of the first occurrence of the pattern, if any.
+    pub fn find(&self, pattern: &[T]) -> Option<usize> {
+        self.find_all(pattern).iter().cloned().min()
+    }
+
+    /// Returns the indices of every occurrence of the pattern, in the lexicographic order of
+    /// their suffixes rather than in ascending order. An empty pattern occurs at every index.
+    pub fn find_all(&self, pattern: &[T]) -> &[usize] {
+        let n = self.sequence.len();
+        let prefix = |i: usize| &self.sequence[i..cmp::min(i + pattern.len(), n)];
+        let start = self.suffixes.partition_point(|&i| prefix(i) < pattern);
+        let end = start + self.suffixes[start..].partition_point(|&i| prefix(i) == pattern);
+        &self.suffixes[start..end]
+    }
+}
+
+#[cfg(test)]
+mod suffix_array_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn brute_force<T: Ord>(sequence: &[T]) -> Vec<usize> {
+        let mut suffixes: Vec<usize> = (0..sequence.len()).collect();
+        suffixes.sort_by(|&a, &b| sequence[a..].cmp(&sequence[b..]));
+        suffixes
+    }
+
+    #[test]
+    fn receives_bytes() {
+        let sequence = b"mississippi".to_vec();
+        let suffix_array = SuffixArray::new(sequence.clone());
+        assert_eq!(suffix_array.suffixes(), &brute_force(&sequence)[..]);
+        assert_eq!(suffix_array.find(b"ssi"), Some(2));
+        assert_eq!(suffix_array.find(b"ssp"), None);
+        assert_eq!(suffix_array.find_all(b"i").len(), 4);
+    }
+
+    #[test]
+    fn receives_chars() {
+        let sequence: Vec<char> = "naïve naïveté".chars().collect();
+        let suffix_array = SuffixArray::new(sequence.clone());
+        assert_eq!(suffix_array.suffixes(), &brute_force(&sequence)[..]);
+        assert_eq!(suffix_array.find(&['ï', 'v', 'e', 't']), Some(8));
+    }
+
+    #[test]
+    fn receives_large_alphabets() {
+        let mut rng = test_util::rng();
+        let sequence: Vec<u32> = (0..2000).map(|_| rng.gen()).collect();
+        let suffix_array = SuffixArray::new(sequence.clone());
+        assert_eq!(suffix_array.suffixes(), &brute_force(&sequence)[..]);
+        assert_eq!(suffix_array.find(&sequence[1000..1010]), Some(1000));
+    }
+
+    #[test]
+    fn agrees_with_bitap_all() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let sequence: Vec<u8> = test_util::random_vec(&mut rng, 0..300, 0..3);
+            let suffix_array = SuffixArray::new(sequence.clone());
+            assert_eq!(suffix_array.suffixes(), &brute_force(&sequence)[..]);
+            for _ in 0..10 {
+                let pattern: Vec<u8> = test_util::random_vec(&mut rng, 1..6, 0..3);
+                let mut found = suffix_array.find_all(&pattern).to_vec();
+                found.sort();
+                assert_eq!(found, bitap_all(&sequence, &pattern).collect::<Vec<_>>());
+                assert_eq!(suffix_array.find(&pattern), bitap(&sequence, &pattern));
+            }
+        }
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let suffix_array = SuffixArray::new(vec![0; 100]);
+        let expected: Vec<usize> = (0..100).rev().collect();
+        assert_eq!(suffix_array.suffixes(), &expected[..]);
+        assert_eq!(suffix_array.find_all(&[0; 99]).len(), 2);
+    }
+
+    #[test]
+    fn computes_rank_as_inverse() {
+        let mut rng = test_util::rng();
+        let sequence: Vec<u8> = (0..300).map(|_| rng.gen_range(0, 2)).collect();
+        let suffix_array = SuffixArray::new(sequence);
+        for (position, &i) in suffix_array.suffixes().iter().enumerate() {
+            assert_eq!(suffix_array.rank()[i], position);
+        }
+    }
+
+    #[test]
+    fn agrees_with_brute_force_lcp() {
+        let mut rng = test_util::rng();
+        for _ in 0..50 {
+            let len = rng.gen_range(0, 400);
+            let sequence: Vec<u8> = (0..len).map(|_| rng.gen_range(0, 4)).collect();
+            let suffix_array = SuffixArray::new(sequence.clone());
+            let suffixes = suffix_array.suffixes();
+            let lcp = suffix_array.lcp();
+            assert_eq!(lcp.len(), len);
+            for i in 1..len {
+                let (a, b) = (&sequence[suffixes[i - 1]..], &sequence[suffixes[i]..]);
+                let common = a.iter().zip(b).take_while(|&(x, y)| x == y).count();
+                assert_eq!(lcp[i], common);
+            }
+        }
+    }
+
+    #[test]
+    fn computes_lcp_of_small_and_equal_sequences() {
+        assert_eq!(SuffixArray::new(vec!['x']).lcp(), vec![0]);
+        assert_eq!(SuffixArray::new(vec![7; 5]).lcp(), vec![0, 1, 2, 3, 4]);
+        assert!(SuffixArray::new(Vec::<u8>::new()).lcp().is_empty());
+    }
+
+    #[test]
+    fn receives_empty_sequence_and_pattern() {
+        let suffix_array = SuffixArray::new(Vec::<u8>::new());
+        assert!(suffix_array.suffixes().is_empty());
+        assert_eq!(suffix_array.find(&[1]), None);
+        assert_eq!(SuffixArray::new(vec![1, 2]).find_all(&[]).len(), 2);
+    }
+}
+
+/// # [Border Array](https://en.wikipedia.org/wiki/Knuth%E2%80%93Morris%E2%80%93Pratt_algorithm#%22Partial_match%22_table_(also_known_as_%22failure_function%22))
+///
+/// Calculates the length of the longest proper border, a prefix that is also a suffix, of every
+/// prefix of the sequence in O(n); this is the failure function of the Knuth–Morris–Pratt
+/// algorithm. border[i] belongs to the prefix sequence[..=i].
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abacaba".chars().collect();
+/// assert_eq!(match_::border_array(&sequence), vec![0, 0, 1, 0, 1, 2, 3]);
+/// ```
+pub fn border_array<T: PartialEq>(sequence: &[T]) -> Vec<usize> {
+    let mut border = vec![0; sequence.len()];
+    for i in 1..sequence.len() {
+        // Try to extend the borders of the previous prefix, from the longest.
+        let mut k = border[i - 1];
+        while k > 0 && sequence[i] != sequence[k] {
+            k = border[k - 1];
+        }
+        border[i] = if sequence[i] == sequence[k] { k + 1 } else { 0 };
+    }
+    border
+}
+
+/// # [Periods](https://en.wikipedia.org/wiki/Periodic_sequence)
+///
+/// Finds every period p < n of the sequence, such that sequence[i] == sequence[i + p] for all
+/// valid i, in ascending order and O(n). The periods are n minus the lengths of the borders of
+/// the sequence, and every border of the sequence is found by following the border array from
+/// the longest one.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abcabcab".chars().collect();
+/// assert_eq!(match_::periods(&sequence), vec![3, 6]);
+/// ```
+pub fn periods<T: PartialEq>(sequence: &[T]) -> Vec<usize> {
+    let n = sequence.len();
+    let border = border_array(sequence);
+    let mut periods = Vec::new();
+    let mut k = border.last().cloned().unwrap_or(0);
+    while k > 0 {
+        periods.push(n - k);
+        k = border[k - 1];
+    }
+    periods
+}
+
+/// Returns true if the smallest period of the sequence is at most half of its length, so that
+/// the sequence consists of at least two full repetitions and a prefix of another one.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abcabca".chars().collect();
+/// assert!(match_::is_periodic(&sequence));
+/// let sequence: Vec<char> = "abcab".chars().collect();
+/// assert!(!match_::is_periodic(&sequence));
+/// ```
+pub fn is_periodic<T: PartialEq>(sequence: &[T]) -> bool {
+    periods(sequence)
+        .first()
+        .is_some_and(|&p| p <= sequence.len() / 2)
+}
+
+/// # [Primitive Root](https://en.wikipedia.org/wiki/Primitive_word)
+///
+/// Finds the shortest prefix whose repetition forms the whole sequence. A primitive sequence,
+/// which is no repetition of a shorter one, is its own root.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let sequence: Vec<char> = "abab".chars().collect();
+/// assert_eq!(match_::primitive_root(&sequence), &['a', 'b']);
+/// let sequence: Vec<char> = "ababa".chars().collect();
+/// assert_eq!(match_::primitive_root(&sequence), &sequence[..]);
+/// ```
+pub fn primitive_root<T: PartialEq>(sequence: &[T]) -> &[T] {
+    // If the smallest period does not divide n, no other period does either.
+    match periods(sequence).first() {
+        Some(&p) if sequence.len().is_multiple_of(p) => &sequence[..p],
+        _ => sequence,
+    }
+}
+
+#[cfg(test)]
+mod period_tests {
+    use super::*;
+    use test_util;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    fn brute_force_periods<T: PartialEq>(sequence: &[T]) -> Vec<usize> {
+        (1..sequence.len())
+            .filter(|&p| (0..sequence.len() - p).all(|i| sequence[i] == sequence[i + p]))
+            .collect()
+    }
+
+    #[test]
+    fn receives_repeated_sequence() {
+        let sequence = chars("abcabcabc");
+        assert_eq!(periods(&sequence), vec![3, 6]);
+        assert!(is_periodic(&sequence));
+        assert_eq!(primitive_root(&sequence), &chars("abc")[..]);
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let sequence = chars("aaaa");
+        assert_eq!(periods(&sequence), vec![1, 2, 3]);
+        assert!(is_periodic(&sequence));
+        assert_eq!(primitive_root(&sequence), &['a']);
+    }
+
+    #[test]
+    fn receives_non_periodic_sequences() {
+        let sequence = chars("abcd");
+        assert!(periods(&sequence).is_empty());
+        assert!(!is_periodic(&sequence));
+        assert_eq!(primitive_root(&sequence), &sequence[..]);
+
+        let sequence = chars("aabaa");
+        assert_eq!(periods(&sequence), vec![3, 4]);
+        assert!(!is_periodic(&sequence));
+        assert_eq!(primitive_root(&sequence), &sequence[..]);
+    }
+
+    #[test]
+    fn receives_single_item_and_empty_sequence() {
+        assert!(periods(&[1]).is_empty());
+        assert!(!is_periodic(&[1]));
+        assert_eq!(primitive_root(&[1]), &[1]);
+        assert!(periods::<u8>(&[]).is_empty());
+        assert!(primitive_root::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            let sequence: Vec<u8> = test_util::random_vec(&mut rng, 0..30, 0..2);
+            let periods = periods(&sequence);
+            assert_eq!(periods, brute_force_periods(&sequence));
+            let root = primitive_root(&sequence);
+            assert!(root.is_empty() || sequence.chunks(root.len()).all(|chunk| chunk == root));
+        }
+    }
+}
+
+/// # [Wildcard Matching](https://en.wikipedia.org/wiki/Matching_wildcards)
+///
+/// Returns true if the whole text matches the pattern, where the `wildcard` item matches zero
+/// or more items, like `*`, and the `any` item matches exactly one item, like `?`. Every other
+/// pattern item matches an equal item. See `wildcard_match` for patterns of `WildcardToken`s,
+/// which can match the wildcard symbols themselves.
+///
+/// Dynamic programming decides whether every prefix of the pattern matches every prefix of the
+/// text, one row of text at a time, in O(nm) time and O(m) space.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let text: Vec<char> = "abcde".chars().collect();
+/// let pattern: Vec<char> = "a*d?".chars().collect();
+/// assert!(match_::wildcard_match_symbols(&text, &pattern, '*', '?'));
+/// ```
+pub fn wildcard_match_symbols<T: PartialEq + Copy>(
+    text: &[T],
+    pattern: &[T],
+    wildcard: T,
+    any: T,
+) -> bool {
+    // previous[j] is true if pattern[..j] matches the text before the current item.
+    let mut previous = vec![false; pattern.len() + 1];
+    previous[0] = true;
+    for j in 1..=pattern.len() {
+        previous[j] = previous[j - 1] && pattern[j - 1] == wildcard;
+    }
+
+    let mut current = vec![false; pattern.len() + 1];
+    for &item in text {
+        current[0] = false; // a non-empty text never matches the empty pattern
+        for j in 1..=pattern.len() {
+            let symbol = pattern[j - 1];
+            current[j] = if symbol == wildcard {
+                // Match nothing, or one more item.
+                current[j - 1] || previous[j]
+            } else {
+                previous[j - 1] && (symbol == any || symbol == item)
+            };
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+    previous[pattern.len()]
+}
+
+#[cfg(test)]
+mod wildcard_match_symbols_tests {
+    use super::*;
+
+    fn matches(text: &str, pattern: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        let pattern: Vec<char> = pattern.chars().collect();
+        wildcard_match_symbols(&text, &pattern, '*', '?')
+    }
+
+    #[test]
+    fn receives_wildcards() {
+        assert!(matches("abc", "a*c"));
+        assert!(matches("abc", "a?c"));
+        assert!(!matches("abc", "a*d"));
+        assert!(!matches("abc", "a?"));
+        assert!(matches("abcbc", "a*bc"));
+        assert!(matches("mississippi", "m*iss*p?i"));
+        assert!(!matches("mississippi", "m*iss*p?x"));
+    }
+
+    #[test]
+    fn receives_only_stars() {
+        for text in &["", "a", "abc"] {
+            assert!(matches(text, "*"));
+            assert!(matches(text, "***"));
+        }
+        assert!(!matches("", "?"));
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        assert!(matches("", ""));
+        assert!(!matches("a", ""));
+    }
+
+    #[test]
+    fn treats_consecutive_stars_as_one() {
+        for text in &["ab", "axxb", "a", "b", "xaby"] {
+            assert_eq!(matches(text, "a***b"), matches(text, "a*b"));
+            assert_eq!(matches(text, "**a**"), matches(text, "*a*"));
+        }
+    }
+
+    #[test]
+    fn receives_integer_sequences() {
+        let (wildcard, any) = (-1, -2);
+        assert!(wildcard_match_symbols(
+            &[1, 2, 3, 4],
+            &[1, wildcard, 4],
+            wildcard,
+            any
+        ));
+        assert!(wildcard_match_symbols(
+            &[1, 2, 3, 4],
+            &[any, 2, wildcard],
+            wildcard,
+            any
+        ));
+        assert!(!wildcard_match_symbols(
+            &[1, 2, 3, 4],
+            &[any, 3, wildcard],
+            wildcard,
+            any
+        ));
+    }
+}
+
+/// An item of a wildcard pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WildcardToken<T> {
+    /// Matches an equal item.
+    Literal(T),
+    /// Matches exactly one item, like `?`.
+    Any,
+    /// Matches zero or more items, like `*`.
+    Star,
+}
+
+impl<T: PartialEq> WildcardToken<T> {
+    /// Returns true if the token consumes the item; a star consumes any item.
+    fn accepts(&self, item: &T) -> bool {
+        match *self {
+            WildcardToken::Literal(ref literal) => literal == item,
+            WildcardToken::Any | WildcardToken::Star => true,
+        }
+    }
+}
+
+/// # [Wildcard Matching](https://en.wikipedia.org/wiki/Matching_wildcards)
+///
+/// Returns true if the whole sequence matches the pattern of literals, `Any` and `Star` tokens.
+///
+/// The pattern is matched greedily with two pointers; on a mismatch, only the last star seen
+/// is retried with one more item, because any earlier star could only absorb what the later
+/// one can. This takes O(1) extra space and O(nm) time in the worst case.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{self, WildcardToken::*};
+///
+/// let sequence = ["GET", "/", "users", "42"];
+/// let pattern = [Literal("GET"), Star, Literal("users"), Any];
+/// assert!(match_::wildcard_match(&sequence, &pattern));
+/// ```
+pub fn wildcard_match<T: PartialEq>(sequence: &[T], pattern: &[WildcardToken<T>]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    // The pattern index after the last star, and the sequence index it currently resumes from.
+    let mut star: Option<(usize, usize)> = None;
+    while i < sequence.len() {
+        match pattern.get(j) {
+            Some(&WildcardToken::Star) => {
+                star = Some((j + 1, i)); // the star matches nothing for now
+                j += 1;
+            }
+            Some(token) if token.accepts(&sequence[i]) => {
+                i += 1;
+                j += 1;
+            }
+            _ => match star {
+                Some((after_star, resume)) => {
+                    // Let the last star absorb one more item.
+                    star = Some((after_star, resume + 1));
+                    i = resume + 1;
+                    j = after_star;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[j..]
+        .iter()
+        .all(|token| *token == WildcardToken::Star)
+}
+
+/// # [Wildcard Matching](https://en.wikipedia.org/wiki/Matching_wildcards) Search
+///
+/// Finds the first window of the sequence that matches the pattern, which is not anchored to
+/// the ends of the sequence: the window that starts first, and the shortest one among those.
+///
+/// All windows are matched at once in O(nm) time and O(m) space, by tracking for each prefix of
+/// the pattern the earliest start of a window it matches so far.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{self, WildcardToken::*};
+///
+/// let sequence: Vec<char> = "xxabcabc".chars().collect();
+/// let pattern = [Literal('a'), Star, Literal('c')];
+/// assert_eq!(match_::wildcard_find(&sequence, &pattern), Some(2..5));
+/// ```
+pub fn wildcard_find<T: PartialEq>(
+    sequence: &[T],
+    pattern: &[WildcardToken<T>],
+) -> Option<Range<usize>> {
+    let m = pattern.len();
+    // starts[j] is the earliest start of a window ending here that pattern[..j] matches.
+    let mut starts: Vec<Option<usize>> = vec![None; m + 1];
+    let mut next: Vec<Option<usize>> = vec![None; m + 1];
+    let mut best: Option<Range<usize>> = None;
+    let earlier = |a: Option<usize>, b: Option<usize>| match (a, b) {
+        (Some(a), Some(b)) => Some(cmp::min(a, b)),
+        _ => a.or(b),
+    };
+
+    for i in 0..=sequence.len() {
+        if best.is_none() {
+            starts[0] = earlier(starts[0], Some(i)); // a window may start here
+        }
+        // Stars may match nothing.
+        for j in 0..m {
+            if pattern[j] == WildcardToken::Star {
+                starts[j + 1] = earlier(starts[j + 1], starts[j]);
+            }
+        }
+        if let Some(start) = starts[m] {
+            if best.as_ref().is_none_or(|best| start < best.start) {
+                best = Some(start..i);
+            }
+        }
+        // Only windows that start before the best one could still beat it.
+        let best_start = best.as_ref().map_or(usize::MAX, |best| best.start);
+        if i == sequence.len()
+            || starts
+                .iter()
+                .all(|start| start.is_none_or(|s| s >= best_start))
+        {
+            break;
+        }
+
+        next.iter_mut().for_each(|start| *start = None);
+        for (j, token) in pattern.iter().enumerate() {
+            if token.accepts(&sequence[i]) {
+                let target = if *token == WildcardToken::Star {
+                    j
+                } else {
+                    j + 1
+                };
+                next[target] = earlier(next[target], starts[j]);
+            }
+        }
+        mem::swap(&mut starts, &mut next);
+    }
+    best
+}
+
+#[cfg(test)]
+mod wildcard_match_tests {
+    use super::WildcardToken::*;
+    use super::*;
+    use rand::Rng;
+    use regex::Regex;
+    use test_util;
+
+    fn tokens(pattern: &str) -> Vec<WildcardToken<char>> {
+        pattern
+            .chars()
+            .map(|c| match c {
+                '*' => Star,
+                '?' => Any,
+                c => Literal(c),
+            })
+            .collect()
+    }
+
+    fn matches(sequence: &str, pattern: &str) -> bool {
+        let sequence: Vec<char> = sequence.chars().collect();
+        wildcard_match(&sequence, &tokens(pattern))
+    }
+
+    fn find(sequence: &str, pattern: &str) -> Option<Range<usize>> {
+        let sequence: Vec<char> = sequence.chars().collect();
+        wildcard_find(&sequence, &tokens(pattern))
+    }
+
+    fn regex(pattern: &str) -> String {
+        pattern.replace('?', ".").replace('*', ".*")
+    }
+
+    #[test]
+    fn receives_adjacent_stars() {
+        assert!(matches("abc", "a**c"));
+        assert!(matches("ac", "a***c"));
+        assert!(!matches("abd", "a**c"));
+        assert_eq!(find("xxaybc", "a**c"), Some(2..6));
+    }
+
+    #[test]
+    fn receives_leading_and_trailing_stars() {
+        assert!(matches("abc", "*c"));
+        assert!(matches("abc", "a*"));
+        assert!(matches("abc", "*b*"));
+        assert!(!matches("abc", "*d*"));
+        assert_eq!(find("xxabc", "*b"), Some(0..4));
+        assert_eq!(find("xxabc", "b*"), Some(3..4));
+    }
+
+    #[test]
+    fn receives_only_wildcards() {
+        assert!(matches("", "*"));
+        assert!(matches("abc", "???"));
+        assert!(matches("abc", "?*?"));
+        assert!(!matches("a", "?*?"));
+        assert_eq!(find("abc", "??"), Some(0..2));
+        assert_eq!(find("abc", "*"), Some(0..0));
+        assert_eq!(find("abc", "????"), None);
+    }
+
+    #[test]
+    fn matches_literal_wildcard_symbols() {
+        let sequence = ['*', '?'];
+        assert!(wildcard_match(&sequence, &[Literal('*'), Literal('?')]));
+        assert!(!wildcard_match(&['a', '?'], &[Literal('*'), Any]));
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        assert!(matches("", ""));
+        assert!(!matches("a", ""));
+        assert_eq!(find("abc", ""), Some(0..0));
+        assert_eq!(find("", ""), Some(0..0));
+    }
+
+    #[test]
+    fn agrees_with_regex() {
+        let mut rng = test_util::rng();
+        let alphabet = ['a', 'b', '?', '*'];
+        for _ in 0..2000 {
+            let sequence: String = (0..rng.gen_range(0, 12))
+                .map(|_| ['a', 'b'][rng.gen_range(0, 2)])
+                .collect();
+            let pattern: String = (0..rng.gen_range(0, 6))
+                .map(|_| alphabet[rng.gen_range(0, 4)])
+                .collect();
+            let anchored = Regex::new(&format!("^{}$", regex(&pattern))).unwrap();
+            assert_eq!(matches(&sequence, &pattern), anchored.is_match(&sequence));
+
+            let chars: Vec<char> = sequence.chars().collect();
+            let symbols: Vec<char> = pattern.chars().collect();
+            assert_eq!(
+                wildcard_match_symbols(&chars, &symbols, '*', '?'),
+                anchored.is_match(&sequence)
+            );
+
+            // The regex finds the same leftmost start; the end is the shortest one from there.
+            let unanchored = Regex::new(&regex(&pattern)).unwrap();
+            let found = find(&sequence, &pattern);
+            assert_eq!(
+                found.clone().map(|found| found.start),
+                unanchored.find(&sequence).map(|m| m.start())
+            );
+            if let Some(found) = found {
+                assert!(matches(&sequence[found.clone()], &pattern));
+                assert!((found.start..found.end)
+                    .all(|end| !matches(&sequence[found.start..end], &pattern)));
+            }
+        }
+    }
+}
+
+/// # [BK-Tree](https://en.wikipedia.org/wiki/BK-tree)
+///
+/// Indexes sequences by their distances under a metric, so that every indexed sequence within a
+/// distance of a query can be found without comparing the query with all of them.
+///
+/// Every child of a node is keyed by its distance to the node. By the triangle inequality, a
+/// sequence within distance r of the query can only be under the child keyed k of a node at
+/// distance d from the query if |d - k| <= r, so the other children are skipped. The metric
+/// should therefore satisfy the triangle inequality, as `levenshtein_distance` and
+/// `damerau_levenshtein_distance` do but `osa_distance` does not; the results may otherwise miss
+/// some sequences.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{self, BkTree};
+///
+/// let mut tree = BkTree::new(match_::levenshtein_distance);
+/// for word in ["book", "books", "cake", "boo", "cape", "cart"].iter() {
+///     tree.insert(word.chars().collect());
+/// }
+/// let query: Vec<char> = "bood".chars().collect();
+/// let found: Vec<(String, u64)> = tree
+///     .find(&query, 1)
+///     .into_iter()
+///     .map(|(word, distance)| (word.iter().collect(), distance))
+///     .collect();
+/// assert_eq!(found, vec![(String::from("book"), 1), (String::from("boo"), 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BkTree<T: PartialEq + Clone> {
+    metric: fn(&[T], &[T]) -> u64,
+    // nodes[0] is the root; the children of a node are keyed by their distance to it.
+    nodes: Vec<BkNode<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct BkNode<T> {
+    item: Vec<T>,
+    children: BTreeMap<u64, usize>,
+}
+
+impl<T: PartialEq + Clone> BkTree<T> {
+    /// Creates an empty tree that measures distances with the metric.
+    pub fn new(metric: fn(&[T], &[T]) -> u64) -> BkTree<T> {
+        BkTree {
+            metric,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Returns the number of sequences in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if there are no sequences in the tree.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a sequence to the tree, unless a sequence at distance 0 from it is there already.
+    pub fn insert(&mut self, item: Vec<T>) {
+        let mut node = 0;
+        while node < self.nodes.len() {
+            let distance = (self.metric)(&item, &self.nodes[node].item);
+            if distance == 0 {
+                return;
+            }
+            match self.nodes[node].children.get(&distance) {
+                Some(&child) => node = child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes[node].children.insert(distance, child);
+                    break;
+                }
+            }
+        }
+        self.nodes.push(BkNode {
+            item,
+            children: BTreeMap::new(),
+        });
+    }
+
+    /// Finds every sequence within `max_distance` of the query, with its distance. The results
+    /// are sorted by distance, then by the order in which the sequences were inserted.
+    pub fn find(&self, query: &[T], max_distance: u64) -> Vec<(&[T], u64)> {
+        let mut found = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(node) = stack.pop() {
+            let node_ = &self.nodes[node];
+            let distance = (self.metric)(query, &node_.item);
+            if distance <= max_distance {
+                found.push((node, distance));
+            }
+            let low = distance.saturating_sub(max_distance);
+            let high = distance.saturating_add(max_distance);
+            stack.extend(node_.children.range(low..=high).map(|(_, &child)| child));
+        }
+
+        found.sort_by_key(|&(node, distance)| (distance, node));
+        found
+            .into_iter()
+            .map(|(node, distance)| (&self.nodes[node].item[..], distance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod bk_tree_tests {
+    use super::*;
+    use test_util;
+
+    fn words(tree: &BkTree<char>, query: &str, max_distance: u64) -> Vec<(String, u64)> {
+        let query: Vec<char> = query.chars().collect();
+        tree.find(&query, max_distance)
+            .into_iter()
+            .map(|(word, distance)| (word.iter().collect(), distance))
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        check_against_brute_force(levenshtein_distance);
+        check_against_brute_force(damerau_levenshtein_distance);
+    }
+
+    fn check_against_brute_force(metric: fn(&[u8], &[u8]) -> u64) {
+        let mut rng = test_util::rng();
+        let mut tree = BkTree::new(metric);
+        let mut dictionary: Vec<Vec<u8>> = Vec::new();
+        for _ in 0..300 {
+            let word: Vec<u8> = test_util::random_vec(&mut rng, 1..8, b'a'..b'e');
+            if !dictionary.contains(&word) {
+                dictionary.push(word.clone());
+            }
+            tree.insert(word);
+        }
+        assert_eq!(tree.len(), dictionary.len());
+
+        for _ in 0..50 {
+            let query: Vec<u8> = test_util::random_vec(&mut rng, 0..8, b'a'..b'e');
+            for max_distance in 0..4 {
+                let mut expected: Vec<(&[u8], u64)> = dictionary
+                    .iter()
+                    .map(|word| (&word[..], metric(&query, word)))
+                    .filter(|&(_, distance)| distance <= max_distance)
+                    .collect();
+                expected.sort_by_key(|&(_, distance)| distance);
+                assert_eq!(tree.find(&query, max_distance), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn accepts_closures() {
+        // Hamming distance is a metric on words of the same length.
+        let mut tree = BkTree::new(|a: &[char], b: &[char]| hamming_distance(a, b).unwrap());
+        for word in ["karolin", "kathrin", "kerstin", "carolin"].iter() {
+            tree.insert(word.chars().collect());
+        }
+        assert_eq!(
+            words(&tree, "karolin", 3),
+            vec![
+                (String::from("karolin"), 0),
+                (String::from("carolin"), 1),
+                (String::from("kathrin"), 3),
+                (String::from("kerstin"), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_duplicates() {
+        let mut tree = BkTree::new(levenshtein_distance);
+        for word in ["cat", "cart", "cat", "cat"].iter() {
+            tree.insert(word.chars().collect());
+        }
+        assert_eq!(tree.len(), 2);
+        assert_eq!(
+            words(&tree, "cat", 1),
+            vec![(String::from("cat"), 0), (String::from("cart"), 1)]
+        );
+    }
+
+    #[test]
+    fn receives_empty_tree() {
+        let tree = BkTree::<char>::new(levenshtein_distance);
+        assert!(tree.is_empty());
+        assert_eq!(words(&tree, "cat", 10), vec![]);
+    }
+}
+
+/// # [Q-Gram Index](https://en.wikipedia.org/wiki/N-gram#n-grams_for_approximate_matching)
+///
+/// Indexes sequences by their q-grams (substrings of length q), so that the sequences within an
+/// edit distance of a query can be found by verifying only a few candidates.
+///
+/// One edit destroys at most q of the q-grams of a sequence, so two sequences within edit
+/// distance k share at least `max(m, n) - q + 1 - q * k` q-grams (counted with multiplicity), and
+/// their lengths differ by at most k. The candidates are the sequences that pass both filters;
+/// some of them may still be farther than k, which is why `find_within` verifies them. When the
+/// bound is not positive, as with sequences shorter than q, only the length filter applies.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::QGramIndex;
+///
+/// let words: Vec<Vec<char>> = ["kitten", "sitting", "mitten", "knitting", "smitten"]
+///     .iter()
+///     .map(|word| word.chars().collect())
+///     .collect();
+/// let index = QGramIndex::new(words, 2);
+/// let query: Vec<char> = "sitten".chars().collect();
+/// assert_eq!(index.candidates(&query, 1), vec![0, 2, 4]);
+/// assert_eq!(index.find_within(&query, 1), vec![(0, 1), (2, 1), (4, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct QGramIndex<T: Eq + Hash + Clone> {
+    q: usize,
+    sequences: Vec<Vec<T>>,
+    // The ids of the sequences containing each q-gram, with how many times they contain it.
+    postings: HashMap<Vec<T>, Vec<(usize, usize)>>,
+    // The ids of the sequences of each length.
+    by_length: BTreeMap<usize, Vec<usize>>,
+}
+
+impl<T: Eq + Hash + Clone> QGramIndex<T> {
+    /// Indexes the sequences by their q-grams; their ids are their positions in `sequences`.
+    ///
+    /// # Panics
+    ///
+    /// * q should be larger than 0
+    pub fn new(sequences: Vec<Vec<T>>, q: usize) -> QGramIndex<T> {
+        if q == 0 {
+            panic!("q should be larger than 0");
+        }
+        let mut postings: HashMap<Vec<T>, Vec<(usize, usize)>> = HashMap::new();
+        let mut by_length: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (id, sequence) in sequences.iter().enumerate() {
+            for (qgram, count) in qgram_counts(sequence, q) {
+                postings
+                    .entry(qgram.to_vec())
+                    .or_default()
+                    .push((id, count));
+            }
+            by_length.entry(sequence.len()).or_default().push(id);
+        }
+        QGramIndex {
+            q,
+            sequences,
+            postings,
+            by_length,
+        }
+    }
+
+    /// Returns the number of indexed sequences.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if there are no indexed sequences.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns the sequence with the given id.
+    ///
+    /// # Panics
+    ///
+    /// * id is out of bounds
+    pub fn get(&self, id: usize) -> &[T] {
+        &self.sequences[id]
+    }
+
+    /// Finds the ids, in ascending order, of the sequences that pass the length and q-gram count
+    /// filters for being within `max_distance` edits of the query. Every sequence within that
+    /// distance is among them.
+    pub fn candidates(&self, query: &[T], max_distance: u64) -> Vec<usize> {
+        let mut shared: HashMap<usize, usize> = HashMap::new();
+        for (qgram, query_count) in qgram_counts(query, self.q) {
+            if let Some(posting) = self.postings.get(qgram) {
+                for &(id, count) in posting {
+                    *shared.entry(id).or_insert(0) += cmp::min(query_count, count);
+                }
+            }
+        }
+
+        let k = max_distance.min(usize::MAX as u64) as usize;
+        let lengths = query.len().saturating_sub(k)..=query.len().saturating_add(k);
+        let mut found = Vec::new();
+        for (&length, ids) in self.by_length.range(lengths) {
+            // max(m, n) - q + 1 - q * k, which may be negative.
+            let bound = cmp::max(length, query.len()) as i128 + 1
+                - self.q as i128 * (1 + max_distance as i128);
+            if bound <= 0 {
+                found.extend(ids.iter().cloned());
+            } else {
+                found.extend(
+                    ids.iter()
+                        .filter(|id| shared.get(id).map_or(0, |&count| count) as i128 >= bound),
+                );
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /// Finds the ids, in ascending order, of the sequences within `max_distance` edits of the
+    /// query, with their edit distances, by verifying the candidates with `levenshtein_within`.
+    pub fn find_within(&self, query: &[T], max_distance: u64) -> Vec<(usize, u64)> {
+        self.candidates(query, max_distance)
+            .into_iter()
+            .filter_map(|id| {
+                levenshtein_within(query, &self.sequences[id], max_distance)
+                    .map(|distance| (id, distance))
+            })
+            .collect()
+    }
+}
+
+/// Counts the q-grams of a sequence.
+fn qgram_counts<T: Eq + Hash>(sequence: &[T], q: usize) -> HashMap<&[T], usize> {
+    let mut counts = HashMap::new();
+    for qgram in sequence.windows(q) {
+        *counts.entry(qgram).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod qgram_index_tests {
+    use super::*;
+    use test_util;
+
+    fn chars(words: &[&str]) -> Vec<Vec<char>> {
+        words.iter().map(|word| word.chars().collect()).collect()
+    }
+
+    #[test]
+    fn verifies_false_positives() {
+        // Within one edit, 4-letter words share at least one bigram. "cdab" shares "ab" and
+        // "cd" with "abcd" and "ab" with "abce", but is at least three edits from both.
+        let index = QGramIndex::new(chars(&["abcd", "abce", "wxyz"]), 2);
+        let query: Vec<char> = "cdab".chars().collect();
+        assert_eq!(index.candidates(&query, 1), vec![0, 1]);
+        assert_eq!(index.find_within(&query, 1), vec![]);
+
+        let query: Vec<char> = "abcf".chars().collect();
+        assert_eq!(index.candidates(&query, 1), vec![0, 1]);
+        assert_eq!(index.find_within(&query, 1), vec![(0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn receives_sequences_shorter_than_q() {
+        let index = QGramIndex::new(chars(&["", "a", "ab", "abc", "abcd"]), 3);
+        let query: Vec<char> = "b".chars().collect();
+        assert_eq!(index.candidates(&query, 1), vec![0, 1, 2]);
+        assert_eq!(index.find_within(&query, 1), vec![(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(index.find_within(&[], 0), vec![(0, 0)]);
+        assert_eq!(index.get(3), &['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn receives_empty_index() {
+        let index = QGramIndex::<char>::new(vec![], 2);
+        assert!(index.is_empty());
+        assert_eq!(index.candidates(&['a'], 5), vec![]);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = test_util::rng();
+        for q in 1..4 {
+            let sequences: Vec<Vec<u8>> = (0..200)
+                .map(|_| test_util::random_vec(&mut rng, 0..12, b'a'..b'd'))
+                .collect();
+            let index = QGramIndex::new(sequences.clone(), q);
+            assert_eq!(index.len(), 200);
+            for _ in 0..20 {
+                let query: Vec<u8> = test_util::random_vec(&mut rng, 0..12, b'a'..b'd');
+                for max_distance in 0..4 {
+                    let expected: Vec<(usize, u64)> = sequences
+                        .iter()
+                        .enumerate()
+                        .map(|(id, sequence)| (id, levenshtein_distance(&query, sequence)))
+                        .filter(|&(_, distance)| distance <= max_distance)
+                        .collect();
+                    assert_eq!(index.find_within(&query, max_distance), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "q should be larger than 0")]
+    fn receives_zero_q() {
+        QGramIndex::new(vec![vec![1, 2]], 0);
+    }
+}
+
+/// Maps the symbols of an alphabet to dense indices in [0, size), so that matchers can keep
+/// tables indexed by symbol instead of hash maps.
+pub trait Alphabet<T> {
+    /// Returns the index of the symbol, or `None` if the symbol is not in the alphabet.
+    fn index(&self, symbol: &T) -> Option<usize>;
+
+    /// Returns the number of symbols in the alphabet.
+    fn size(&self) -> usize;
+}
+
+/// The alphabet of every byte, where each byte is its own index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteAlphabet;
+
+impl Alphabet<u8> for ByteAlphabet {
+    fn index(&self, symbol: &u8) -> Option<usize> {
+        Some(*symbol as usize)
+    }
+
+    fn size(&self) -> usize {
+        256
+    }
+}
+
+/// An alphabet of the observed symbols, indexed in the order they were first observed.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{Alphabet, MappedAlphabet};
+///
+/// let alphabet = MappedAlphabet::new("banana".chars());
+/// assert_eq!(alphabet.size(), 3);
+/// assert_eq!(alphabet.index(&'a'), Some(1));
+/// assert_eq!(alphabet.index(&'z'), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MappedAlphabet<T: Eq + Hash> {
+    indices: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> MappedAlphabet<T> {
+    /// Creates an alphabet of the symbols, where the first occurrence of a symbol sets its index
+    /// and repeated symbols are ignored.
+    pub fn new<I: IntoIterator<Item = T>>(symbols: I) -> MappedAlphabet<T> {
+        let mut indices = HashMap::new();
+        for symbol in symbols {
+            let index = indices.len();
+            indices.entry(symbol).or_insert(index);
+        }
+        MappedAlphabet { indices }
+    }
+}
+
+impl<T: Eq + Hash> Alphabet<T> for MappedAlphabet<T> {
+    fn index(&self, symbol: &T) -> Option<usize> {
+        self.indices.get(symbol).cloned()
+    }
+
+    fn size(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+#[cfg(test)]
+mod alphabet_tests {
+    use super::*;
+
+    #[test]
+    fn indexes_bytes() {
+        assert_eq!(ByteAlphabet.size(), 256);
+        assert_eq!(ByteAlphabet.index(&0), Some(0));
+        assert_eq!(ByteAlphabet.index(&b'a'), Some(97));
+        assert_eq!(ByteAlphabet.index(&255), Some(255));
+    }
+
+    #[test]
+    fn indexes_observed_symbols_densely() {
+        let alphabet = MappedAlphabet::new(vec!["to", "be", "or", "not", "to", "be"]);
+        assert_eq!(alphabet.size(), 4);
+        let indices: Vec<Option<usize>> = ["to", "be", "or", "not", "?"]
+            .iter()
+            .map(|symbol| alphabet.index(symbol))
+            .collect();
+        assert_eq!(indices, vec![Some(0), Some(1), Some(2), Some(3), None]);
+    }
+
+    #[test]
+    fn receives_no_symbols() {
+        let alphabet = MappedAlphabet::new(Vec::<char>::new());
+        assert_eq!(alphabet.size(), 0);
+        assert_eq!(alphabet.index(&'a'), None);
+    }
+}
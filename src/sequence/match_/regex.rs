@@ -0,0 +1,458 @@
+//! # Regex
+//!
+//! Regular expressions over bytes, matched by simulating Thompson's NFA.
+
+use std::error::Error;
+use std::fmt;
+
+/// # [Thompson's Construction](https://en.wikipedia.org/wiki/Thompson%27s_construction)
+///
+/// A regular expression compiled into a nondeterministic finite automaton, which is simulated on
+/// every possible path at once rather than by backtracking, so matching takes O(mn) time for a
+/// pattern of length m and a text of length n.
+///
+/// The syntax supports:
+///
+/// * `.` matching any byte
+/// * `*` matching the preceding expression zero or more times
+/// * `+` matching the preceding expression one or more times
+/// * `|` matching either the expression before or the one after it
+/// * `(` and `)` grouping an expression
+/// * `\` matching the next byte literally
+///
+/// Every other byte matches itself.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::regex::Regex;
+///
+/// let regex = Regex::new("(ab|c)+d").unwrap();
+/// assert!(regex.matches(b"abcabd"));
+/// assert!(!regex.matches(b"abd!"));
+/// assert_eq!(regex.find(b"xxcd abd"), Some((2, 4)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Regex {
+    states: Vec<State>,
+    start: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Byte(u8, usize),
+    Any(usize),
+    Split(usize, usize),
+    Match,
+}
+
+#[derive(Debug)]
+enum Node {
+    Empty,
+    Byte(u8),
+    Any,
+    Concat(Vec<Node>),
+    Alternate(Box<Node>, Box<Node>),
+    Star(Box<Node>),
+    Plus(Box<Node>),
+}
+
+impl Regex {
+    /// Compiles the pattern.
+    ///
+    /// # Errors
+    ///
+    /// * `RegexError::UnbalancedParenthesis` if a parenthesis is not closed or not opened
+    /// * `RegexError::MissingOperand` if `*` or `+` does not follow an expression
+    /// * `RegexError::TrailingEscape` if the pattern ends with `\`
+    pub fn new(pattern: &str) -> Result<Regex, RegexError> {
+        let mut parser = Parser {
+            pattern: pattern.as_bytes(),
+            position: 0,
+        };
+        let node = parser.alternation()?;
+        if parser.position < parser.pattern.len() {
+            // Only an unopened parenthesis stops the parser early.
+            return Err(RegexError::UnbalancedParenthesis(parser.position));
+        }
+
+        let mut regex = Regex {
+            states: vec![State::Match],
+            start: 0,
+        };
+        regex.start = regex.compile(&node, 0);
+        Ok(regex)
+    }
+
+    /// Returns true if the whole text matches the regex.
+    pub fn matches(&self, text: &[u8]) -> bool {
+        let mut current = Threads::new(self.states.len());
+        let mut next = Threads::new(self.states.len());
+        current.add(&self.states, self.start, 0);
+        for &byte in text {
+            self.step(&current, &mut next, byte);
+            std::mem::swap(&mut current, &mut next);
+        }
+        current.has_match(&self.states).is_some()
+    }
+
+    /// Finds the first match in the text as (start, end): the match that starts first, and the
+    /// longest one among those.
+    pub fn find(&self, text: &[u8]) -> Option<(usize, usize)> {
+        let mut current = Threads::new(self.states.len());
+        let mut next = Threads::new(self.states.len());
+        let mut best: Option<(usize, usize)> = None;
+        for i in 0..=text.len() {
+            if best.is_none() {
+                // A match may start here; it loses to every earlier start.
+                current.add(&self.states, self.start, i);
+            }
+            if let Some(start) = current.has_match(&self.states) {
+                if best.is_none_or(|(best_start, _)| start <= best_start) {
+                    best = Some((start, i));
+                }
+            }
+            if i == text.len() || current.is_empty() {
+                break;
+            }
+            self.step(&current, &mut next, text[i]);
+            std::mem::swap(&mut current, &mut next);
+            if let Some((best_start, _)) = best {
+                current.retain(|start| start <= best_start);
+            }
+        }
+        best
+    }
+
+    /// Advances every thread over the byte.
+    fn step(&self, current: &Threads, next: &mut Threads, byte: u8) {
+        next.clear();
+        for &(state, start) in &current.threads {
+            match self.states[state] {
+                State::Byte(b, out) if b == byte => next.add(&self.states, out, start),
+                State::Any(out) => next.add(&self.states, out, start),
+                _ => {}
+            }
+        }
+    }
+
+    /// Compiles the node into states leading to next, returning the first state.
+    fn compile(&mut self, node: &Node, next: usize) -> usize {
+        match *node {
+            Node::Empty => next,
+            Node::Byte(byte) => self.push(State::Byte(byte, next)),
+            Node::Any => self.push(State::Any(next)),
+            Node::Concat(ref nodes) => nodes
+                .iter()
+                .rev()
+                .fold(next, |next, node| self.compile(node, next)),
+            Node::Alternate(ref a, ref b) => {
+                let a = self.compile(a, next);
+                let b = self.compile(b, next);
+                self.push(State::Split(a, b))
+            }
+            Node::Star(ref inner) | Node::Plus(ref inner) => {
+                // The split either loops back into the inner expression or leaves.
+                let split = self.push(State::Split(0, next));
+                let body = self.compile(inner, split);
+                self.states[split] = State::Split(body, next);
+                match *node {
+                    Node::Star(_) => split,
+                    _ => body,
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, state: State) -> usize {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+}
+
+/// The states reached by the simulation, each with the earliest start of a path reaching it,
+/// ordered by start.
+struct Threads {
+    threads: Vec<(usize, usize)>,
+    is_added: Vec<bool>,
+}
+
+impl Threads {
+    fn new(states: usize) -> Threads {
+        Threads {
+            threads: Vec::new(),
+            is_added: vec![false; states],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.threads.is_empty()
+    }
+
+    fn clear(&mut self) {
+        for &(state, _) in &self.threads {
+            self.is_added[state] = false;
+        }
+        self.threads.clear();
+    }
+
+    fn retain<F: Fn(usize) -> bool>(&mut self, keep: F) {
+        let is_added = &mut self.is_added;
+        self.threads.retain(|&(state, start)| {
+            is_added[state] = keep(start);
+            is_added[state]
+        });
+    }
+
+    /// Adds the state and every state reachable from it without consuming a byte; states that
+    /// were added before keep their earlier start.
+    fn add(&mut self, states: &[State], state: usize, start: usize) {
+        let mut stack = vec![state];
+        while let Some(state) = stack.pop() {
+            if self.is_added[state] {
+                continue;
+            }
+            self.is_added[state] = true;
+            self.threads.push((state, start));
+            if let State::Split(a, b) = states[state] {
+                stack.push(b);
+                stack.push(a);
+            }
+        }
+    }
+
+    /// Returns the start of the thread in the match state, if any.
+    fn has_match(&self, states: &[State]) -> Option<usize> {
+        self.threads
+            .iter()
+            .find(|&&(state, _)| matches!(states[state], State::Match))
+            .map(|&(_, start)| start)
+    }
+}
+
+struct Parser<'a> {
+    pattern: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.pattern.get(self.position).cloned()
+    }
+
+    fn alternation(&mut self) -> Result<Node, RegexError> {
+        let mut node = self.concatenation()?;
+        while self.peek() == Some(b'|') {
+            self.position += 1;
+            let other = self.concatenation()?;
+            node = Node::Alternate(Box::new(node), Box::new(other));
+        }
+        Ok(node)
+    }
+
+    fn concatenation(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = Vec::new();
+        while let Some(byte) = self.peek() {
+            let mut node = match byte {
+                b'|' | b')' => break,
+                b'*' | b'+' => return Err(RegexError::MissingOperand(self.position)),
+                b'(' => {
+                    let open = self.position;
+                    self.position += 1;
+                    let node = self.alternation()?;
+                    if self.peek() != Some(b')') {
+                        return Err(RegexError::UnbalancedParenthesis(open));
+                    }
+                    self.position += 1;
+                    node
+                }
+                b'.' => {
+                    self.position += 1;
+                    Node::Any
+                }
+                b'\\' => {
+                    let byte = match self.pattern.get(self.position + 1) {
+                        Some(&byte) => byte,
+                        None => return Err(RegexError::TrailingEscape),
+                    };
+                    self.position += 2;
+                    Node::Byte(byte)
+                }
+                _ => {
+                    self.position += 1;
+                    Node::Byte(byte)
+                }
+            };
+            loop {
+                node = match self.peek() {
+                    Some(b'*') => Node::Star(Box::new(node)),
+                    Some(b'+') => Node::Plus(Box::new(node)),
+                    _ => break,
+                };
+                self.position += 1;
+            }
+            nodes.push(node);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(nodes),
+        })
+    }
+}
+
+/// Errors from compiling a regex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexError {
+    /// The parenthesis at the given byte position is not closed or not opened
+    UnbalancedParenthesis(usize),
+    /// The operator at the given byte position does not follow an expression
+    MissingOperand(usize),
+    /// The pattern ends with an escape
+    TrailingEscape,
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegexError::UnbalancedParenthesis(position) => {
+                write!(f, "parenthesis at position {} is unbalanced", position)
+            }
+            RegexError::MissingOperand(position) => {
+                write!(f, "operator at position {} has no operand", position)
+            }
+            RegexError::TrailingEscape => write!(f, "pattern ends with an escape"),
+        }
+    }
+}
+
+impl Error for RegexError {}
+
+#[cfg(test)]
+mod regex_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_star() {
+        let regex = Regex::new("a*b").unwrap();
+        assert!(regex.matches(b"b"));
+        assert!(regex.matches(b"aaab"));
+        assert!(!regex.matches(b"aaa"));
+        assert!(!regex.matches(b"aaabb"));
+    }
+
+    #[test]
+    fn receives_alternation() {
+        let regex = Regex::new("a|b").unwrap();
+        assert!(regex.matches(b"a"));
+        assert!(regex.matches(b"b"));
+        assert!(!regex.matches(b"c"));
+        assert!(!regex.matches(b"ab"));
+    }
+
+    #[test]
+    fn receives_plus_and_any() {
+        let regex = Regex::new("x.+y").unwrap();
+        assert!(regex.matches(b"x-y"));
+        assert!(regex.matches(b"xyyy"));
+        assert!(!regex.matches(b"xy"));
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        let regex = Regex::new("").unwrap();
+        assert!(regex.matches(b""));
+        assert!(!regex.matches(b"a"));
+        assert_eq!(regex.find(b"abc"), Some((0, 0)));
+    }
+
+    #[test]
+    fn receives_nested_parentheses() {
+        let regex = Regex::new("((ab)*c|d(e|f)+)g").unwrap();
+        assert!(regex.matches(b"cg"));
+        assert!(regex.matches(b"ababcg"));
+        assert!(regex.matches(b"defefg"));
+        assert!(!regex.matches(b"dg"));
+        assert!(!regex.matches(b"abg"));
+    }
+
+    #[test]
+    fn receives_nullable_loops() {
+        let regex = Regex::new("(a*)*b").unwrap();
+        assert!(regex.matches(b"b"));
+        assert!(regex.matches(b"aab"));
+        let regex = Regex::new("(|a)+").unwrap();
+        assert!(regex.matches(b""));
+        assert!(regex.matches(b"aaa"));
+    }
+
+    #[test]
+    fn escapes_operators() {
+        let regex = Regex::new(r"a\*\(b\)").unwrap();
+        assert!(regex.matches(b"a*(b)"));
+        assert!(!regex.matches(b"aa(b)"));
+    }
+
+    #[test]
+    fn finds_leftmost_longest_match() {
+        let regex = Regex::new("a+").unwrap();
+        assert_eq!(regex.find(b"xaaay"), Some((1, 4)));
+        let regex = Regex::new("ab|abcd|bcdef").unwrap();
+        assert_eq!(regex.find(b"xabcdef"), Some((1, 5)));
+        let regex = Regex::new("b*").unwrap();
+        assert_eq!(regex.find(b"abb"), Some((0, 0)));
+        let regex = Regex::new("z").unwrap();
+        assert_eq!(regex.find(b"abc"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert_eq!(
+            Regex::new("(ab").unwrap_err(),
+            RegexError::UnbalancedParenthesis(0)
+        );
+        assert_eq!(
+            Regex::new("ab)c").unwrap_err(),
+            RegexError::UnbalancedParenthesis(2)
+        );
+        assert_eq!(Regex::new("*a").unwrap_err(), RegexError::MissingOperand(0));
+        assert_eq!(
+            Regex::new("a|+").unwrap_err(),
+            RegexError::MissingOperand(2)
+        );
+        assert_eq!(Regex::new("a\\").unwrap_err(), RegexError::TrailingEscape);
+        assert_eq!(
+            RegexError::MissingOperand(2).to_string(),
+            "operator at position 2 has no operand"
+        );
+    }
+
+    #[test]
+    fn agrees_with_regex_crate() {
+        let mut rng = test_util::rng();
+        let pieces = ["a", "b", ".", "a*", "b+", "(a|b)", "(ab|b)*", "(a|)+"];
+        for _ in 0..500 {
+            let pattern: String = (0..rng.gen_range(0, 5))
+                .map(|_| pieces[rng.gen_range(0, pieces.len())])
+                .collect();
+            let text: String = (0..rng.gen_range(0, 10))
+                .map(|_| ["a", "b", "c"][rng.gen_range(0, 3)])
+                .collect();
+            let regex = Regex::new(&pattern).unwrap();
+            let anchored = ::regex::Regex::new(&format!("^(?s:{})$", pattern)).unwrap();
+            assert_eq!(regex.matches(text.as_bytes()), anchored.is_match(&text));
+
+            // Leftmost-longest: the same start as the regex crate, and no longer match there.
+            let unanchored = ::regex::Regex::new(&format!("(?s:{})", pattern)).unwrap();
+            let found = regex.find(text.as_bytes());
+            let expected = unanchored.find(&text).map(|m| m.start());
+            assert_eq!(found.map(|(start, _)| start), expected);
+            if let Some((start, end)) = found {
+                assert!(anchored.is_match(&text[start..end]));
+                assert!((end + 1..=text.len()).all(|e| !anchored.is_match(&text[start..e])));
+            }
+        }
+    }
+}
@@ -0,0 +1,861 @@
+//! # Str
+//!
+//! Wrappers of the match functions that receive string slices and operate on their chars.
+//!
+//! Every returned index or length counts chars, not bytes, unless the function says otherwise;
+//! the `_byte_*` variants return byte indices that can be used to slice the strings.
+//!
+//! With the `unicode` feature, the `_graphemes` variants operate on extended grapheme clusters
+//! instead, so that e.g. "é" written as "e" and a combining accent counts as one item.
+
+use sequence::match_::{self, ApproxMatch, EditOp, PatternError};
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Collects the chars of a string.
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+/// Splits a string on whitespace into lowercased tokens of chars.
+fn tokens(s: &str) -> Vec<Vec<char>> {
+    s.split_whitespace()
+        .map(|token| token.chars().flat_map(char::to_lowercase).collect())
+        .collect()
+}
+
+/// Converts a char index of a string into a byte index (the string length if out of range).
+fn byte_index(s: &str, char_index: usize) -> usize {
+    s.char_indices()
+        .nth(char_index)
+        .map_or(s.len(), |(byte_index, _)| byte_index)
+}
+
+/// Exactly matches a pattern over the given text, returning the char index of the match.
+/// See `match_::bitap`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::bitap("naïve world", "world"), Some(6));
+/// ```
+pub fn bitap(text: &str, pattern: &str) -> Option<usize> {
+    match_::bitap(&chars(text), &chars(pattern))
+}
+
+/// Exactly matches a pattern over the given text, returning the byte index of the match.
+/// See `match_::bitap`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let text = "naïve world";
+/// let i = match_::str::bitap_byte_index(text, "world").unwrap();
+/// assert_eq!(i, 7);
+/// assert_eq!(&text[i..], "world");
+/// ```
+pub fn bitap_byte_index(text: &str, pattern: &str) -> Option<usize> {
+    bitap(text, pattern).map(|i| byte_index(text, i))
+}
+
+/// Exactly matches a pattern over the given text, returning the char index of every occurrence,
+/// including overlapping ones. See `match_::bitap_all`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::bitap_all("ïaïaïa", "ïaï"), vec![0, 2]);
+/// ```
+pub fn bitap_all(text: &str, pattern: &str) -> Vec<usize> {
+    match_::bitap_all(&chars(text), &chars(pattern)).collect()
+}
+
+/// Exactly matches a pattern of char classes over the given text, returning the char index of
+/// the match. Each class is given as a string of its acceptable chars. See `match_::bitap_classes`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// // R is A or G, Y is C or T.
+/// assert_eq!(match_::str::bitap_classes("GATTACA", &["AG", "CT", "A"]), Ok(Some(4)));
+/// ```
+///
+/// # Errors
+///
+/// * `PatternError::EmptyClass` if a class is empty, since it can never match
+pub fn bitap_classes(text: &str, pattern: &[&str]) -> Result<Option<usize>, PatternError> {
+    let pattern: Vec<Vec<char>> = pattern.iter().map(|class| chars(class)).collect();
+    match_::bitap_classes(&chars(text), &pattern)
+}
+
+/// Returns a copy of the text where every non-overlapping occurrence of the pattern, found from
+/// left to right, is replaced. See `match_::replace_all`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::replace_all("café café", "é", "e"), "cafe cafe");
+/// ```
+pub fn replace_all(text: &str, pattern: &str, replacement: &str) -> String {
+    match_::replace_all(&chars(text), &chars(pattern), &chars(replacement))
+        .into_iter()
+        .collect()
+}
+
+/// Returns a copy of the text where the first occurrence of the pattern is replaced.
+/// See `match_::replace_first`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::replace_first("café café", "é", "e"), "cafe café");
+/// ```
+pub fn replace_first(text: &str, pattern: &str, replacement: &str) -> String {
+    match_::replace_first(&chars(text), &chars(pattern), &chars(replacement))
+        .into_iter()
+        .collect()
+}
+
+/// Returns true if the whole text matches the pattern in chars, where the `wildcard` char
+/// matches zero or more chars and the `any` char matches exactly one char.
+/// See `match_::wildcard_match_symbols`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert!(match_::str::wildcard_match_symbols("crème brûlée", "cr?me*ée", '*', '?'));
+/// ```
+pub fn wildcard_match_symbols(text: &str, pattern: &str, wildcard: char, any: char) -> bool {
+    match_::wildcard_match_symbols(&chars(text), &chars(pattern), wildcard, any)
+}
+
+/// Calculates the minimum edit distance between two strings in chars.
+/// See `match_::levenshtein_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_distance("sitting", "kitten"), 3);
+/// ```
+pub fn levenshtein_distance(a: &str, b: &str) -> u64 {
+    match_::levenshtein_distance(&chars(a), &chars(b))
+}
+
+/// Calculates the minimum edit distance between two strings in chars with two rows of memory.
+/// See `match_::levenshtein_distance_optimised`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_distance_optimised("sitting", "kitten"), 3);
+/// ```
+pub fn levenshtein_distance_optimised(a: &str, b: &str) -> u64 {
+    match_::levenshtein_distance_optimised(&chars(a), &chars(b))
+}
+
+/// Calculates the minimum edit distance between two strings in chars only if it does not exceed
+/// `max`. See `match_::levenshtein_within`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_within("sitting", "kitten", 3), Some(3));
+/// assert_eq!(match_::str::levenshtein_within("sitting", "kitten", 2), None);
+/// ```
+pub fn levenshtein_within(a: &str, b: &str, max: u64) -> Option<u64> {
+    match_::levenshtein_within(&chars(a), &chars(b), max)
+}
+
+/// Calculates the minimum edit distance between two strings in chars, giving up once it exceeds
+/// `max_distance`. See `match_::levenshtein_distance_bounded`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_distance_bounded("kitten", "sitting", 1), None);
+/// ```
+pub fn levenshtein_distance_bounded(a: &str, b: &str, max_distance: u64) -> Option<u64> {
+    match_::levenshtein_distance_bounded(&chars(a), &chars(b), max_distance)
+}
+
+/// Calculates the minimum edit distance between two strings in chars with Myers' bit-parallel
+/// algorithm. See `match_::levenshtein_myers`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_myers("sitting", "kitten"), 3);
+/// ```
+pub fn levenshtein_myers(a: &str, b: &str) -> u64 {
+    match_::levenshtein_myers(&chars(a), &chars(b))
+}
+
+/// Calculates the minimum edit distance between two strings in chars, along with the char edits.
+/// See `match_::levenshtein_edits`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_::{self, EditOp};
+///
+/// let (distance, edits) = match_::str::levenshtein_edits("cat", "cut");
+/// assert_eq!(distance, 1);
+/// assert_eq!(edits[1], EditOp::Substitute { from: 'a', to: 'u' });
+/// ```
+pub fn levenshtein_edits(a: &str, b: &str) -> (u64, Vec<EditOp<char>>) {
+    match_::levenshtein_edits(&chars(a), &chars(b))
+}
+
+/// Calculates the normalized Levenshtein similarity between two strings in chars.
+/// See `match_::levenshtein_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::levenshtein_similarity("abcd", "abce"), 0.75);
+/// ```
+pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    match_::levenshtein_similarity(&chars(a), &chars(b))
+}
+
+/// Calculates the optimal string alignment distance between two strings in chars.
+/// See `match_::osa_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::osa_distance("CA", "ABC"), 3);
+/// ```
+pub fn osa_distance(a: &str, b: &str) -> u64 {
+    match_::osa_distance(&chars(a), &chars(b))
+}
+
+/// Calculates the Damerau–Levenshtein distance between two strings in chars.
+/// See `match_::damerau_levenshtein_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::damerau_levenshtein_distance("CA", "ABC"), 2);
+/// ```
+pub fn damerau_levenshtein_distance(a: &str, b: &str) -> u64 {
+    match_::damerau_levenshtein_distance(&chars(a), &chars(b))
+}
+
+/// Calculates the normalized Damerau–Levenshtein similarity between two strings in chars.
+/// See `match_::damerau_levenshtein_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::damerau_levenshtein_similarity("abcd", "bacd"), 0.75);
+/// ```
+pub fn damerau_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    match_::damerau_levenshtein_similarity(&chars(a), &chars(b))
+}
+
+/// Calculates the Hamming distance between two strings in chars, or `None` if their lengths in
+/// chars differ. See `match_::hamming_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::hamming_distance("naïve", "naive"), Some(1));
+/// ```
+pub fn hamming_distance(a: &str, b: &str) -> Option<u64> {
+    match_::hamming_distance(&chars(a), &chars(b))
+}
+
+/// Calculates the Jaro similarity between two strings in chars. See `match_::jaro_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::jaro_similarity("naïve", "naïve"), 1.0);
+/// ```
+pub fn jaro_similarity(a: &str, b: &str) -> f64 {
+    match_::jaro_similarity(&chars(a), &chars(b))
+}
+
+/// Calculates the n-gram similarity between two strings, using n-grams of chars.
+/// See `match_::ngram_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::ngram_similarity("night", "nacht", 2), 0.25);
+/// ```
+///
+/// # Panics
+///
+/// * n is 0
+pub fn ngram_similarity(a: &str, b: &str, n: usize) -> f64 {
+    match_::ngram_similarity(&chars(a), &chars(b), n)
+}
+
+/// Calculates the Sørensen–Dice coefficient between two strings, using char bigrams.
+/// See `match_::dice_coefficient`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::dice_coefficient("night", "nacht"), 0.25);
+/// ```
+pub fn dice_coefficient(a: &str, b: &str) -> f64 {
+    match_::dice_coefficient(&chars(a), &chars(b))
+}
+
+/// Calculates the Ratcliff/Obershelp similarity between two strings in chars.
+/// See `match_::gestalt_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::gestalt_similarity("WIKIMEDIA", "WIKIMANIA"), 14.0 / 18.0);
+/// ```
+pub fn gestalt_similarity(a: &str, b: &str) -> f64 {
+    match_::gestalt_similarity(&chars(a), &chars(b))
+}
+
+/// Calculates the token sort similarity between two strings, splitting them on whitespace into
+/// case-insensitive tokens of chars. See `match_::token_sort_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::token_sort_similarity("Smith,  John", "john smith,"), 1.0);
+/// ```
+pub fn token_sort_similarity(a: &str, b: &str) -> f64 {
+    match_::token_sort_similarity(&tokens(a), &tokens(b))
+}
+
+/// Calculates the token set similarity between two strings, splitting them on whitespace into
+/// case-insensitive tokens of chars. See `match_::token_set_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::token_set_similarity("New York Mets", "new york mets vs braves"), 1.0);
+/// ```
+pub fn token_set_similarity(a: &str, b: &str) -> f64 {
+    match_::token_set_similarity(&tokens(a), &tokens(b))
+}
+
+/// Finds the longest common substring of two strings as (char index in a, char index in b,
+/// length in chars). See `match_::longest_common_substring`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::longest_common_substring("xabcdey", "abcdz"), (1, 0, 4));
+/// ```
+pub fn longest_common_substring(a: &str, b: &str) -> (usize, usize, usize) {
+    match_::longest_common_substring(&chars(a), &chars(b))
+}
+
+/// Finds the longest common substring of two strings, returned as a slice of a.
+/// See `match_::longest_common_substring`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::longest_common_substring_str("a naïve idea", "naïvety"), "naïve");
+/// ```
+pub fn longest_common_substring_str<'a>(a: &'a str, b: &str) -> &'a str {
+    let (start, _, length) = longest_common_substring(a, b);
+    &a[byte_index(a, start)..byte_index(a, start + length)]
+}
+
+/// Finds the window of the text with the minimum edit distance to the pattern, in char indices.
+/// See `match_::best_match`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let found = match_::str::best_match("the quick brown fox", "bruwn").unwrap();
+/// assert_eq!((found.start, found.end, found.distance), (10, 15, 1));
+/// ```
+pub fn best_match(text: &str, pattern: &str) -> Option<ApproxMatch> {
+    match_::best_match(&chars(text), &chars(pattern))
+}
+
+/// Finds the window of the text with the minimum edit distance to the pattern, in byte indices.
+/// See `match_::best_match`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// let text = "a naïve fox";
+/// let found = match_::str::best_match_byte_range(text, "naive").unwrap();
+/// assert_eq!(&text[found.start..found.end], "naïve");
+/// ```
+pub fn best_match_byte_range(text: &str, pattern: &str) -> Option<ApproxMatch> {
+    best_match(text, pattern).map(|found| ApproxMatch {
+        start: byte_index(text, found.start),
+        end: byte_index(text, found.end),
+        distance: found.distance,
+    })
+}
+
+/// Finds the window of the text with the minimum edit distance to the pattern, in char indices,
+/// only if that distance does not exceed `max_distance`. See `match_::best_match_within`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert!(match_::str::best_match_within("the quick brown fox", "bruwn", 0).is_none());
+/// ```
+pub fn best_match_within(text: &str, pattern: &str, max_distance: u64) -> Option<ApproxMatch> {
+    match_::best_match_within(&chars(text), &chars(pattern), max_distance)
+}
+
+/// Finds the longest palindrome of chars in the text, returned as a slice of the text.
+/// See `match_::longest_palindrome`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::longest_palindrome_str("ça été"), "été");
+/// ```
+pub fn longest_palindrome_str(text: &str) -> &str {
+    let range = match_::longest_palindrome(&chars(text));
+    &text[byte_index(text, range.start)..byte_index(text, range.end)]
+}
+
+/// Calculates the length in chars of the longest proper border of every char prefix of the text.
+/// See `match_::border_array`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::border_array("ïaï"), vec![0, 0, 1]);
+/// ```
+pub fn border_array(text: &str) -> Vec<usize> {
+    match_::border_array(&chars(text))
+}
+
+/// Finds every period of the text in chars, in ascending order. See `match_::periods`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::periods("ïaïaï"), vec![2, 4]);
+/// ```
+pub fn periods(text: &str) -> Vec<usize> {
+    match_::periods(&chars(text))
+}
+
+/// Returns true if the smallest period of the text in chars is at most half of its length.
+/// See `match_::is_periodic`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert!(match_::str::is_periodic("🦀🦀🦀"));
+/// ```
+pub fn is_periodic(text: &str) -> bool {
+    match_::is_periodic(&chars(text))
+}
+
+/// Finds the shortest prefix of the text whose repetition forms the whole text.
+/// See `match_::primitive_root`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::primitive_root_str("ïaïaïa"), "ïa");
+/// ```
+pub fn primitive_root_str(text: &str) -> &str {
+    let root = match_::primitive_root(&chars(text)).len();
+    &text[..byte_index(text, root)]
+}
+
+/// Collects the extended grapheme clusters of a string.
+#[cfg(feature = "unicode")]
+fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+/// Calculates the minimum edit distance between two strings in extended grapheme clusters.
+/// See `match_::levenshtein_distance`.
+///
+/// This costs more than `levenshtein_distance`: segmenting takes a pass over both strings with
+/// the Unicode segmentation rules, and every comparison compares string slices instead of chars.
+/// The same holds for every `_graphemes` variant.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// // "e" followed by a combining acute accent is one grapheme but two chars.
+/// assert_eq!(match_::str::levenshtein_graphemes("cafe\u{301}", "cafe"), 1);
+/// assert_eq!(match_::str::levenshtein_distance("cafe\u{301}", "cafe"), 1);
+/// assert_eq!(match_::str::levenshtein_graphemes("cafe\u{301}", "café"), 1);
+/// assert_eq!(match_::str::levenshtein_distance("cafe\u{301}", "café"), 2);
+/// ```
+#[cfg(feature = "unicode")]
+pub fn levenshtein_graphemes(a: &str, b: &str) -> u64 {
+    match_::levenshtein_distance(&graphemes(a), &graphemes(b))
+}
+
+/// Calculates the Hamming distance between two strings in extended grapheme clusters, or `None`
+/// if their lengths in graphemes differ. See `match_::hamming_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::hamming_graphemes("ne\u{301}e", "nee"), Some(1));
+/// assert_eq!(match_::str::hamming_distance("ne\u{301}e", "nee"), None);
+/// ```
+#[cfg(feature = "unicode")]
+pub fn hamming_graphemes(a: &str, b: &str) -> Option<u64> {
+    match_::hamming_distance(&graphemes(a), &graphemes(b))
+}
+
+/// Calculates the Damerau–Levenshtein distance between two strings in extended grapheme
+/// clusters. See `match_::damerau_levenshtein_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::damerau_levenshtein_graphemes("e\u{301}a", "ae\u{301}"), 1);
+/// assert_eq!(match_::str::damerau_levenshtein_distance("e\u{301}a", "ae\u{301}"), 2);
+/// ```
+#[cfg(feature = "unicode")]
+pub fn damerau_levenshtein_graphemes(a: &str, b: &str) -> u64 {
+    match_::damerau_levenshtein_distance(&graphemes(a), &graphemes(b))
+}
+
+/// Calculates the Jaro similarity between two strings in extended grapheme clusters.
+/// See `match_::jaro_similarity`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::match_;
+///
+/// assert_eq!(match_::str::jaro_graphemes("e\u{301}", "é"), 0.0);
+/// assert_eq!(match_::str::jaro_graphemes("e\u{301}", "e\u{301}"), 1.0);
+/// ```
+#[cfg(feature = "unicode")]
+pub fn jaro_graphemes(a: &str, b: &str) -> f64 {
+    match_::jaro_similarity(&graphemes(a), &graphemes(b))
+}
+
+#[cfg(test)]
+mod bitap_tests {
+    use super::*;
+
+    #[test]
+    fn receives_ascii_strings() {
+        assert_eq!(bitap("hello, world", "wor"), Some(7));
+        assert_eq!(bitap_byte_index("hello, world", "wor"), Some(7));
+        assert_eq!(bitap("hello, world", "xyz"), None);
+    }
+
+    #[test]
+    fn receives_multi_byte_strings() {
+        // 'ï' takes 2 bytes and '🦀' takes 4 bytes.
+        let text = "naïve 🦀 crab";
+        assert_eq!(bitap(text, "crab"), Some(8));
+        assert_eq!(bitap_byte_index(text, "crab"), Some(12));
+        assert_eq!(bitap(text, "🦀"), Some(6));
+        assert_eq!(bitap_byte_index(text, "🦀"), Some(7));
+    }
+
+    #[test]
+    fn matches_char_classes() {
+        assert_eq!(bitap_classes("naïve 🦀", &["ïi", "v"]), Ok(Some(2)));
+        assert_eq!(bitap_classes("naïve 🦀", &[" ", "🦀🐍"]), Ok(Some(5)));
+        assert_eq!(bitap_classes("naive", &["ï", "v"]), Ok(None));
+        assert_eq!(
+            bitap_classes("naïve", &["n", ""]),
+            Err(PatternError::EmptyClass(1))
+        );
+    }
+
+    #[test]
+    fn finds_every_occurrence() {
+        assert_eq!(bitap_all("🦀🦀🦀", "🦀🦀"), vec![0, 1]);
+        assert_eq!(bitap_all("naïve", "x"), vec![]);
+        assert_eq!(bitap_all("ïï", ""), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn replaces_occurrences() {
+        assert_eq!(replace_all("🦀🦀🦀", "🦀🦀", "🐍"), "🐍🦀");
+        assert_eq!(replace_all("naïve naïve", "ï", "i"), "naive naive");
+        assert_eq!(replace_first("naïve naïve", "ï", "i"), "naive naïve");
+        assert_eq!(replace_all("naïve", "", "x"), "naïve");
+        assert_eq!(replace_first("naïve", "x", "y"), "naïve");
+    }
+
+    #[test]
+    fn receives_empty_pattern() {
+        assert_eq!(bitap("naïve", ""), Some(0));
+        assert_eq!(bitap_byte_index("", ""), Some(0));
+    }
+}
+
+#[cfg(test)]
+mod wildcard_tests {
+    use super::*;
+
+    #[test]
+    fn matches_multi_byte_chars() {
+        // '?' matches the 2-byte 'ï' and the 4-byte '🦀' as one char each.
+        assert!(wildcard_match_symbols("naïve", "na?ve", '*', '?'));
+        assert!(wildcard_match_symbols("🦀", "?", '*', '?'));
+        assert!(!wildcard_match_symbols("🦀", "??", '*', '?'));
+        assert!(wildcard_match_symbols("🦀 crab", "🦀*", '*', '?'));
+        assert!(wildcard_match_symbols("", "*", '*', '?'));
+    }
+
+    #[test]
+    fn receives_custom_symbols() {
+        assert!(wildcard_match_symbols("a*c", "a%c", '%', '_'));
+        assert!(wildcard_match_symbols("abc", "_b_", '%', '_'));
+        assert!(!wildcard_match_symbols("abc", "a*", '%', '_'));
+    }
+}
+
+#[cfg(test)]
+mod distance_tests {
+    use super::*;
+
+    #[test]
+    fn counts_chars_not_bytes() {
+        assert_eq!(levenshtein_distance("naïve", "naive"), 1);
+        assert_eq!(levenshtein_myers("naïve", "naive"), 1);
+        assert_eq!(levenshtein_distance_optimised("naïve", "naive"), 1);
+        assert_eq!(levenshtein_within("naïve", "naive", 1), Some(1));
+        assert_eq!(levenshtein_distance_bounded("naïve", "naive", 0), None);
+        assert_eq!(osa_distance("🦀🐍", "🐍🦀"), 1);
+        assert_eq!(damerau_levenshtein_distance("🦀🐍", "🐍🦀"), 1);
+        assert_eq!(levenshtein_distance("🦀", ""), 1);
+    }
+
+    #[test]
+    fn returns_char_edits() {
+        let (distance, edits) = levenshtein_edits("naïve", "naive");
+        assert_eq!(distance, 1);
+        assert_eq!(
+            edits[2],
+            EditOp::Substitute {
+                from: 'ï', to: 'i'
+            }
+        );
+    }
+
+    #[test]
+    fn computes_similarities() {
+        assert_eq!(levenshtein_similarity("naïve", "naive"), 0.8);
+        assert_eq!(damerau_levenshtein_similarity("🦀🐍", "🐍🦀"), 0.5);
+        assert_eq!(ngram_similarity("🦀🦀", "🦀🦀", 2), 1.0);
+        assert_eq!(dice_coefficient("ab", "ba"), 0.0);
+        assert_eq!(gestalt_similarity("naïve", "naïve"), 1.0);
+    }
+
+    #[test]
+    fn compares_lowercased_tokens() {
+        assert_eq!(
+            token_sort_similarity("ÉCOLE  Normale", "normale école"),
+            1.0
+        );
+        assert_eq!(token_set_similarity("John SMITH", "smith john smith"), 1.0);
+        assert_eq!(token_sort_similarity("  ", ""), 1.0);
+        assert!(token_sort_similarity("Smith, John", "john smith") < 1.0);
+    }
+}
+
+#[cfg(test)]
+mod substring_tests {
+    use super::*;
+
+    #[test]
+    fn returns_char_positions() {
+        assert_eq!(longest_common_substring("ïabc", "xabc"), (1, 1, 3));
+    }
+
+    #[test]
+    fn returns_str_slice() {
+        assert_eq!(
+            longest_common_substring_str("🦀 crab 🦀", "a crab!"),
+            " crab"
+        );
+        assert_eq!(longest_common_substring_str("abc", "xyz"), "");
+    }
+
+    #[test]
+    fn returns_char_and_byte_windows() {
+        let text = "🦀 naïve crab";
+        let found = best_match(text, "naive").unwrap();
+        assert_eq!((found.start, found.end, found.distance), (2, 7, 1));
+        let found = best_match_byte_range(text, "naive").unwrap();
+        assert_eq!((found.start, found.end), (5, 11));
+        assert_eq!(&text[found.start..found.end], "naïve");
+        assert_eq!(best_match_within(text, "naive", 0), None);
+    }
+}
+
+#[cfg(test)]
+mod palindrome_tests {
+    use super::*;
+
+    #[test]
+    fn returns_str_slice() {
+        assert_eq!(longest_palindrome_str("x🦀ï🦀y"), "🦀ï🦀");
+        assert_eq!(longest_palindrome_str(""), "");
+    }
+}
+
+#[cfg(test)]
+mod period_tests {
+    use super::*;
+
+    #[test]
+    fn counts_chars_not_bytes() {
+        assert_eq!(border_array("🦀a🦀"), vec![0, 0, 1]);
+        assert_eq!(periods("🦀a🦀"), vec![2]);
+        assert_eq!(periods("ïïï"), vec![1, 2]);
+        assert!(is_periodic("ïaïaï"));
+        assert!(!is_periodic("ïaï"));
+    }
+
+    #[test]
+    fn slices_the_root() {
+        assert_eq!(primitive_root_str("🦀a🦀a"), "🦀a");
+        assert_eq!(primitive_root_str("🦀a🦀"), "🦀a🦀");
+        assert_eq!(primitive_root_str(""), "");
+    }
+}
+
+#[cfg(all(test, feature = "unicode"))]
+mod grapheme_tests {
+    use super::*;
+
+    // "e" followed by U+0301 COMBINING ACUTE ACCENT
+    const DECOMPOSED_E: &str = "e\u{301}";
+    // U+1F44B WAVING HAND followed by U+1F3FD MEDIUM SKIN TONE
+    const WAVING_HAND: &str = "\u{1F44B}\u{1F3FD}";
+
+    #[test]
+    fn counts_combining_characters_once() {
+        let a = format!("caf{}", DECOMPOSED_E);
+        assert_eq!(levenshtein_graphemes(&a, "cafe"), 1);
+        assert_eq!(levenshtein_distance(&a, "cafe"), 1);
+        assert_eq!(levenshtein_graphemes(&a, "caf"), 1);
+        assert_eq!(levenshtein_distance(&a, "caf"), 2);
+        assert_eq!(hamming_graphemes(&a, "cafe"), Some(1));
+        assert_eq!(hamming_distance(&a, "cafe"), None);
+    }
+
+    #[test]
+    fn counts_skin_tone_modifiers_once() {
+        let a = format!("hi {}", WAVING_HAND);
+        assert_eq!(levenshtein_graphemes(&a, "hi"), 2);
+        assert_eq!(levenshtein_distance(&a, "hi"), 3);
+        assert_eq!(levenshtein_graphemes(&a, "hi \u{1F44B}"), 1);
+        assert_eq!(levenshtein_distance(&a, "hi \u{1F44B}"), 1);
+        assert_eq!(hamming_graphemes(&a, "hi !"), Some(1));
+    }
+
+    #[test]
+    fn transposes_graphemes() {
+        let a = format!("{}{}", DECOMPOSED_E, WAVING_HAND);
+        let b = format!("{}{}", WAVING_HAND, DECOMPOSED_E);
+        assert_eq!(damerau_levenshtein_graphemes(&a, &b), 1);
+        assert_eq!(damerau_levenshtein_distance(&a, &b), 4);
+    }
+
+    #[test]
+    fn computes_grapheme_jaro() {
+        let a = format!("n{}e", DECOMPOSED_E);
+        assert_eq!(jaro_graphemes(&a, &a), 1.0);
+        assert!((jaro_graphemes(&a, "nee") - 7.0 / 9.0).abs() < 1e-12);
+        assert!((jaro_similarity(&a, "nee") - 11.0 / 12.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn agrees_with_chars_on_ascii() {
+        for &(a, b) in &[("kitten", "sitting"), ("CA", "ABC"), ("", "abc")] {
+            assert_eq!(levenshtein_graphemes(a, b), levenshtein_distance(a, b));
+            assert_eq!(
+                damerau_levenshtein_graphemes(a, b),
+                damerau_levenshtein_distance(a, b)
+            );
+            assert_eq!(jaro_graphemes(a, b), jaro_similarity(a, b));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! # Merge
+//!
+//! A collection of functions to merge sorted sequences.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// # [K-Way Merge](https://en.wikipedia.org/wiki/K-way_merge_algorithm)
+///
+/// Lazily merges k iterators, each sorted in ascending order, into one sorted iterator. A
+/// min-heap holds the next item of every iterator, so each item is yielded in O(log k) time
+/// with O(k) extra space. Equal items are yielded in the order of their iterators, so the merge
+/// is stable.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::merge;
+///
+/// let merged: Vec<i32> = merge::merge_sorted(vec![
+///     vec![1, 4, 7].into_iter(),
+///     vec![2, 5, 8].into_iter(),
+///     vec![3, 6, 9].into_iter(),
+/// ])
+/// .collect();
+/// assert_eq!(merged, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+pub fn merge_sorted<I: Iterator>(iters: Vec<I>) -> MergeSorted<I>
+where
+    I::Item: Ord,
+{
+    MergeSorted::new(iters)
+}
+
+/// An iterator that merges sorted iterators, created by `merge_sorted`.
+pub struct MergeSorted<I: Iterator> {
+    iters: Vec<I>,
+    // The next item of every unfinished iterator, with the index of the iterator.
+    heads: BinaryHeap<Reverse<(I::Item, usize)>>,
+}
+
+impl<I: Iterator> MergeSorted<I>
+where
+    I::Item: Ord,
+{
+    /// Creates the merge of the iterators, taking the first item of each.
+    pub fn new(mut iters: Vec<I>) -> MergeSorted<I> {
+        let heads = iters
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, iter)| iter.next().map(|item| Reverse((item, i))))
+            .collect();
+        MergeSorted { iters, heads }
+    }
+}
+
+impl<I: Iterator> Iterator for MergeSorted<I>
+where
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let Reverse((item, i)) = self.heads.pop()?;
+        if let Some(next) = self.iters[i].next() {
+            self.heads.push(Reverse((next, i)));
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let heads = self.heads.len();
+        self.iters.iter().map(Iterator::size_hint).fold(
+            (heads, Some(heads)),
+            |(low, high), (iter_low, iter_high)| {
+                let high = match (high, iter_high) {
+                    (Some(high), Some(iter_high)) => high.checked_add(iter_high),
+                    _ => None,
+                };
+                (low.saturating_add(iter_low), high)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod merge_sorted_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn merges_two_iterators() {
+        let a = vec![-3, 0, 0, 5, 9];
+        let b = vec![-4, 0, 2, 10, 11, 12];
+        let merged: Vec<i32> = merge_sorted(vec![a.into_iter(), b.into_iter()]).collect();
+        assert_eq!(merged, vec![-4, -3, 0, 0, 0, 2, 5, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn receives_no_iterators() {
+        let mut merged = merge_sorted(Vec::<::std::vec::IntoIter<i32>>::new());
+        assert_eq!(merged.size_hint(), (0, Some(0)));
+        assert_eq!(merged.next(), None);
+    }
+
+    #[test]
+    fn receives_one_iterator() {
+        let merged: Vec<u8> = merge_sorted(vec![b"aabcz".iter().cloned()]).collect();
+        assert_eq!(merged, b"aabcz");
+    }
+
+    #[test]
+    fn receives_empty_iterators() {
+        let iters = vec![vec![], vec![2, 3], vec![], vec![1]];
+        let merged: Vec<i32> =
+            merge_sorted(iters.into_iter().map(Vec::into_iter).collect()).collect();
+        assert_eq!(merged, vec![1, 2, 3]);
+    }
+
+    /// An item ordered only by its key, so equal items can still tell where they came from.
+    #[derive(Debug)]
+    struct Tagged(i32, char);
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Tagged) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Tagged) -> Option<::std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Tagged) -> ::std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn keeps_equal_items_stable() {
+        let a = vec![Tagged(1, 'a'), Tagged(2, 'a'), Tagged(2, 'a')];
+        let b = vec![Tagged(1, 'b'), Tagged(2, 'b')];
+        let c = vec![Tagged(0, 'c'), Tagged(1, 'c')];
+        let tags: String = merge_sorted(vec![a.into_iter(), b.into_iter(), c.into_iter()])
+            .map(|item| item.1)
+            .collect();
+        assert_eq!(tags, "cabcaab");
+    }
+
+    #[test]
+    fn merges_many_iterators() {
+        let mut rng = test_util::rng();
+        let iters: Vec<Vec<u32>> = (0..100)
+            .map(|_| {
+                let mut iter: Vec<u32> = (0..1000).map(|_| rng.gen_range(0, 10_000)).collect();
+                iter.sort();
+                iter
+            })
+            .collect();
+        let mut expected: Vec<u32> = iters.iter().flatten().cloned().collect();
+        expected.sort();
+
+        let merged = merge_sorted(iters.into_iter().map(Vec::into_iter).collect());
+        assert_eq!(merged.size_hint(), (100_000, Some(100_000)));
+        assert_eq!(merged.collect::<Vec<u32>>(), expected);
+    }
+}
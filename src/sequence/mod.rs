@@ -2,13 +2,20 @@
 //!
 //! A collection of modules containing sequence algorithms.
 
+#[cfg(feature = "std")]
 pub mod match_;
+#[cfg(feature = "std")]
+#[macro_use]
 pub mod selection;
 #[macro_use]
 pub mod search;
-// pub mod merge;
+#[cfg(feature = "std")]
+pub mod alignment;
+#[cfg(feature = "std")]
+pub mod merge;
+#[cfg(feature = "std")]
 pub mod permutation;
-// pub mod alignment;
 // pub mod sort;
-// pub mod subsequence;
+#[cfg(feature = "std")]
+pub mod subsequence;
 // pub mod substring;
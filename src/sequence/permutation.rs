@@ -1,6 +1,9 @@
 //! # Permutation
 //!
-//! A collection of functions to generate permutations of a sequence.
+//! A collection of functions to generate permutations and combinations of a sequence.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// # [Heap's Algorithm](https://en.wikipedia.org/wiki/Heap%27s_algorithm)
 ///
@@ -67,7 +70,7 @@ impl<T: Clone> Iterator for HeapGen<T> {
             let counter = self.swaps[self.n];
             if counter < self.n {
                 // Swap two elements based on n.
-                if self.n % 2 == 0 {
+                if self.n.is_multiple_of(2) {
                     self.last_permutation.swap(0, self.n);
                 } else {
                     self.last_permutation.swap(counter, self.n);
@@ -169,6 +172,112 @@ mod heap_tests {
     }
 }
 
+/// Generates all possible permutations of *n* objects in parallel with
+///  [Rayon](https://github.com/rayon-rs/rayon).
+///
+/// The permutations are partitioned into one task per index of the sequence, whose element is
+/// swapped to the front and fixed; every task runs `HeapGen` on the remaining *n - 1* elements.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rayon;
+/// extern crate ult_algo;
+///
+/// use rayon::prelude::*;
+/// use ult_algo::sequence::permutation;
+///
+/// fn main() {
+///     let sequence = vec![1, 2, 3, 4];
+///     assert_eq!(permutation::par_permutations(sequence).count(), 24);
+///     // Equal elements are told apart by their index, so there are still 3! permutations.
+///     assert_eq!(permutation::par_permutations(vec![1, 1, 2]).count(), 6);
+/// }
+/// ```
+///
+/// # Gotchas
+///
+/// * Order of generated permutations is not deterministic
+/// * Duplicate elements yield duplicate permutations (the same as `HeapGen`)
+#[cfg(feature = "parallel")]
+pub fn par_permutations<T>(sequence: Vec<T>) -> impl ParallelIterator<Item = Vec<T>>
+where
+    T: Clone + Send + Sync,
+{
+    // An empty sequence still has exactly one (empty) permutation.
+    let firsts: Vec<Option<usize>> = if sequence.is_empty() {
+        vec![None]
+    } else {
+        (0..sequence.len()).map(Some).collect()
+    };
+    firsts.into_par_iter().flat_map_iter(move |i| {
+        let mut suffix = sequence.clone();
+        let first = i.map(|i| {
+            // fix the first element of this task
+            suffix.swap(0, i);
+            suffix.remove(0)
+        });
+
+        HeapGen::new(suffix).map(move |permutation| match first {
+            Some(ref first) => {
+                let mut full_permutation = Vec::with_capacity(permutation.len() + 1);
+                full_permutation.push(first.clone());
+                full_permutation.extend(permutation);
+                full_permutation
+            }
+            None => permutation,
+        })
+    })
+}
+
+#[cfg(all(test, feature = "parallel"))]
+mod par_permutations_tests {
+    use super::par_permutations;
+    use rayon::prelude::*;
+
+    #[test]
+    fn generate_correct_number_of_permutations() {
+        let sequence: Vec<usize> = (1..8).collect();
+        assert_eq!(par_permutations(sequence).count(), 5040);
+    }
+
+    #[test]
+    fn generate_unique_permutations() {
+        let sequence = vec![1, 2, 3, 4, 5];
+        let mut permutations: Vec<Vec<usize>> = par_permutations(sequence).collect();
+        permutations.sort_unstable();
+        permutations.dedup();
+        assert_eq!(permutations.len(), 120);
+    }
+
+    #[test]
+    fn receives_duplicate_elements() {
+        let mut permutations: Vec<Vec<u8>> = par_permutations(vec![1, 2, 1]).collect();
+        assert_eq!(permutations.len(), 6);
+        permutations.sort_unstable();
+        permutations.dedup();
+        assert_eq!(
+            permutations,
+            vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]]
+        );
+
+        let permutations: Vec<Vec<char>> = par_permutations(vec!['a'; 4]).collect();
+        assert_eq!(permutations.len(), 24);
+    }
+
+    #[test]
+    fn receives_single_element_sequence() {
+        let permutations: Vec<Vec<char>> = par_permutations(vec!['a']).collect();
+        assert_eq!(permutations, vec![vec!['a']]);
+    }
+
+    #[test]
+    fn receives_empty_sequence() {
+        let permutations: Vec<Vec<u8>> = par_permutations(vec![]).collect();
+        assert_eq!(permutations, vec![Vec::<u8>::new()]);
+    }
+}
+
 /// # [Steinhaus–Johnson–Trotter Algorithm](https://en.wikipedia.org/wiki/Steinhaus%E2%80%93Johnson%E2%80%93Trotter_algorithm)
 ///
 /// Generates all the permutations of *n* elements.
@@ -361,3 +470,712 @@ mod sjt_tests {
         assert_eq!(permutations.len(), 24);
     }
 }
+
+/// # Permutations of a [Multiset](https://en.wikipedia.org/wiki/Permutation#Permutations_of_multisets)
+///
+/// Generates all the distinct permutations of a sequence that may contain equal elements, which
+/// are n! / (n1! n2! ...) in total, where every n_i is how many times an element repeats.
+/// `SJTEven` treats equal elements as different ones and yields repeated permutations.
+///
+/// A Steinhaus–Johnson–Trotter order, where consecutive permutations differ by swapping two
+/// adjacent elements, does not exist for every multiset: [1, 1, 2, 2] has none. So this variant
+/// keeps the sequence sorted at first and steps to the next permutation in lexicographic order
+/// ([Narayana Pandita's algorithm](https://en.wikipedia.org/wiki/Permutation#Generation_in_lexicographic_order)),
+/// which swaps two elements and reverses the suffix after the first of them.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// let gen = permutation::MultisetSJT::new(vec![2, 1, 1]);
+/// assert_eq!(gen.collect::<Vec<_>>(), [[1, 1, 2], [1, 2, 1], [2, 1, 1]]);
+/// ```
+///
+/// # Gotchas
+///
+/// * The first permutation is the sorted sequence, not the sequence itself
+/// * It consumes the vector; we can optionally clone the vector first
+/// * It clones its internal representation for every iteration
+pub struct MultisetSJT<T: Clone + Ord> {
+    /// Last generated permutation
+    last_permutation: Vec<T>,
+    /// Number of iterations
+    count: usize,
+}
+
+impl<T: Clone + Ord> MultisetSJT<T> {
+    pub fn new(mut sequence: Vec<T>) -> MultisetSJT<T> {
+        sequence.sort();
+        MultisetSJT {
+            last_permutation: sequence,
+            count: 0,
+        }
+    }
+}
+
+impl<T: Clone + Ord> Iterator for MultisetSJT<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.count += 1;
+        if self.count == 1 {
+            // Return the sorted sequence as the first permutation.
+            return Some(self.last_permutation.to_vec());
+        }
+
+        // Find the last element that is smaller than its successor.
+        let permutation = &mut self.last_permutation;
+        let i = match (1..permutation.len())
+            .rev()
+            .find(|&i| permutation[i - 1] < permutation[i])
+        {
+            Some(i) => i - 1,
+            None => {
+                // The permutation is in descending order, so all permutations have been
+                // generated. Reset state so it may regenerate them.
+                permutation.reverse();
+                self.count = 0;
+                return None;
+            }
+        };
+
+        // Swap it with the last element larger than itself, then sort the suffix after it.
+        let j = (i + 1..permutation.len())
+            .rev()
+            .find(|&j| permutation[j] > permutation[i])
+            .unwrap();
+        permutation.swap(i, j);
+        permutation[i + 1..].reverse();
+        Some(permutation.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod multiset_sjt_tests {
+    use super::{MultisetSJT, SJTEven};
+
+    fn factorial(n: usize) -> usize {
+        (1..n + 1).product()
+    }
+
+    #[test]
+    fn generate_correct_number_of_permutations() {
+        assert_eq!(MultisetSJT::new(vec![1, 1, 2]).count(), 3);
+        assert_eq!(MultisetSJT::new(vec![1, 1, 1]).count(), 1);
+        assert_eq!(
+            MultisetSJT::new(vec!['a', 'b', 'a', 'c', 'b', 'a']).count(),
+            60
+        );
+        assert_eq!(
+            MultisetSJT::new(vec![3, 1, 2, 2, 1, 3, 3, 1]).count(),
+            factorial(8) / (factorial(3) * factorial(2) * factorial(3))
+        );
+        assert_eq!(MultisetSJT::<i32>::new(vec![]).count(), 1);
+    }
+
+    #[test]
+    fn generate_unique_permutations() {
+        let sequence = vec![4, 2, 2, 7, 4, 2];
+        let mut sorted = sequence.clone();
+        sorted.sort();
+        let permutations: Vec<Vec<i32>> = MultisetSJT::new(sequence).collect();
+        let mut distinct = permutations.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), permutations.len());
+        assert!(permutations.iter().all(|permutation| {
+            let mut permutation = permutation.clone();
+            permutation.sort();
+            permutation == sorted
+        }));
+    }
+
+    #[test]
+    fn agree_with_sjt_even_on_distinct_elements() {
+        let sequence = vec![1, 2, 3, 4, 5];
+        let mut multiset: Vec<Vec<i32>> = MultisetSJT::new(sequence.clone()).collect();
+        let mut distinct: Vec<Vec<i32>> = SJTEven::new(sequence).collect();
+        assert_eq!(multiset.len(), distinct.len());
+        multiset.sort_unstable();
+        distinct.sort_unstable();
+        assert_eq!(multiset, distinct);
+    }
+
+    #[test]
+    fn regenerate_permutations() {
+        let mut gen = MultisetSJT::new(vec![1, 2, 1, 2]);
+        let first: Vec<Vec<i32>> = gen.by_ref().collect();
+        assert_eq!(first.len(), 6);
+        assert_eq!(gen.collect::<Vec<_>>(), first);
+    }
+}
+
+/// # [Permutation](https://en.wikipedia.org/wiki/Permutation#One-line_notation) Application
+///
+/// Reorders the data by a permutation in one-line notation, so that `result[i] = data[perm[i]]`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// let data = ['a', 'b', 'c', 'd'];
+/// assert_eq!(permutation::apply_permutation(&data, &[2, 0, 3, 1]), vec!['c', 'a', 'd', 'b']);
+/// ```
+///
+/// # Panics
+///
+/// * perm should have the same length as data
+/// * perm should only contain indices smaller than data's length
+pub fn apply_permutation<T: Clone>(data: &[T], perm: &[usize]) -> Vec<T> {
+    check_permutation_bounds(data.len(), perm);
+    perm.iter().map(|&i| data[i].clone()).collect()
+}
+
+/// Reorders the data by a permutation in place, like `apply_permutation`, in O(n) time without
+/// extra memory. Every cycle of the permutation is followed from its first index, pulling each
+/// item into the place of the previous one. The visited indices are marked by flipping all bits
+/// of their entries in `perm`, which is restored before returning.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// let mut data = ['a', 'b', 'c', 'd'];
+/// let mut perm = [2, 0, 3, 1];
+/// permutation::apply_permutation_inplace(&mut data, &mut perm);
+/// assert_eq!(data, ['c', 'a', 'd', 'b']);
+/// assert_eq!(perm, [2, 0, 3, 1]);
+/// ```
+///
+/// # Panics
+///
+/// * perm should have the same length as data
+/// * perm should only contain indices smaller than data's length
+/// * perm should not contain repeated indices
+pub fn apply_permutation_inplace<T>(data: &mut [T], perm: &mut [usize]) {
+    check_permutation_bounds(data.len(), perm);
+    // Slices are never longer than isize::MAX, so a flipped index has its highest bit set.
+    let is_marked = |i: usize| i > isize::MAX as usize;
+
+    // Mark the entry at every index found in perm; a repeated index finds its entry marked.
+    let mut repeated = false;
+    for i in 0..perm.len() {
+        let target = if is_marked(perm[i]) {
+            !perm[i]
+        } else {
+            perm[i]
+        };
+        if is_marked(perm[target]) {
+            repeated = true;
+            break;
+        }
+        perm[target] = !perm[target];
+    }
+    for entry in perm.iter_mut().filter(|entry| is_marked(**entry)) {
+        *entry = !*entry;
+    }
+    if repeated {
+        panic!("perm should not contain repeated indices");
+    }
+
+    for start in 0..perm.len() {
+        if is_marked(perm[start]) {
+            continue;
+        }
+        let mut i = start;
+        loop {
+            let next = perm[i];
+            perm[i] = !next;
+            if next == start {
+                break;
+            }
+            data.swap(i, next);
+            i = next;
+        }
+    }
+    for entry in perm.iter_mut() {
+        *entry = !*entry;
+    }
+}
+
+/// Checks that perm has one index smaller than len for every item.
+fn check_permutation_bounds(len: usize, perm: &[usize]) {
+    if perm.len() != len {
+        panic!("perm should have the same length as data");
+    }
+    if perm.iter().any(|&i| i >= len) {
+        panic!("perm should only contain indices smaller than data's length");
+    }
+}
+
+#[cfg(test)]
+mod apply_permutation_tests {
+    use super::*;
+    use rand::Rng;
+    use std::panic;
+    use test_util;
+
+    fn apply_both(data: &[i32], perm: &[usize]) -> Vec<i32> {
+        let applied = apply_permutation(data, perm);
+        let mut inplace = data.to_vec();
+        let mut perm_ = perm.to_vec();
+        apply_permutation_inplace(&mut inplace, &mut perm_);
+        assert_eq!(inplace, applied);
+        assert_eq!(perm_, perm);
+        applied
+    }
+
+    #[test]
+    fn applies_identity() {
+        let data = [5, 3, 8, 1];
+        assert_eq!(apply_both(&data, &[0, 1, 2, 3]), data.to_vec());
+        assert_eq!(apply_both(&[], &[]), vec![]);
+    }
+
+    #[test]
+    fn applies_transposition() {
+        assert_eq!(
+            apply_both(&[10, 20, 30, 40], &[0, 3, 2, 1]),
+            vec![10, 40, 30, 20]
+        );
+    }
+
+    #[test]
+    fn applies_cycles() {
+        // (0 2 4) and (1 3)
+        assert_eq!(
+            apply_both(&[0, 1, 2, 3, 4], &[2, 3, 4, 1, 0]),
+            vec![2, 3, 4, 1, 0]
+        );
+    }
+
+    #[test]
+    fn undoes_with_inverse() {
+        let mut rng = test_util::rng();
+        for n in 0..50 {
+            let data: Vec<i32> = (0..n).map(|_| rng.gen_range(-100, 100)).collect();
+            let mut perm: Vec<usize> = (0..n as usize).collect();
+            rng.shuffle(&mut perm);
+            let mut inverse = vec![0; perm.len()];
+            for (i, &p) in perm.iter().enumerate() {
+                inverse[p] = i;
+            }
+            assert_eq!(apply_both(&apply_both(&data, &perm), &inverse), data);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "perm should only contain indices smaller than data's length")]
+    fn receives_index_out_of_bounds() {
+        apply_permutation(&[1, 2, 3], &[0, 3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "perm should only contain indices smaller than data's length")]
+    fn receives_index_out_of_bounds_inplace() {
+        apply_permutation_inplace(&mut [1, 2, 3], &mut [0, 3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "perm should have the same length as data")]
+    fn receives_short_permutation() {
+        apply_permutation(&[1, 2, 3], &[0, 1]);
+    }
+
+    #[test]
+    fn rejects_repeated_indices_untouched() {
+        let mut data = [1, 2, 3];
+        let mut perm = [2, 0, 2];
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            apply_permutation_inplace(&mut data, &mut perm)
+        }));
+        assert!(result.is_err());
+        assert_eq!(data, [1, 2, 3]);
+        assert_eq!(perm, [2, 0, 2]);
+    }
+}
+
+/// # [Permutation Composition](https://en.wikipedia.org/wiki/Permutation#Composition_of_permutations)
+///
+/// Composes two permutations in one-line notation, so that `result[i] = p[q[i]]`. Applying the
+/// result to some data is the same as applying `p` first and then `q`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// assert_eq!(permutation::compose_permutations(&[1, 2, 0], &[2, 1, 0]), vec![0, 2, 1]);
+/// ```
+///
+/// # Panics
+///
+/// * p and q should have the same length
+/// * p and q should only contain indices smaller than their length
+pub fn compose_permutations(p: &[usize], q: &[usize]) -> Vec<usize> {
+    if p.len() != q.len() {
+        panic!("p and q should have the same length");
+    }
+    if p.iter().chain(q).any(|&i| i >= p.len()) {
+        panic!("p and q should only contain indices smaller than their length");
+    }
+    q.iter().map(|&i| p[i]).collect()
+}
+
+/// # [Inverse Permutation](https://en.wikipedia.org/wiki/Permutation#Inverse_permutations)
+///
+/// Inverts a permutation in one-line notation, so that composing it with its inverse in either
+/// order gives the identity permutation.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// assert_eq!(permutation::invert_permutation(&[2, 0, 3, 1]), vec![1, 3, 0, 2]);
+/// ```
+///
+/// # Panics
+///
+/// * p should only contain indices smaller than its length
+/// * p should not contain repeated indices
+pub fn invert_permutation(p: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![p.len(); p.len()];
+    for (i, &j) in p.iter().enumerate() {
+        if j >= p.len() {
+            panic!("p should only contain indices smaller than its length");
+        }
+        if inverse[j] != p.len() {
+            panic!("p should not contain repeated indices");
+        }
+        inverse[j] = i;
+    }
+    inverse
+}
+
+#[cfg(test)]
+mod compose_permutations_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn identity(n: usize) -> Vec<usize> {
+        (0..n).collect()
+    }
+
+    #[test]
+    fn cancels_with_inverse() {
+        let mut rng = test_util::rng();
+        for n in 0..50 {
+            let mut p = identity(n);
+            rng.shuffle(&mut p);
+            let inverse = invert_permutation(&p);
+            assert_eq!(compose_permutations(&p, &inverse), identity(n));
+            assert_eq!(compose_permutations(&inverse, &p), identity(n));
+        }
+    }
+
+    #[test]
+    fn is_not_commutative() {
+        let (p, q) = ([1, 0, 2], [0, 2, 1]);
+        assert_eq!(compose_permutations(&p, &q), vec![1, 2, 0]);
+        assert_eq!(compose_permutations(&q, &p), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn matches_applying_in_sequence() {
+        let data = ['a', 'b', 'c', 'd'];
+        let (p, q) = ([2, 0, 3, 1], [3, 2, 0, 1]);
+        assert_eq!(
+            apply_permutation(&data, &compose_permutations(&p, &q)),
+            apply_permutation(&apply_permutation(&data, &p), &q)
+        );
+    }
+
+    #[test]
+    fn receives_identity() {
+        let p = [3, 1, 0, 2];
+        assert_eq!(
+            compose_permutations(&identity(4), &identity(4)),
+            identity(4)
+        );
+        assert_eq!(compose_permutations(&p, &identity(4)), p.to_vec());
+        assert_eq!(compose_permutations(&identity(4), &p), p.to_vec());
+        assert_eq!(invert_permutation(&identity(4)), identity(4));
+        assert_eq!(invert_permutation(&[]), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "p and q should have the same length")]
+    fn receives_different_lengths() {
+        compose_permutations(&[0, 1, 2], &[1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "p and q should only contain indices smaller than their length")]
+    fn receives_index_out_of_bounds() {
+        compose_permutations(&[0, 3, 1], &[0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "p should only contain indices smaller than its length")]
+    fn receives_index_out_of_bounds_inverse() {
+        invert_permutation(&[0, 3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "p should not contain repeated indices")]
+    fn receives_repeated_indices_inverse() {
+        invert_permutation(&[0, 2, 2]);
+    }
+}
+
+/// # [Combinations](https://en.wikipedia.org/wiki/Combination)
+///
+/// Generates all the k-combinations of *n* elements without repetition, which are
+/// C(n, k) = n! / (k! (n - k)!) in total, in lexicographic order of their positions.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// let gen = permutation::CombinationGen::new(vec!['a', 'b', 'c', 'd'], 2);
+/// assert_eq!(
+///     gen.collect::<Vec<_>>(),
+///     [['a', 'b'], ['a', 'c'], ['a', 'd'], ['b', 'c'], ['b', 'd'], ['c', 'd']]
+/// );
+/// ```
+///
+/// # Gotchas
+///
+/// * It consumes the vector; we can optionally clone the vector first
+/// * Equal elements at different positions are treated as different elements
+pub struct CombinationGen<T: Clone> {
+    /// Elements to choose from
+    sequence: Vec<T>,
+    /// Positions of the chosen elements in the next combination (None when done)
+    next_indices: Option<Vec<usize>>,
+    /// Number of chosen elements
+    k: usize,
+}
+
+impl<T: Clone> CombinationGen<T> {
+    pub fn new(sequence: Vec<T>, k: usize) -> CombinationGen<T> {
+        CombinationGen {
+            next_indices: first_combination(sequence.len(), k),
+            sequence,
+            k,
+        }
+    }
+}
+
+/// Returns the positions of the first k-combination of n elements, if there is any.
+fn first_combination(n: usize, k: usize) -> Option<Vec<usize>> {
+    if k <= n {
+        Some((0..k).collect())
+    } else {
+        None
+    }
+}
+
+impl<T: Clone> Iterator for CombinationGen<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut indices = match self.next_indices.take() {
+            Some(indices) => indices,
+            None => {
+                // Reset state so it may regenerate all combinations.
+                self.next_indices = first_combination(self.sequence.len(), self.k);
+                return None;
+            }
+        };
+        let combination = indices.iter().map(|&i| self.sequence[i].clone()).collect();
+
+        // Find the rightmost position that can still move right, then pack the rest after it.
+        let (n, k) = (self.sequence.len(), self.k);
+        if let Some(i) = (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            self.next_indices = Some(indices);
+        }
+        Some(combination)
+    }
+}
+
+/// # [Combinations with Repetition](https://en.wikipedia.org/wiki/Combination#Number_of_combinations_with_repetition)
+///
+/// Generates all the k-combinations of *n* elements with repetition (k-multisets), which are
+/// C(n + k - 1, k) in total, in lexicographic order of their positions. Unlike `CombinationGen`,
+/// every element may be chosen more than once, so k may be larger than n.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::permutation;
+///
+/// let gen = permutation::MultiCombinationGen::new(vec!['a', 'b', 'c'], 2);
+/// assert_eq!(
+///     gen.collect::<Vec<_>>(),
+///     [['a', 'a'], ['a', 'b'], ['a', 'c'], ['b', 'b'], ['b', 'c'], ['c', 'c']]
+/// );
+/// ```
+///
+/// # Gotchas
+///
+/// * It consumes the vector; we can optionally clone the vector first
+/// * Equal elements at different positions are treated as different elements
+pub struct MultiCombinationGen<T: Clone> {
+    /// Elements to choose from
+    sequence: Vec<T>,
+    /// Positions of the chosen elements in the next combination (None when done)
+    next_indices: Option<Vec<usize>>,
+    /// Number of chosen elements
+    k: usize,
+}
+
+impl<T: Clone> MultiCombinationGen<T> {
+    pub fn new(sequence: Vec<T>, k: usize) -> MultiCombinationGen<T> {
+        MultiCombinationGen {
+            next_indices: first_multicombination(sequence.len(), k),
+            sequence,
+            k,
+        }
+    }
+}
+
+/// Returns the positions of the first k-combination with repetition of n elements, if there is
+/// any.
+fn first_multicombination(n: usize, k: usize) -> Option<Vec<usize>> {
+    if n > 0 || k == 0 {
+        Some(vec![0; k])
+    } else {
+        None
+    }
+}
+
+impl<T: Clone> Iterator for MultiCombinationGen<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut indices = match self.next_indices.take() {
+            Some(indices) => indices,
+            None => {
+                // Reset state so it may regenerate all combinations.
+                self.next_indices = first_multicombination(self.sequence.len(), self.k);
+                return None;
+            }
+        };
+        let combination = indices.iter().map(|&i| self.sequence[i].clone()).collect();
+
+        // Find the rightmost position that is not at the last element yet, then move it right
+        // and let every position after it start over from the same element.
+        let last = self.sequence.len().saturating_sub(1);
+        if let Some(i) = (0..self.k).rev().find(|&i| indices[i] < last) {
+            indices[i] += 1;
+            for j in i + 1..self.k {
+                indices[j] = indices[i];
+            }
+            self.next_indices = Some(indices);
+        }
+        Some(combination)
+    }
+}
+
+#[cfg(test)]
+mod combination_tests {
+    use super::*;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        if k > n {
+            return 0;
+        }
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    fn is_non_decreasing(combination: &[usize]) -> bool {
+        combination.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    #[test]
+    fn counts_combinations() {
+        for n in 0..7 {
+            for k in 0..9 {
+                let combinations: Vec<Vec<usize>> =
+                    CombinationGen::new((0..n).collect(), k).collect();
+                assert_eq!(combinations.len(), binomial(n, k));
+                assert!(combinations
+                    .iter()
+                    .all(|c| c.windows(2).all(|pair| pair[0] < pair[1])));
+            }
+        }
+    }
+
+    #[test]
+    fn counts_multicombinations() {
+        for n in 1..7 {
+            for k in 0..9 {
+                let combinations: Vec<Vec<usize>> =
+                    MultiCombinationGen::new((0..n).collect(), k).collect();
+                assert_eq!(combinations.len(), binomial(n + k - 1, k));
+                assert!(combinations
+                    .iter()
+                    .all(|c| c.len() == k && is_non_decreasing(c)));
+                let mut distinct = combinations.clone();
+                distinct.dedup();
+                assert_eq!(distinct, combinations);
+            }
+        }
+    }
+
+    #[test]
+    fn receives_k_zero() {
+        assert_eq!(
+            MultiCombinationGen::new(vec![1, 2, 3], 0).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+        assert_eq!(
+            MultiCombinationGen::<i32>::new(vec![], 0).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+        assert_eq!(
+            CombinationGen::new(vec![1, 2, 3], 0).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+        assert_eq!(MultiCombinationGen::<i32>::new(vec![], 2).count(), 0);
+    }
+
+    #[test]
+    fn receives_k_larger_than_length() {
+        assert_eq!(CombinationGen::new(vec![1, 2], 3).count(), 0);
+        assert_eq!(
+            MultiCombinationGen::new(vec![1, 2], 3).collect::<Vec<_>>(),
+            [[1, 1, 1], [1, 1, 2], [1, 2, 2], [2, 2, 2]]
+        );
+    }
+
+    #[test]
+    fn agrees_without_repetition_for_k_one() {
+        let sequence = vec!['x', 'y', 'z', 'x'];
+        assert_eq!(
+            CombinationGen::new(sequence.clone(), 1).collect::<Vec<_>>(),
+            MultiCombinationGen::new(sequence, 1).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn regenerates_after_exhaustion() {
+        let mut gen = MultiCombinationGen::new(vec![1, 2, 3], 2);
+        let first: Vec<_> = gen.by_ref().collect();
+        assert_eq!(gen.collect::<Vec<_>>(), first);
+        let mut gen = CombinationGen::new(vec![1, 2, 3], 2);
+        let first: Vec<_> = gen.by_ref().collect();
+        assert_eq!(gen.collect::<Vec<_>>(), first);
+    }
+}
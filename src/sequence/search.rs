@@ -1,9 +1,14 @@
 //! # Search
 //!
 //! A collection of functions to search for a value from a sequence/function.
+//!
+//! This module only depends on `core`, so it is also available without the `std` feature.
+//! Every panicking search function has a `_checked` variant that returns a `SearchError` instead.
 
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops;
 use num_traits::{FromPrimitive, ToPrimitive};
-use std::ops;
 
 /// Brings all sequence search types and functions required by macros into scope.
 #[macro_export]
@@ -95,18 +100,49 @@ macro_rules! ternary_min {
 ///
 /// * Absolute precision is smaller than 1e-14
 pub fn ternary<F>(
+    search_target: SearchTarget,
+    f: F,
+    left: f64,
+    right: f64,
+    absolute_precision: f64,
+) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    ternary_checked(search_target, f, left, right, absolute_precision)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Finds the minimum or maximum of a
+///  [unimodal](https://en.wikipedia.org/wiki/Unimodality#Unimodal_function) function,
+/// returning an error instead of panicking.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let search_target = search::SearchTarget::Maximum;
+/// let local_maximum = search::ternary_checked(search_target, |x| x % 5.0, 25.4, 30.1, 0.05);
+/// assert_eq!(local_maximum, Ok(29.990194395991274));
+///
+/// let search_target = search::SearchTarget::Maximum;
+/// let result = search::ternary_checked(search_target, |x| x % 5.0, 25.4, 30.1, 1e-15);
+/// assert_eq!(result, Err(search::SearchError::PrecisionTooSmall));
+/// ```
+pub fn ternary_checked<F>(
     search_target: SearchTarget,
     f: F,
     mut left: f64,
     mut right: f64,
     absolute_precision: f64,
-) -> f64
+) -> Result<f64, SearchError>
 where
     F: Fn(f64) -> f64,
 {
     // Ensure that the loop always ends.
     if absolute_precision < 1e-14 {
-        panic!("absolute precision is too small");
+        return Err(SearchError::PrecisionTooSmall);
     }
 
     while (right - left).abs() >= absolute_precision {
@@ -125,7 +161,7 @@ where
             right = right_third;
         }
     }
-    (right + left) / 2f64 // found local maximum
+    Ok((right + left) / 2f64) // found local maximum
 }
 
 /// Kinds of a search target
@@ -226,6 +262,24 @@ mod ternary_tests {
         ternary(search_target, |x| x % 5.0, 30.1, 25.4, 1e-15);
     }
 
+    #[test]
+    fn checked_finds_max() {
+        let search_target = SearchTarget::Maximum;
+        assert_eq!(
+            ternary_checked(search_target, |x| x % 5.0, 25.4, 30.1, 0.05),
+            Ok(29.990194395991274)
+        );
+    }
+
+    #[test]
+    fn checked_receives_very_small_abs_precision() {
+        let search_target = SearchTarget::Minimum;
+        assert_eq!(
+            ternary_checked(search_target, |x| x % 5.0, 30.1, 25.4, 1e-15),
+            Err(SearchError::PrecisionTooSmall)
+        );
+    }
+
     #[test]
     fn use_ternary_max_macro() {
         assert_eq!(
@@ -243,6 +297,120 @@ mod ternary_tests {
     }
 }
 
+/// Finds the minimum or maximum of a
+///  [unimodal](https://en.wikipedia.org/wiki/Unimodality#Unimodal_function) function on the
+/// integers between left and right (both inclusive), with ternary search. Instead of a precision,
+/// it narrows the range down to at most 3 integers and compares them, so the result is the exact
+/// optimum. The function may only take equal values next to each other at the optimum, where
+/// the smallest such integer is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let search_target = search::SearchTarget::Maximum;
+/// assert_eq!(search::ternary_int(search_target, |x| -(x - 7) * (x - 7), -100, 100), 7);
+/// ```
+pub fn ternary_int<F>(search_target: SearchTarget, f: F, mut left: i64, mut right: i64) -> i64
+where
+    F: Fn(i64) -> i64,
+{
+    if left > right {
+        core::mem::swap(&mut left, &mut right);
+    }
+    // Whether the value at x is strictly better than the value at y.
+    let is_better = |x: i64, y: i64| match search_target {
+        SearchTarget::Minimum => f(x) < f(y),
+        SearchTarget::Maximum => f(x) > f(y),
+    };
+
+    // Measure the range without overflowing on wide ranges.
+    let width = |left: i64, right: i64| i128::from(right) - i128::from(left);
+    while width(left, right) > 2 {
+        // Split the range into thirds.
+        let third = (width(left, right) / 3) as i64;
+        let (left_third, right_third) = (left + third, right - third);
+        if is_better(right_third, left_third) {
+            left = left_third + 1;
+        } else {
+            right = right_third;
+        }
+    }
+    (left..=right).fold(left, |best, x| if is_better(x, best) { x } else { best })
+}
+
+#[cfg(test)]
+mod ternary_int_tests {
+    use super::*;
+
+    #[test]
+    fn finds_max_of_negative_parabola() {
+        for top in -50..50 {
+            let f = |x: i64| -(x - top) * (x - top) + 3;
+            assert_eq!(ternary_int(SearchTarget::Maximum, f, -60, 60), top);
+        }
+    }
+
+    #[test]
+    fn finds_min_of_u_shape() {
+        for bottom in -20..20 {
+            let f = |x: i64| (x - bottom).abs() * 4 - 9;
+            assert_eq!(ternary_int(SearchTarget::Minimum, f, 20, -20), bottom);
+        }
+    }
+
+    #[test]
+    fn finds_within_one_of_real_optimum() {
+        // The real maximum of -(2x - 11)² is at 5.5.
+        let x = ternary_int(
+            SearchTarget::Maximum,
+            |x| -(2 * x - 11) * (2 * x - 11),
+            0,
+            1000,
+        );
+        assert_eq!(x, 5);
+        assert!((x as f64 - 5.5).abs() <= 1.0);
+    }
+
+    #[test]
+    fn receives_small_ranges() {
+        let f = |x: i64| x * x;
+        assert_eq!(ternary_int(SearchTarget::Minimum, f, 4, 4), 4);
+        assert_eq!(ternary_int(SearchTarget::Maximum, f, 4, 4), 4);
+        assert_eq!(ternary_int(SearchTarget::Minimum, f, -1, 0), 0);
+        assert_eq!(ternary_int(SearchTarget::Maximum, f, -2, 0), -2);
+    }
+
+    #[test]
+    fn receives_widest_range() {
+        let f = |x: i64| -x.saturating_sub(12345).saturating_abs();
+        let x = ternary_int(SearchTarget::Maximum, f, i64::MIN, i64::MAX);
+        assert_eq!(x, 12345);
+    }
+
+    #[test]
+    fn receives_range_ending_at_max() {
+        let f = |x: i64| x;
+        assert_eq!(
+            ternary_int(SearchTarget::Minimum, f, i64::MAX - 1, i64::MAX),
+            i64::MAX - 1
+        );
+        assert_eq!(
+            ternary_int(SearchTarget::Maximum, f, i64::MAX - 1, i64::MAX),
+            i64::MAX
+        );
+        assert_eq!(
+            ternary_int(SearchTarget::Maximum, f, i64::MAX, i64::MAX),
+            i64::MAX
+        );
+        assert_eq!(
+            ternary_int(SearchTarget::Minimum, f, i64::MIN, i64::MIN + 2),
+            i64::MIN
+        );
+    }
+}
+
 /// # [Exponential Search](https://en.wikipedia.org/wiki/Exponential_search)
 ///
 /// Search for index/position of an item in a sorted sequence with the exponential search algorithm.
@@ -255,30 +423,62 @@ mod ternary_tests {
 /// let sequence: Vec<u32> = (0..100).collect();
 /// assert_eq!(search::exponential(&sequence, &87).unwrap(), 87);
 /// ```
+///
+/// # Panics
+///
+/// * Two items of the sequence could not be compared (e.g. NaN)
 pub fn exponential<T: PartialOrd>(sequence: &[T], val: &T) -> Option<usize> {
+    exponential_checked(sequence, val).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Search for index/position of an item in a sorted sequence with the exponential search
+/// algorithm, returning an error instead of silently misbehaving.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let sequence: Vec<u32> = (0..100).collect();
+/// assert_eq!(search::exponential_checked(&sequence, &87), Ok(Some(87)));
+///
+/// let sequence = vec![1.0, 2.0, f64::NAN, 4.0];
+/// assert_eq!(
+///     search::exponential_checked(&sequence, &3.0),
+///     Err(search::SearchError::Incomparable)
+/// );
+/// ```
+pub fn exponential_checked<T: PartialOrd>(
+    sequence: &[T],
+    val: &T,
+) -> Result<Option<usize>, SearchError> {
     let size = sequence.len();
     if size == 0 {
-        return None;
+        return Ok(None);
     }
 
     // Find the upper and lower bounds for the search space.
     let mut bound = 1;
-    while bound < size && sequence[bound] < *val {
-        bound *= 2;
+    while bound < size {
+        match sequence[bound].partial_cmp(val) {
+            Some(Ordering::Less) => bound *= 2,
+            Some(_) => break,
+            None => return Err(SearchError::Incomparable),
+        }
     }
 
     // Make a slice and perform a binary search on it.
     let (lower_bound, upper_bound) = (bound / 2, (bound + 1).min(size));
-    if let Some(i) = binary(&sequence[lower_bound..upper_bound], val).index {
-        Some(lower_bound + i) // i is relative to the lower bound
-    } else {
-        None
-    }
+    // i is relative to the lower bound
+    let result = binary_checked(&sequence[lower_bound..upper_bound], val)?;
+    Ok(result.index.map(|i| lower_bound + i))
 }
 
 #[cfg(test)]
 mod exponential_tests {
-    use super::exponential;
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
 
     #[test]
     fn receives_integer_sequence() {
@@ -309,6 +509,28 @@ mod exponential_tests {
         let sequence: Vec<i32> = (0..100).collect();
         assert_eq!(exponential(&sequence, &-200), None);
     }
+
+    #[test]
+    fn checked_receives_integer_sequence() {
+        let sequence: Vec<u32> = (0..100).collect();
+        assert_eq!(exponential_checked(&sequence, &87), Ok(Some(87)));
+        assert_eq!(exponential_checked(&sequence, &100), Ok(None));
+    }
+
+    #[test]
+    #[should_panic(expected = "items of the sequence are not comparable")]
+    fn receives_incomparable_items() {
+        exponential(&[1.0, 2.0, 3.0, f64::NAN, 5.0, 6.0], &5.0);
+    }
+
+    #[test]
+    fn checked_receives_incomparable_items() {
+        let sequence = [1.0, 2.0, 3.0, f64::NAN, 5.0, 6.0];
+        assert_eq!(
+            exponential_checked(&sequence, &5.0),
+            Err(SearchError::Incomparable)
+        );
+    }
 }
 
 /// Search for index/position of an item in a sorted sequence.
@@ -437,13 +659,13 @@ pub fn binary_nearest_neighbor<T>(sequence: &[T], val: &T) -> Option<usize>
 where
     T: Copy + PartialOrd + PartialEq + ops::Sub<Output = T>,
 {
-    if sequence.len() == 0 {
+    if sequence.is_empty() {
         return None;
     }
     let result = binary(sequence, val);
     // If target is the first item or is smaller than that, return successor.
     if result.rank == 0 {
-        return if result.index == None {
+        return if result.index.is_none() {
             Some(0)
         } else {
             Some(1)
@@ -480,23 +702,47 @@ where
 /// assert_eq!(result.index.unwrap(), 87);
 /// assert_eq!(result.rank, 87);
 /// ```
+///
+/// # Panics
+///
+/// * Two items of the sequence could not be compared (e.g. NaN)
 pub fn binary<T: PartialOrd + PartialEq>(sequence: &[T], val: &T) -> BinarySearchResult {
+    binary_checked(sequence, val).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Search for position and rank of an item in a sorted sequence with the binary search algorithm,
+/// returning an error when the items cannot be compared (e.g. NaN).
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let sequence: Vec<u32> = (0..100).collect();
+/// let result = search::binary_checked(&sequence, &87).unwrap();
+/// assert_eq!(result.index.unwrap(), 87);
+/// assert_eq!(result.rank, 87);
+/// ```
+pub fn binary_checked<T: PartialOrd>(
+    sequence: &[T],
+    val: &T,
+) -> Result<BinarySearchResult, SearchError> {
     let (mut left, mut right) = (0, sequence.len() as isize - 1);
 
     while left <= right {
-        let m = ((left + right) as f64 / 2f64).floor() as usize;
-        if sequence[m] < *val {
-            left = (m + 1) as isize;
-        } else if sequence[m] > *val {
-            right = m as isize - 1;
-        } else {
-            return BinarySearchResult::new(Some(m), m);
+        let m = ((left + right) / 2) as usize;
+        match sequence[m].partial_cmp(val) {
+            Some(Ordering::Less) => left = (m + 1) as isize,
+            Some(Ordering::Greater) => right = m as isize - 1,
+            Some(Ordering::Equal) => return Ok(BinarySearchResult::new(Some(m), m)),
+            None => return Err(SearchError::Incomparable),
         }
     }
-    BinarySearchResult::new(None, left as usize)
+    Ok(BinarySearchResult::new(None, left as usize))
 }
 
 /// Result from ult_algo::sequence::search::binary
+#[derive(Debug, PartialEq)]
 pub struct BinarySearchResult {
     /// Position of the item
     pub index: Option<usize>,
@@ -523,6 +769,8 @@ impl BinarySearchResult {
 #[cfg(test)]
 mod binary_tests {
     use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
 
     #[test]
     #[should_panic(expected = "rank should be less than or equal to index")]
@@ -665,6 +913,43 @@ mod binary_tests {
         let sequence = vec![10, 20, 50, 60, 70, 75, 100];
         assert_eq!(binary_nearest_neighbor(&sequence, &106).unwrap(), 6);
     }
+
+    #[test]
+    fn checked_receives_integer_sequence() {
+        let sequence: Vec<u32> = (0..100).collect();
+        assert_eq!(
+            binary_checked(&sequence, &87),
+            Ok(BinarySearchResult::new(Some(87), 87))
+        );
+        assert_eq!(
+            binary_checked(&sequence, &100),
+            Ok(BinarySearchResult::new(None, 100))
+        );
+    }
+
+    #[test]
+    fn checked_receives_empty_sequence() {
+        let sequence: [u32; 0] = [];
+        assert_eq!(
+            binary_checked(&sequence, &1),
+            Ok(BinarySearchResult::new(None, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "items of the sequence are not comparable")]
+    fn receives_incomparable_items() {
+        binary(&[1.0, 2.0, f64::NAN, 4.0, 5.0], &3.0);
+    }
+
+    #[test]
+    fn checked_receives_incomparable_items() {
+        let sequence = [1.0, 2.0, f64::NAN, 4.0, 5.0];
+        assert_eq!(
+            binary_checked(&sequence, &3.0),
+            Err(SearchError::Incomparable)
+        );
+    }
 }
 
 /// # [Interpolation Search](https://en.wikipedia.org/wiki/Interpolation_search)
@@ -694,16 +979,42 @@ where
         + ops::Mul<Output = T>
         + ops::Div<Output = T>,
 {
-    if sequence.len() == 0 {
-        return None;
+    interpolation_checked(sequence, val).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Search for index/position of an item in a sorted sequence with the interpolation search
+/// algorithm, returning an error instead of panicking.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let sequence: Vec<u32> = (0..100).collect();
+/// assert_eq!(search::interpolation_checked(&sequence, &87), Ok(Some(87)));
+/// assert_eq!(search::interpolation_checked(&sequence, &100), Ok(None));
+/// ```
+pub fn interpolation_checked<T>(sequence: &[T], val: &T) -> Result<Option<usize>, SearchError>
+where
+    T: Copy
+        + PartialOrd
+        + PartialEq
+        + FromPrimitive
+        + ToPrimitive
+        + ops::Sub<Output = T>
+        + ops::Mul<Output = T>
+        + ops::Div<Output = T>,
+{
+    if sequence.is_empty() {
+        return Ok(None);
     }
     let (mut low, mut high) = (0, sequence.len() - 1);
     // Only when there are more than 1 item left and val is in the range.
     while sequence[high] != sequence[low] && (*val >= sequence[low] && *val <= sequence[high]) {
         // Interpolate position of sought item.
-        let k = T::from_usize(high - low).unwrap();
+        let k = T::from_usize(high - low).ok_or(SearchError::InvalidCast)?;
         let offset = (*val - sequence[low]) * k / (sequence[high] - sequence[low]);
-        let mid = low + T::to_usize(&offset).unwrap();
+        let mid = low + T::to_usize(&offset).ok_or(SearchError::InvalidCast)?;
 
         // Narrow the search space.
         if sequence[mid] < *val {
@@ -711,20 +1022,22 @@ where
         } else if sequence[mid] > *val {
             high = mid - 1;
         } else {
-            return Some(mid); // gotcha!
+            return Ok(Some(mid)); // gotcha!
         }
     }
     // Either it's the last item or there is none.
     if sequence[low] == *val {
-        Some(low)
+        Ok(Some(low))
     } else {
-        None
+        Ok(None)
     }
 }
 
 #[cfg(test)]
 mod interpolation_tests {
-    use super::interpolation;
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
 
     #[test]
     fn receives_integer_sequence() {
@@ -755,4 +1068,576 @@ mod interpolation_tests {
         let sequence: Vec<i32> = (0..100).collect();
         assert_eq!(interpolation(&sequence, &-200), None);
     }
+
+    #[test]
+    fn checked_receives_integer_sequence() {
+        let sequence: Vec<u32> = (0..100).collect();
+        assert_eq!(interpolation_checked(&sequence, &87), Ok(Some(87)));
+        assert_eq!(interpolation_checked(&sequence, &100), Ok(None));
+    }
+
+    #[test]
+    fn checked_receives_uncastable_offset() {
+        let sequence = [1.0, 2.0, f64::INFINITY];
+        assert_eq!(
+            interpolation_checked(&sequence, &f64::INFINITY),
+            Err(SearchError::InvalidCast)
+        );
+    }
+}
+
+/// Search for index/position of an item in a sorted sequence with the interpolation search
+/// algorithm, where the caller estimates where an item lies between two others. Given
+/// `(val, low_val, high_val)`, `position_estimate` returns the fraction of the way from low_val
+/// to high_val at which val is expected, so that sequences that are not uniformly distributed,
+/// such as exponential or logarithmic ones, are searched in few steps. Fractions outside [0, 1]
+/// are clamped, and NaN is taken as the middle. A poor estimate may take up to O(n) steps.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let sequence: Vec<u64> = (0..40).map(|i| 1 << i).collect();
+/// let log2 = |x: &u64| 63.0 - x.leading_zeros() as f64;
+/// let estimate = |val: &u64, low: &u64, high: &u64| {
+///     (log2(val) - log2(low)) / (log2(high) - log2(low))
+/// };
+/// assert_eq!(search::interpolation_nonuniform(&sequence, &(1 << 29), estimate), Some(29));
+/// assert_eq!(search::interpolation_nonuniform(&sequence, &1000, estimate), None);
+/// ```
+pub fn interpolation_nonuniform<T, F>(
+    sequence: &[T],
+    val: &T,
+    position_estimate: F,
+) -> Option<usize>
+where
+    T: PartialOrd,
+    F: Fn(&T, &T, &T) -> f64,
+{
+    if sequence.is_empty() {
+        return None;
+    }
+    let (mut low, mut high) = (0, sequence.len() - 1);
+    // Only when there are more than 1 item left and val is in the range.
+    while low < high && *val >= sequence[low] && *val <= sequence[high] {
+        // Estimate position of sought item.
+        let fraction = position_estimate(val, &sequence[low], &sequence[high]);
+        let fraction = if fraction.is_nan() {
+            0.5
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        let mid = low + ((high - low) as f64 * fraction) as usize;
+
+        // Narrow the search space.
+        if sequence[mid] < *val {
+            low = mid + 1;
+        } else if sequence[mid] > *val {
+            high = mid - 1;
+        } else {
+            return Some(mid); // gotcha!
+        }
+    }
+    // Either it's the last item or there is none.
+    if low < sequence.len() && sequence[low] == *val {
+        Some(low)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod interpolation_nonuniform_tests {
+    use super::*;
+    use core::cell::Cell;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
+
+    /// Powers of 1.01, whose logarithms are uniformly distributed.
+    fn exponential_sequence(n: usize) -> Vec<f64> {
+        let mut power = 1.0;
+        (0..n)
+            .map(|_| {
+                power *= 1.01;
+                power
+            })
+            .collect()
+    }
+
+    fn log_estimate(val: &f64, low: &f64, high: &f64) -> f64 {
+        (val.ln() - low.ln()) / (high.ln() - low.ln())
+    }
+
+    #[test]
+    fn finds_every_position() {
+        let sequence = exponential_sequence(1000);
+        for (i, val) in sequence.iter().enumerate() {
+            assert_eq!(
+                interpolation_nonuniform(&sequence, val, log_estimate),
+                Some(i)
+            );
+            assert_eq!(
+                interpolation_nonuniform(&sequence, &(val * 1.001), log_estimate),
+                None
+            );
+        }
+        assert_eq!(
+            interpolation_nonuniform(&sequence, &0.5, log_estimate),
+            None
+        );
+        assert_eq!(interpolation_nonuniform(&[], &0.5, log_estimate), None);
+    }
+
+    #[test]
+    fn probes_less_than_binary_search() {
+        let sequence = exponential_sequence(1000);
+        let probes = Cell::new(0);
+        let counted_estimate = |val: &f64, low: &f64, high: &f64| {
+            probes.set(probes.get() + 1);
+            log_estimate(val, low, high)
+        };
+        for val in sequence.iter() {
+            interpolation_nonuniform(&sequence, val, counted_estimate);
+        }
+        let interpolation_probes = probes.get();
+
+        let mut binary_probes = 0;
+        for val in sequence.iter() {
+            let (mut low, mut high) = (0, sequence.len());
+            while low < high {
+                binary_probes += 1;
+                let mid = (low + high) / 2;
+                if sequence[mid] < *val {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+        }
+        assert!(interpolation_probes * 3 < binary_probes);
+    }
+
+    #[test]
+    fn survives_poor_estimates() {
+        let sequence: Vec<u32> = (0..100).map(|i| i * 3).collect();
+        for &estimate in [-1.0, 0.0, 0.999, 2.0, f64::NAN].iter() {
+            for (i, val) in sequence.iter().enumerate() {
+                let found = interpolation_nonuniform(&sequence, val, |_, _, _| estimate);
+                assert_eq!(found, Some(i));
+            }
+            assert_eq!(
+                interpolation_nonuniform(&sequence, &100, |_, _, _| estimate),
+                None
+            );
+        }
+    }
+}
+
+/// # [Linear Search](https://en.wikipedia.org/wiki/Linear_search)
+///
+/// Search for index/position of the first occurrence of an item in an unsorted sequence.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let sequence = [4, 8, 15, 16, 23, 42, 15];
+/// assert_eq!(search::linear_scan(&sequence, &15), Some(2));
+/// assert_eq!(search::linear_scan(&sequence, &7), None);
+/// ```
+pub fn linear_scan<T: PartialEq>(sequence: &[T], val: &T) -> Option<usize> {
+    sequence.iter().position(|x| x == val)
+}
+
+/// Search for index/position of the first occurrence of a byte in a byte slice, comparing 16
+/// bytes at a time with SSE2 on x86 and falling back to `linear_scan` elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search;
+///
+/// let haystack = b"the quick brown fox jumps over the lazy dog";
+/// assert_eq!(search::linear_scan_simd(haystack, b'z'), Some(37));
+/// assert_eq!(search::linear_scan_simd(haystack, b'!'), None);
+/// ```
+pub fn linear_scan_simd(haystack: &[u8], needle: u8) -> Option<usize> {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::*;
+
+        let mut i = 0;
+        // SAFETY: SSE2 is enabled at compile time, and each unaligned load reads the 16 bytes
+        // starting at i, which are within the haystack.
+        unsafe {
+            let needles = _mm_set1_epi8(needle as i8);
+            while i + 16 <= haystack.len() {
+                let chunk = _mm_loadu_si128(haystack.as_ptr().add(i) as *const __m128i);
+                // One bit per byte of the chunk, set where the byte equals the needle.
+                let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(chunk, needles));
+                if mask != 0 {
+                    return Some(i + mask.trailing_zeros() as usize);
+                }
+                i += 16;
+            }
+        }
+        // Scan the tail that does not fill a chunk.
+        linear_scan(&haystack[i..], &needle).map(|j| i + j)
+    }
+    #[cfg(not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )))]
+    {
+        linear_scan(haystack, &needle)
+    }
+}
+
+#[cfg(test)]
+mod linear_scan_tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
+
+    #[test]
+    fn finds_first_item() {
+        let sequence: Vec<u8> = (0..100).collect();
+        assert_eq!(linear_scan(&sequence, &0), Some(0));
+        assert_eq!(linear_scan_simd(&sequence, 0), Some(0));
+    }
+
+    #[test]
+    fn finds_middle_item() {
+        let sequence: Vec<u8> = (0..100).collect();
+        assert_eq!(linear_scan(&sequence, &50), Some(50));
+        assert_eq!(linear_scan_simd(&sequence, 50), Some(50));
+    }
+
+    #[test]
+    fn finds_last_item() {
+        let sequence: Vec<u8> = (0..100).collect();
+        assert_eq!(linear_scan(&sequence, &99), Some(99));
+        assert_eq!(linear_scan_simd(&sequence, 99), Some(99));
+    }
+
+    #[test]
+    fn finds_non_existent_item() {
+        let sequence: Vec<u8> = (0..100).collect();
+        assert_eq!(linear_scan(&sequence, &100), None);
+        assert_eq!(linear_scan_simd(&sequence, 100), None);
+    }
+
+    #[test]
+    fn receives_empty_sequence() {
+        assert_eq!(linear_scan(&[], &1), None);
+        assert_eq!(linear_scan_simd(&[], 1), None);
+    }
+
+    #[test]
+    fn finds_first_of_repeated_items() {
+        let mut sequence = [0u8; 40];
+        for &i in &[17, 18, 33] {
+            sequence[i] = 1;
+        }
+        assert_eq!(linear_scan(&sequence, &1), Some(17));
+        assert_eq!(linear_scan_simd(&sequence, 1), Some(17));
+    }
+
+    #[test]
+    fn agrees_on_every_position_and_length() {
+        for len in 0..50 {
+            for position in 0..len {
+                let mut sequence = vec![b'a'; len];
+                sequence[position] = b'b';
+                assert_eq!(linear_scan_simd(&sequence, b'b'), Some(position));
+            }
+            assert_eq!(linear_scan_simd(&vec![b'a'; len], b'b'), None);
+        }
+    }
+}
+
+/// # [Rabin Fingerprint](https://en.wikipedia.org/wiki/Rabin_fingerprint)
+///
+/// Hashes bytes as the remainder of their polynomial over GF(2) divided by an irreducible
+/// polynomial of degree 64, such that a window can be rolled over data one byte at a time: a
+/// byte is appended by multiplying the hash by x<sup>8</sup>, and the oldest byte is removed by
+/// cancelling out what it contributes from the start of the window. The polynomial is
+/// x^64 + x^4 + x^3 + x + 1.
+///
+/// `remove` computes that contribution for any window size in O(log n) multiplications. A
+/// fingerprint made `with_window` has a table of it for every byte, built once for its window
+/// size, which makes `remove` and `roll` O(1) for windows of that size.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::search::RabinFingerprint;
+///
+/// let rabin = RabinFingerprint::with_window(4);
+/// let data = b"rolling hash";
+/// let mut hash = rabin.hash(&data[..4]);
+/// for i in 4..data.len() {
+///     hash = rabin.roll(hash, data[i - 4], data[i]);
+///     assert_eq!(hash, rabin.hash(&data[i - 3..i + 1]));
+/// }
+///
+/// let rabin = RabinFingerprint::new();
+/// let hash = rabin.append(rabin.hash(b"abc"), b'd');
+/// assert_eq!(rabin.remove(hash, b'a', 4), rabin.hash(b"bcd"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RabinFingerprint {
+    /// Coefficients of the polynomial below x^64, which is implied
+    polynomial: u64,
+    window_size: Option<usize>,
+    /// The contribution of every byte at the start of a window, to be cancelled out
+    removals: [u64; 256],
+}
+
+impl Default for RabinFingerprint {
+    fn default() -> RabinFingerprint {
+        RabinFingerprint::new()
+    }
+}
+
+impl RabinFingerprint {
+    /// Creates a fingerprint without a window size, whose `remove` takes O(log n) time.
+    pub fn new() -> RabinFingerprint {
+        RabinFingerprint {
+            polynomial: 0x1b,
+            window_size: None,
+            removals: [0; 256],
+        }
+    }
+
+    /// Creates a fingerprint for rolling a window of window_size bytes over data in O(1) time
+    /// per byte.
+    ///
+    /// # Panics
+    ///
+    /// * window_size is 0
+    pub fn with_window(window_size: usize) -> RabinFingerprint {
+        let mut rabin = RabinFingerprint::new();
+        let shift = rabin.window_shift(window_size);
+        for byte in 0..256 {
+            rabin.removals[byte] = rabin.multiply(byte as u64, shift);
+        }
+        rabin.window_size = Some(window_size);
+        rabin
+    }
+
+    /// Returns the number of bytes in the rolled window, if the fingerprint was made with one.
+    pub fn window_size(&self) -> Option<usize> {
+        self.window_size
+    }
+
+    /// Hashes all the bytes of the data.
+    pub fn hash(&self, data: &[u8]) -> u64 {
+        data.iter().fold(0, |hash, &byte| self.append(hash, byte))
+    }
+
+    /// Rolls a hash forward by appending a byte after the hashed data.
+    pub fn append(&self, hash: u64, byte: u8) -> u64 {
+        (0..8).fold(hash, |hash, _| self.times_x(hash)) ^ u64::from(byte)
+    }
+
+    /// Rolls a hash forward by removing the first byte of the hashed window of window_size
+    /// bytes, in O(1) time if that is the window size of the fingerprint.
+    ///
+    /// # Panics
+    ///
+    /// * window_size is 0
+    pub fn remove(&self, hash: u64, byte: u8, window_size: usize) -> u64 {
+        if self.window_size == Some(window_size) {
+            hash ^ self.removals[byte as usize]
+        } else {
+            hash ^ self.multiply(u64::from(byte), self.window_shift(window_size))
+        }
+    }
+
+    /// Slides the hashed window one byte forward, removing its first byte and appending the next.
+    ///
+    /// # Panics
+    ///
+    /// * the fingerprint was not made with a window size
+    pub fn roll(&self, hash: u64, outgoing: u8, incoming: u8) -> u64 {
+        let window_size = match self.window_size {
+            Some(window_size) => window_size,
+            None => panic!("the fingerprint should be made with_window to roll"),
+        };
+        self.append(self.remove(hash, outgoing, window_size), incoming)
+    }
+
+    /// Returns the power of x that the first byte of a window of window_size bytes has been
+    /// multiplied by, once per bit after it.
+    fn window_shift(&self, window_size: usize) -> u64 {
+        if window_size == 0 {
+            panic!("window_size should be larger than 0");
+        }
+        self.power_of_x(8 * (window_size as u64 - 1))
+    }
+
+    /// Multiplies a polynomial by x modulo the fingerprint polynomial.
+    fn times_x(&self, a: u64) -> u64 {
+        if a >> 63 == 1 {
+            (a << 1) ^ self.polynomial
+        } else {
+            a << 1
+        }
+    }
+
+    /// Multiplies two polynomials modulo the fingerprint polynomial.
+    fn multiply(&self, a: u64, b: u64) -> u64 {
+        (0..64).rev().fold(0, |product, bit| {
+            let product = self.times_x(product);
+            if (b >> bit) & 1 == 1 {
+                product ^ a
+            } else {
+                product
+            }
+        })
+    }
+
+    /// Raises x to the power of e modulo the fingerprint polynomial by repeated squaring.
+    fn power_of_x(&self, mut e: u64) -> u64 {
+        let (mut power, mut base) = (1, 2);
+        while e > 0 {
+            if e & 1 == 1 {
+                power = self.multiply(power, base);
+            }
+            base = self.multiply(base, base);
+            e >>= 1;
+        }
+        power
+    }
+}
+
+#[cfg(test)]
+mod rabin_fingerprint_tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::vec::Vec;
+
+    /// Bytes from a xorshift generator, as tests cannot depend on rand without std.
+    fn random_bytes(state: &mut u64, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                *state ^= *state << 13;
+                *state ^= *state >> 7;
+                *state ^= *state << 17;
+                (*state >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hashes_empty_data_to_zero() {
+        assert_eq!(RabinFingerprint::new().hash(&[]), 0);
+    }
+
+    #[test]
+    fn rolls_like_hashing_from_scratch() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        let data = random_bytes(&mut state, 500);
+        for &window in [1, 2, 7, 8, 9, 31, 64, 100].iter() {
+            let (rabin, plain) = (
+                RabinFingerprint::with_window(window),
+                RabinFingerprint::new(),
+            );
+            assert_eq!(rabin.window_size(), Some(window));
+            let (mut hash, mut plain_hash) =
+                (rabin.hash(&data[..window]), plain.hash(&data[..window]));
+            for i in window..data.len() {
+                hash = rabin.roll(hash, data[i - window], data[i]);
+                plain_hash =
+                    plain.append(plain.remove(plain_hash, data[i - window], window), data[i]);
+                assert_eq!(hash, rabin.hash(&data[i + 1 - window..i + 1]));
+                assert_eq!(plain_hash, hash);
+            }
+        }
+    }
+
+    #[test]
+    fn removes_an_added_byte() {
+        for &rabin in &[&RabinFingerprint::new(), &RabinFingerprint::with_window(7)] {
+            for byte in 0..=255 {
+                assert_eq!(rabin.remove(rabin.append(0, byte), byte, 1), 0);
+                let hash = rabin.hash(b"window");
+                let mut added = vec![byte];
+                added.extend_from_slice(b"window");
+                assert_eq!(rabin.remove(rabin.hash(&added), byte, 7), hash);
+            }
+        }
+    }
+
+    #[test]
+    fn hashes_independently_of_window_size() {
+        let data = b"the window size only matters for removals";
+        assert_eq!(
+            RabinFingerprint::new().hash(data),
+            RabinFingerprint::with_window(100).hash(data)
+        );
+    }
+
+    #[test]
+    fn rarely_collides() {
+        let rabin = RabinFingerprint::new();
+        let mut state = 88_172_645_463_325_252;
+        let mut collisions = 0;
+        for _ in 0..10_000 {
+            let a = random_bytes(&mut state, 16);
+            let b = random_bytes(&mut state, 16);
+            if a != b && rabin.hash(&a) == rabin.hash(&b) {
+                collisions += 1;
+            }
+        }
+        assert!(collisions < 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "window_size should be larger than 0")]
+    fn receives_empty_window() {
+        RabinFingerprint::with_window(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window_size should be larger than 0")]
+    fn removes_from_empty_window() {
+        RabinFingerprint::new().remove(0, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the fingerprint should be made with_window to roll")]
+    fn rolls_without_window() {
+        RabinFingerprint::new().roll(0, 0, 0);
+    }
+}
+
+/// Errors from the checked search functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchError {
+    /// Absolute precision is smaller than 1e-14
+    PrecisionTooSmall,
+    /// Generic type failed to be casted from and to usize
+    InvalidCast,
+    /// Two items of the sequence could not be compared (e.g. NaN)
+    Incomparable,
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SearchError::PrecisionTooSmall => write!(f, "absolute precision is too small"),
+            SearchError::InvalidCast => write!(f, "failed to cast between the item type and usize"),
+            SearchError::Incomparable => write!(f, "items of the sequence are not comparable"),
+        }
+    }
 }
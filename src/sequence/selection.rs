@@ -2,12 +2,19 @@
 //!
 //! A collection of functions to select an item from a sequence.
 
+use num_traits::ToPrimitive;
 use rand;
 use rand::Rng;
+use sequence::permutation;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Range;
 
 /// # [Quickselect](https://en.wikipedia.org/wiki/Quickselect)
 ///
 /// Selects the k-th smallest element in an unordered slice, where 0 <= k < list.len().
+/// Items that are incomparable to each other, such as NaN, are treated as equal.
 ///
 /// # Examples
 ///
@@ -26,31 +33,186 @@ use rand::Rng;
 ///
 /// * k is larger than or equal to the list's length
 pub fn quick_smallest<T: PartialOrd>(list: &mut [T], k: usize) -> &T {
+    quick_smallest_with_rng(list, k, &mut rand::thread_rng())
+}
+
+/// Selects the k-th smallest element in an unordered slice with quickselect, like
+/// `quick_smallest`, where the pivots are drawn from the given random number generator. A
+/// seeded generator makes the selection reproducible, down to how the slice is reordered.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// extern crate ult_algo;
+///
+/// use rand::{SeedableRng, StdRng};
+/// use ult_algo::sequence::selection;
+///
+/// fn main() {
+///     let mut rng = StdRng::from_seed(&[2018][..]);
+///     let mut list = [10, -30, 5, -2, 7, 0];
+///     assert_eq!(*selection::quick_smallest_with_rng(&mut list, 2, &mut rng), 0);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn quick_smallest_with_rng<'a, T, R>(list: &'a mut [T], k: usize, rng: &mut R) -> &'a T
+where
+    T: PartialOrd,
+    R: Rng,
+{
+    select_by(list, k, &mut partial_order, rng)
+}
+
+/// Selects the k-th smallest element in an unordered slice with quickselect, like
+/// `quick_smallest`, where the order is defined by a comparator. It is called once for every
+/// item of every partition, and may keep state such as a count of comparisons.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [("ann", 72), ("bob", 91), ("cat", 85), ("dan", 64)];
+/// let best = selection::quick_smallest_by(&mut list, 0, |a, b| b.1.cmp(&a.1));
+/// assert_eq!(best.0, "bob");
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn quick_smallest_by<T, F>(list: &mut [T], k: usize, mut compare: F) -> &T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    select_by(list, k, &mut compare, &mut rand::thread_rng())
+}
+
+/// Orders two items that may be incomparable (such as NaN) by treating them as equal, so that
+/// they end up in the same partition around a pivot.
+fn partial_order<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Quickselect by a comparator, with pivots drawn from a random number generator.
+fn select_by<'a, T, F, R>(
+    mut list: &'a mut [T],
+    mut k: usize,
+    compare: &mut F,
+    rng: &mut R,
+) -> &'a T
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     if k >= list.len() {
         panic!("k={} should be smaller than list's length", k);
     }
 
-    while list.len() != 1 {
+    // Narrow the list down to the part holding the k-th smallest element, instead of recursing.
+    while list.len() > 1 {
         // Randomly choose a pivot and partitions the list accordingly.
-        let mut rng = rand::thread_rng();
         let pivot_idx = rng.gen_range(0, list.len());
-        let pivot_idx = partition(list, pivot_idx);
+        let equal = partition_by(list, pivot_idx, compare);
 
-        if k == pivot_idx {
-            return &list[k]; // found it!
-        } else if k < pivot_idx {
-            return quick_smallest(&mut list[..pivot_idx], k); // take the left side
-        } else {
+        if k < equal.start {
+            list = &mut { list }[..equal.start]; // take the left side
+        } else if k >= equal.end {
             // Take the right side and transform k to fit the new slice.
-            return quick_smallest(&mut list[pivot_idx + 1..], k - pivot_idx - 1);
+            k -= equal.end;
+            list = &mut { list }[equal.end..];
+        } else {
+            break; // found it!
+        }
+    }
+    &list[k]
+}
+
+/// Partitions a list around the value of the chosen pivot into three parts: items smaller than
+/// the pivot, items neither smaller nor larger, and items larger than the pivot. Returns the
+/// range of the middle part, so that a list of equal items is done with in one pass.
+fn partition<T: PartialOrd>(list: &mut [T], pivot_idx: usize) -> Range<usize> {
+    partition_by(list, pivot_idx, &mut partial_order)
+}
+
+/// # [Three-way Partitioning](https://en.wikipedia.org/wiki/Dutch_national_flag_problem)
+///
+/// Partitions a list around the value of the item at pivot_idx in one pass, like the Dutch
+/// national flag problem: items smaller than the pivot come first, then items equal to it, then
+/// larger items. Returns the bounds (start, end) of the equal items, where end is exclusive.
+/// Quickselect uses it so that lists with many equal items do not degrade it, as the search is
+/// over once k falls within the equal items.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [3, 1, 3, 5, 0, 3, 4];
+/// let (start, end) = selection::partition3(&mut list, 0);
+/// assert_eq!((start, end), (2, 5));
+/// assert!(list[..2].iter().all(|&x| x < 3));
+/// assert_eq!(list[2..5], [3, 3, 3]);
+/// assert!(list[5..].iter().all(|&x| x > 3));
+/// ```
+///
+/// # Panics
+///
+/// * pivot_idx should be smaller than list's length
+pub fn partition3<T: PartialOrd>(list: &mut [T], pivot_idx: usize) -> (usize, usize) {
+    if pivot_idx >= list.len() {
+        panic!("pivot_idx should be smaller than list's length");
+    }
+    let equal = partition(list, pivot_idx);
+    (equal.start, equal.end)
+}
+
+/// Partitions a list like `partition`, where the order is defined by a comparator.
+fn partition_by<T, F>(list: &mut [T], pivot_idx: usize, compare: &mut F) -> Range<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    list.swap(0, pivot_idx); // move pivot to the front, out of the way
+    let (pivot, rest) = list.split_at_mut(1);
+    let equal = partition_around_by(rest, &pivot[0], compare);
+    list.swap(0, equal.start); // move pivot to the start of its equals
+    equal.start..equal.end + 1
+}
+
+/// Partitions a list around a given value, which is not one of its items, into three parts:
+/// items smaller than the value, items neither smaller nor larger, and items larger than the
+/// value, where the order is defined by a comparator. Returns the range of the middle part.
+fn partition_around_by<T, F>(list: &mut [T], pivot: &T, compare: &mut F) -> Range<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let (mut smaller_end, mut i, mut larger_start) = (0, 0, list.len());
+    while i < larger_start {
+        // Compare with pivot value.
+        match compare(&list[i], pivot) {
+            Ordering::Less => {
+                list.swap(smaller_end, i); // put it into the left side of pivot
+                smaller_end += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                larger_start -= 1;
+                list.swap(i, larger_start); // put it into the right side of pivot
+            }
+            Ordering::Equal => i += 1,
         }
     }
-    &list[0] // only one possibility
+    smaller_end..larger_start
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod quick_tests {
     use super::*;
+    use std::thread;
 
     #[test]
     fn receives_integer_vector_slice() {
@@ -76,23 +238,2190 @@ mod quick_tests {
         let mut list = vec![10, -30, -2, 5, 7, 0];
         quick_smallest(&mut list[..], 6);
     }
+
+    #[test]
+    fn runs_on_a_small_stack() {
+        let handle = thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let mut list: Vec<u32> = (0..10_000_000).rev().collect();
+                *quick_smallest(&mut list, 1234567)
+            })
+            .unwrap();
+        assert_eq!(handle.join().unwrap(), 1234567);
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let mut list = [3; 1000];
+        assert_eq!(*quick_smallest(&mut list[..], 500), 3);
+        let mut list = [2, 1, 2, 2, 1, 2, 3];
+        assert_eq!(*quick_smallest(&mut list[..], 1), 1);
+        assert_eq!(*quick_smallest(&mut list[..], 5), 2);
+    }
+}
+
+#[cfg(test)]
+mod quick_with_rng_tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static PIVOTS: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// An integer that records every value it is compared against, which is always the pivot.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Recorded(i32);
+
+    impl PartialOrd for Recorded {
+        fn partial_cmp(&self, pivot: &Recorded) -> Option<Ordering> {
+            PIVOTS.with(|pivots| pivots.borrow_mut().push(pivot.0));
+            self.0.partial_cmp(&pivot.0)
+        }
+    }
+
+    fn select_recorded(list: &[i32], k: usize, seed: usize) -> (i32, Vec<Recorded>, Vec<i32>) {
+        let mut list: Vec<Recorded> = list.iter().map(|&x| Recorded(x)).collect();
+        let mut rng = StdRng::from_seed(&[seed][..]);
+        PIVOTS.with(|pivots| pivots.borrow_mut().clear());
+        let kth = quick_smallest_with_rng(&mut list, k, &mut rng).0;
+        (kth, list, PIVOTS.with(|pivots| pivots.borrow().clone()))
+    }
+
+    #[test]
+    fn repeats_runs_with_the_same_seed() {
+        let list: Vec<i32> = (0..500).map(|i| (i * 7919) % 503).collect();
+        let first = select_recorded(&list, 250, 42);
+        assert_eq!(select_recorded(&list, 250, 42), first);
+        assert!(first.2.len() >= list.len() - 1);
+    }
+
+    #[test]
+    fn agrees_with_sorting_across_seeds() {
+        let list: Vec<i32> = (0..200).map(|i| (i * 37) % 101 - 50).collect();
+        let mut sorted = list.clone();
+        sorted.sort();
+        for seed in 0..50 {
+            let mut rng = StdRng::from_seed(&[seed][..]);
+            let k = seed * 4;
+            assert_eq!(
+                *quick_smallest_with_rng(&mut list.clone(), k, &mut rng),
+                sorted[k]
+            );
+        }
+    }
 }
 
-/// Partitions a list based on the chosen pivot. Left side items are smaller than the pivot,
-/// while right side items are larger than or equal to the pivot.
-fn partition<T: PartialOrd>(list: &mut [T], pivot_idx: usize) -> usize {
-    let last_idx = list.len() - 1;
-    let mut store_idx = 0;
+#[cfg(test)]
+mod quick_by_tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Player {
+        name: &'static str,
+        score: u32,
+    }
 
-    list.swap(pivot_idx, last_idx); // move pivot to end
+    #[test]
+    fn selects_in_descending_order() {
+        let mut list = [10, -30, -2, 5, 7, 0];
+        assert_eq!(*quick_smallest_by(&mut list, 0, |a, b| b.cmp(a)), 10);
+        assert_eq!(*quick_smallest_by(&mut list, 4, |a, b| b.cmp(a)), -2);
+    }
 
-    for i in 0..last_idx {
-        // Compare with pivot value.
-        if list[i] < list[last_idx] {
-            list.swap(store_idx, i); // put it into the left side of pivot
-            store_idx += 1;
+    #[test]
+    fn selects_structs_by_score() {
+        let mut players = [
+            Player {
+                name: "ann",
+                score: 72,
+            },
+            Player {
+                name: "bob",
+                score: 91,
+            },
+            Player {
+                name: "cat",
+                score: 85,
+            },
+            Player {
+                name: "dan",
+                score: 64,
+            },
+            Player {
+                name: "eve",
+                score: 85,
+            },
+        ];
+        let median = quick_smallest_by(&mut players, 2, |a, b| a.score.cmp(&b.score));
+        assert_eq!(median.score, 85);
+        let lowest = quick_smallest_by(&mut players, 0, |a, b| a.score.cmp(&b.score));
+        assert_eq!(
+            lowest,
+            &Player {
+                name: "dan",
+                score: 64
+            }
+        );
+    }
+
+    #[test]
+    fn counts_comparisons() {
+        let mut list: Vec<u32> = (0..1000).rev().collect();
+        let mut comparisons = 0;
+        let kth = *quick_smallest_by(&mut list, 500, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert_eq!(kth, 500);
+        assert!(comparisons >= 999);
+    }
+
+    #[test]
+    fn receives_incomparable_items() {
+        for k in 0..5 {
+            let mut list = [3.0, f64::NAN, 1.0, f64::NAN, 2.0];
+            quick_smallest(&mut list, k);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "k=6 should be smaller than list's length")]
+    fn receives_invalid_k() {
+        let mut list = [10, -30, -2, 5, 7, 0];
+        quick_smallest_by(&mut list, 6, |a, b| a.cmp(b));
+    }
+}
+
+/// Selects the k-th smallest element in an unordered slice with quickselect, like
+/// `quick_smallest`, where items are ordered by a key extracted from them. The key function is
+/// called twice for every comparison, which is O(n) times on average but O(n²) times in the
+/// worst case; prefer `quick_smallest_by_cached_key` when it is expensive.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = ["kiwi", "fig", "banana", "apple"];
+/// assert_eq!(*selection::quick_smallest_by_key(&mut list, 0, |s| s.len()), "fig");
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn quick_smallest_by_key<T, K, F>(list: &mut [T], k: usize, mut key: F) -> &T
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    quick_smallest_by(list, k, |a, b| partial_order(&key(a), &key(b)))
+}
+
+/// Selects the k-th smallest element in an unordered slice by a key, like
+/// `quick_smallest_by_key`, but calls the key function exactly once per item. The keys are
+/// computed into a side list of (key, index) pairs first, which is selected over, and the list
+/// is then reordered in place to match, in O(n) time with O(n) extra memory.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [3.75, 10.0, 0.125, 42.5];
+/// let kth = selection::quick_smallest_by_cached_key(&mut list, 0, |x| x.to_string().len());
+/// assert_eq!(*kth, 10.0);
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn quick_smallest_by_cached_key<T, K, F>(list: &mut [T], k: usize, key: F) -> &T
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    if k >= list.len() {
+        panic!("k={} should be smaller than list's length", k);
+    }
+    let mut keyed: Vec<(K, usize)> = list.iter().map(key).zip(0..).collect();
+    quick_smallest_by(&mut keyed, k, |a, b| partial_order(&a.0, &b.0));
+    let mut perm: Vec<usize> = keyed.into_iter().map(|(_, i)| i).collect();
+    permutation::apply_permutation_inplace(list, &mut perm);
+    &list[k]
+}
+
+#[cfg(test)]
+mod quick_by_key_tests {
+    use super::*;
+    use rand::Rng;
+    use std::cell::Cell;
+    use test_util;
+
+    /// Length of the shortest formatting of a float, an expensive key to compute.
+    fn formatted_len(x: &f64) -> usize {
+        format!("{}", x).len()
+    }
+
+    #[test]
+    fn selects_by_key() {
+        let mut list = [-7, 3, -1, 10, 5];
+        assert_eq!(*quick_smallest_by_key(&mut list, 0, |x: &i32| x.abs()), -1);
+        assert_eq!(*quick_smallest_by_key(&mut list, 4, |x: &i32| x.abs()), 10);
+        assert_eq!(
+            *quick_smallest_by_cached_key(&mut list, 1, |x: &i32| x.abs()),
+            3
+        );
+    }
+
+    #[test]
+    fn agrees_with_sorting_by_key() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let list: Vec<f64> = test_util::random_vec(&mut rng, 1..80, -1000..1000)
+                .into_iter()
+                .map(|x| x as f64 / 8.0)
+                .collect();
+            let k = rng.gen_range(0, list.len());
+            let mut sorted = list.iter().map(formatted_len).collect::<Vec<_>>();
+            sorted.sort();
+            let kth = *quick_smallest_by_key(&mut list.clone(), k, formatted_len);
+            assert_eq!(formatted_len(&kth), sorted[k]);
+            let mut cached = list.clone();
+            let kth = *quick_smallest_by_cached_key(&mut cached, k, formatted_len);
+            assert_eq!(formatted_len(&kth), sorted[k]);
+            assert!(cached[..k].iter().all(|x| formatted_len(x) <= sorted[k]));
+            assert!(cached[k + 1..]
+                .iter()
+                .all(|x| formatted_len(x) >= sorted[k]));
+        }
+    }
+
+    #[test]
+    fn calls_cached_key_once_per_item() {
+        let list: Vec<f64> = (0..1000).map(|i| i as f64 / 7.0).collect();
+        let calls = Cell::new(0);
+        let counted_len = |x: &f64| {
+            calls.set(calls.get() + 1);
+            formatted_len(x)
+        };
+        quick_smallest_by_cached_key(&mut list.clone(), 500, &counted_len);
+        assert_eq!(calls.get(), list.len());
+
+        calls.set(0);
+        quick_smallest_by_key(&mut list.clone(), 500, &counted_len);
+        assert!(calls.get() > list.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "k=3 should be smaller than list's length")]
+    fn receives_invalid_k() {
+        quick_smallest_by_cached_key(&mut [1, 2, 3], 3, |&x| x);
+    }
+}
+
+/// Selects the k-th largest element in an unordered slice, where 0 <= k < list.len(), so that
+/// k = 0 selects the maximum. It is a quickselect for the (len - 1 - k)-th smallest element.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(*selection::quick_largest(&mut list[..], 0), 10);
+/// assert_eq!(*selection::quick_largest(&mut list[..], 2), 5);
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn quick_largest<T: PartialOrd>(list: &mut [T], k: usize) -> &T {
+    if k >= list.len() {
+        panic!(
+            "k={} should be smaller than list's length to select the k-th largest",
+            k
+        );
+    }
+    let smallest_k = list.len() - 1 - k;
+    quick_smallest(list, smallest_k)
+}
+
+/// Selects (a copy of) the k-th smallest element in a sequence with quickselect.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate ult_algo;
+///
+/// fn main() {
+///     let mut list = vec![10, -30, 5, -2, 7, 0];
+///     assert_eq!(kth_smallest!(list, 1), -2);
+/// }
+/// ```
+///
+/// # Panics (propagate = ult_algo::sequence::selection::quick_smallest)
+#[macro_export]
+macro_rules! kth_smallest {
+    ($sequence:expr, $k:expr) => {
+        $crate::sequence::selection::quick_smallest(&mut $sequence[..], $k).clone()
+    };
+}
+
+/// Selects (a copy of) the k-th largest element in a sequence with quickselect.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate ult_algo;
+///
+/// fn main() {
+///     let mut list = vec![10, -30, 5, -2, 7, 0];
+///     assert_eq!(kth_largest!(list, 1), 7);
+/// }
+/// ```
+///
+/// # Panics (propagate = ult_algo::sequence::selection::quick_largest)
+#[macro_export]
+macro_rules! kth_largest {
+    ($sequence:expr, $k:expr) => {
+        $crate::sequence::selection::quick_largest(&mut $sequence[..], $k).clone()
+    };
+}
+
+#[cfg(test)]
+mod quick_largest_tests {
+    use super::*;
+
+    #[test]
+    fn receives_integer_vector_slice() {
+        let mut list = [10, -30, -2, 5, 7, 0];
+        assert_eq!(*quick_largest(&mut list[..], 3), 0);
+        assert_eq!(*quick_largest(&mut list[..], 0), 10);
+        assert_eq!(*quick_largest(&mut list[..], 5), -30);
+    }
+
+    #[test]
+    fn receives_char_vector_slice() {
+        let mut list = ['z', 'b', 'e', 'y', 'm', 'k'];
+        assert_eq!(*quick_largest(&mut list[..], 1), 'y');
+    }
+
+    #[test]
+    fn receives_partial_slice() {
+        let mut list = [10, -30, 5, -2, 7, 0];
+        assert_eq!(*quick_largest(&mut list[1..5], 0), 7);
+        assert_eq!(*quick_largest(&mut list[1..5], 1), 5);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "k=6 should be smaller than list's length to select the k-th largest"
+    )]
+    fn receives_invalid_k() {
+        let mut list = [10, -30, -2, 5, 7, 0];
+        quick_largest(&mut list[..], 6);
+    }
+
+    #[test]
+    fn matches_max_and_macros() {
+        let mut list = vec![4, 8, -1, 8, 3, 0, 12, 5];
+        let max = *list.iter().max().unwrap();
+        assert_eq!(*quick_largest(&mut list[..], 0), max);
+        assert_eq!(kth_largest!(list, 0), max);
+        assert_eq!(kth_smallest!(list, 0), -1);
+        let mut sorted = list.clone();
+        sorted.sort();
+        for k in 0..list.len() {
+            assert_eq!(kth_smallest!(list, k), sorted[k]);
+            assert_eq!(kth_largest!(list, k), sorted[list.len() - 1 - k]);
+        }
+    }
+}
+
+/// Reorders a slice around its k-th smallest element, like `slice::select_nth_unstable`, and
+/// returns the items before it, the element itself and the items after it. Every item before
+/// index k is smaller than or equal to the k-th element, and every item after it is larger than
+/// or equal to it; neither side is sorted. It runs on the same quickselect as `quick_smallest`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// let (smaller, median, larger) = selection::partition_at(&mut list, 3);
+/// assert_eq!(*median, 5);
+/// assert!(smaller.iter().all(|&x| x <= 5));
+/// assert!(larger.iter().all(|&x| x >= 5));
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn partition_at<T: PartialOrd>(list: &mut [T], k: usize) -> (&mut [T], &mut T, &mut [T]) {
+    quick_smallest(list, k);
+    let (smaller, rest) = list.split_at_mut(k);
+    let (kth, larger) = rest.split_first_mut().unwrap();
+    (smaller, kth, larger)
+}
+
+#[cfg(test)]
+mod partition_at_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn check_partition(list: &[i32], k: usize) {
+        let mut sorted = list.to_vec();
+        sorted.sort();
+        let mut list = list.to_vec();
+        let (smaller, kth, larger) = partition_at(&mut list, k);
+        assert_eq!(*kth, sorted[k]);
+        assert_eq!((smaller.len(), larger.len()), (k, sorted.len() - k - 1));
+        assert!(smaller.iter().all(|item| item <= kth));
+        assert!(larger.iter().all(|item| item >= kth));
+        list.sort();
+        assert_eq!(list, sorted);
+    }
+
+    #[test]
+    fn partitions_random_inputs() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            let list: Vec<i32> = test_util::random_vec(&mut rng, 1..60, -20..20);
+            let k = rng.gen_range(0, list.len());
+            check_partition(&list, k);
+        }
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        check_partition(&[4; 100], 37);
+        check_partition(&[4; 100], 0);
+        check_partition(&[4; 100], 99);
+    }
+
+    #[test]
+    fn receives_k_at_both_ends() {
+        let list = [10, -30, 5, -2, 7, 0, 8];
+        check_partition(&list, 0);
+        check_partition(&list, list.len() - 1);
+        check_partition(&[1], 0);
+    }
+
+    #[test]
+    fn returns_mutable_kth() {
+        let mut list = [3, 1, 2];
+        *partition_at(&mut list, 1).1 = 20;
+        assert_eq!(list, [1, 20, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "k=3 should be smaller than list's length")]
+    fn receives_invalid_k() {
+        partition_at(&mut [1, 2, 3], 3);
+    }
+}
+
+#[cfg(test)]
+mod partition3_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn check_partition3(list: &[i32], pivot_idx: usize) {
+        let pivot = list[pivot_idx];
+        let mut list = list.to_vec();
+        let (start, end) = partition3(&mut list, pivot_idx);
+        assert!(list[..start].iter().all(|&x| x < pivot));
+        assert!(list[start..end].iter().all(|&x| x == pivot));
+        assert!(list[end..].iter().all(|&x| x > pivot));
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let mut list = [7; 50];
+        assert_eq!(partition3(&mut list, 13), (0, 50));
+        assert_eq!(partition3(&mut [7], 0), (0, 1));
+    }
+
+    #[test]
+    fn receives_two_distinct_values() {
+        let list = [1, 2, 2, 1, 1, 2, 1, 2, 2, 2];
+        for pivot_idx in 0..list.len() {
+            check_partition3(&list, pivot_idx);
         }
+        let mut list_ = list;
+        assert_eq!(partition3(&mut list_, 0), (0, 4));
+        assert_eq!(partition3(&mut list_, 9), (4, 10));
+    }
+
+    #[test]
+    fn partitions_random_inputs() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            let list: Vec<i32> = test_util::random_vec(&mut rng, 1..60, 0..5);
+            let pivot_idx = rng.gen_range(0, list.len());
+            check_partition3(&list, pivot_idx);
+        }
+    }
+
+    #[test]
+    fn selects_from_mostly_equal_items() {
+        let mut rng = test_util::rng();
+        let mut list: Vec<u16> = (0..100_000)
+            .map(|i| {
+                if i % 10 == 0 {
+                    rng.gen_range(100, 600)
+                } else {
+                    200
+                }
+            })
+            .collect();
+        let mut sorted = list.clone();
+        sorted.sort();
+        for &k in [0, 9_999, 50_000, 95_000, 99_999].iter() {
+            assert_eq!(*quick_smallest(&mut list, k), sorted[k]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "pivot_idx should be smaller than list's length")]
+    fn receives_invalid_pivot() {
+        partition3::<i32>(&mut [], 0);
+    }
+}
+
+/// # [Median](https://en.wikipedia.org/wiki/Median)
+///
+/// Selects the median of an unordered slice with quickselect, or None if it is empty. For an
+/// even length, it is the lower of the two middle elements (see `median_f64` for the others).
+/// The list is reordered so that the median is at index (len - 1) / 2.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// assert_eq!(selection::median(&mut [10, -30, 5, -2, 7]), Some(5));
+/// assert_eq!(selection::median(&mut ['d', 'a', 'c', 'b']), Some('b'));
+/// assert_eq!(selection::median::<i32>(&mut []), None);
+/// ```
+pub fn median<T: PartialOrd + Clone>(list: &mut [T]) -> Option<T> {
+    if list.is_empty() {
+        return None;
+    }
+    let k = (list.len() - 1) / 2;
+    Some(quick_smallest(list, k).clone())
+}
+
+/// Which value to take as the median of an even number of items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MedianPolicy {
+    /// The lower of the two middle items
+    Lower,
+    /// The upper of the two middle items
+    Upper,
+    /// The average of the two middle items
+    Interpolate,
+}
+
+/// Selects the median of an unordered slice of numbers as a float, or None if it is empty. For
+/// an even length, the policy chooses between the two middle items or their average. Both are
+/// found with a single quickselect: the upper one is the smallest item after the lower one.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection::{self, MedianPolicy};
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(selection::median_f64(&mut list, MedianPolicy::Lower), Some(0.0));
+/// assert_eq!(selection::median_f64(&mut list, MedianPolicy::Upper), Some(5.0));
+/// assert_eq!(selection::median_f64(&mut list, MedianPolicy::Interpolate), Some(2.5));
+/// ```
+///
+/// # Panics
+///
+/// * items should be convertible to f64
+pub fn median_f64<T>(list: &mut [T], policy: MedianPolicy) -> Option<f64>
+where
+    T: PartialOrd + ToPrimitive,
+{
+    if list.is_empty() {
+        return None;
+    }
+    let to_f64 = |x: &T| x.to_f64().expect("items should be convertible to f64");
+    let n = list.len();
+    let (_, lower, larger) = partition_at(list, (n - 1) / 2);
+    if n % 2 == 1 || policy == MedianPolicy::Lower {
+        return Some(to_f64(lower));
+    }
+    let (first, rest) = larger
+        .split_first()
+        .expect("an even length list has an item after the lower middle");
+    let upper = rest
+        .iter()
+        .fold(first, |min, x| if *x < *min { x } else { min });
+    match policy {
+        MedianPolicy::Upper => Some(to_f64(upper)),
+        _ => Some((to_f64(lower) + to_f64(upper)) / 2.0),
+    }
+}
+
+#[cfg(test)]
+mod median_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn check_median(list: &[i64]) {
+        let mut sorted = list.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let (lower, upper) = (sorted[(n - 1) / 2] as f64, sorted[n / 2] as f64);
+        assert_eq!(median(&mut list.to_vec()), Some(sorted[(n - 1) / 2]));
+        let policies = [
+            (MedianPolicy::Lower, lower),
+            (MedianPolicy::Upper, upper),
+            (MedianPolicy::Interpolate, (lower + upper) / 2.0),
+        ];
+        for &(policy, expected) in policies.iter() {
+            assert_eq!(median_f64(&mut list.to_vec(), policy), Some(expected));
+        }
+    }
+
+    #[test]
+    fn agrees_with_sorting() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            check_median(&test_util::random_vec(&mut rng, 1..60, -1000..1000));
+        }
+    }
+
+    #[test]
+    fn receives_even_lengths_and_duplicates() {
+        check_median(&[4, 1]);
+        check_median(&[3, 3, 3, 3]);
+        check_median(&[1, 2, 2, 1, 1, 2]);
+        check_median(&[7]);
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let list: Vec<i64> = (0..2 * rng.gen_range(1, 40))
+                .map(|_| rng.gen_range(0, 3))
+                .collect();
+            check_median(&list);
+        }
+    }
+
+    #[test]
+    fn receives_floats_and_strings() {
+        let mut list = [2.5, -1.0, 8.0, 0.5];
+        assert_eq!(median_f64(&mut list, MedianPolicy::Interpolate), Some(1.5));
+        assert_eq!(median(&mut ["pear", "fig", "apple"]), Some("fig"));
+    }
+
+    #[test]
+    fn receives_empty_list() {
+        assert_eq!(median::<u8>(&mut []), None);
+        assert_eq!(median_f64::<u8>(&mut [], MedianPolicy::Interpolate), None);
+    }
+}
+
+/// How to compute a quantile that falls between two items, following the definitions of NumPy:
+/// with the items sorted, the q-quantile is at the (possibly fractional) index q·(n - 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantileMethod {
+    /// The nearest item, or the one at the even index on a tie
+    Nearest,
+    /// The item before the index
+    Lower,
+    /// The item after the index
+    Higher,
+    /// The average of the items before and after the index
+    Midpoint,
+    /// The linear interpolation between the items before and after the index
+    Linear,
+}
+
+/// # [Quantile](https://en.wikipedia.org/wiki/Quantile)
+///
+/// Computes the q-quantile of an unordered slice, or None if it is empty, for q between 0 and
+/// 1 (q = 0.95 for the 95th percentile). The one or two items around the quantile are found
+/// with quickselect instead of sorting, which reorders the list.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection::{self, QuantileMethod};
+///
+/// let mut latencies = [12.0, 7.0, 30.0, 9.0, 15.0];
+/// assert_eq!(selection::quantile(&mut latencies, 0.5, QuantileMethod::Linear), Some(12.0));
+/// assert_eq!(selection::quantile(&mut latencies, 0.9, QuantileMethod::Linear), Some(24.0));
+/// assert_eq!(selection::quantile(&mut latencies, 0.9, QuantileMethod::Higher), Some(30.0));
+/// ```
+///
+/// # Panics
+///
+/// * q should be between 0 and 1
+/// * list should not contain NaN
+pub fn quantile(list: &mut [f64], q: f64, method: QuantileMethod) -> Option<f64> {
+    quantiles(list, &[q], method).map(|values| values[0])
+}
+
+/// Computes several quantiles of an unordered slice at once, like `quantile`, or None if it is
+/// empty. Every quickselect partition is shared by all the quantiles on its side, so k
+/// quantiles take O(n log k) time on average.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection::{self, QuantileMethod};
+///
+/// let mut latencies: Vec<f64> = (1..101).map(f64::from).collect();
+/// let percentiles = selection::quantiles(&mut latencies, &[0.5, 0.95, 0.99], QuantileMethod::Lower);
+/// assert_eq!(percentiles, Some(vec![50.0, 95.0, 99.0]));
+/// ```
+///
+/// # Panics
+///
+/// * q should be between 0 and 1
+/// * list should not contain NaN
+pub fn quantiles(list: &mut [f64], qs: &[f64], method: QuantileMethod) -> Option<Vec<f64>> {
+    if !qs.iter().all(|&q| (0.0..=1.0).contains(&q)) {
+        panic!("q should be between 0 and 1");
+    }
+    if list.iter().any(|x| x.is_nan()) {
+        panic!("list should not contain NaN");
+    }
+    if list.is_empty() {
+        return None;
+    }
+
+    // Select the items just before and after every quantile's fractional index.
+    let positions: Vec<f64> = qs.iter().map(|q| q * (list.len() - 1) as f64).collect();
+    let mut ranks: Vec<usize> = positions
+        .iter()
+        .flat_map(|&position| vec![position.floor() as usize, position.ceil() as usize])
+        .collect();
+    ranks.sort_unstable();
+    ranks.dedup();
+    select_ranks(list, &ranks);
+
+    let values = positions.iter().map(|&position| {
+        let (lower, higher) = (
+            list[position.floor() as usize],
+            list[position.ceil() as usize],
+        );
+        let fraction = position - position.floor();
+        match method {
+            QuantileMethod::Lower => lower,
+            QuantileMethod::Higher => higher,
+            QuantileMethod::Midpoint => (lower + higher) / 2.0,
+            QuantileMethod::Linear => lower + (higher - lower) * fraction,
+            QuantileMethod::Nearest => {
+                let is_lower_even = (position.floor() as usize).is_multiple_of(2);
+                if fraction < 0.5 || (fraction == 0.5 && is_lower_even) {
+                    lower
+                } else {
+                    higher
+                }
+            }
+        }
+    });
+    Some(values.collect())
+}
+
+/// Reorders a list so that the items at all the given ranks, which are sorted, are the ones a
+/// sorted list would have there. The middle rank is selected first, and the ranks on each side
+/// of it are then selected within that side only.
+fn select_ranks<T: PartialOrd>(list: &mut [T], ranks: &[usize]) {
+    if ranks.is_empty() {
+        return;
+    }
+    let middle = ranks.len() / 2;
+    let k = ranks[middle];
+    let (smaller, _, larger) = partition_at(list, k);
+    select_ranks(smaller, &ranks[..middle]);
+    let larger_ranks: Vec<usize> = ranks[middle + 1..].iter().map(|r| r - k - 1).collect();
+    select_ranks(larger, &larger_ranks);
+}
+
+#[cfg(test)]
+mod quantile_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    const METHODS: [QuantileMethod; 5] = [
+        QuantileMethod::Nearest,
+        QuantileMethod::Lower,
+        QuantileMethod::Higher,
+        QuantileMethod::Midpoint,
+        QuantileMethod::Linear,
+    ];
+
+    /// The quantile computed from a sorted list, by the definitions of NumPy.
+    fn sorted_quantile(sorted: &[f64], q: f64, method: QuantileMethod) -> f64 {
+        let position = q * (sorted.len() - 1) as f64;
+        let (i, j) = (position.floor() as usize, position.ceil() as usize);
+        match method {
+            // Round half to even, like np.around.
+            QuantileMethod::Nearest => match (position - i as f64).partial_cmp(&0.5) {
+                Some(Ordering::Less) => sorted[i],
+                Some(Ordering::Equal) if i.is_multiple_of(2) => sorted[i],
+                _ => sorted[j],
+            },
+            QuantileMethod::Lower => sorted[i],
+            QuantileMethod::Higher => sorted[j],
+            QuantileMethod::Midpoint => (sorted[i] + sorted[j]) / 2.0,
+            QuantileMethod::Linear => sorted[i] + (sorted[j] - sorted[i]) * (position - i as f64),
+        }
+    }
+
+    #[test]
+    fn agrees_with_sorting() {
+        let mut rng = test_util::rng();
+        for _ in 0..300 {
+            let list: Vec<f64> = test_util::random_vec(&mut rng, 1..50, -100..100)
+                .into_iter()
+                .map(|x| x as f64 / 4.0)
+                .collect();
+            let mut sorted = list.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let qs: Vec<f64> = (0..rng.gen_range(1, 8)).map(|_| rng.gen::<f64>()).collect();
+            for &method in METHODS.iter() {
+                let expected: Vec<f64> = qs
+                    .iter()
+                    .map(|&q| sorted_quantile(&sorted, q, method))
+                    .collect();
+                assert_eq!(quantiles(&mut list.clone(), &qs, method), Some(expected));
+                assert_eq!(
+                    quantile(&mut list.clone(), qs[0], method),
+                    Some(sorted_quantile(&sorted, qs[0], method))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn follows_numpy_conventions() {
+        let list = [1.0, 2.0, 3.0, 4.0];
+        // q = 0.5 falls at index 1.5, and q = 0.5 / 3 at index 0.5.
+        let expected = [
+            (QuantileMethod::Nearest, 3.0, 1.0),
+            (QuantileMethod::Lower, 2.0, 1.0),
+            (QuantileMethod::Higher, 3.0, 2.0),
+            (QuantileMethod::Midpoint, 2.5, 1.5),
+            (QuantileMethod::Linear, 2.5, 1.5),
+        ];
+        for &(method, half, sixth) in expected.iter() {
+            assert_eq!(quantile(&mut list.clone(), 0.5, method), Some(half));
+            assert_eq!(quantile(&mut list.clone(), 0.5 / 3.0, method), Some(sixth));
+            assert_eq!(quantile(&mut list.clone(), 0.0, method), Some(1.0));
+            assert_eq!(quantile(&mut list.clone(), 1.0, method), Some(4.0));
+        }
+    }
+
+    #[test]
+    fn receives_edge_cases() {
+        assert_eq!(quantile(&mut [], 0.5, QuantileMethod::Linear), None);
+        assert_eq!(quantile(&mut [7.5], 0.3, QuantileMethod::Linear), Some(7.5));
+        assert_eq!(
+            quantiles(&mut [3.0, 1.0, 2.0], &[], QuantileMethod::Linear),
+            Some(vec![])
+        );
+        assert_eq!(
+            quantiles(&mut [5.0; 20], &[0.1, 0.5, 0.9], QuantileMethod::Midpoint),
+            Some(vec![5.0; 3])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "q should be between 0 and 1")]
+    fn receives_invalid_q() {
+        quantile(&mut [1.0, 2.0], 1.5, QuantileMethod::Linear);
+    }
+
+    #[test]
+    #[should_panic(expected = "list should not contain NaN")]
+    fn receives_nan() {
+        quantile(&mut [1.0, f64::NAN], 0.5, QuantileMethod::Linear);
+    }
+}
+
+/// # [Median of Medians](https://en.wikipedia.org/wiki/Median_of_medians)
+///
+/// Selects the k-th smallest element in an unordered slice, where 0 <= k < list.len(), in O(n)
+/// time even in the worst case, with the BFPRT algorithm (Blum, Floyd, Pratt, Rivest and Tarjan).
+/// It works like `quick_smallest`, but the pivot is chosen deterministically: the list is split
+/// into groups of five, and the median of the group medians, itself selected recursively, is
+/// larger and smaller than at least 30% of the items. The list is reordered so that the k-th
+/// smallest element is at index k.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(*selection::median_of_medians(&mut list, 2), 0);
+/// assert_eq!(list[2], 0);
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn median_of_medians<T: PartialOrd>(list: &mut [T], k: usize) -> &T {
+    if k >= list.len() {
+        panic!("k={} should be smaller than list's length", k);
+    }
+    select_deterministic(list, k);
+    &list[k]
+}
+
+/// Moves the k-th smallest element of the list to index k.
+fn select_deterministic<T: PartialOrd>(list: &mut [T], k: usize) {
+    if list.len() <= 5 {
+        insertion_sort(list);
+        return;
+    }
+
+    // Move the median of every group of five to the front, then select their median.
+    let groups = list.len().div_ceil(5);
+    for group in 0..groups {
+        let start = group * 5;
+        let end = (start + 5).min(list.len());
+        insertion_sort(&mut list[start..end]);
+        list.swap(group, start + (end - start - 1) / 2);
+    }
+    select_deterministic(&mut list[..groups], groups / 2);
+
+    let equal = partition(list, groups / 2);
+    if k < equal.start {
+        select_deterministic(&mut list[..equal.start], k);
+    } else if k >= equal.end {
+        select_deterministic(&mut list[equal.end..], k - equal.end);
+    }
+}
+
+/// Sorts a short list in place.
+fn insertion_sort<T: PartialOrd>(list: &mut [T]) {
+    for i in 1..list.len() {
+        let mut j = i;
+        while j > 0 && list[j] < list[j - 1] {
+            list.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod median_of_medians_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util::{self, Counted};
+
+    #[test]
+    fn partitions_around_a_value() {
+        let mut list = [5, 1, 4, 1, 5, 9, 2, 6, 5];
+        let equal = partition_around_by(&mut list, &5, &mut partial_order);
+        assert_eq!(equal, 4..7);
+        assert!(list[..4].iter().all(|&x| x < 5));
+        assert_eq!(list[4..7], [5, 5, 5]);
+        assert!(list[7..].iter().all(|&x| x > 5));
+        // The value need not be in the list.
+        assert_eq!(partition_around_by(&mut list, &3, &mut partial_order), 3..3);
+        assert_eq!(partition_around_by(&mut [], &3, &mut partial_order), 0..0);
+    }
+
+    fn check_all_ranks(list: &[i64]) {
+        let mut sorted = list.to_vec();
+        sorted.sort();
+        for k in 0..list.len() {
+            let mut list = list.to_vec();
+            assert_eq!(*median_of_medians(&mut list, k), sorted[k]);
+            assert!(list[..k].iter().all(|&item| item <= sorted[k]));
+            assert!(list[k + 1..].iter().all(|&item| item >= sorted[k]));
+        }
+    }
+
+    /// Inputs that are known to be hard for simple pivot choices.
+    fn inputs(n: usize) -> Vec<Vec<i64>> {
+        let n = n as i64;
+        vec![
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            vec![7; n as usize],
+            // organ pipe
+            (0..n).map(|i| i.min(n - 1 - i)).collect(),
+            // sawtooth with few distinct values
+            (0..n).map(|i| i % 5).collect(),
+            // sorted groups of five in descending order
+            (0..n).map(|i| n - i / 5 * 5 + i % 5).collect(),
+        ]
+    }
+
+    #[test]
+    fn receives_ordered_and_equal_inputs() {
+        for list in inputs(37) {
+            check_all_ranks(&list);
+        }
+    }
+
+    #[test]
+    fn agrees_with_sorting() {
+        test_util::agrees(
+            200,
+            |rng| {
+                let list: Vec<i64> = test_util::random_vec(rng, 1..60, -20..20);
+                let k = rng.gen_range(0, list.len());
+                (list, k)
+            },
+            |&(ref list, k)| *median_of_medians(&mut list.clone(), k),
+            |&(ref list, k)| test_util::sorted(list)[k],
+        );
+        check_all_ranks(&[1]);
+    }
+
+    #[test]
+    fn compares_linearly_many_times() {
+        for (shape, _) in inputs(1).iter().enumerate() {
+            let mut previous = None;
+            for &n in [1000, 2000, 4000, 8000].iter() {
+                let mut list: Vec<Counted> = inputs(n)[shape].iter().map(|&x| Counted(x)).collect();
+                Counted::reset();
+                median_of_medians(&mut list, n / 2);
+                let comparisons = Counted::comparisons();
+                assert!(
+                    comparisons <= 25 * n,
+                    "{} comparisons for n={}",
+                    comparisons,
+                    n
+                );
+                if let Some(previous) = previous {
+                    assert!(comparisons <= previous * 5 / 2);
+                }
+                previous = Some(comparisons);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "k=3 should be smaller than list's length")]
+    fn receives_invalid_k() {
+        median_of_medians(&mut [1, 2, 3], 3);
+    }
+}
+
+/// # [Introselect](https://en.wikipedia.org/wiki/Introselect)
+///
+/// Selects the k-th smallest element in an unordered slice, where 0 <= k < list.len(), like
+/// `quick_smallest` with random pivots, but gives up on them after 2·log2(n) partitions and
+/// finishes with `median_of_medians` on the part of the list still left. It is as fast as
+/// quickselect on average, yet O(n) in the worst case. The list is reordered so that the k-th
+/// smallest element is at index k.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(*selection::introselect(&mut list, 4), 7);
+/// ```
+///
+/// # Panics
+///
+/// * k is larger than or equal to the list's length
+pub fn introselect<T: PartialOrd>(list: &mut [T], k: usize) -> &T {
+    if k >= list.len() {
+        panic!("k={} should be smaller than list's length", k);
+    }
+    let mut rng = rand::thread_rng();
+    introselect_by(list, k, |part| rng.gen_range(0, part.len()));
+    &list[k]
+}
+
+/// Moves the k-th smallest element of the list to index k, partitioning around the pivots
+/// chosen by `choose_pivot` until the partitions run out. Returns true if it had to fall back
+/// to the median of medians.
+fn introselect_by<T, F>(list: &mut [T], k: usize, mut choose_pivot: F) -> bool
+where
+    T: PartialOrd,
+    F: FnMut(&[T]) -> usize,
+{
+    let mut partitions_left = 2 * (usize::BITS - list.len().leading_zeros());
+    // list[start..end] holds the k-th smallest element; the items around it are partitioned.
+    let (mut start, mut end) = (0, list.len());
+    while end - start > 1 {
+        if partitions_left == 0 {
+            select_deterministic(&mut list[start..end], k - start);
+            return true;
+        }
+        partitions_left -= 1;
+
+        let pivot_idx = choose_pivot(&list[start..end]);
+        let equal = partition(&mut list[start..end], pivot_idx);
+        if k < start + equal.start {
+            end = start + equal.start;
+        } else if k >= start + equal.end {
+            start += equal.end;
+        } else {
+            break;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod introselect_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn agrees_with_sorting() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let mut list: Vec<i64> = test_util::random_vec(&mut rng, 1..100, -20..20);
+            let k = rng.gen_range(0, list.len());
+            let sorted = test_util::sorted(&list);
+            assert_eq!(*introselect(&mut list, k), sorted[k]);
+            assert!(list[..k].iter().all(|&item| item <= sorted[k]));
+            assert!(list[k + 1..].iter().all(|&item| item >= sorted[k]));
+        }
+    }
+
+    #[test]
+    fn falls_back_on_bad_pivots() {
+        // Always picking the largest item shrinks the list by one item per partition.
+        let largest = |part: &[i64]| {
+            (0..part.len())
+                .max_by(|&i, &j| part[i].partial_cmp(&part[j]).unwrap())
+                .unwrap()
+        };
+        let mut list: Vec<i64> = (0..1000).collect();
+        test_util::rng().shuffle(&mut list);
+        assert!(introselect_by(&mut list, 10, largest));
+        assert_eq!(list[10], 10);
+        assert!(list[..10].iter().all(|&item| item < 10));
+        assert!(list[11..].iter().all(|&item| item > 10));
+
+        // Good pivots find it well within the partitions allowed.
+        let mut list: Vec<i64> = (0..1000).rev().collect();
+        assert!(!introselect_by(&mut list, 10, |part| part.len() / 2));
+        assert_eq!(list[10], 10);
+    }
+
+    #[test]
+    fn receives_equal_items() {
+        let mut list = vec![4; 1000];
+        assert_eq!(*introselect(&mut list, 999), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "k=0 should be smaller than list's length")]
+    fn receives_invalid_k() {
+        introselect::<i32>(&mut [], 0);
+    }
+}
+
+/// # Top-k Smallest Elements
+///
+/// Returns the k smallest elements of a list in ascending order without modifying it. If k is
+/// larger than or equal to the list's length, the whole list is returned sorted.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(selection::top_k_smallest(&list, 3), vec![-30, -2, 0]);
+/// assert_eq!(selection::top_k_smallest(&list, 10), vec![-30, -2, 0, 5, 7, 10]);
+/// ```
+pub fn top_k_smallest<T: PartialOrd + Clone>(list: &[T], k: usize) -> Vec<T> {
+    top_k_smallest_in_place(&mut list.to_vec(), k).to_vec()
+}
+
+/// Moves the k smallest elements of a list to its front in ascending order and returns them as
+/// a subslice. The list is partitioned around its k-th smallest element with quickselect, and
+/// only the left part is sorted, which takes O(n + k log k) time on average.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(selection::top_k_smallest_in_place(&mut list, 2), &[-30, -2]);
+/// ```
+pub fn top_k_smallest_in_place<T: PartialOrd>(list: &mut [T], k: usize) -> &mut [T] {
+    let k = k.min(list.len());
+    if k < list.len() {
+        quick_smallest(list, k);
+    }
+    let smallest = &mut list[..k];
+    smallest.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    smallest
+}
+
+/// Returns the k largest elements of a list in descending order without modifying it. If k is
+/// larger than or equal to the list's length, the whole list is returned sorted.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(selection::top_k_largest(&list, 3), vec![10, 7, 5]);
+/// ```
+pub fn top_k_largest<T: PartialOrd + Clone>(list: &[T], k: usize) -> Vec<T> {
+    top_k_largest_in_place(&mut list.to_vec(), k).to_vec()
+}
+
+/// Moves the k largest elements of a list to its back and returns them as a subslice in
+/// descending order, like `top_k_smallest_in_place`.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let mut list = [10, -30, 5, -2, 7, 0];
+/// assert_eq!(selection::top_k_largest_in_place(&mut list, 2), &[10, 7]);
+/// ```
+pub fn top_k_largest_in_place<T: PartialOrd>(list: &mut [T], k: usize) -> &mut [T] {
+    let k = k.min(list.len());
+    if k < list.len() {
+        quick_largest(list, k);
+    }
+    let start = list.len() - k;
+    let largest = &mut list[start..];
+    largest.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    largest
+}
+
+#[cfg(test)]
+mod top_k_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn agrees_with_sorting() {
+        test_util::agrees(
+            300,
+            |rng| {
+                let list: Vec<i32> = test_util::random_vec(rng, 0..80, -25..25);
+                let k = rng.gen_range(0, list.len() + 3);
+                (list, k)
+            },
+            |&(ref list, k)| (top_k_smallest(list, k), top_k_largest(list, k)),
+            |&(ref list, k)| {
+                let sorted = test_util::sorted(list);
+                let largest = sorted.iter().rev().take(k).cloned().collect();
+                (sorted.into_iter().take(k).collect(), largest)
+            },
+        );
+    }
+
+    #[test]
+    fn receives_k_zero_and_len() {
+        let list = [3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(top_k_smallest(&list, 0), vec![]);
+        assert_eq!(top_k_largest(&list, 0), vec![]);
+        assert_eq!(top_k_smallest(&list, 8), vec![1, 1, 2, 3, 4, 5, 6, 9]);
+        assert_eq!(top_k_largest(&list, 8), vec![9, 6, 5, 4, 3, 2, 1, 1]);
+        assert_eq!(top_k_smallest::<i32>(&[], 0), vec![]);
+    }
+
+    #[test]
+    fn keeps_items_in_place() {
+        let mut list = ['z', 'b', 'e', 'y', 'm', 'k'];
+        assert_eq!(top_k_smallest_in_place(&mut list, 3), &['b', 'e', 'k']);
+        assert_eq!(&list[..3], &['b', 'e', 'k']);
+        assert_eq!(top_k_largest_in_place(&mut list, 2), &['z', 'y']);
+        assert_eq!(&list[4..], &['z', 'y']);
+        list.sort();
+        assert_eq!(list, ['b', 'e', 'k', 'm', 'y', 'z']);
+    }
+
+    #[test]
+    fn receives_floats() {
+        let list = [2.5, -1.0, 0.0, 7.25, 3.5];
+        assert_eq!(top_k_smallest(&list, 2), vec![-1.0, 0.0]);
+        assert_eq!(top_k_largest(&list, 2), vec![7.25, 3.5]);
+    }
+}
+
+/// Which end of the order `TopK` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopKMode {
+    /// The k smallest items, in ascending order
+    Smallest,
+    /// The k largest items, in descending order
+    Largest,
+}
+
+/// # Streaming Top-k Elements
+///
+/// Keeps the k smallest or largest items of a stream too large to collect, in O(k) memory. The
+/// kept items form a bounded [binary heap](https://en.wikipedia.org/wiki/Binary_heap) with the
+/// worst of them at the root, which every new item only has to beat to take its place, so a
+/// push takes O(log k) time. Among equal items, the ones pushed first are kept and come first.
+/// Items that are incomparable to each other, such as NaN, are treated as equal.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection::{TopK, TopKMode};
+///
+/// let mut top = TopK::new(3, TopKMode::Largest);
+/// for score in vec![72, 91, 85, 64, 99, 85] {
+///     top.push(score);
+/// }
+/// assert_eq!(top.into_sorted_vec(), vec![99, 91, 85]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    mode: TopKMode,
+    /// The kept items with their push counts, the worst at the root.
+    heap: Vec<(T, usize)>,
+    pushed: usize,
+}
+
+impl<T: PartialOrd> TopK<T> {
+    /// Creates an empty `TopK` that keeps k items.
+    pub fn new(k: usize, mode: TopKMode) -> TopK<T> {
+        TopK {
+            k,
+            mode,
+            heap: Vec::new(),
+            pushed: 0,
+        }
+    }
+
+    /// Returns the number of items kept, which is at most k.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if no items are kept.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Offers an item, which is kept if it is among the k best so far.
+    pub fn push(&mut self, item: T) {
+        let entry = (item, self.pushed);
+        self.pushed += 1;
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+            let last = self.heap.len() - 1;
+            self.sift_up(last);
+        } else if self.k > 0 && self.compare(&entry, &self.heap[0]) == Ordering::Less {
+            self.heap[0] = entry;
+            self.sift_down(0);
+        }
+    }
+
+    /// Returns the kept items from the best to the worst.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mode = self.mode;
+        let mut entries = self.heap;
+        entries.sort_by(|a, b| compare_entries(mode, a, b));
+        entries.into_iter().map(|(item, _)| item).collect()
+    }
+
+    fn compare(&self, a: &(T, usize), b: &(T, usize)) -> Ordering {
+        compare_entries(self.mode, a, b)
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.compare(&self.heap[i], &self.heap[parent]) != Ordering::Greater {
+                break;
+            }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut worst = i;
+            for child in (2 * i + 1..2 * i + 3).filter(|&child| child < self.heap.len()) {
+                if self.compare(&self.heap[child], &self.heap[worst]) == Ordering::Greater {
+                    worst = child;
+                }
+            }
+            if worst == i {
+                break;
+            }
+            self.heap.swap(i, worst);
+            i = worst;
+        }
+    }
+}
+
+/// Orders entries from the best to the worst: by item in the mode's direction, then by when
+/// they were pushed.
+fn compare_entries<T: PartialOrd>(mode: TopKMode, a: &(T, usize), b: &(T, usize)) -> Ordering {
+    let by_item = match mode {
+        TopKMode::Smallest => partial_order(&a.0, &b.0),
+        TopKMode::Largest => partial_order(&b.0, &a.0),
+    };
+    by_item.then(a.1.cmp(&b.1))
+}
+
+/// Returns the k smallest or largest items of an iterator from the best to the worst with
+/// `TopK`, in O(k) memory.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection::{self, TopKMode};
+///
+/// let remainders = (1..1_000_000).map(|x| x % 1000);
+/// assert_eq!(selection::top_k_of_iter(remainders, 2, TopKMode::Largest), vec![999, 999]);
+/// let letters = selection::top_k_of_iter("stream".chars(), 3, TopKMode::Smallest);
+/// assert_eq!(letters, vec!['a', 'e', 'm']);
+/// ```
+pub fn top_k_of_iter<I>(iter: I, k: usize, mode: TopKMode) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: PartialOrd,
+{
+    let mut top = TopK::new(k, mode);
+    for item in iter {
+        top.push(item);
+    }
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod top_k_stream_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    /// A value with a tag that takes no part in comparisons, to tell equal values apart.
+    #[derive(Debug, Clone, Copy)]
+    struct Tagged(i32, usize);
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Tagged) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Tagged) -> Option<Ordering> {
+            self.0.partial_cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn agrees_with_sorting() {
+        test_util::agrees(
+            300,
+            |rng| {
+                let stream: Vec<i32> = test_util::random_vec(rng, 0..200, -50..50);
+                let k = rng.gen_range(0, stream.len() + 5);
+                (stream, k)
+            },
+            |&(ref stream, k)| {
+                let smallest = top_k_of_iter(stream.iter().cloned(), k, TopKMode::Smallest);
+                (
+                    smallest,
+                    top_k_of_iter(stream.clone(), k, TopKMode::Largest),
+                )
+            },
+            |&(ref stream, k)| {
+                let sorted = test_util::sorted(stream);
+                let largest = sorted.iter().rev().take(k).cloned().collect();
+                (sorted.into_iter().take(k).collect(), largest)
+            },
+        );
+    }
+
+    #[test]
+    fn receives_k_zero_and_short_streams() {
+        let mut top = TopK::new(0, TopKMode::Smallest);
+        top.push(1);
+        assert!(top.is_empty());
+        assert_eq!(top.into_sorted_vec(), vec![]);
+        assert_eq!(
+            top_k_of_iter(vec![3, 1, 2], 10, TopKMode::Smallest),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            top_k_of_iter(Vec::<i32>::new(), 3, TopKMode::Largest),
+            vec![]
+        );
+        assert_eq!(
+            top_k_of_iter(vec![2, 3, 1], usize::MAX, TopKMode::Largest),
+            vec![3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn keeps_first_pushed_among_equal_items() {
+        let stream = [5, 3, 5, 1, 5, 3, 5, 9];
+        let tagged = stream.iter().enumerate().map(|(i, &x)| Tagged(x, i));
+        let tags: Vec<usize> = top_k_of_iter(tagged, 4, TopKMode::Smallest)
+            .iter()
+            .map(|item| item.1)
+            .collect();
+        assert_eq!(tags, vec![3, 1, 5, 0]);
+
+        let tagged = stream.iter().enumerate().map(|(i, &x)| Tagged(x, i));
+        let tags: Vec<usize> = top_k_of_iter(tagged, 3, TopKMode::Largest)
+            .iter()
+            .map(|item| item.1)
+            .collect();
+        assert_eq!(tags, vec![7, 0, 2]);
+    }
+
+    #[test]
+    fn keeps_bounded_length() {
+        let mut top = TopK::new(5, TopKMode::Largest);
+        for x in 0..1000 {
+            top.push(x % 97);
+            assert!(top.len() <= 5);
+        }
+        assert_eq!(top.into_sorted_vec(), vec![96; 5]);
+    }
+}
+
+/// # [Boyer–Moore Majority Vote](https://en.wikipedia.org/wiki/Boyer%E2%80%93Moore_majority_vote_algorithm)
+///
+/// Finds the element that makes up more than half of a list, or None if there is no such
+/// element, in O(n) time and O(1) extra memory. A first pass keeps a candidate and a vote count
+/// that every other element cancels; a second pass verifies that the candidate is a majority.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// assert_eq!(selection::majority(&[2, 7, 2, 2, 5, 2]), Some(2));
+/// assert_eq!(selection::majority(&[2, 7, 2, 7]), None);
+/// ```
+pub fn majority<T: PartialEq + Clone>(list: &[T]) -> Option<T> {
+    let mut candidate = None;
+    let mut votes = 0;
+    for item in list {
+        if votes == 0 {
+            candidate = Some(item);
+            votes = 1;
+        } else if candidate == Some(item) {
+            votes += 1;
+        } else {
+            votes -= 1;
+        }
+    }
+    // The candidate is only a majority if there is one.
+    candidate
+        .filter(|&candidate| list.iter().filter(|&item| item == candidate).count() * 2 > list.len())
+        .cloned()
+}
+
+/// # [Mode](https://en.wikipedia.org/wiki/Mode_(statistics))
+///
+/// Finds the most frequent element of a list and how many times it occurs, or None if the list
+/// is empty, by counting in a hash map. On a tie, the element encountered first wins.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// assert_eq!(selection::mode(&["b", "a", "c", "a", "b"]), Some(("b", 2)));
+/// assert_eq!(selection::mode::<i32>(&[]), None);
+/// ```
+pub fn mode<T: Eq + Hash + Clone>(list: &[T]) -> Option<(T, usize)> {
+    let mut counts = HashMap::new();
+    for item in list {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    let mut best: Option<(&T, usize)> = None;
+    for item in list {
+        let count = counts[item];
+        if best.is_none_or(|(_, best_count)| count > best_count) {
+            best = Some((item, count));
+        }
+    }
+    best.map(|(item, count)| (item.clone(), count))
+}
+
+#[cfg(test)]
+mod majority_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    fn brute_force_counts(list: &[u8]) -> Vec<usize> {
+        list.iter()
+            .map(|x| list.iter().filter(|&y| y == x).count())
+            .collect()
+    }
+
+    #[test]
+    fn receives_exact_half() {
+        assert_eq!(majority(&[1, 2, 1, 2]), None);
+        assert_eq!(majority(&[1, 1, 2, 3]), None);
+        assert_eq!(majority(&[1, 1, 2, 3, 1]), Some(1));
+        assert_eq!(majority(&[1, 1, 1, 2, 2, 3]), None);
+        // The candidate after voting is 3, which is not a majority.
+        assert_eq!(majority(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn breaks_mode_ties_by_first_occurrence() {
+        assert_eq!(mode(&[3, 1, 1, 3, 2, 2]), Some((3, 2)));
+        assert_eq!(mode(&[4, 5, 6]), Some((4, 1)));
+        assert_eq!(mode(&['x', 'y', 'y', 'x', 'x']), Some(('x', 3)));
+    }
+
+    #[test]
+    fn receives_single_and_empty_lists() {
+        assert_eq!(majority(&["only"]), Some("only"));
+        assert_eq!(mode(&["only"]), Some(("only", 1)));
+        assert_eq!(majority::<u8>(&[]), None);
+        assert_eq!(mode::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        test_util::agrees(
+            500,
+            |rng| {
+                let values = rng.gen_range(1, 4);
+                test_util::random_vec(rng, 1..30, 0..values)
+            },
+            |list| (mode(list), majority(list)),
+            |list| {
+                let counts = brute_force_counts(list);
+                let max = *counts.iter().max().unwrap();
+                let first = counts.iter().position(|&count| count == max).unwrap();
+                let majority = if max * 2 > list.len() {
+                    Some(list[first])
+                } else {
+                    None
+                };
+                (Some((list[first], max)), majority)
+            },
+        );
+    }
+}
+
+/// # Sliding Window Maximum with a Monotonic [Deque](https://en.wikipedia.org/wiki/Double-ended_queue)
+///
+/// Selects the largest element of every window of consecutive elements in O(n), returning
+/// `data.len() - window + 1` elements, or none if the window is longer than the data.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let data = [1, 3, -1, -3, 5, 3, 6, 7];
+/// assert_eq!(selection::sliding_window_max(&data, 3), vec![3, 3, 5, 5, 6, 7]);
+/// ```
+///
+/// # Panics
+///
+/// * window is 0
+pub fn sliding_window_max<T: Copy + Ord>(data: &[T], window: usize) -> Vec<T> {
+    sliding_window_by(data, window, |newer, older| newer >= older)
+}
+
+/// # Sliding Window Minimum with a Monotonic [Deque](https://en.wikipedia.org/wiki/Double-ended_queue)
+///
+/// Selects the smallest element of every window of consecutive elements in O(n), returning
+/// `data.len() - window + 1` elements, or none if the window is longer than the data.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::selection;
+///
+/// let data = [1, 3, -1, -3, 5, 3, 6, 7];
+/// assert_eq!(selection::sliding_window_min(&data, 3), vec![-1, -3, -3, -3, 3, 3]);
+/// ```
+///
+/// # Panics
+///
+/// * window is 0
+pub fn sliding_window_min<T: Copy + Ord>(data: &[T], window: usize) -> Vec<T> {
+    sliding_window_by(data, window, |newer, older| newer <= older)
+}
+
+/// Selects the best element of every window, where `beats(newer, older)` is true if the newer
+/// element makes the older one useless for every later window.
+fn sliding_window_by<T, F>(data: &[T], window: usize, beats: F) -> Vec<T>
+where
+    T: Copy,
+    F: Fn(&T, &T) -> bool,
+{
+    if window == 0 {
+        panic!("window should be larger than 0");
+    }
+    if window > data.len() {
+        return Vec::new();
+    }
+
+    // Indices of the candidates in the current window, whose elements get worse from the front
+    // to the back; the front is the best element of the window.
+    let mut candidates: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut selected = Vec::with_capacity(data.len() - window + 1);
+    for (i, item) in data.iter().enumerate() {
+        while candidates.back().is_some_and(|&j| beats(item, &data[j])) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+        if candidates[0] + window <= i {
+            candidates.pop_front(); // slid out of the window
+        }
+        if i + 1 >= window {
+            selected.push(data[candidates[0]]);
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod sliding_window_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_known_sequence() {
+        let data = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(sliding_window_max(&data, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(sliding_window_min(&data, 3), vec![-1, -3, -3, -3, 3, 3]);
+    }
+
+    #[test]
+    fn receives_window_of_one() {
+        let data = ['q', 'a', 'z', 'a'];
+        assert_eq!(sliding_window_max(&data, 1), data.to_vec());
+        assert_eq!(sliding_window_min(&data, 1), data.to_vec());
+    }
+
+    #[test]
+    fn receives_window_of_whole_data() {
+        let data = [4, 9, 2, 9, 2];
+        assert_eq!(sliding_window_max(&data, 5), vec![9]);
+        assert_eq!(sliding_window_min(&data, 5), vec![2]);
+    }
+
+    #[test]
+    fn receives_empty_data_or_longer_window() {
+        assert!(sliding_window_max::<i32>(&[], 3).is_empty());
+        assert!(sliding_window_min(&[1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        test_util::agrees(
+            100,
+            |rng| {
+                let data: Vec<i32> = test_util::random_vec(rng, 1..200, -50..50);
+                let window = rng.gen_range(1, data.len() + 1);
+                (data, window)
+            },
+            |&(ref data, window)| {
+                (
+                    sliding_window_max(data, window),
+                    sliding_window_min(data, window),
+                )
+            },
+            |&(ref data, window)| {
+                let max = data.windows(window).map(|w| *w.iter().max().unwrap());
+                let min = data.windows(window).map(|w| *w.iter().min().unwrap());
+                (max.collect(), min.collect())
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "window should be larger than 0")]
+    fn receives_zero_window() {
+        sliding_window_max(&[1, 2, 3], 0);
+    }
+}
+
+/// # Weighted Random Selection with the [Alias Method](https://en.wikipedia.org/wiki/Alias_method)
+///
+/// Draws items at random with probabilities proportional to their weights in O(1) time per draw,
+/// after O(n) preprocessing (Vose's algorithm). Every one of the n slots of the table holds an
+/// item, the probability of keeping it, and an alias item to take otherwise.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// extern crate ult_algo;
+///
+/// use ult_algo::sequence::selection::AliasTable;
+///
+/// fn main() {
+///     let table = AliasTable::new(vec!["never", "always"], &[0.0, 2.5]);
+///     let mut rng = rand::thread_rng();
+///     assert_eq!(*table.sample(&mut rng), "always");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AliasTable<T: Clone> {
+    /// Items to draw from
+    items: Vec<T>,
+    /// Probability of keeping the item of every slot
+    probabilities: Vec<f64>,
+    /// Index of the item to take when the item of a slot is not kept
+    aliases: Vec<usize>,
+}
+
+impl<T: Clone> AliasTable<T> {
+    /// Creates a table to draw the items from, each with a probability proportional to the
+    /// weight at the same index.
+    ///
+    /// # Panics
+    ///
+    /// * items should not be empty
+    /// * weights should have the same length as items
+    /// * weights should be finite and non-negative
+    /// * weights should add up to a positive number
+    pub fn new(items: Vec<T>, weights: &[f64]) -> AliasTable<T> {
+        if items.is_empty() {
+            panic!("items should not be empty");
+        }
+        if weights.len() != items.len() {
+            panic!("weights should have the same length as items");
+        }
+        if !weights.iter().all(|&w| w.is_finite() && w >= 0.0) {
+            panic!("weights should be finite and non-negative");
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            panic!("weights should add up to a positive number");
+        }
+
+        // Scale the weights so that they average 1, then let every slot under 1 be topped up
+        // by a slot over 1.
+        let n = items.len();
+        let mut scaled: Vec<f64> = weights.iter().map(|&w| w * n as f64 / total).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+        let mut probabilities = vec![1.0; n];
+        let mut aliases: Vec<usize> = (0..n).collect();
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            probabilities[s] = scaled[s];
+            aliases[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left is 1 up to rounding errors.
+        AliasTable {
+            items,
+            probabilities,
+            aliases,
+        }
+    }
+
+    /// Draws an item at random.
+    pub fn sample<'a, R: Rng>(&'a self, rng: &mut R) -> &'a T {
+        let slot = rng.gen_range(0, self.items.len());
+        if rng.gen::<f64>() < self.probabilities[slot] {
+            &self.items[slot]
+        } else {
+            &self.items[self.aliases[slot]]
+        }
+    }
+}
+
+/// Selects one item at random with probability proportional to its weight, with an
+/// `AliasTable`. Returns None if there are no items or all the weights are 0. Building the
+/// table takes O(n) time, so keep an `AliasTable` to draw more than once.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// extern crate ult_algo;
+///
+/// use ult_algo::sequence::selection;
+///
+/// fn main() {
+///     let mut rng = rand::thread_rng();
+///     let items = ['a', 'b', 'c'];
+///     assert_eq!(selection::weighted_random(&items, &[0.0, 0.0, 1.0], &mut rng), Some(&'c'));
+///     assert_eq!(selection::weighted_random(&items, &[0.0; 3], &mut rng), None);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// * weights should have the same length as items
+/// * weights should be finite and non-negative
+pub fn weighted_random<'a, T: Clone, R: Rng>(
+    items: &'a [T],
+    weights: &[f64],
+    rng: &mut R,
+) -> Option<&'a T> {
+    if weights.len() != items.len() {
+        panic!("weights should have the same length as items");
+    }
+    if !weights.iter().all(|&w| w.is_finite() && w >= 0.0) {
+        panic!("weights should be finite and non-negative");
+    }
+    if weights.iter().sum::<f64>() == 0.0 {
+        return None;
+    }
+    let table = AliasTable::new((0..items.len()).collect(), weights);
+    Some(&items[*table.sample(rng)])
+}
+
+#[cfg(test)]
+mod alias_table_tests {
+    use super::*;
+    use test_util;
+
+    fn check_frequencies(weights: &[f64]) {
+        let table = AliasTable::new((0..weights.len()).collect(), weights);
+        let mut rng = test_util::rng();
+        let mut counts = vec![0; weights.len()];
+        let samples = 100_000;
+        for _ in 0..samples {
+            counts[*table.sample(&mut rng)] += 1;
+        }
+        let total: f64 = weights.iter().sum();
+        for (i, &count) in counts.iter().enumerate() {
+            let frequency = count as f64 / samples as f64;
+            assert!(
+                (frequency - weights[i] / total).abs() < 0.01,
+                "item {} drawn with frequency {}",
+                i,
+                frequency
+            );
+        }
+    }
+
+    #[test]
+    fn draws_proportionally_to_weights() {
+        check_frequencies(&[1.0, 2.0, 3.0, 4.0]);
+        check_frequencies(&[0.5, 0.0, 10.0, 0.25, 3.0, 0.0, 7.5]);
+        check_frequencies(&[1.0; 9]);
+    }
+
+    #[test]
+    fn never_draws_zero_weights() {
+        let table = AliasTable::new(vec!['a', 'b', 'c', 'd'], &[0.0, 1.0, 0.0, 1e-9]);
+        let mut rng = test_util::rng();
+        for _ in 0..10_000 {
+            let item = *table.sample(&mut rng);
+            assert!(item == 'b' || item == 'd');
+        }
+    }
+
+    #[test]
+    fn selects_weighted_random() {
+        let mut rng = test_util::rng();
+        assert_eq!(weighted_random::<i32, _>(&[], &[], &mut rng), None);
+        assert_eq!(weighted_random(&[1, 2], &[0.0, 0.0], &mut rng), None);
+        assert_eq!(weighted_random(&[1, 2], &[0.0, 3.0], &mut rng), Some(&2));
+        assert_eq!(weighted_random(&["only"], &[0.1], &mut rng), Some(&"only"));
+    }
+
+    #[test]
+    #[should_panic(expected = "weights should be finite and non-negative")]
+    fn receives_negative_weight() {
+        AliasTable::new(vec![1, 2], &[1.0, -0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights should be finite and non-negative")]
+    fn receives_weights_cancelling_out() {
+        weighted_random(&[1, 2], &[1.0, -1.0], &mut test_util::rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "weights should have the same length as items")]
+    fn receives_missing_weight() {
+        weighted_random(&[1, 2], &[1.0], &mut test_util::rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "items should not be empty")]
+    fn receives_no_items() {
+        AliasTable::<u8>::new(vec![], &[]);
+    }
+}
+
+/// # [Floyd's Sampling Algorithm](https://doi.org/10.1145/30401.315746)
+///
+/// Draws k distinct indices from 0..n without replacement, every k-subset being equally likely,
+/// in O(k) time and memory instead of shuffling all n indices. For every j in n-k..n, a random
+/// t in 0..=j is taken, unless it was taken before, in which case j is taken instead: j cannot
+/// have been taken yet, and it stands in for t so that every subset stays equally likely. The
+/// indices come in the order they were taken, which is not uniformly random itself.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// extern crate ult_algo;
+///
+/// use rand::{SeedableRng, StdRng};
+/// use ult_algo::sequence::selection;
+///
+/// fn main() {
+///     let mut rng = StdRng::from_seed(&[2018][..]);
+///     let mut sample = selection::sample_without_replacement(1_000_000, 3, &mut rng);
+///     sample.sort();
+///     sample.dedup();
+///     assert_eq!(sample.len(), 3);
+///     assert!(sample.iter().all(|&i| i < 1_000_000));
+/// }
+/// ```
+///
+/// # Panics
+///
+/// * k should not be larger than n
+pub fn sample_without_replacement<R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    if k > n {
+        panic!("k should not be larger than n");
+    }
+    let mut taken = HashSet::with_capacity(k);
+    let mut sample = Vec::with_capacity(k);
+    for j in n - k..n {
+        let t = rng.gen_range(0, j + 1);
+        let index = if taken.insert(t) { t } else { j };
+        taken.insert(index);
+        sample.push(index);
+    }
+    sample
+}
+
+/// Draws k distinct items from a slice without replacement, every k-subset of positions being
+/// equally likely, with `sample_without_replacement`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// extern crate ult_algo;
+///
+/// use ult_algo::sequence::selection;
+///
+/// fn main() {
+///     let list = ["ann", "bob", "cat"];
+///     let sample = selection::sample_slice(&list, 3, &mut rand::thread_rng());
+///     assert_eq!(sample.len(), 3);
+///     assert!(list.iter().all(|item| sample.contains(&item)));
+/// }
+/// ```
+///
+/// # Panics
+///
+/// * k should not be larger than list's length
+pub fn sample_slice<'a, T, R: Rng>(list: &'a [T], k: usize, rng: &mut R) -> Vec<&'a T> {
+    if k > list.len() {
+        panic!("k should not be larger than list's length");
+    }
+    sample_without_replacement(list.len(), k, rng)
+        .into_iter()
+        .map(|i| &list[i])
+        .collect()
+}
+
+#[cfg(test)]
+mod sample_tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+    use test_util;
+
+    #[test]
+    fn draws_distinct_indices() {
+        let mut rng = test_util::rng();
+        for &(n, k) in &[(0, 0), (1, 1), (10, 0), (10, 10), (1000, 5), (100, 99)] {
+            let mut sample = sample_without_replacement(n, k, &mut rng);
+            sample.sort();
+            sample.dedup();
+            assert_eq!(sample.len(), k);
+            assert!(sample.iter().all(|&i| i < n));
+        }
+    }
+
+    #[test]
+    fn draws_every_subset_uniformly() {
+        // There are 10 subsets of size 2 from 0..5, each expected 10% of the time.
+        let mut rng = test_util::rng();
+        let mut counts = HashMap::new();
+        let trials = 100_000;
+        for _ in 0..trials {
+            let mut sample = sample_without_replacement(5, 2, &mut rng);
+            sample.sort();
+            *counts.entry(sample).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 10);
+        for (subset, &count) in &counts {
+            let frequency = count as f64 / trials as f64;
+            assert!(
+                (frequency - 0.1).abs() < 0.01,
+                "subset {:?} drawn with frequency {}",
+                subset,
+                frequency
+            );
+        }
+    }
+
+    #[test]
+    fn repeats_runs_with_the_same_seed() {
+        let draw = |seed| sample_without_replacement(1000, 20, &mut StdRng::from_seed(&[seed][..]));
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+    }
+
+    #[test]
+    fn samples_slices() {
+        let list = ['a', 'b', 'c', 'd', 'e'];
+        let mut rng = StdRng::from_seed(&[2018][..]);
+        let sample = sample_slice(&list, 3, &mut rng);
+        let indices = sample_without_replacement(5, 3, &mut StdRng::from_seed(&[2018][..]));
+        let expected: Vec<&char> = indices.iter().map(|&i| &list[i]).collect();
+        assert_eq!(sample, expected);
+        assert_eq!(
+            sample_slice::<char, _>(&[], 0, &mut rng),
+            Vec::<&char>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "k should not be larger than n")]
+    fn receives_k_larger_than_n() {
+        sample_without_replacement(3, 4, &mut test_util::rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "k should not be larger than list's length")]
+    fn receives_k_larger_than_list() {
+        sample_slice(&[1, 2], 3, &mut test_util::rng());
     }
-    list.swap(store_idx, last_idx); // move pivot to its final sorted place
-    store_idx
 }
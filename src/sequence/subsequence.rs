@@ -0,0 +1,299 @@
+//! # Subsequence
+//!
+//! A collection of functions to find and count subsequences of a sequence.
+
+/// # [Inversion](https://en.wikipedia.org/wiki/Inversion_(discrete_mathematics))
+///
+/// Counts the pairs of positions i < j where `sequence[i] > sequence[j]`, by merge sorting a copy
+/// of the sequence in O(n log n) time.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::subsequence;
+///
+/// assert_eq!(subsequence::count_inversions(&[2, 4, 1, 3, 5]), 3);
+/// assert_eq!(subsequence::count_inversions(&[1, 2, 3]), 0);
+/// ```
+pub fn count_inversions<T: Ord + Clone>(sequence: &[T]) -> u64 {
+    let mut items = sequence.to_vec();
+    let mut buffer = Vec::with_capacity(items.len());
+    merge_count(&mut items, &mut buffer)
+}
+
+/// Sorts the items and returns their inversions.
+fn merge_count<T: Ord + Clone>(items: &mut [T], buffer: &mut Vec<T>) -> u64 {
+    if items.len() < 2 {
+        return 0;
+    }
+    let mid = items.len() / 2;
+    let mut inversions = {
+        let (left, right) = items.split_at_mut(mid);
+        merge_count(left, buffer) + merge_count(right, buffer)
+    };
+
+    buffer.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < items.len() {
+        if items[j] < items[i] {
+            // items[j] is smaller than every item left in the left half.
+            inversions += (mid - i) as u64;
+            buffer.push(items[j].clone());
+            j += 1;
+        } else {
+            buffer.push(items[i].clone());
+            i += 1;
+        }
+    }
+    buffer.extend_from_slice(&items[i..mid]);
+    buffer.extend_from_slice(&items[j..]);
+    items.clone_from_slice(buffer);
+    inversions
+}
+
+/// Counts the pairs of positions i < j where `sequence[i] > sequence[j]`, by replacing the items
+/// with their ranks and counting the larger ranks seen so far in a
+/// [Fenwick tree](https://en.wikipedia.org/wiki/Fenwick_tree), in O(n log n) time.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::subsequence;
+///
+/// assert_eq!(subsequence::count_inversions_bit(&[2, 4, 1, 3, 5]), 3);
+/// assert_eq!(subsequence::count_inversions_bit(&['c', 'b', 'a']), 3);
+/// ```
+pub fn count_inversions_bit<T: Ord + Clone>(sequence: &[T]) -> u64 {
+    let mut sorted = sequence.to_vec();
+    sorted.sort();
+    sorted.dedup();
+
+    // tree[i] counts the ranks in (i - lowbit(i), i], for 1-based ranks.
+    let mut tree = vec![0u64; sorted.len() + 1];
+    let mut inversions = 0;
+    for (seen, item) in sequence.iter().enumerate() {
+        let rank = sorted.partition_point(|x| x < item) + 1;
+        // Subtract the seen items with a rank not larger than this one.
+        let mut i = rank;
+        let mut not_larger = 0;
+        while i > 0 {
+            not_larger += tree[i];
+            i &= i - 1;
+        }
+        inversions += seen as u64 - not_larger;
+
+        let mut i = rank;
+        while i < tree.len() {
+            tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+    inversions
+}
+
+#[cfg(test)]
+mod count_inversions_tests {
+    use super::*;
+    use rand::Rng;
+    use test_util;
+
+    #[test]
+    fn receives_sorted_sequence() {
+        let sequence: Vec<u32> = (0..100).collect();
+        assert_eq!(count_inversions(&sequence), 0);
+        assert_eq!(count_inversions_bit(&sequence), 0);
+    }
+
+    #[test]
+    fn receives_reverse_sorted_sequence() {
+        let sequence: Vec<u32> = (0..100).rev().collect();
+        assert_eq!(count_inversions(&sequence), 100 * 99 / 2);
+        assert_eq!(count_inversions_bit(&sequence), 100 * 99 / 2);
+    }
+
+    #[test]
+    fn receives_classic_sequence() {
+        assert_eq!(count_inversions(&[2, 4, 1, 3, 5]), 3);
+        assert_eq!(count_inversions_bit(&[2, 4, 1, 3, 5]), 3);
+    }
+
+    #[test]
+    fn receives_empty_sequence() {
+        assert_eq!(count_inversions::<u32>(&[]), 0);
+        assert_eq!(count_inversions_bit::<u32>(&[]), 0);
+    }
+
+    #[test]
+    fn ignores_equal_items() {
+        assert_eq!(count_inversions(&[3, 1, 1, 3, 3]), 2);
+        assert_eq!(count_inversions_bit(&[3, 1, 1, 3, 3]), 2);
+    }
+
+    #[test]
+    fn agrees_on_random_sequences() {
+        let mut rng = test_util::rng();
+        for &range in &[10, 1_000_000] {
+            let sequence: Vec<i32> = (0..1000).map(|_| rng.gen_range(0, range)).collect();
+            assert_eq!(count_inversions(&sequence), count_inversions_bit(&sequence));
+        }
+
+        let sequence: Vec<i32> = (0..100).map(|_| rng.gen_range(0, 10)).collect();
+        let brute_force = (0..sequence.len())
+            .flat_map(|i| (i + 1..sequence.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| sequence[i] > sequence[j])
+            .count();
+        assert_eq!(count_inversions(&sequence), brute_force as u64);
+    }
+}
+
+/// # [Bitonic Subsequence](https://en.wikipedia.org/wiki/Longest_bitonic_subsequence)
+///
+/// Finds a longest subsequence that strictly increases and then strictly decreases, where
+/// either part may be empty. A longest strictly increasing subsequence ending at every item,
+/// and a longest strictly decreasing one starting at every item, are found with patience
+/// sorting in two passes, in O(n log n) time; the item where they combine to the longest
+/// subsequence (the first one, on ties) is the peak.
+///
+/// # Examples
+///
+/// ```
+/// use ult_algo::sequence::subsequence;
+///
+/// let sequence = [1, 11, 2, 10, 4, 5, 2, 1];
+/// assert_eq!(
+///     subsequence::longest_bitonic_subsequence(&sequence),
+///     vec![1, 11, 10, 4, 2, 1]
+/// );
+/// ```
+pub fn longest_bitonic_subsequence<T: Ord + Clone>(sequence: &[T]) -> Vec<T> {
+    let (increasing, increasing_previous) = increasing_runs(sequence, false);
+    let (decreasing, decreasing_next) = increasing_runs(sequence, true);
+    let peak = match (0..sequence.len()).max_by(|&i, &j| {
+        (increasing[i] + decreasing[i])
+            .cmp(&(increasing[j] + decreasing[j]))
+            .then(j.cmp(&i))
+    }) {
+        Some(peak) => peak,
+        None => return Vec::new(),
+    };
+
+    let mut indices = Vec::new();
+    let mut current = Some(peak);
+    while let Some(i) = current {
+        indices.push(i);
+        current = increasing_previous[i];
+    }
+    indices.reverse();
+    let mut current = decreasing_next[peak];
+    while let Some(i) = current {
+        indices.push(i);
+        current = decreasing_next[i];
+    }
+    indices.into_iter().map(|i| sequence[i].clone()).collect()
+}
+
+/// Returns the length of a longest strictly increasing subsequence ending at every item, and the
+/// item before it in that subsequence; in reverse, the subsequences are read from the end, so
+/// they strictly decrease from every item, and the links point to the next item.
+fn increasing_runs<T: Ord>(sequence: &[T], reverse: bool) -> (Vec<usize>, Vec<Option<usize>>) {
+    let n = sequence.len();
+    let mut lengths = vec![0; n];
+    let mut links = vec![None; n];
+    // tails[k] is the item with the smallest value that ends a subsequence of length k + 1.
+    let mut tails: Vec<usize> = Vec::new();
+    let order: Box<dyn Iterator<Item = usize>> = if reverse {
+        Box::new((0..n).rev())
+    } else {
+        Box::new(0..n)
+    };
+    for i in order {
+        let k = tails.partition_point(|&t| sequence[t] < sequence[i]);
+        links[i] = k.checked_sub(1).map(|k| tails[k]);
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+        lengths[i] = k + 1;
+    }
+    (lengths, links)
+}
+
+#[cfg(test)]
+mod longest_bitonic_subsequence_tests {
+    use super::*;
+    use test_util;
+
+    fn is_bitonic<T: Ord>(sequence: &[T]) -> bool {
+        let peak = sequence
+            .windows(2)
+            .position(|pair| pair[0] >= pair[1])
+            .unwrap_or(sequence.len());
+        sequence[peak..].windows(2).all(|pair| pair[0] > pair[1])
+    }
+
+    fn is_subsequence<T: PartialEq>(subsequence: &[T], sequence: &[T]) -> bool {
+        let mut items = sequence.iter();
+        subsequence.iter().all(|x| items.any(|y| x == y))
+    }
+
+    #[test]
+    fn receives_increasing_sequence() {
+        let sequence: Vec<u32> = (0..10).collect();
+        assert_eq!(longest_bitonic_subsequence(&sequence), sequence);
+    }
+
+    #[test]
+    fn receives_decreasing_sequence() {
+        let sequence: Vec<u32> = (0..10).rev().collect();
+        assert_eq!(longest_bitonic_subsequence(&sequence), sequence);
+    }
+
+    #[test]
+    fn receives_classic_example() {
+        let sequence = [1, 11, 2, 10, 4, 5, 2, 1];
+        let subsequence = longest_bitonic_subsequence(&sequence);
+        assert_eq!(subsequence.len(), 6);
+        assert!(is_bitonic(&subsequence) && is_subsequence(&subsequence, &sequence));
+    }
+
+    #[test]
+    fn receives_short_sequences() {
+        assert_eq!(longest_bitonic_subsequence(&[7]), vec![7]);
+        assert_eq!(longest_bitonic_subsequence::<u32>(&[]), vec![]);
+        assert_eq!(longest_bitonic_subsequence(&[3, 3, 3]).len(), 1);
+    }
+
+    #[test]
+    fn agrees_with_quadratic_algorithm() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let sequence: Vec<u8> = test_util::random_vec(&mut rng, 0..30, 0..10);
+            let n = sequence.len();
+            let mut increasing = vec![1; n];
+            let mut decreasing = vec![1; n];
+            for i in 0..n {
+                for j in 0..i {
+                    if sequence[j] < sequence[i] {
+                        increasing[i] = increasing[i].max(increasing[j] + 1);
+                    }
+                }
+            }
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    if sequence[j] < sequence[i] {
+                        decreasing[i] = decreasing[i].max(decreasing[j] + 1);
+                    }
+                }
+            }
+            let expected = (0..n)
+                .map(|i| increasing[i] + decreasing[i] - 1)
+                .max()
+                .unwrap_or(0);
+
+            let subsequence = longest_bitonic_subsequence(&sequence);
+            assert_eq!(subsequence.len(), expected);
+            assert!(is_bitonic(&subsequence) && is_subsequence(&subsequence, &sequence));
+        }
+    }
+}
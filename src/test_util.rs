@@ -0,0 +1,106 @@
+//! # Test Util
+//!
+//! Helpers shared by the tests of several modules.
+
+use rand::distributions::range::SampleRange;
+use rand::{Rng, SeedableRng, StdRng};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// Creates a random number generator with a fixed seed, so that a failing test fails the same
+/// way every time it runs.
+pub fn rng() -> StdRng {
+    StdRng::from_seed(&[0x5eed][..])
+}
+
+/// Generates a vector whose length is drawn from `lengths` and whose items are drawn from
+/// `items`.
+pub fn random_vec<T, R>(rng: &mut R, lengths: Range<usize>, items: Range<T>) -> Vec<T>
+where
+    T: Copy + PartialOrd + SampleRange,
+    R: Rng,
+{
+    (0..rng.gen_range(lengths.start, lengths.end))
+        .map(|_| rng.gen_range(items.start, items.end))
+        .collect()
+}
+
+/// Returns a sorted copy of the list.
+pub fn sorted<T: Clone + Ord>(list: &[T]) -> Vec<T> {
+    let mut sorted = list.to_vec();
+    sorted.sort();
+    sorted
+}
+
+/// Checks that `f` gives the same output as a simpler `expected` on `trials` inputs made by
+/// `generate`, reporting the first input on which they differ.
+pub fn agrees<I, O, G, F, E>(trials: usize, mut generate: G, f: F, expected: E)
+where
+    I: Debug,
+    O: Debug + PartialEq,
+    G: FnMut(&mut StdRng) -> I,
+    F: Fn(&I) -> O,
+    E: Fn(&I) -> O,
+{
+    let mut rng = rng();
+    for _ in 0..trials {
+        let input = generate(&mut rng);
+        assert_eq!(f(&input), expected(&input), "input: {:?}", input);
+    }
+}
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An integer that counts how many times it is compared, by equality or by order, on the
+/// current thread.
+#[derive(Clone, Copy, Debug)]
+pub struct Counted(pub i64);
+
+impl Counted {
+    /// Starts counting the comparisons from 0 again.
+    pub fn reset() {
+        COMPARISONS.with(|comparisons| comparisons.set(0));
+    }
+
+    /// Returns the number of comparisons since the last reset.
+    pub fn comparisons() -> usize {
+        COMPARISONS.with(Cell::get)
+    }
+
+    fn count() {
+        COMPARISONS.with(|comparisons| comparisons.set(comparisons.get() + 1));
+    }
+}
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Counted) -> bool {
+        Counted::count();
+        self.0 == other.0
+    }
+}
+
+impl Eq for Counted {}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Counted) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted {
+    fn cmp(&self, other: &Counted) -> Ordering {
+        Counted::count();
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for Counted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}